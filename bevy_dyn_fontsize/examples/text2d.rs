@@ -0,0 +1,96 @@
+//!
+//! Displays text with a constant size in the world, which stays pixel-perfect while the
+//! window is resized, or the camera is zoomed with the +/- keys. The lower line of text is
+//! given a max width, so it is shrunk to fit the box drawn around it.
+//!
+//! Run with `cargo run -p bevy_dyn_fontsize --example text2d`
+//!
+
+// -------------------------------------------------------------------------------------------------
+// Included Symbols
+
+use bevy::camera::ScalingMode;
+use bevy::prelude::*;
+use bevy_dyn_fontsize::{DynamicFontSize, DynamicFontsizePlugin};
+
+// -------------------------------------------------------------------------------------------------
+// Constants
+
+const VIEW_HEIGHT: f32 = 100f32;
+const BOX_SIZE: Vec2 = Vec2::new(60f32, 6f32);
+const ZOOM_PER_SEC: f32 = 1.5;
+
+// -------------------------------------------------------------------------------------------------
+// Main
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, DynamicFontsizePlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (zoom_camera, draw_box))
+        .run();
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Systems
+
+// Spawn a camera showing VIEW_HEIGHT units of the world vertically, and two lines of text.
+fn setup(mut commands: Commands) {
+    let camera = commands
+        .spawn((
+            Camera2d,
+            Projection::Orthographic(OrthographicProjection {
+                scaling_mode: ScalingMode::FixedVertical {
+                    viewport_height: VIEW_HEIGHT,
+                },
+                ..OrthographicProjection::default_2d()
+            }),
+        ))
+        .id();
+
+    commands.spawn((
+        DynamicFontSize {
+            height_in_world: 10f32,
+            render_camera: camera,
+            max_width_in_world: None,
+        },
+        Text2d::new("Pixel-perfect"),
+        Transform::from_xyz(0f32, 10f32, 0f32),
+    ));
+    commands.spawn((
+        DynamicFontSize {
+            height_in_world: BOX_SIZE.y,
+            render_camera: camera,
+            max_width_in_world: Some(BOX_SIZE.x),
+        },
+        Text2d::new("A rather long line of text, fitted into its box"),
+        Transform::from_xyz(0f32, -10f32, 0f32),
+    ));
+}
+
+// Zoom the camera in and out while the +/- keys are held.
+fn zoom_camera(
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut projection: Single<&mut Projection, With<Camera2d>>,
+) {
+    let Projection::Orthographic(ortho) = projection.as_mut() else {
+        return;
+    };
+    let zoom = ZOOM_PER_SEC.powf(time.delta_secs());
+    if keys.pressed(KeyCode::Equal) || keys.pressed(KeyCode::NumpadAdd) {
+        ortho.scale /= zoom;
+    }
+    if keys.pressed(KeyCode::Minus) || keys.pressed(KeyCode::NumpadSubtract) {
+        ortho.scale *= zoom;
+    }
+}
+
+// Outline the box the lower line of text is fitted into.
+fn draw_box(mut gizmos: Gizmos) {
+    gizmos.rect_2d(
+        Isometry2d::from_xy(0f32, -10f32),
+        BOX_SIZE,
+        Color::srgb(0.3, 0.3, 0.3),
+    );
+}
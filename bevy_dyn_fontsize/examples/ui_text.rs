@@ -0,0 +1,93 @@
+//!
+//! Displays Bevy UI text which scales with the window, relative to a 1280x720 reference
+//! resolution. The plugin is configured with its builder methods to run in PostUpdate, and a
+//! system ordered after its Resize set logs each new font size, and any text which couldn't
+//! be sized.
+//!
+//! Run with `cargo run -p bevy_dyn_fontsize --example ui_text`
+//!
+
+// -------------------------------------------------------------------------------------------------
+// Included Symbols
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_dyn_fontsize::{
+    DynamicFontSizeError, DynamicFontsizePlugin, DynamicUiFontSize, FontSizeQuantization, Systems,
+};
+
+// -------------------------------------------------------------------------------------------------
+// Constants
+
+const REFERENCE_RESOLUTION: Vec2 = Vec2::new(1280f32, 720f32);
+
+// -------------------------------------------------------------------------------------------------
+// Main
+
+fn main() {
+    App::new()
+        .add_plugins((
+            DefaultPlugins,
+            DynamicFontsizePlugin::default()
+                .in_schedule(PostUpdate)
+                .with_debounce_time(Duration::from_millis(200))
+                .with_quantization(FontSizeQuantization {
+                    step: 2f32,
+                    min: 8f32,
+                    max: 96f32,
+                }),
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(PostUpdate, log_font_sizes.after(Systems::Resize))
+        .run();
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Systems
+
+// Spawn a camera, and a title and caption laid out for the reference resolution.
+fn setup(mut commands: Commands) {
+    let camera = commands.spawn(Camera2d).id();
+
+    commands
+        .spawn(Node {
+            width: percent(100),
+            height: percent(100),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                DynamicUiFontSize {
+                    font_size: 72f32,
+                    reference_resolution: REFERENCE_RESOLUTION,
+                    render_camera: camera,
+                },
+                Text::new("Scaled UI Text"),
+            ));
+            parent.spawn((
+                DynamicUiFontSize {
+                    font_size: 24f32,
+                    reference_resolution: REFERENCE_RESOLUTION,
+                    render_camera: camera,
+                },
+                Text::new("Resize the window to see the text scale with it"),
+            ));
+        });
+}
+
+// Log the font size of any text which was just resized, and any which couldn't be.
+fn log_font_sizes(
+    fonts: Query<(&Text, Ref<TextFont>), With<DynamicUiFontSize>>,
+    mut errors: MessageReader<DynamicFontSizeError>,
+) {
+    for (text, font) in fonts.iter().filter(|(_, font)| font.is_changed()) {
+        info!("Sized \"{}\" at {}", text.0, font.font_size);
+    }
+    for error in errors.read() {
+        warn!("Unable to size text {}: {:?}", error.text, error.kind);
+    }
+}
@@ -3,10 +3,58 @@
 //! text to be displayed even when the window is resized. It allows this
 //! to be done while keeping the text a constant size in world units.
 //!
-//! Current Limitation: the plugin only adjusts font sizing parameters when
-//! the window itself is resized. If the camera projection is altered in some
-//! other way, the font size will not be updated. I.e. this plugin assumes
-//! a constant camera projection is present.
+//! Font sizing parameters are adjusted when the window is resized, when the
+//! projection or viewport of a text entity's camera changes (e.g. a zoom or a
+//! change of ScalingMode), and when a text entity is spawned or its
+//! DynamicFontSize changes.
+//!
+//! New text entities are sized as soon as their camera is ready to render,
+//! rather than waiting for one of the above.
+//!
+//! Each text entity is sized for the viewport of its own camera, so text drawn
+//! by cameras rendering to different windows, to images, or to split-screen
+//! viewports of the same window is each pixel-perfect for its own target.
+//!
+//! Both orthographic and perspective cameras are supported. Bevy UI text nodes
+//! can also be sized, relative to a reference resolution, with DynamicUiFontSize.
+//!
+//! Text entities which can't be sized are skipped, without holding up the others,
+//! and reported with a DynamicFontSizeError message.
+//!
+//! Font sizes are in logical pixels, which Bevy rasterizes at the scale factor of
+//! the render target, so text is pixel-perfect on HiDPI screens too. Optionally,
+//! font sizes can be quantized to discrete steps within a min/max range.
+//!
+//! Text can also be given a maximum width in the world, in which case it is fitted
+//! into a box: its font is shrunk if the laid-out text would be too wide.
+//!
+//! The plugin's systems run in the Update schedule by default, and may be placed in
+//! another schedule or system set with the builder methods of DynamicFontsizePlugin.
+//! Other systems can be ordered against them using the public Systems sets.
+//!
+//! ```no_run
+//! use bevy::prelude::*;
+//! use bevy_dyn_fontsize::{DynamicFontSize, DynamicFontsizePlugin};
+//!
+//! fn main() {
+//!     App::new()
+//!         .add_plugins((DefaultPlugins, DynamicFontsizePlugin::default()))
+//!         .add_systems(Startup, setup)
+//!         .run();
+//! }
+//!
+//! fn setup(mut commands: Commands) {
+//!     let camera = commands.spawn(Camera2d).id();
+//!     commands.spawn((
+//!         DynamicFontSize {
+//!             height_in_world: 40.0,
+//!             render_camera: camera,
+//!             max_width_in_world: None,
+//!         },
+//!         Text2d::new("Pixel-perfect at any window size"),
+//!     ));
+//! }
+//! ```
 //!
 
 // -------------------------------------------------------------------------------------------------
@@ -14,7 +62,9 @@
 
 use std::time::Duration;
 
+use bevy::ecs::schedule::{InternedScheduleLabel, InternedSystemSet, ScheduleLabel};
 use bevy::prelude::*;
+use bevy::text::TextLayoutInfo;
 use bevy::window::WindowResized;
 
 // -------------------------------------------------------------------------------------------------
@@ -27,30 +77,151 @@ const DEFAULT_DEBOUNCE_DURATION: Duration = Duration::from_millis(100);
 
 ///
 /// This plugin allows pixel-perfect text to be displayed with a constant in-world
-/// size. If the window is resized, resulting in a change to the ratio of in-world
-/// units to on-screen pixels, the plugin will automatically update font rendering
-/// to ensure it is pixel-perfect given the new projection.
+/// size. If the window is resized or the camera projection changes, resulting in a
+/// change to the ratio of in-world units to on-screen pixels, the plugin will
+/// automatically update font rendering to ensure it is pixel-perfect given the new
+/// projection. Text entities are also resized when their DynamicFontSize is changed.
+///
+/// Newly spawned text entities are sized straight away, in the first frame their camera
+/// has a known viewport and projection, even if the camera is spawned after the text.
 ///
 /// It uses a debouncer to avoid storms of updates on every frame while a window
-/// is actively being resized.
+/// is actively being resized, or a projection is continuously changing.
+///
+/// The plugin may be configured through its fields, or with its builder methods, e.g.
+/// `DynamicFontsizePlugin::default().in_schedule(PostUpdate).with_debounce_time(delay)`.
 ///
 pub struct DynamicFontsizePlugin {
     /// The duration to use when debouncing window resize events. Defaults to 100 ms.
     pub debounce_time: Duration,
+    ///
+    /// Optional quantization of font sizes, limiting how often new glyphs are rasterized while
+    /// a window is continuously resized. Text keeps its size in the world either way, but is
+    /// only approximately pixel-perfect when quantized. Defaults to None.
+    ///
+    pub quantization: Option<FontSizeQuantization>,
+    /// The schedule the plugin's systems are added to. Defaults to Update.
+    pub schedule: InternedScheduleLabel,
+    ///
+    /// Optional system set to add all of the plugin's systems to, on top of its own Systems
+    /// sets, so they may be ordered or run conditionally along with the app's own systems.
+    /// Defaults to None.
+    ///
+    pub system_set: Option<InternedSystemSet>,
 }
 
 impl Default for DynamicFontsizePlugin {
     fn default() -> Self {
         DynamicFontsizePlugin {
             debounce_time: DEFAULT_DEBOUNCE_DURATION,
+            quantization: None,
+            schedule: Update.intern(),
+            system_set: None,
         }
     }
 }
 
+impl DynamicFontsizePlugin {
+    /// Use the given duration when debouncing window resizes and other changes.
+    pub fn with_debounce_time(mut self, debounce_time: Duration) -> Self {
+        self.debounce_time = debounce_time;
+        self
+    }
+
+    /// Quantize font sizes as given.
+    pub fn with_quantization(mut self, quantization: FontSizeQuantization) -> Self {
+        self.quantization = Some(quantization);
+        self
+    }
+
+    /// Add the plugin's systems to the given schedule, rather than Update.
+    pub fn in_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = schedule.intern();
+        self
+    }
+
+    /// Add all of the plugin's systems to the given system set.
+    pub fn in_set(mut self, system_set: impl SystemSet) -> Self {
+        self.system_set = Some(system_set.intern());
+        self
+    }
+}
+
 impl Plugin for DynamicFontsizePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (handle_window_resize, handle_font_resize))
-            .insert_resource(WindowResizeDebouncer::from_duration(self.debounce_time));
+        app.configure_sets(
+            self.schedule,
+            Systems::DetectChanges.before(Systems::Resize),
+        );
+        if let Some(system_set) = self.system_set {
+            app.configure_sets(
+                self.schedule,
+                (Systems::DetectChanges, Systems::Resize).in_set(system_set),
+            );
+        }
+
+        app.add_systems(
+            self.schedule,
+            (
+                (handle_window_resize, handle_camera_change).in_set(Systems::DetectChanges),
+                (handle_font_resize, size_new_fonts)
+                    .chain()
+                    .in_set(Systems::Resize),
+            ),
+        )
+        .add_message::<DynamicFontSizeError>()
+        .insert_resource(WindowResizeDebouncer::from_duration(self.debounce_time))
+        .insert_resource(FontSizeSettings {
+            quantization: self.quantization,
+        });
+    }
+}
+
+///
+/// The public SystemSets of the plugin, in its configured schedule. DetectChanges always
+/// runs before Resize.
+///
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Systems {
+    ///
+    /// Window resizes, and changes to cameras and dynamically sized text, are detected in this
+    /// set. Changes made before it are picked up in the same frame, rather than the next one.
+    ///
+    DetectChanges,
+
+    ///
+    /// Text is sized in this set, and DynamicFontSizeError messages are written. Systems
+    /// reading the font size or scale of dynamically sized text, or reading these messages,
+    /// should be ordered after it.
+    ///
+    Resize,
+}
+
+///
+/// Quantization of dynamically computed font sizes. Font sizes are rounded to the nearest
+/// multiple of the step, then clamped to the min/max range.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FontSizeQuantization {
+    /// The step between font sizes. Zero or less disables rounding, leaving only the clamp.
+    pub step: f32,
+    /// The smallest font size to use.
+    pub min: f32,
+    /// The largest font size to use.
+    pub max: f32,
+}
+
+impl FontSizeQuantization {
+    ///
+    /// Quantize the given font size. The min takes priority if it is above the max.
+    ///
+    pub fn apply(&self, font_size: f32) -> f32 {
+        let rounded = if self.step > 0f32 {
+            (font_size / self.step).round() * self.step
+        } else {
+            font_size
+        };
+        rounded.min(self.max).max(self.min)
     }
 }
 
@@ -63,8 +234,74 @@ impl Plugin for DynamicFontsizePlugin {
 pub struct DynamicFontSize {
     /// Font size (height) in world units. The plugin will pin the text to this size in the world.
     pub height_in_world: f32,
-    /// The 2D camera rendering this text entity. Dynamic resizing is based on its projection.
+    ///
+    /// The camera rendering this text entity. Dynamic resizing is based on its projection,
+    /// and the size of its viewport on whichever render target it draws to.
+    ///
+    /// With a perspective projection, the text is sized for its depth in front of the camera
+    /// and the camera's field of view. Moving the text or camera doesn't resize the text by
+    /// itself; it is resized on the next change to the window, projection or viewport.
+    ///
+    pub render_camera: Entity,
+    ///
+    /// Optional maximum width of the text in world units. If the laid-out text would be wider
+    /// than this at height_in_world, its font is shrunk until it fits, so the text fits in a box
+    /// of max_width_in_world by height_in_world. The text stays pixel-perfect, and is refitted
+    /// (after the debounce time) whenever its Text2d content changes. None to only pin height.
+    ///
+    pub max_width_in_world: Option<f32>,
+}
+
+///
+/// This component should be added to Bevy UI text nodes that need to be dynamically
+/// sized. It requires a Text component on the same entity.
+///
+/// The font size is given in logical pixels for a viewport of the reference resolution,
+/// and is scaled with the viewport of the render camera. The scaling follows whichever of
+/// the viewport's width and height is smaller relative to the reference resolution, so a
+/// layout designed at the reference resolution still fits.
+///
+#[derive(Component)]
+#[require(Text)]
+pub struct DynamicUiFontSize {
+    /// Font size in logical pixels when the viewport is at the reference resolution.
+    pub font_size: f32,
+    /// The viewport size (in logical pixels) the font size is designed for.
+    pub reference_resolution: Vec2,
+    /// The camera whose viewport this UI text is drawn to.
+    pub render_camera: Entity,
+}
+
+///
+/// Message written when a text entity with DynamicFontSize or DynamicUiFontSize can't be
+/// sized. Other text entities are still sized as normal, and this one keeps its previous
+/// size. It is written each time the text is due to be resized, after the debounce time.
+///
+#[derive(Message, Clone, Copy, Debug, PartialEq)]
+pub struct DynamicFontSizeError {
+    /// The text entity which couldn't be sized.
+    pub text: Entity,
+    /// The render camera configured for the text entity.
     pub render_camera: Entity,
+    /// Why the text entity couldn't be sized.
+    pub kind: DynamicFontSizeErrorKind,
+}
+
+///
+/// The reasons a text entity can't be sized, as reported by DynamicFontSizeError.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DynamicFontSizeErrorKind {
+    /// The render camera doesn't exist, or isn't a camera with a projection.
+    MissingCamera,
+    /// The render camera uses a custom projection, which can't be measured.
+    UnsupportedProjection,
+    ///
+    /// The viewport of the render camera has no size (e.g. the window is minimized, or its
+    /// render target isn't known yet), or the view has no size where the text is (e.g. text
+    /// behind a perspective camera). For UI text, this includes a zero reference resolution.
+    ///
+    ZeroSizeViewport,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -86,6 +323,15 @@ struct WindowResizeDebouncer {
     duration: Duration,
 }
 
+//
+// This resource holds the plugin's configuration for how font sizes are computed.
+//
+#[derive(Resource, Default)]
+struct FontSizeSettings {
+    // Quantization to apply to computed font sizes, if any.
+    quantization: Option<FontSizeQuantization>,
+}
+
 impl WindowResizeDebouncer {
     fn from_duration(duration: Duration) -> Self {
         Self {
@@ -93,8 +339,23 @@ impl WindowResizeDebouncer {
             duration,
         }
     }
+
+    // (Re)start the timer for the full debounce duration.
+    fn restart(&mut self) {
+        self.timer = Timer::new(self.duration, TimerMode::Once);
+    }
 }
 
+// -------------------------------------------------------------------------------------------------
+// Private Components
+
+//
+// Marker component added to text entities once they have been sized for the first time,
+// so that later resizing goes through the debouncer.
+//
+#[derive(Component)]
+struct InitiallySized;
+
 // -------------------------------------------------------------------------------------------------
 // Private Systems
 
@@ -110,26 +371,81 @@ fn handle_window_resize(
 ) {
     if !messages.is_empty() {
         messages.clear();
-        debouncer.timer = Timer::new(debouncer.duration, TimerMode::Once);
+        debouncer.restart();
     }
 }
 
+//
+// This system detects any change affecting the sizing of text entities other than a window
+// resize: a change to the projection or Camera (e.g. its viewport or render target) of a
+// camera which renders dynamically sized text, or a DynamicFontSize or DynamicUiFontSize
+// being added or changed. These start the debounce timer just like a window resize, so text
+// is resized through the same path once the changes settle.
+//
+// For text with a max width, a change to its content also starts the debounce timer, so it
+// is refitted once the new content has been laid out.
+//
+fn handle_camera_change(
+    fonts: Query<(Ref<DynamicFontSize>, Ref<Text2d>)>,
+    ui_fonts: Query<Ref<DynamicUiFontSize>>,
+    cameras: Query<Ref<Camera>>,
+    projections: Query<Ref<Projection>>,
+    mut debouncer: ResMut<WindowResizeDebouncer>,
+) {
+    let font_cfgs = fonts.iter().map(|(font_cfg, text)| {
+        let refit = font_cfg.max_width_in_world.is_some() && text.is_changed();
+        (font_cfg.is_changed() || refit, font_cfg.render_camera)
+    });
+    let ui_font_cfgs = ui_fonts
+        .iter()
+        .map(|font_cfg| (font_cfg.is_changed(), font_cfg.render_camera));
+    let changed = font_cfgs.chain(ui_font_cfgs).any(|(cfg_changed, camera)| {
+        cfg_changed
+            || cameras.get(camera).is_ok_and(|camera| camera.is_changed())
+            || projections
+                .get(camera)
+                .is_ok_and(|projection| projection.is_changed())
+    });
+    if changed {
+        debouncer.restart();
+    }
+}
+
+// The components of a DynamicFontSize text entity used to size it.
+type FontSizing = (
+    Entity,
+    &'static DynamicFontSize,
+    &'static GlobalTransform,
+    &'static TextLayoutInfo,
+    &'static mut TextFont,
+    &'static mut Transform,
+);
+
 //
 // This system is responsible for the actual resizing of relevant text entities. It only
 // provides this functionality if the debounce timer has just completed. Note that
 // adjustments are only performed on entities with DynamicFontSize components.
-// The system will use the camera's projection to detect the new on-screen size of
-// the text, and set its font size and scale accordingly.
+// The system will use the camera's projection and the size of its viewport to detect
+// the new on-screen size of the text, and set its font size and scale accordingly.
 //
-// Note: if the render camera is invalid or doesn't use an orthographic projection,
-// no text sizing adjustments will be performed for that entity.
+// The viewport is the area of the camera's render target it draws to (in logical pixels),
+// whether that target is a window or an image, so each camera is handled independently.
+//
+// UI text nodes with DynamicUiFontSize are resized at the same time, scaling their font
+// size with the viewport of their camera.
+//
+// Note: if the render camera is invalid, uses a custom projection, or its render target
+// isn't known yet, no text sizing adjustments will be performed for that entity. Instead,
+// a DynamicFontSizeError is written, and the remaining entities are still sized.
 //
 fn handle_font_resize(
     time: Res<Time>,
     mut debouncer: ResMut<WindowResizeDebouncer>,
-    window: Single<&Window>,
-    fonts: Query<(&DynamicFontSize, &mut TextFont, &mut Transform)>,
-    projections: Query<&Projection>,
+    fonts: Query<FontSizing>,
+    ui_fonts: Query<(Entity, &DynamicUiFontSize, &mut TextFont), Without<DynamicFontSize>>,
+    cameras: Query<(&Camera, &Projection, &GlobalTransform)>,
+    settings: Res<FontSizeSettings>,
+    mut errors: MessageWriter<DynamicFontSizeError>,
 ) {
     debouncer.timer.tick(time.delta());
 
@@ -137,26 +453,202 @@ fn handle_font_resize(
         return;
     }
 
-    for (font_cfg, mut font, mut transform) in fonts {
-        let projection = projections.get(font_cfg.render_camera);
+    for (entity, font_cfg, text_transform, layout, mut font, mut transform) in fonts {
+        let sized = cameras
+            .get(font_cfg.render_camera)
+            .map_err(|_| DynamicFontSizeErrorKind::MissingCamera)
+            .and_then(|camera| {
+                fit_font_size(
+                    &settings,
+                    font_cfg,
+                    camera,
+                    text_transform,
+                    layout,
+                    &mut font,
+                    &mut transform,
+                )
+            });
+
+        if let Err(kind) = sized {
+            errors.write(DynamicFontSizeError {
+                text: entity,
+                render_camera: font_cfg.render_camera,
+                kind,
+            });
+        }
+    }
+
+    for (entity, font_cfg, mut font) in ui_fonts {
+        let sized = cameras
+            .get(font_cfg.render_camera)
+            .map_err(|_| DynamicFontSizeErrorKind::MissingCamera)
+            .and_then(|(camera, _, _)| fit_ui_font_size(&settings, font_cfg, camera, &mut font));
+
+        if let Err(kind) = sized {
+            errors.write(DynamicFontSizeError {
+                text: entity,
+                render_camera: font_cfg.render_camera,
+                kind,
+            });
+        }
+    }
+}
+
+// UI text nodes which haven't been sized yet, kept apart from DynamicFontSize text entities.
+type UnsizedUiFont = (Without<InitiallySized>, Without<DynamicFontSize>);
 
-        let Ok(Projection::Orthographic(projection)) = projection else {
-            // If we can't find the associated projection, just leave the sizing.
-            return;
+//
+// This system sizes text entities which haven't been sized yet, without waiting for the
+// debouncer. Entities whose camera isn't ready yet (e.g. it hasn't been spawned, or its
+// render target and projection haven't been computed) are left until a later frame, when
+// they are sized as soon as the camera is ready. As this is expected for a few frames, no
+// DynamicFontSizeError is written here; handle_font_resize reports text which stays unsized.
+//
+fn size_new_fonts(
+    mut commands: Commands,
+    fonts: Query<FontSizing, Without<InitiallySized>>,
+    ui_fonts: Query<(Entity, &DynamicUiFontSize, &mut TextFont), UnsizedUiFont>,
+    cameras: Query<(&Camera, &Projection, &GlobalTransform)>,
+    settings: Res<FontSizeSettings>,
+) {
+    for (entity, font_cfg, text_transform, layout, mut font, mut transform) in fonts {
+        let Ok(camera) = cameras.get(font_cfg.render_camera) else {
+            continue;
+        };
+
+        if fit_font_size(
+            &settings,
+            font_cfg,
+            camera,
+            text_transform,
+            layout,
+            &mut font,
+            &mut transform,
+        )
+        .is_ok()
+        {
+            commands.entity(entity).insert(InitiallySized);
+        }
+    }
+
+    for (entity, font_cfg, mut font) in ui_fonts {
+        let Ok((camera, _, _)) = cameras.get(font_cfg.render_camera) else {
+            continue;
         };
 
-        // projection.area includes entire panel of window in world units, even
+        if fit_ui_font_size(&settings, font_cfg, camera, &mut font).is_ok() {
+            commands.entity(entity).insert(InitiallySized);
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Functions
+
+//
+// Sets the font size and scale of a text entity so it is pixel-perfect at its configured
+// height in the world, for the given camera. Returns an error without changing anything if
+// the camera's projection isn't supported, or it (or its viewport) has no height yet.
+//
+// If the text has a max width, the font size is shrunk as needed for the laid-out text to
+// fit. The layout is from the current font size, and its width scales with the font size.
+// Text which hasn't been laid out yet is only sized for its height.
+//
+// If the font size is quantized, the scale is set from the quantized size instead, so the
+// text still has its configured height (or fitted width) in the world.
+//
+fn fit_font_size(
+    settings: &FontSizeSettings,
+    font_cfg: &DynamicFontSize,
+    (camera, projection, camera_transform): (&Camera, &Projection, &GlobalTransform),
+    text_transform: &GlobalTransform,
+    layout: &TextLayoutInfo,
+    font: &mut TextFont,
+    transform: &mut Transform,
+) -> Result<(), DynamicFontSizeErrorKind> {
+    let cam_height = match projection {
+        // projection.area includes the entire viewport in world units, even
         // if there are borders or cropped out bits.
-        let cam_height = projection.area.height();
-        let win_height = window.height();
-
-        // Skip on 0 to cover "minimize" case and prevent divide-by-zero scenario
-        if (cam_height > 0f32) && (win_height > 0f32) {
-            // win_height / cam_height gives us conversion b/t in-world and pixel units
-            font.font_size = (font_cfg.height_in_world / cam_height) * win_height;
-            transform.scale = Vec3::splat(cam_height / win_height);
+        Projection::Orthographic(ortho) => ortho.area.height(),
+
+        // The height of the view at the text's depth along the camera's view direction
+        Projection::Perspective(persp) => {
+            let depth = (text_transform.translation() - camera_transform.translation())
+                .dot(*camera_transform.forward());
+            2f32 * depth * (persp.fov / 2f32).tan()
+        }
+        Projection::Custom(_) => return Err(DynamicFontSizeErrorKind::UnsupportedProjection),
+    };
+    let viewport_height = viewport_size(camera).y;
+
+    // Skip on 0 to cover "minimize" case and prevent divide-by-zero scenario. This also
+    // skips text which is behind a perspective camera.
+    if (cam_height <= 0f32) || (viewport_height <= 0f32) {
+        return Err(DynamicFontSizeErrorKind::ZeroSizeViewport);
+    }
+
+    // viewport_height / cam_height gives us conversion b/t in-world and pixel units
+    let mut font_size = (font_cfg.height_in_world / cam_height) * viewport_height;
+
+    if let Some(max_width) = font_cfg.max_width_in_world
+        && (layout.size.x > 0f32)
+        && (font.font_size > 0f32)
+    {
+        let width_in_world =
+            layout.size.x * (font_size / font.font_size) * (cam_height / viewport_height);
+        if width_in_world > max_width {
+            font_size *= max_width / width_in_world;
+        }
+    }
+
+    match settings.quantization {
+        None => {
+            font.font_size = font_size;
+            transform.scale = Vec3::splat(cam_height / viewport_height);
+        }
+        Some(quantization) => {
+            font.font_size = quantization.apply(font_size);
+            transform.scale =
+                Vec3::splat(font_size * cam_height / viewport_height / font.font_size);
         }
     }
+    Ok(())
+}
+
+//
+// Sets the font size of a UI text node, scaling its configured size at the reference
+// resolution by the size of the camera's viewport. Returns an error without changing
+// anything if the viewport or reference resolution has no size.
+//
+fn fit_ui_font_size(
+    settings: &FontSizeSettings,
+    font_cfg: &DynamicUiFontSize,
+    camera: &Camera,
+    font: &mut TextFont,
+) -> Result<(), DynamicFontSizeErrorKind> {
+    let viewport_size = viewport_size(camera);
+
+    if (viewport_size.min_element() <= 0f32)
+        || (font_cfg.reference_resolution.min_element() <= 0f32)
+    {
+        return Err(DynamicFontSizeErrorKind::ZeroSizeViewport);
+    }
+
+    let font_size =
+        font_cfg.font_size * (viewport_size / font_cfg.reference_resolution).min_element();
+    font.font_size = settings
+        .quantization
+        .map_or(font_size, |quantization| quantization.apply(font_size));
+    Ok(())
+}
+
+//
+// The size of the camera's viewport in logical pixels, or zero if the camera's render target
+// isn't known yet. Font sizes are in logical pixels too, as Bevy rasterizes text at the scale
+// factor of the render target.
+//
+fn viewport_size(camera: &Camera) -> Vec2 {
+    camera.logical_viewport_size().unwrap_or(Vec2::ZERO)
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -165,7 +657,8 @@ fn handle_font_resize(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use bevy::window::WindowResolution;
+    use bevy::camera::{ComputedCameraValues, RenderTargetInfo, Viewport};
+    use bevy_test_helpers::prelude::*;
 
     #[test]
     fn test_plugin_build() {
@@ -194,7 +687,9 @@ mod tests {
         // Validate systems were added to Update schedule as intended
         let mut exp_update_systems = [
             (core::any::type_name_of_val(&handle_window_resize), false),
+            (core::any::type_name_of_val(&handle_camera_change), false),
             (core::any::type_name_of_val(&handle_font_resize), false),
+            (core::any::type_name_of_val(&size_new_fonts), false),
         ];
         app.get_schedule(Update)
             .expect("Expected Update schedule to exist in app")
@@ -226,9 +721,16 @@ mod tests {
     #[test]
     fn test_plugin_build_nondefault() {
         let mut app = App::new();
-        app.add_plugins(DynamicFontsizePlugin {
-            debounce_time: Duration::from_secs(4),
-        });
+        let quantization = FontSizeQuantization {
+            step: 2f32,
+            min: 8f32,
+            max: 64f32,
+        };
+        app.add_plugins(
+            DynamicFontsizePlugin::default()
+                .with_debounce_time(Duration::from_secs(4))
+                .with_quantization(quantization),
+        );
 
         // Validate WindowResizeDebouncer is created appropriately by plugin build
         let world = app.world();
@@ -244,6 +746,77 @@ mod tests {
                 );
             }
         }
+
+        // Validate FontSizeSettings carries the rest of the plugin cfg
+        let settings = world.resource::<FontSizeSettings>();
+        assert_eq!(settings.quantization, Some(quantization));
+    }
+
+    #[test]
+    fn test_plugin_build_placement() {
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        struct AppFontSystems;
+
+        // Each system is in its public set, by default in Update
+        validate_sys_in_plugin(
+            DynamicFontsizePlugin::default(),
+            Update,
+            handle_window_resize,
+            Some(Systems::DetectChanges),
+        );
+        validate_sys_in_plugin(
+            DynamicFontsizePlugin::default(),
+            Update,
+            handle_camera_change,
+            Some(Systems::DetectChanges),
+        );
+        validate_sys_in_plugin(
+            DynamicFontsizePlugin::default(),
+            Update,
+            handle_font_resize,
+            Some(Systems::Resize),
+        );
+        validate_sys_in_plugin(
+            DynamicFontsizePlugin::default(),
+            Update,
+            size_new_fonts,
+            Some(Systems::Resize),
+        );
+
+        // Or in the configured schedule and set
+        let placed = || {
+            DynamicFontsizePlugin::default()
+                .in_schedule(PostUpdate)
+                .in_set(AppFontSystems)
+        };
+        validate_sys_in_plugin(
+            placed(),
+            PostUpdate,
+            handle_window_resize,
+            Some(AppFontSystems),
+        );
+        validate_sys_in_plugin(
+            placed(),
+            PostUpdate,
+            handle_camera_change,
+            Some(AppFontSystems),
+        );
+        validate_sys_in_plugin(
+            placed(),
+            PostUpdate,
+            handle_font_resize,
+            Some(AppFontSystems),
+        );
+        validate_sys_in_plugin(placed(), PostUpdate, size_new_fonts, Some(AppFontSystems));
+        validate_sys_in_plugin(placed(), PostUpdate, size_new_fonts, Some(Systems::Resize));
+
+        let mut app = App::new();
+        app.add_plugins(placed());
+        assert!(
+            app.get_schedule(Update)
+                .is_none_or(|schedule| schedule.systems_len() == 0),
+            "Expected no systems left in Update when placed in PostUpdate",
+        );
     }
 
     #[test]
@@ -304,12 +877,306 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_handle_camera_change_system() {
+        let mut world = World::default();
+        let change_sys = world.register_system(handle_camera_change);
+        let mut inflight_timer = Timer::new(Duration::from_secs(1), TimerMode::Once);
+        inflight_timer.tick(Duration::from_millis(500));
+        world.insert_resource(WindowResizeDebouncer {
+            duration: Duration::from_secs(1),
+            timer: inflight_timer.clone(),
+        });
+
+        // Run the system with no text entities. Expect nothing to change
+        let camera = world
+            .spawn((
+                Camera::default(),
+                Projection::Orthographic(OrthographicProjection::default_2d()),
+            ))
+            .id();
+        let other_camera = world
+            .spawn(Projection::Orthographic(
+                OrthographicProjection::default_2d(),
+            ))
+            .id();
+        world.run_system(change_sys).unwrap();
+        assert_eq!(
+            world.resource::<WindowResizeDebouncer>().timer,
+            inflight_timer,
+            "Expected no change to timer with no text entities",
+        );
+
+        // Newly spawned text starts the timer
+        let txt = world
+            .spawn(DynamicFontSize {
+                height_in_world: 1f32,
+                render_camera: camera,
+                max_width_in_world: None,
+            })
+            .id();
+        world.run_system(change_sys).unwrap();
+        assert_debounce_restarted(&mut world, &inflight_timer, "after spawning text");
+
+        // Nothing changed since, so nothing to do
+        world.run_system(change_sys).unwrap();
+        assert_eq!(
+            world.resource::<WindowResizeDebouncer>().timer,
+            inflight_timer,
+            "Expected no change to timer when nothing changed",
+        );
+
+        // A change to the projection of a camera not rendering text is ignored
+        world
+            .get_mut::<Projection>(other_camera)
+            .unwrap()
+            .set_changed();
+        world.run_system(change_sys).unwrap();
+        assert_eq!(
+            world.resource::<WindowResizeDebouncer>().timer,
+            inflight_timer,
+            "Expected no change to timer when an unrelated projection changed",
+        );
+
+        // A change to the projection of the text's camera starts the timer
+        if let Projection::Orthographic(ortho) =
+            world.get_mut::<Projection>(camera).unwrap().into_inner()
+        {
+            ortho.scale = 2f32;
+        }
+        world.run_system(change_sys).unwrap();
+        assert_debounce_restarted(&mut world, &inflight_timer, "after projection change");
+
+        // As does a change to the text's DynamicFontSize
+        world
+            .get_mut::<DynamicFontSize>(txt)
+            .unwrap()
+            .height_in_world = 2f32;
+        world.run_system(change_sys).unwrap();
+        assert_debounce_restarted(&mut world, &inflight_timer, "after DynamicFontSize change");
+
+        // And a change to the viewport of the text's camera
+        world.get_mut::<Camera>(camera).unwrap().viewport = Some(Viewport::default());
+        world.run_system(change_sys).unwrap();
+        assert_debounce_restarted(&mut world, &inflight_timer, "after viewport change");
+    }
+
+    #[test]
+    fn test_projection_change_resizes_text() {
+        let mut app = App::new();
+        app.add_plugins(DynamicFontsizePlugin::default());
+        app.init_resource::<Time>().add_message::<WindowResized>();
+        let camera = app
+            .world_mut()
+            .spawn((
+                camera_with_target(UVec2::new(400, 200)),
+                Projection::Orthographic(OrthographicProjection {
+                    area: Rect::new(0f32, 0f32, 20f32, 10f32),
+                    ..OrthographicProjection::default_2d()
+                }),
+            ))
+            .id();
+        let txt = app
+            .world_mut()
+            .spawn(DynamicFontSize {
+                height_in_world: 1f32,
+                render_camera: camera,
+                max_width_in_world: None,
+            })
+            .id();
+
+        // Text is sized in the first frame after spawning
+        advance_and_update(&mut app, Duration::ZERO);
+        assert_eq!(app.world().get::<TextFont>(txt).unwrap().font_size, 20f32);
+        advance_and_update(&mut app, DEFAULT_DEBOUNCE_DURATION);
+
+        // Then resized, without any window resize, once a zoom settles
+        if let Projection::Orthographic(ortho) = app
+            .world_mut()
+            .get_mut::<Projection>(camera)
+            .unwrap()
+            .into_inner()
+        {
+            ortho.area = Rect::new(0f32, 0f32, 10f32, 5f32);
+        }
+        advance_and_update(&mut app, DEFAULT_DEBOUNCE_DURATION / 2);
+        assert_eq!(
+            app.world().get::<TextFont>(txt).unwrap().font_size,
+            20f32,
+            "Expected no resize before the debounce time has passed",
+        );
+        advance_and_update(&mut app, DEFAULT_DEBOUNCE_DURATION);
+        let font_size = app.world().get::<TextFont>(txt).unwrap().font_size;
+        assert_eq!(
+            font_size, 40f32,
+            "Expected text resized for the zoomed projection"
+        );
+        assert_eq!(
+            app.world().get::<Transform>(txt).unwrap().scale,
+            Vec3::splat(5f32 / 200f32),
+        );
+    }
+
+    #[test]
+    fn test_handle_font_resize_per_camera() {
+        let mut world = World::default();
+        world.init_resource::<Time>();
+        world.init_resource::<Messages<DynamicFontSizeError>>();
+        world.init_resource::<FontSizeSettings>();
+        world.insert_resource(WindowResizeDebouncer::from_duration(Duration::ZERO));
+        world.resource_mut::<WindowResizeDebouncer>().restart();
+        let projection = Projection::Orthographic(OrthographicProjection {
+            area: Rect::new(0f32, 0f32, 20f32, 10f32),
+            ..OrthographicProjection::default_2d()
+        });
+
+        // Left half of a split-screen window, and a camera drawing to another target
+        // whose scale factor is 2
+        let mut split_camera = camera_with_target(UVec2::new(800, 400));
+        split_camera.viewport = Some(Viewport {
+            physical_size: UVec2::new(400, 400),
+            ..default()
+        });
+        let mut other_camera = camera_with_target(UVec2::new(1000, 600));
+        other_camera
+            .computed
+            .target_info
+            .as_mut()
+            .unwrap()
+            .scale_factor = 2f32;
+        let split_camera = world.spawn((split_camera, projection.clone())).id();
+        let other_camera = world.spawn((other_camera, projection)).id();
+        let txt1 = world
+            .spawn(DynamicFontSize {
+                height_in_world: 1f32,
+                render_camera: split_camera,
+                max_width_in_world: None,
+            })
+            .id();
+        let txt2 = world
+            .spawn(DynamicFontSize {
+                height_in_world: 1f32,
+                render_camera: other_camera,
+                max_width_in_world: None,
+            })
+            .id();
+
+        world.run_system_cached(handle_font_resize).unwrap();
+        assert_eq!(
+            world.get::<TextFont>(txt1).unwrap().font_size,
+            40f32,
+            "Expected text sized for the 400 pixel high viewport of its camera",
+        );
+        assert_eq!(
+            world.get::<TextFont>(txt2).unwrap().font_size,
+            30f32,
+            "Expected text sized for the 300 logical pixel high target of its camera",
+        );
+        assert_eq!(
+            world.get::<Transform>(txt2).unwrap().scale,
+            Vec3::splat(10f32 / 300f32),
+        );
+    }
+
+    #[test]
+    fn test_size_new_fonts_system() {
+        let mut world = World::default();
+        world.init_resource::<FontSizeSettings>();
+        let camera = world
+            .spawn((
+                camera_with_target(UVec2::new(400, 200)),
+                Projection::Orthographic(OrthographicProjection {
+                    area: Rect::new(0f32, 0f32, 20f32, 10f32),
+                    ..OrthographicProjection::default_2d()
+                }),
+            ))
+            .id();
+        let txt = world
+            .spawn(DynamicFontSize {
+                height_in_world: 1f32,
+                render_camera: camera,
+                max_width_in_world: None,
+            })
+            .id();
+
+        // New text is sized on the first run, without waiting for the debouncer
+        world.run_system_cached(size_new_fonts).unwrap();
+        assert_eq!(
+            world.get::<TextFont>(txt).unwrap().font_size,
+            20f32,
+            "Expected new text sized straight away",
+        );
+        assert_eq!(
+            world.get::<Transform>(txt).unwrap().scale,
+            Vec3::splat(10f32 / 200f32),
+        );
+
+        // Once sized, it's left to the debouncer
+        world.get_mut::<TextFont>(txt).unwrap().font_size = 5f32;
+        world.run_system_cached(size_new_fonts).unwrap();
+        assert_eq!(
+            world.get::<TextFont>(txt).unwrap().font_size,
+            5f32,
+            "Expected text not to be sized again by the initial sizing",
+        );
+    }
+
+    #[test]
+    fn test_size_new_fonts_camera_not_ready() {
+        let mut world = World::default();
+        world.init_resource::<FontSizeSettings>();
+
+        // The camera's entity exists, but it isn't a camera yet
+        let camera = world.spawn_empty().id();
+        let txt = world
+            .spawn(DynamicFontSize {
+                height_in_world: 1f32,
+                render_camera: camera,
+                max_width_in_world: None,
+            })
+            .id();
+        world.run_system_cached(size_new_fonts).unwrap();
+        assert_eq!(
+            world.get::<TextFont>(txt).unwrap().font_size,
+            TextFont::default().font_size,
+            "Expected no sizing before the camera exists",
+        );
+
+        // The camera is spawned, but its render target and projection aren't computed yet
+        world.entity_mut(camera).insert((
+            Camera::default(),
+            Projection::Orthographic(OrthographicProjection::default_2d()),
+        ));
+        world.run_system_cached(size_new_fonts).unwrap();
+        assert_eq!(
+            world.get::<TextFont>(txt).unwrap().font_size,
+            TextFont::default().font_size,
+            "Expected no sizing before the camera is ready",
+        );
+
+        // Sized in the first frame the camera is ready
+        *world.get_mut::<Camera>(camera).unwrap() = camera_with_target(UVec2::new(400, 200));
+        if let Projection::Orthographic(ortho) =
+            world.get_mut::<Projection>(camera).unwrap().into_inner()
+        {
+            ortho.area = Rect::new(0f32, 0f32, 20f32, 10f32);
+        }
+        world.run_system_cached(size_new_fonts).unwrap();
+        assert_eq!(
+            world.get::<TextFont>(txt).unwrap().font_size,
+            20f32,
+            "Expected text sized once the camera is ready",
+        );
+    }
+
     #[test]
     fn test_handle_font_resize_system() {
         let mut world = World::default();
 
         // Insert necessary resources. Timer primed to fire in 1 sec
         world.init_resource::<Time>();
+        world.init_resource::<Messages<DynamicFontSizeError>>();
+        world.init_resource::<FontSizeSettings>();
         world.insert_resource(WindowResizeDebouncer {
             duration: Duration::from_secs(1),
             timer: Timer::new(Duration::from_secs(1), TimerMode::Once),
@@ -318,56 +1185,62 @@ mod tests {
         // Local copy of some configured heights, for easier access
         let height_in_world_1 = 4f32;
         let height_in_world_2 = 30f32;
-        let win_height = 200;
+        let viewport_height = 200;
         let proj_height = 20f32;
 
         // First, create and run setup system to get Entities in place and store their id's
         let setup_sys = world.register_system(
             // Create a couple text elements for system to act on, plus projection
             move |mut commands: Commands| {
-                commands.spawn(Window {
-                    resolution: WindowResolution::new(0, 0), // Start with 0 scenario
-                    ..default()
-                });
                 let p_ortho = commands
-                    .spawn(Projection::Orthographic(OrthographicProjection {
-                        area: Rect::new(0f32, 0f32, 0f32, 0f32), // Start with 0 scenario
-                        ..OrthographicProjection::default_2d()
-                    }))
+                    .spawn((
+                        camera_with_target(UVec2::ZERO), // Start with 0 scenario
+                        Projection::Orthographic(OrthographicProjection {
+                            area: Rect::new(0f32, 0f32, 0f32, 0f32), // Start with 0 scenario
+                            ..OrthographicProjection::default_2d()
+                        }),
+                    ))
                     .id();
                 let p_persp = commands
-                    .spawn(Projection::Perspective(PerspectiveProjection::default()))
+                    .spawn((
+                        camera_with_target(UVec2::new(500, 200)),
+                        Projection::Perspective(PerspectiveProjection::default()),
+                    ))
                     .id();
                 let txt1 = commands
                     .spawn((DynamicFontSize {
                         height_in_world: height_in_world_1,
                         render_camera: p_ortho,
+                        max_width_in_world: None,
                     },))
                     .id();
                 let txt2 = commands
                     .spawn((DynamicFontSize {
                         height_in_world: height_in_world_2,
                         render_camera: p_ortho,
+                        max_width_in_world: None,
                     },))
                     .id();
                 let txt3 = commands
                     .spawn((DynamicFontSize {
                         height_in_world: 100f32,
                         render_camera: p_persp,
+                        max_width_in_world: None,
                     },))
                     .id();
                 let txt4 = commands
                     .spawn((DynamicFontSize {
                         height_in_world: 100f32,
                         render_camera: Entity::PLACEHOLDER,
+                        max_width_in_world: None,
                     },))
                     .id();
 
                 // Return each entity id for test to use
-                (p_ortho, txt1, txt2, txt3, txt4)
+                (p_ortho, p_persp, txt1, txt2, txt3, txt4)
             },
         );
-        let (proj, txt1, txt2, txt3, txt4) = world.run_system(setup_sys).unwrap();
+        let (proj, p_persp, txt1, txt2, txt3, txt4) = world.run_system(setup_sys).unwrap();
 
         // Register resize_sys, which we are testing
         let resize_sys = world.register_system(handle_font_resize);
@@ -434,9 +1307,9 @@ mod tests {
             "Expected debounce timer to have finished on last tick during resize system",
         );
 
-        // Now prime for real test. Set projection and window size to non-zero, and reset timer.
-        let mut win = world.query::<&mut Window>().single_mut(&mut world).unwrap();
-        win.resolution = WindowResolution::new(500, win_height);
+        // Now prime for real test. Set projection and viewport size to non-zero, and reset timer.
+        *world.get_mut::<Camera>(proj).unwrap() =
+            camera_with_target(UVec2::new(500, viewport_height));
         let proj = world
             .query::<&mut Projection>()
             .get_mut(&mut world, proj)
@@ -456,6 +1329,9 @@ mod tests {
             .advance_by(Duration::from_millis(1100));
 
         // Trigger the system again. Now we will expect the entities to be updated
+        world
+            .resource_mut::<Messages<DynamicFontSizeError>>()
+            .clear();
         world
             .run_system(resize_sys)
             .expect("Expected resize system to succeed on third run (nominal case)");
@@ -465,12 +1341,12 @@ mod tests {
         let (font, transform) = query.get(&world, txt1).unwrap();
         assert_eq!(
             font.font_size,
-            (height_in_world_1 / proj_height) * win_height as f32,
+            (height_in_world_1 / proj_height) * viewport_height as f32,
             "Expected TextFont 1 to have correctly-adjusted size on third run (nominal case)",
         );
         assert_eq!(
             transform.scale.y,
-            proj_height / win_height as f32,
+            proj_height / viewport_height as f32,
             "Expected Transform 1 to have correctly-adjusted height on third run (nominal case)",
         );
         assert_eq!(
@@ -482,12 +1358,12 @@ mod tests {
         let (font, transform) = query.get(&world, txt2).unwrap();
         assert_eq!(
             font.font_size,
-            (height_in_world_2 / proj_height) * win_height as f32,
+            (height_in_world_2 / proj_height) * viewport_height as f32,
             "Expected TextFont 2 to have correctly-adjusted size on third run (nominal case)",
         );
         assert_eq!(
             transform.scale.y,
-            proj_height / win_height as f32,
+            proj_height / viewport_height as f32,
             "Expected Transform 2 to have correctly-adjusted height on third run (nominal case)",
         );
         assert_eq!(
@@ -495,7 +1371,8 @@ mod tests {
             "Expected Transform 2 to have equal x/y scaling on third run (nominal case)",
         );
 
-        // Validate remaining text elements (should not have been adjusted due to invalid cfg)
+        // Validate remaining text elements (should not have been adjusted due to invalid cfg,
+        // or text sitting at the perspective camera's position with no depth in front of it)
         for (idx, txt) in [txt3, txt4].iter().enumerate() {
             let (font, transform) = query.get(&world, *txt).unwrap();
             assert_eq!(
@@ -511,5 +1388,561 @@ mod tests {
                 idx + 3,
             );
         }
+        let mut errors = font_size_errors(&world);
+        errors.sort_by_key(|error| error.text.index());
+        assert_eq!(
+            errors,
+            [
+                DynamicFontSizeError {
+                    text: txt3,
+                    render_camera: p_persp,
+                    kind: DynamicFontSizeErrorKind::ZeroSizeViewport,
+                },
+                DynamicFontSizeError {
+                    text: txt4,
+                    render_camera: Entity::PLACEHOLDER,
+                    kind: DynamicFontSizeErrorKind::MissingCamera,
+                },
+            ],
+            "Expected errors for the text elements which couldn't be sized",
+        );
+    }
+
+    #[test]
+    fn test_handle_font_resize_mixed_entities() {
+        let mut world = World::default();
+        world.init_resource::<Time>();
+        world.init_resource::<Messages<DynamicFontSizeError>>();
+        world.init_resource::<FontSizeSettings>();
+        world.insert_resource(WindowResizeDebouncer::from_duration(Duration::ZERO));
+        world.resource_mut::<WindowResizeDebouncer>().restart();
+        let projection = Projection::Orthographic(OrthographicProjection {
+            area: Rect::new(0f32, 0f32, 20f32, 10f32),
+            ..OrthographicProjection::default_2d()
+        });
+        let camera = world
+            .spawn((camera_with_target(UVec2::new(400, 200)), projection.clone()))
+            .id();
+        let custom_camera = world
+            .spawn((
+                camera_with_target(UVec2::new(400, 200)),
+                Projection::custom(PerspectiveProjection::default()),
+            ))
+            .id();
+        let minimized_camera = world
+            .spawn((camera_with_target(UVec2::ZERO), projection))
+            .id();
+        let not_a_camera = world.spawn(Transform::default()).id();
+
+        // Invalid text entities spawned either side of valid ones, which must still be sized
+        let missing = world
+            .spawn(DynamicFontSize {
+                height_in_world: 1f32,
+                render_camera: Entity::PLACEHOLDER,
+                max_width_in_world: None,
+            })
+            .id();
+        let valid = world
+            .spawn(DynamicFontSize {
+                height_in_world: 1f32,
+                render_camera: camera,
+                max_width_in_world: None,
+            })
+            .id();
+        let custom = world
+            .spawn(DynamicFontSize {
+                height_in_world: 1f32,
+                render_camera: custom_camera,
+                max_width_in_world: None,
+            })
+            .id();
+        let minimized = world
+            .spawn(DynamicFontSize {
+                height_in_world: 1f32,
+                render_camera: minimized_camera,
+                max_width_in_world: None,
+            })
+            .id();
+        let ui_missing = world
+            .spawn(DynamicUiFontSize {
+                font_size: 20f32,
+                reference_resolution: Vec2::new(400f32, 200f32),
+                render_camera: not_a_camera,
+            })
+            .id();
+        let ui_valid = world
+            .spawn(DynamicUiFontSize {
+                font_size: 20f32,
+                reference_resolution: Vec2::new(200f32, 100f32),
+                render_camera: camera,
+            })
+            .id();
+        let valid2 = world
+            .spawn(DynamicFontSize {
+                height_in_world: 2f32,
+                render_camera: camera,
+                max_width_in_world: None,
+            })
+            .id();
+
+        world.run_system_cached(handle_font_resize).unwrap();
+        for (txt, exp_size) in [(valid, 20f32), (valid2, 40f32), (ui_valid, 40f32)] {
+            assert_eq!(
+                world.get::<TextFont>(txt).unwrap().font_size,
+                exp_size,
+                "Expected valid text {txt} sized despite invalid text entities",
+            );
+        }
+        for txt in [missing, custom, minimized, ui_missing] {
+            assert_eq!(
+                world.get::<TextFont>(txt).unwrap().font_size,
+                TextFont::default().font_size,
+                "Expected invalid text {txt} left unsized",
+            );
+        }
+
+        // Each invalid text entity is reported once, with the reason
+        let mut errors = font_size_errors(&world);
+        errors.sort_by_key(|error| error.text.index());
+        assert_eq!(
+            errors,
+            [
+                DynamicFontSizeError {
+                    text: missing,
+                    render_camera: Entity::PLACEHOLDER,
+                    kind: DynamicFontSizeErrorKind::MissingCamera,
+                },
+                DynamicFontSizeError {
+                    text: custom,
+                    render_camera: custom_camera,
+                    kind: DynamicFontSizeErrorKind::UnsupportedProjection,
+                },
+                DynamicFontSizeError {
+                    text: minimized,
+                    render_camera: minimized_camera,
+                    kind: DynamicFontSizeErrorKind::ZeroSizeViewport,
+                },
+                DynamicFontSizeError {
+                    text: ui_missing,
+                    render_camera: not_a_camera,
+                    kind: DynamicFontSizeErrorKind::MissingCamera,
+                },
+            ],
+        );
+
+        // Nothing is reported before the debouncer finishes again
+        world
+            .resource_mut::<Messages<DynamicFontSizeError>>()
+            .clear();
+        world.run_system_cached(handle_font_resize).unwrap();
+        assert!(font_size_errors(&world).is_empty());
+    }
+
+    #[test]
+    fn test_fit_font_size_perspective() {
+        let camera = camera_with_target(UVec2::new(400, 200));
+        let projection = Projection::Perspective(PerspectiveProjection {
+            fov: core::f32::consts::FRAC_PI_2,
+            ..default()
+        });
+        let camera_transform = GlobalTransform::from_xyz(5f32, 0f32, 0f32);
+        let font_cfg = DynamicFontSize {
+            height_in_world: 1f32,
+            render_camera: Entity::PLACEHOLDER,
+            max_width_in_world: None,
+        };
+
+        // 10 units in front of a camera with a 90 degree fov, the view is 20 units high
+        let mut font = TextFont::default();
+        let mut transform = Transform::default();
+        assert_eq!(
+            fit_font_size(
+                &FontSizeSettings::default(),
+                &font_cfg,
+                (&camera, &projection, &camera_transform),
+                &GlobalTransform::from_xyz(3f32, 4f32, -10f32),
+                &TextLayoutInfo::default(),
+                &mut font,
+                &mut transform,
+            ),
+            Ok(()),
+        );
+        assert!(
+            (font.font_size - 10f32).abs() < 0.001,
+            "Expected text sized for its depth, but got {}",
+            font.font_size,
+        );
+        assert!((transform.scale - Vec3::splat(0.1)).abs().max_element() < 0.0001);
+
+        // Further away, the same text is smaller on screen
+        assert_eq!(
+            fit_font_size(
+                &FontSizeSettings::default(),
+                &font_cfg,
+                (&camera, &projection, &camera_transform),
+                &GlobalTransform::from_xyz(5f32, 0f32, -40f32),
+                &TextLayoutInfo::default(),
+                &mut font,
+                &mut transform,
+            ),
+            Ok(()),
+        );
+        assert!((font.font_size - 2.5).abs() < 0.001);
+
+        // Text behind the camera is left alone
+        let mut font = TextFont::default();
+        assert_eq!(
+            fit_font_size(
+                &FontSizeSettings::default(),
+                &font_cfg,
+                (&camera, &projection, &camera_transform),
+                &GlobalTransform::from_xyz(5f32, 0f32, 10f32),
+                &TextLayoutInfo::default(),
+                &mut font,
+                &mut transform,
+            ),
+            Err(DynamicFontSizeErrorKind::ZeroSizeViewport),
+        );
+        assert_eq!(font.font_size, TextFont::default().font_size);
+    }
+
+    #[test]
+    fn test_fit_font_size_max_width() {
+        let camera = camera_with_target(UVec2::new(400, 200));
+        let projection = Projection::Orthographic(OrthographicProjection {
+            area: Rect::new(0f32, 0f32, 20f32, 10f32),
+            ..OrthographicProjection::default_2d()
+        });
+        let camera = (&camera, &projection, &GlobalTransform::IDENTITY);
+        let mut font_cfg = DynamicFontSize {
+            height_in_world: 1f32,
+            render_camera: Entity::PLACEHOLDER,
+            max_width_in_world: Some(5f32),
+        };
+
+        // Laid out 400 pixels wide at a font size of 20, the text is 20 units wide at its height
+        let layout = TextLayoutInfo {
+            size: Vec2::new(400f32, 20f32),
+            ..default()
+        };
+        let mut font = TextFont::from_font_size(20f32);
+        let mut transform = Transform::default();
+        let sized = fit_font_size(
+            &FontSizeSettings::default(),
+            &font_cfg,
+            camera,
+            &GlobalTransform::IDENTITY,
+            &layout,
+            &mut font,
+            &mut transform,
+        );
+        assert_eq!(sized, Ok(()));
+        assert_eq!(
+            font.font_size, 5f32,
+            "Expected font shrunk for the text to fit its max width",
+        );
+        assert_eq!(
+            transform.scale,
+            Vec3::splat(10f32 / 200f32),
+            "Expected the scale to stay pixel-perfect",
+        );
+
+        // Narrow enough text is only sized for its height
+        font_cfg.max_width_in_world = Some(30f32);
+        let mut font = TextFont::from_font_size(20f32);
+        let sized = fit_font_size(
+            &FontSizeSettings::default(),
+            &font_cfg,
+            camera,
+            &GlobalTransform::IDENTITY,
+            &layout,
+            &mut font,
+            &mut transform,
+        );
+        assert_eq!(sized, Ok(()));
+        assert_eq!(font.font_size, 20f32);
+
+        // As is text which hasn't been laid out yet
+        font_cfg.max_width_in_world = Some(5f32);
+        let sized = fit_font_size(
+            &FontSizeSettings::default(),
+            &font_cfg,
+            camera,
+            &GlobalTransform::IDENTITY,
+            &TextLayoutInfo::default(),
+            &mut font,
+            &mut transform,
+        );
+        assert_eq!(sized, Ok(()));
+        assert_eq!(font.font_size, 20f32);
+
+        // Quantized, the scale keeps the fitted width in the world
+        let quantized = FontSizeSettings {
+            quantization: Some(FontSizeQuantization {
+                step: 4f32,
+                min: 4f32,
+                max: 100f32,
+            }),
+        };
+        let mut font = TextFont::from_font_size(20f32);
+        let layout = TextLayoutInfo {
+            size: Vec2::new(480f32, 20f32),
+            ..default()
+        };
+        let sized = fit_font_size(
+            &quantized,
+            &font_cfg,
+            camera,
+            &GlobalTransform::IDENTITY,
+            &layout,
+            &mut font,
+            &mut transform,
+        );
+        assert_eq!(sized, Ok(()));
+        assert_eq!(font.font_size, 4f32);
+        let width_in_world = layout.size.x * (font.font_size / 20f32) * transform.scale.x;
+        assert!(
+            (width_in_world - 5f32).abs() < 0.001,
+            "Expected quantized text to still fit its max width, but it was {width_in_world}",
+        );
+    }
+
+    #[test]
+    fn test_handle_camera_change_refits_content() {
+        let mut world = World::default();
+        let change_sys = world.register_system(handle_camera_change);
+        let mut inflight_timer = Timer::new(Duration::from_secs(1), TimerMode::Once);
+        inflight_timer.tick(Duration::from_millis(500));
+        world.insert_resource(WindowResizeDebouncer {
+            duration: Duration::from_secs(1),
+            timer: inflight_timer.clone(),
+        });
+        let camera = world
+            .spawn((
+                Camera::default(),
+                Projection::Orthographic(OrthographicProjection::default_2d()),
+            ))
+            .id();
+        let fitted = world
+            .spawn((
+                DynamicFontSize {
+                    height_in_world: 1f32,
+                    render_camera: camera,
+                    max_width_in_world: Some(10f32),
+                },
+                Text2d::new("Player 1 Wins!"),
+            ))
+            .id();
+        let unfitted = world
+            .spawn((
+                DynamicFontSize {
+                    height_in_world: 1f32,
+                    render_camera: camera,
+                    max_width_in_world: None,
+                },
+                Text2d::new("0"),
+            ))
+            .id();
+        world.run_system(change_sys).unwrap();
+        assert_debounce_restarted(&mut world, &inflight_timer, "after spawning text");
+
+        // Changing the content of text without a max width doesn't need any resizing
+        world.get_mut::<Text2d>(unfitted).unwrap().0 = "1".into();
+        world.run_system(change_sys).unwrap();
+        assert_eq!(
+            world.resource::<WindowResizeDebouncer>().timer,
+            inflight_timer,
+            "Expected no change to timer after changing unfitted text",
+        );
+
+        // But text with a max width is refitted for its new content
+        world.get_mut::<Text2d>(fitted).unwrap().0 = "A Much Longer Name Wins!".into();
+        world.run_system(change_sys).unwrap();
+        assert_debounce_restarted(&mut world, &inflight_timer, "after changing fitted text");
+    }
+
+    #[test]
+    fn test_ui_font_resize() {
+        let mut world = World::default();
+        world.init_resource::<Time>();
+        world.init_resource::<Messages<DynamicFontSizeError>>();
+        world.init_resource::<FontSizeSettings>();
+        world.insert_resource(WindowResizeDebouncer::from_duration(Duration::ZERO));
+        let projection = Projection::Orthographic(OrthographicProjection::default_2d());
+        let wide_camera = world
+            .spawn((
+                camera_with_target(UVec2::new(1600, 900)),
+                projection.clone(),
+            ))
+            .id();
+        let narrow_camera = world
+            .spawn((camera_with_target(UVec2::new(400, 600)), projection))
+            .id();
+        let wide_txt = world
+            .spawn(DynamicUiFontSize {
+                font_size: 20f32,
+                reference_resolution: Vec2::new(800f32, 600f32),
+                render_camera: wide_camera,
+            })
+            .id();
+        let narrow_txt = world
+            .spawn(DynamicUiFontSize {
+                font_size: 20f32,
+                reference_resolution: Vec2::new(800f32, 600f32),
+                render_camera: narrow_camera,
+            })
+            .id();
+
+        // New UI text is sized straight away, following the more constrained dimension
+        world.run_system_cached(size_new_fonts).unwrap();
+        assert_eq!(
+            world.get::<TextFont>(wide_txt).unwrap().font_size,
+            30f32,
+            "Expected UI text scaled with the viewport height",
+        );
+        assert_eq!(
+            world.get::<TextFont>(narrow_txt).unwrap().font_size,
+            10f32,
+            "Expected UI text scaled with the viewport width",
+        );
+        assert!(world.get::<InitiallySized>(wide_txt).is_some());
+
+        // And resized with the rest of the text once the debouncer finishes
+        world.get_mut::<Camera>(wide_camera).unwrap().viewport = Some(Viewport {
+            physical_size: UVec2::new(800, 300),
+            ..default()
+        });
+        world.resource_mut::<WindowResizeDebouncer>().restart();
+        world.run_system_cached(handle_font_resize).unwrap();
+        assert_eq!(
+            world.get::<TextFont>(wide_txt).unwrap().font_size,
+            10f32,
+            "Expected UI text resized for the new viewport",
+        );
+        assert_eq!(world.get::<Transform>(wide_txt), None);
+    }
+
+    #[test]
+    fn test_font_size_quantization() {
+        let quantization = FontSizeQuantization {
+            step: 4f32,
+            min: 8f32,
+            max: 40f32,
+        };
+        assert_eq!(quantization.apply(21.9), 20f32);
+        assert_eq!(quantization.apply(22.1), 24f32);
+        assert_eq!(quantization.apply(2f32), 8f32, "Expected min clamp");
+        assert_eq!(quantization.apply(100f32), 40f32, "Expected max clamp");
+
+        // Without a step, only the clamp applies
+        let clamp_only = FontSizeQuantization {
+            step: 0f32,
+            ..quantization
+        };
+        assert_eq!(clamp_only.apply(21.9), 21.9);
+        assert_eq!(clamp_only.apply(41f32), 40f32);
+    }
+
+    #[test]
+    fn test_handle_font_resize_hidpi_and_quantized() {
+        let mut world = World::default();
+        world.init_resource::<Time>();
+        world.init_resource::<Messages<DynamicFontSizeError>>();
+        world.init_resource::<FontSizeSettings>();
+        world.insert_resource(WindowResizeDebouncer::from_duration(Duration::ZERO));
+
+        // A HiDPI target, 300 logical pixels high
+        let mut camera = camera_with_target(UVec2::new(1000, 600));
+        camera.computed.target_info.as_mut().unwrap().scale_factor = 2f32;
+        let camera = world
+            .spawn((
+                camera,
+                Projection::Orthographic(OrthographicProjection {
+                    area: Rect::new(0f32, 0f32, 20f32, 10f32),
+                    ..OrthographicProjection::default_2d()
+                }),
+            ))
+            .id();
+        let txt = world
+            .spawn(DynamicFontSize {
+                height_in_world: 1f32,
+                render_camera: camera,
+                max_width_in_world: None,
+            })
+            .id();
+        let ui_txt = world
+            .spawn(DynamicUiFontSize {
+                font_size: 10f32,
+                reference_resolution: Vec2::new(500f32, 300f32),
+                render_camera: camera,
+            })
+            .id();
+
+        // Sized for the logical pixels of the target, which Bevy rasterizes at its scale factor
+        world.resource_mut::<WindowResizeDebouncer>().restart();
+        world.run_system_cached(handle_font_resize).unwrap();
+        assert_eq!(world.get::<TextFont>(txt).unwrap().font_size, 30f32);
+        assert_eq!(
+            world.get::<Transform>(txt).unwrap().scale,
+            Vec3::splat(10f32 / 300f32),
+        );
+        assert_eq!(world.get::<TextFont>(ui_txt).unwrap().font_size, 10f32);
+
+        // Quantized, the scale follows the quantized size to keep the height in the world
+        world.resource_mut::<FontSizeSettings>().quantization = Some(FontSizeQuantization {
+            step: 8f32,
+            min: 16f32,
+            max: 48f32,
+        });
+        world.resource_mut::<WindowResizeDebouncer>().restart();
+        world.run_system_cached(handle_font_resize).unwrap();
+        assert_eq!(world.get::<TextFont>(txt).unwrap().font_size, 32f32);
+        assert_eq!(
+            world.get::<Transform>(txt).unwrap().scale,
+            Vec3::splat(1f32 / 32f32),
+        );
+        assert_eq!(world.get::<TextFont>(ui_txt).unwrap().font_size, 16f32);
+    }
+
+    // --- Helper Functions ---
+
+    // Collect the DynamicFontSizeError messages written since the buffers were last cleared.
+    fn font_size_errors(world: &World) -> Vec<DynamicFontSizeError> {
+        world
+            .resource::<Messages<DynamicFontSizeError>>()
+            .iter_current_update_messages()
+            .copied()
+            .collect()
+    }
+
+    // Assert the debounce timer was restarted from the given timer, then put it back.
+    fn assert_debounce_restarted(world: &mut World, inflight_timer: &Timer, when: &str) {
+        let mut debouncer = world.resource_mut::<WindowResizeDebouncer>();
+        assert_eq!(
+            debouncer.timer,
+            Timer::new(Duration::from_secs(1), TimerMode::Once),
+            "Expected debounce timer to be restarted {when}",
+        );
+        debouncer.timer = inflight_timer.clone();
+    }
+
+    // Create a camera whose render target has been resolved to the given physical size.
+    fn camera_with_target(physical_size: UVec2) -> Camera {
+        Camera {
+            computed: ComputedCameraValues {
+                target_info: Some(RenderTargetInfo {
+                    physical_size,
+                    scale_factor: 1f32,
+                }),
+                ..default()
+            },
+            ..default()
+        }
+    }
+
+    // Advance the time of the app by the given duration, and run a single update.
+    fn advance_and_update(app: &mut App, duration: Duration) {
+        app.world_mut().resource_mut::<Time>().advance_by(duration);
+        app.update();
     }
 }
@@ -0,0 +1,335 @@
+//!
+//! Headless tests of DynamicFontsizePlugin, using only its public API as an app would. No GPU
+//! or real window is needed: window resizes are simulated by updating the window and the
+//! computed render target of its camera, as the render app would, and writing the matching
+//! WindowResized message. Bevy's text layout can be added too, to check the size glyphs are
+//! rasterized at.
+//!
+
+// -------------------------------------------------------------------------------------------------
+// Included Symbols
+
+use std::any::TypeId;
+use std::time::Duration;
+
+use bevy::camera::visibility::VisibleEntities;
+use bevy::camera::{ComputedCameraValues, RenderTargetInfo};
+use bevy::image::TextureAtlasPlugin;
+use bevy::prelude::*;
+use bevy::sprite::update_text2d_layout;
+use bevy::text::{FontAtlasSets, TextLayoutInfo, TextPlugin};
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::WindowResized;
+use bevy_dyn_fontsize::{
+    DynamicFontSize, DynamicFontSizeError, DynamicFontSizeErrorKind, DynamicFontsizePlugin, Systems,
+};
+
+// -------------------------------------------------------------------------------------------------
+// Constants
+
+const DEBOUNCE_TIME: Duration = Duration::from_millis(100);
+const FRAME_TIME: Duration = Duration::from_millis(10);
+
+// -------------------------------------------------------------------------------------------------
+// Test Harness
+
+//
+// A headless app with the plugin added, a window, and an orthographic camera drawing to it.
+// The camera's projection is always 100 world units high.
+//
+struct Harness {
+    app: App,
+    window: Entity,
+    camera: Entity,
+}
+
+impl Harness {
+    fn new(plugin: DynamicFontsizePlugin, window_size: UVec2) -> Self {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, plugin))
+            .add_message::<WindowResized>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_TIME));
+        let window = app.world_mut().spawn(Window::default()).id();
+        let camera = app
+            .world_mut()
+            .spawn((
+                Camera {
+                    computed: ComputedCameraValues {
+                        target_info: Some(RenderTargetInfo {
+                            physical_size: window_size,
+                            scale_factor: 1f32,
+                        }),
+                        ..default()
+                    },
+                    ..default()
+                },
+                Projection::Orthographic(OrthographicProjection {
+                    area: Rect::new(0f32, 0f32, 100f32, 100f32),
+                    ..OrthographicProjection::default_2d()
+                }),
+            ))
+            .id();
+
+        // The first update doesn't advance time
+        app.update();
+        Self {
+            app,
+            window,
+            camera,
+        }
+    }
+
+    // Spawn text of the given height in the world, drawn by the camera.
+    fn spawn_text(&mut self, height_in_world: f32) -> Entity {
+        self.app
+            .world_mut()
+            .spawn(DynamicFontSize {
+                height_in_world,
+                render_camera: self.camera,
+                max_width_in_world: None,
+            })
+            .id()
+    }
+
+    // Resize the window, and the camera's render target along with it.
+    fn resize_window(&mut self, size: UVec2) {
+        let world = self.app.world_mut();
+        world
+            .get_mut::<Window>(self.window)
+            .unwrap()
+            .resolution
+            .set_physical_resolution(size.x, size.y);
+        world
+            .get_mut::<Camera>(self.camera)
+            .unwrap()
+            .computed
+            .target_info
+            .as_mut()
+            .unwrap()
+            .physical_size = size;
+        world.write_message(WindowResized {
+            window: self.window,
+            width: size.x as f32,
+            height: size.y as f32,
+        });
+    }
+
+    // Zoom the camera, so its projection covers the given height in the world.
+    fn set_projection_height(&mut self, height: f32) {
+        let mut projection = self
+            .app
+            .world_mut()
+            .get_mut::<Projection>(self.camera)
+            .unwrap();
+        if let Projection::Orthographic(ortho) = projection.as_mut() {
+            ortho.area = Rect::new(0f32, 0f32, height, height);
+        }
+    }
+
+    // Run frames until at least the given time has passed.
+    fn run_for(&mut self, duration: Duration) {
+        let frames = duration.as_millis().div_ceil(FRAME_TIME.as_millis());
+        for _ in 0..frames {
+            self.app.update();
+        }
+    }
+
+    fn font_size(&self, text: Entity) -> f32 {
+        self.app.world().get::<TextFont>(text).unwrap().font_size
+    }
+
+    fn scale(&self, text: Entity) -> Vec3 {
+        self.app.world().get::<Transform>(text).unwrap().scale
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Tests
+
+#[test]
+fn test_window_resize() {
+    let mut harness = Harness::new(DynamicFontsizePlugin::default(), UVec2::new(800, 600));
+    let text = harness.spawn_text(10f32);
+
+    // Sized in the first frame
+    harness.app.update();
+    assert_eq!(harness.font_size(text), 60f32);
+    assert_eq!(harness.scale(text), Vec3::splat(100f32 / 600f32));
+
+    // While the window is continuously resized, the text isn't resized
+    for height in [500, 400, 300] {
+        harness.resize_window(UVec2::new(800, height));
+        harness.run_for(DEBOUNCE_TIME / 2);
+        assert_eq!(
+            harness.font_size(text),
+            60f32,
+            "Expected no resize while the window is still being resized",
+        );
+    }
+
+    // Once resizing has settled, it is resized for the final window size
+    harness.run_for(DEBOUNCE_TIME);
+    assert_eq!(harness.font_size(text), 30f32);
+    assert_eq!(harness.scale(text), Vec3::splat(100f32 / 300f32));
+}
+
+#[test]
+fn test_projection_change() {
+    let mut harness = Harness::new(DynamicFontsizePlugin::default(), UVec2::new(800, 600));
+    let text = harness.spawn_text(10f32);
+    harness.run_for(DEBOUNCE_TIME * 2);
+    assert_eq!(harness.font_size(text), 60f32);
+
+    // Zooming in to show half as much of the world doubles the size on screen
+    harness.set_projection_height(50f32);
+    harness.run_for(DEBOUNCE_TIME / 2);
+    assert_eq!(
+        harness.font_size(text),
+        60f32,
+        "Expected no resize before the debounce time has passed",
+    );
+    harness.run_for(DEBOUNCE_TIME);
+    assert_eq!(harness.font_size(text), 120f32);
+    assert_eq!(harness.scale(text), Vec3::splat(50f32 / 600f32));
+}
+
+#[test]
+fn test_schedule_and_set_placement() {
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    struct AppFontSystems;
+
+    #[derive(Resource, Default)]
+    struct SeenFontSize(f32);
+
+    let plugin = DynamicFontsizePlugin::default()
+        .with_debounce_time(Duration::from_millis(50))
+        .in_schedule(PostUpdate)
+        .in_set(AppFontSystems);
+    let mut harness = Harness::new(plugin, UVec2::new(800, 600));
+    let text = harness.spawn_text(10f32);
+
+    // A system ordered after the plugin's set sees the new size in the same frame
+    harness.app.init_resource::<SeenFontSize>().add_systems(
+        PostUpdate,
+        (|fonts: Query<&TextFont>, mut seen: ResMut<SeenFontSize>| {
+            seen.0 = fonts.iter().map(|font| font.font_size).sum();
+        })
+        .after(AppFontSystems),
+    );
+    harness.app.update();
+    assert_eq!(harness.app.world().resource::<SeenFontSize>().0, 60f32);
+
+    harness.resize_window(UVec2::new(800, 300));
+    harness.run_for(Duration::from_millis(60));
+    assert_eq!(harness.font_size(text), 30f32);
+    assert_eq!(harness.app.world().resource::<SeenFontSize>().0, 30f32);
+}
+
+#[test]
+fn test_errors_observed_by_app() {
+    #[derive(Resource, Default)]
+    struct SeenErrors(Vec<DynamicFontSizeError>);
+
+    let mut harness = Harness::new(DynamicFontsizePlugin::default(), UVec2::new(800, 600));
+    harness.app.init_resource::<SeenErrors>().add_systems(
+        Update,
+        (|mut errors: MessageReader<DynamicFontSizeError>, mut seen: ResMut<SeenErrors>| {
+            seen.0.extend(errors.read().copied());
+        })
+        .after(Systems::Resize),
+    );
+    let text = harness.spawn_text(10f32);
+    let orphan = harness
+        .app
+        .world_mut()
+        .spawn(DynamicFontSize {
+            height_in_world: 10f32,
+            render_camera: Entity::PLACEHOLDER,
+            max_width_in_world: None,
+        })
+        .id();
+
+    // The orphaned text is reported once the text is due to be resized, without holding up
+    // the valid text
+    harness.run_for(DEBOUNCE_TIME * 2);
+    assert_eq!(harness.font_size(text), 60f32);
+    assert_eq!(
+        harness.app.world().resource::<SeenErrors>().0,
+        [DynamicFontSizeError {
+            text: orphan,
+            render_camera: Entity::PLACEHOLDER,
+            kind: DynamicFontSizeErrorKind::MissingCamera,
+        }],
+    );
+}
+
+#[test]
+fn test_hidpi_rasterized_size() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        ImagePlugin::default(),
+        TextureAtlasPlugin,
+        TextPlugin,
+        DynamicFontsizePlugin::default(),
+    ))
+    .add_message::<WindowResized>()
+    .add_systems(PostUpdate, update_text2d_layout);
+
+    // A HiDPI target with a scale factor of 2, 600 logical pixels high, showing 100 world
+    // units. The camera sees the text, as the visibility systems would have found.
+    let camera = app
+        .world_mut()
+        .spawn((
+            Camera {
+                computed: ComputedCameraValues {
+                    target_info: Some(RenderTargetInfo {
+                        physical_size: UVec2::new(1600, 1200),
+                        scale_factor: 2f32,
+                    }),
+                    ..default()
+                },
+                ..default()
+            },
+            Projection::Orthographic(OrthographicProjection {
+                area: Rect::new(0f32, 0f32, 100f32, 100f32),
+                ..OrthographicProjection::default_2d()
+            }),
+        ))
+        .id();
+    let text = app
+        .world_mut()
+        .spawn((
+            Text2d::new("Pong"),
+            DynamicFontSize {
+                height_in_world: 10f32,
+                render_camera: camera,
+                max_width_in_world: None,
+            },
+        ))
+        .id();
+    app.world_mut()
+        .get_mut::<VisibleEntities>(camera)
+        .unwrap()
+        .push(text, TypeId::of::<Sprite>());
+    app.update();
+
+    // Sized in logical pixels, and rasterized at the 12 physical pixels per world unit of the
+    // target, not at the scale factor squared
+    let world = app.world();
+    assert_eq!(world.get::<TextFont>(text).unwrap().font_size, 60f32);
+    assert_eq!(
+        world.get::<TextLayoutInfo>(text).unwrap().scale_factor,
+        2f32
+    );
+    let atlas_set = world
+        .resource::<FontAtlasSets>()
+        .get(AssetId::<Font>::default())
+        .expect("Expected glyphs rasterized with the default font");
+    let rasterized_sizes: Vec<f32> = atlas_set
+        .iter()
+        .map(|(key, _)| f32::from_bits(key.0))
+        .collect();
+    assert_eq!(rasterized_sizes, [120f32]);
+}
@@ -7,9 +7,14 @@
 // Included Symbols
 
 use core::any::type_name;
+use core::fmt::{Debug, Write};
 
+use bevy::ecs::schedule::InternedSystemSet;
 use bevy::ecs::schedule::ScheduleBuildError;
 use bevy::ecs::schedule::ScheduleLabel;
+use bevy::ecs::schedule::graph::{DiGraph, Direction};
+use bevy::ecs::schedule::{NodeId, ScheduleGraph, SystemKey};
+use bevy::platform::collections::HashSet;
 use bevy::prelude::*;
 
 // -------------------------------------------------------------------------------------------------
@@ -17,7 +22,10 @@ use bevy::prelude::*;
 
 /// The prelude includes all basic members of this crate and should be included with prelude::*
 pub mod prelude {
-    pub use super::validate_sys_in_plugin;
+    pub use super::{
+        assert_message_count, assert_messages_eq, assert_runs_after, assert_runs_before,
+        drain_messages, send_messages, validate_sys_in_plugin,
+    };
 }
 
 ///
@@ -42,18 +50,19 @@ pub fn validate_sys_in_plugin<P, L, S, Marker, SS>(
 
     let mut found_system = false;
     app.get_schedule(schedule.clone())
-        .expect(&format!(
-            "Expected {:?} schedule to exist in app after adding {} plugin",
-            type_name::<L>(),
-            type_name::<P>(),
-        ))
+        .unwrap_or_else(|| {
+            panic!(
+                "Expected {:?} schedule to exist in app after adding {} plugin",
+                type_name::<L>(),
+                type_name::<P>(),
+            )
+        })
         .graph()
         .systems
         .iter()
         .for_each(|(_, boxed_sys, _)| {
             if boxed_sys.name().as_string() == type_name::<S>() {
                 found_system = true;
-                return;
             }
         });
 
@@ -91,3 +100,432 @@ pub fn validate_sys_in_plugin<P, L, S, Marker, SS>(
         );
     };
 }
+
+///
+/// Validates that the system or system set `first` runs before the system or system set
+/// `second`, within the given schedule, after installing the given plugin in a new App.
+/// The ordering may be direct, or transitive through other systems and system sets,
+/// including any sets either of them belongs to. For a system set, every system in it
+/// must run before (or after) every system in the other.
+///
+/// This fails if either is missing from the schedule, or if they share any systems (e.g. a
+/// system and a set it belongs to), since neither can then run before the other.
+///
+pub fn assert_runs_before<P, L, A, MarkerA, B, MarkerB>(plugin: P, schedule: L, first: A, second: B)
+where
+    P: Plugin,
+    L: ScheduleLabel + Clone,
+    A: IntoSystemSet<MarkerA>,
+    B: IntoSystemSet<MarkerB>,
+{
+    let first = first.into_system_set().intern();
+    let second = second.into_system_set().intern();
+    let first_name = ordering_name(first, type_name::<A>());
+    let second_name = ordering_name(second, type_name::<B>());
+    let mut app = App::new();
+    app.add_plugins(plugin);
+    assert!(
+        app.get_schedule(schedule.clone()).is_some(),
+        "Expected {:?} schedule to exist in app after adding {} plugin",
+        type_name::<L>(),
+        type_name::<P>(),
+    );
+
+    // Build the schedule, so any errors in it are reported rather than mistaken for orderings
+    let (init_result, first_node, second_node) = app
+        .world_mut()
+        .try_schedule_scope(schedule.clone(), |world, sched| {
+            let init_result = sched.initialize(world);
+            let graph = sched.graph_mut();
+            (init_result, set_node(graph, first), set_node(graph, second))
+        })
+        .unwrap();
+    if let Err(err) = init_result {
+        panic!(
+            "Expected {first_name} to run before {second_name} in {} schedule, but couldn't tell due to: {err}",
+            type_name::<L>(),
+        );
+    }
+
+    let (Some(first_node), Some(second_node)) = (first_node, second_node) else {
+        panic!(
+            "Expected {first_name} to run before {second_name} in {} schedule after adding {} plugin, but one of them is missing from the schedule",
+            type_name::<L>(),
+            type_name::<P>(),
+        );
+    };
+    let graph = app.get_schedule(schedule).unwrap().graph();
+    let hierarchy = graph.hierarchy().graph();
+    assert!(
+        systems_under(hierarchy, first_node).is_disjoint(&systems_under(hierarchy, second_node)),
+        "Expected {first_name} to run before {second_name} in {} schedule, but they share systems",
+        type_name::<L>(),
+    );
+    if runs_before(graph, first_node, second_node) {
+        return;
+    }
+    let actual = match runs_before(graph, second_node, first_node) {
+        true => "it runs after instead",
+        false => "they aren't ordered that way",
+    };
+    panic!(
+        "Expected {first_name} to run before {second_name} in {} schedule after adding {} plugin, but {actual}",
+        type_name::<L>(),
+        type_name::<P>(),
+    );
+}
+
+///
+/// Validates that the system or system set `second` runs after the system or system set
+/// `first`. This is the same check as assert_runs_before, for when reading the ordering
+/// the other way around is clearer.
+///
+pub fn assert_runs_after<P, L, B, MarkerB, A, MarkerA>(plugin: P, schedule: L, second: B, first: A)
+where
+    P: Plugin,
+    L: ScheduleLabel + Clone,
+    B: IntoSystemSet<MarkerB>,
+    A: IntoSystemSet<MarkerA>,
+{
+    assert_runs_before(plugin, schedule, first, second);
+}
+
+///
+/// Writes the given messages to the world, adding the Messages resource for their type first
+/// if it doesn't exist yet, so they can be read by the next system run.
+///
+pub fn send_messages<M: Message>(world: &mut World, messages: impl IntoIterator<Item = M>) {
+    world
+        .get_resource_or_init::<Messages<M>>()
+        .write_batch(messages);
+}
+
+///
+/// Removes and returns all messages of the given type from the world, oldest first. This
+/// allows a test to check only the messages written since the last drain. An empty Vec is
+/// returned if the Messages resource for the type doesn't exist.
+///
+pub fn drain_messages<M: Message>(world: &mut World) -> Vec<M> {
+    world
+        .get_resource_mut::<Messages<M>>()
+        .map(|mut messages| messages.drain().collect())
+        .unwrap_or_default()
+}
+
+///
+/// Validates that the world holds exactly the given number of messages of the given type,
+/// listing the messages it holds if not.
+///
+#[track_caller]
+pub fn assert_message_count<M: Message + Debug>(world: &World, count: usize) {
+    let actual = read_messages::<M>(world);
+    assert!(
+        actual.len() == count,
+        "Expected {count} {} messages but got {}:\n{}",
+        type_name::<M>(),
+        actual.len(),
+        message_list(&actual),
+    );
+}
+
+///
+/// Validates that the world holds exactly the given messages of the given type, in order.
+/// If not, a diff is printed marking each expected message missing with `-`, and each
+/// unexpected message with `+`.
+///
+#[track_caller]
+pub fn assert_messages_eq<M: Message + Debug + PartialEq>(world: &World, expected: &[M]) {
+    let actual = read_messages::<M>(world);
+    if actual.iter().copied().eq(expected) {
+        return;
+    }
+    panic!(
+        "Expected {} messages didn't match (-expected +actual):\n{}",
+        type_name::<M>(),
+        message_diff(expected, &actual),
+    );
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Functions
+
+// Name a system or system set in an ordering assertion message. The type name is clearest for
+// systems, but sets are named by value, since it distinguishes the variants of a set enum.
+fn ordering_name(set: InternedSystemSet, type_name: &str) -> String {
+    match set.system_type() {
+        Some(_) => type_name.to_string(),
+        None => format!("{set:?}"),
+    }
+}
+
+// Get the node of the given system set in the schedule's graph, if the schedule has it.
+fn set_node(graph: &mut ScheduleGraph, set: InternedSystemSet) -> Option<NodeId> {
+    graph
+        .system_sets
+        .contains(set)
+        .then(|| NodeId::Set(graph.system_sets.get_key_or_insert(set)))
+}
+
+//
+// Get every node at or below (or above) the given node of the schedule's hierarchy, i.e. every
+// system and set in a set (or every set holding a system or set).
+//
+fn hierarchy_nodes(hierarchy: &DiGraph<NodeId>, node: NodeId, dir: Direction) -> Vec<NodeId> {
+    let mut nodes = vec![node];
+    let mut idx = 0;
+    while let Some(&node) = nodes.get(idx) {
+        for next in hierarchy.neighbors_directed(node, dir) {
+            if !nodes.contains(&next) {
+                nodes.push(next);
+            }
+        }
+        idx += 1;
+    }
+    nodes
+}
+
+// Get every system at or below the given node of the schedule's hierarchy.
+fn systems_under(hierarchy: &DiGraph<NodeId>, node: NodeId) -> HashSet<SystemKey> {
+    hierarchy_nodes(hierarchy, node, Direction::Outgoing)
+        .into_iter()
+        .filter_map(|node| node.as_system())
+        .collect()
+}
+
+//
+// Whether every system of the first node runs before every system of the second. The first
+// node may be ordered before the second as a whole, or each of its systems may be ordered
+// before it separately (e.g. a system, whose orderings are on the system rather than its set).
+//
+fn runs_before(graph: &ScheduleGraph, first: NodeId, second: NodeId) -> bool {
+    let first_systems = systems_under(graph.hierarchy().graph(), first);
+    reaches(graph, first, second)
+        || (!first_systems.is_empty()
+            && first_systems
+                .into_iter()
+                .all(|system| reaches(graph, NodeId::System(system), second)))
+}
+
+//
+// Whether the `to` node is ordered after the `from` node, following the dependencies of the
+// schedule transitively. A node runs before whatever the sets holding it run before, and every
+// system and set in a set runs after whatever the set runs after. As when the schedule is
+// built, an empty set passes on the orderings of the nodes either side of it.
+//
+fn reaches(graph: &ScheduleGraph, from: NodeId, to: NodeId) -> bool {
+    let hierarchy = graph.hierarchy().graph();
+    let dependency = graph.dependency().graph();
+    let mut reached: HashSet<NodeId> = HashSet::default();
+    let mut stack = vec![from];
+    while let Some(node) = stack.pop() {
+        // An empty set holding the node doesn't run after it, so only follow the sets of a
+        // system or of the starting node
+        let departures = match node.is_system() || node == from {
+            true => hierarchy_nodes(hierarchy, node, Direction::Incoming),
+            false => vec![node],
+        };
+        for departure in departures {
+            for next in dependency.neighbors_directed(departure, Direction::Outgoing) {
+                for after in hierarchy_nodes(hierarchy, next, Direction::Outgoing) {
+                    if reached.insert(after) {
+                        stack.push(after);
+                    }
+                }
+            }
+        }
+    }
+
+    let to_systems = systems_under(hierarchy, to);
+    reached.contains(&to)
+        || (!to_systems.is_empty()
+            && to_systems
+                .into_iter()
+                .all(|system| reached.contains(&NodeId::System(system))))
+}
+
+// Read all messages of the given type which are still held by the world, oldest first.
+#[track_caller]
+fn read_messages<M: Message>(world: &World) -> Vec<&M> {
+    let Some(messages) = world.get_resource::<Messages<M>>() else {
+        panic!(
+            "Expected Messages<{}> resource to exist in the world",
+            type_name::<M>(),
+        );
+    };
+    let oldest = messages.oldest_message_count();
+    (oldest..oldest + messages.len())
+        .filter_map(|id| messages.get_message(id))
+        .map(|(message, _)| message)
+        .collect()
+}
+
+// List messages one per line, with their index.
+fn message_list<M: Debug>(messages: &[&M]) -> String {
+    let mut list = String::new();
+    for (idx, message) in messages.iter().enumerate() {
+        let _ = writeln!(list, "  [{idx}] {message:?}");
+    }
+    list
+}
+
+// Describe the differences between expected and actual messages, line by line. Messages at
+// the same index which match are listed unmarked, for context.
+fn message_diff<M: Debug + PartialEq>(expected: &[M], actual: &[&M]) -> String {
+    let mut diff = String::new();
+    for idx in 0..expected.len().max(actual.len()) {
+        match (expected.get(idx), actual.get(idx)) {
+            (Some(exp), Some(act)) if exp == *act => {
+                let _ = writeln!(diff, "  [{idx}] {exp:?}");
+            }
+            (exp, act) => {
+                if let Some(exp) = exp {
+                    let _ = writeln!(diff, "- [{idx}] {exp:?}");
+                }
+                if let Some(act) = act {
+                    let _ = writeln!(diff, "+ [{idx}] {act:?}");
+                }
+            }
+        }
+    }
+    diff
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assert_runs_before() {
+        // Ordered directly by a system, by a set, and transitively through both
+        assert_runs_before(OrderingPlugin, Update, sys_a, sys_b);
+        assert_runs_before(OrderingPlugin, Update, TestSets::First, TestSets::Second);
+        assert_runs_before(OrderingPlugin, Update, sys_b, TestSets::Second);
+        assert_runs_before(OrderingPlugin, Update, sys_a, sys_c);
+        assert_runs_after(OrderingPlugin, Update, sys_c, sys_a);
+    }
+
+    #[test]
+    #[should_panic(expected = "but they aren't ordered that way")]
+    fn test_assert_runs_before_unordered() {
+        assert_runs_before(OrderingPlugin, Update, sys_a, sys_d);
+    }
+
+    #[test]
+    #[should_panic(expected = "but it runs after instead")]
+    fn test_assert_runs_before_reversed() {
+        assert_runs_before(OrderingPlugin, Update, sys_c, sys_a);
+    }
+
+    #[test]
+    #[should_panic(expected = "but they share systems")]
+    fn test_assert_runs_before_set_member() {
+        assert_runs_before(OrderingPlugin, Update, TestSets::First, sys_b);
+    }
+
+    #[test]
+    #[should_panic(expected = "but one of them is missing from the schedule")]
+    fn test_assert_runs_before_missing() {
+        assert_runs_before(OrderingPlugin, Update, sys_a, sys_missing);
+    }
+
+    #[test]
+    fn test_messages_eq() {
+        let mut world = message_world();
+        send_messages(&mut world, [TestMessage(1), TestMessage(2)]);
+        assert_message_count::<TestMessage>(&world, 2);
+        assert_messages_eq(&world, &[TestMessage(1), TestMessage(2)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "  [0] TestMessage(1)\n- [1] TestMessage(3)\n+ [1] TestMessage(2)\n")]
+    fn test_messages_eq_mismatch() {
+        let mut world = message_world();
+        send_messages(&mut world, [TestMessage(1), TestMessage(2)]);
+        assert_messages_eq(&world, &[TestMessage(1), TestMessage(3)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "(-expected +actual):\n  [0] TestMessage(1)\n- [1] TestMessage(2)\n")]
+    fn test_messages_eq_missing() {
+        let mut world = message_world();
+        send_messages(&mut world, [TestMessage(1)]);
+        assert_messages_eq(&world, &[TestMessage(1), TestMessage(2)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "(-expected +actual):\n  [0] TestMessage(1)\n+ [1] TestMessage(2)\n")]
+    fn test_messages_eq_extra() {
+        let mut world = message_world();
+        send_messages(&mut world, [TestMessage(1), TestMessage(2)]);
+        assert_messages_eq(&world, &[TestMessage(1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "messages but got 2:\n  [0] TestMessage(1)\n  [1] TestMessage(2)\n")]
+    fn test_message_count_mismatch() {
+        let mut world = message_world();
+        send_messages(&mut world, [TestMessage(1), TestMessage(2)]);
+        assert_message_count::<TestMessage>(&world, 1);
+    }
+
+    #[test]
+    fn test_drain_messages() {
+        let mut world = message_world();
+        send_messages(&mut world, [TestMessage(1), TestMessage(2)]);
+        assert_eq!(
+            drain_messages::<TestMessage>(&mut world),
+            vec![TestMessage(1), TestMessage(2)]
+        );
+
+        // Draining consumes the messages, so nothing is left to read
+        assert!(drain_messages::<TestMessage>(&mut world).is_empty());
+        assert_message_count::<TestMessage>(&world, 0);
+    }
+
+    // --- Helper Types ---
+
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    enum TestSets {
+        First,
+        Second,
+    }
+
+    #[derive(Message, Debug, PartialEq)]
+    struct TestMessage(u32);
+
+    // Plugin ordering sys_a before sys_b (in the First set), which runs before the Second set
+    // holding sys_c. sys_d isn't ordered against any of them.
+    struct OrderingPlugin;
+
+    impl Plugin for OrderingPlugin {
+        fn build(&self, app: &mut App) {
+            app.configure_sets(Update, TestSets::First.before(TestSets::Second))
+                .add_systems(
+                    Update,
+                    (
+                        sys_a.before(sys_b),
+                        sys_b.in_set(TestSets::First),
+                        sys_c.in_set(TestSets::Second),
+                        sys_d,
+                    ),
+                );
+        }
+    }
+
+    // --- Helper Functions ---
+
+    fn sys_a() {}
+    fn sys_b() {}
+    fn sys_c() {}
+    fn sys_d() {}
+    fn sys_missing() {}
+
+    fn message_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<Messages<TestMessage>>();
+        world
+    }
+}
@@ -0,0 +1,335 @@
+//!
+//! The ai module contains computer controlled opponents, which can take the place of any
+//! player and move their paddles to intercept the ball.
+//!
+
+// -------------------------------------------------------------------------------------------------
+// Included Symbols
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::ball::Ball;
+use crate::common::*;
+use crate::paddle::{self, AllPaddleHitboxes, MoveDirection, MovePaddle, PaddleHitbox};
+
+// -------------------------------------------------------------------------------------------------
+// Public API
+
+///
+/// The AiPlugin moves the paddles of every player listed in the AiPlayers resource, by
+/// sending MovePaddle messages to the PaddlePlugin each frame. Paddles of any other players
+/// are left under the control of user input.
+///
+pub struct AiPlugin;
+
+impl Plugin for AiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AiPlayers>()
+            .add_message::<MovePaddle>()
+            .add_systems(Update, drive_ai_paddles.in_set(Systems::MovePaddleSndr))
+            .configure_sets(
+                Update,
+                Systems::MovePaddleSndr.before(paddle::Systems::HandleInput),
+            );
+    }
+}
+
+/// These SystemSets are used to control any system ordering dependencies on this plugin
+#[derive(SystemSet, Debug, Clone, Hash, PartialEq, Eq)]
+pub enum Systems {
+    ///
+    /// Update systems which send MovePaddle messages for computer controlled players. This
+    /// set is already ordered before the HandleInput set of the PaddlePlugin.
+    ///
+    MovePaddleSndr,
+}
+
+///
+/// Resource listing which players are computer controlled, and how well each one plays.
+/// Players without an entry are controlled by user input.
+///
+#[derive(Resource, Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AiPlayers(pub PerPlayer<AiDifficulty>);
+
+/// How well a computer controlled player plays.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AiDifficulty {
+    /// Reacts late, tracks the ball loosely and never returns to the middle.
+    Easy,
+    /// Reacts once the ball is past the middle, and returns to the middle afterwards.
+    #[default]
+    Medium,
+    /// Reacts immediately, and predicts where the ball will arrive after bouncing off walls.
+    Hard,
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Types
+
+// Parameters tuning how a computer controlled player behaves.
+struct AiParams {
+    // How close the ball must be to the paddle before the player reacts to it.
+    reaction_distance: f32,
+
+    // How far off target the paddle may be before the player moves it.
+    dead_zone: f32,
+
+    // Whether the player predicts where the ball will arrive, rather than following it.
+    predicts: bool,
+
+    // Whether the player returns to the middle of its edge when not reacting to the ball.
+    recenters: bool,
+}
+
+impl AiDifficulty {
+    // Get the parameters tuning how a player of this difficulty behaves.
+    fn params(self) -> AiParams {
+        match self {
+            AiDifficulty::Easy => AiParams {
+                reaction_distance: ARENA_WIDTH * 0.4,
+                dead_zone: 0.5,
+                predicts: false,
+                recenters: false,
+            },
+            AiDifficulty::Medium => AiParams {
+                reaction_distance: ARENA_WIDTH * 0.6,
+                dead_zone: 0.25,
+                predicts: false,
+                recenters: true,
+            },
+            AiDifficulty::Hard => AiParams {
+                reaction_distance: f32::INFINITY,
+                dead_zone: 0.1,
+                predicts: true,
+                recenters: true,
+            },
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Systems
+
+// Steers the paddles of every computer controlled player towards the ball.
+fn drive_ai_paddles(
+    ai_players: Res<AiPlayers>,
+    ball: Single<(&Ball, &Transform)>,
+    paddles: Query<AllPaddleHitboxes>,
+    mut move_msgs: MessageWriter<MovePaddle>,
+) {
+    let (ball, ball_tf) = ball.into_inner();
+    for hitbox in &paddles {
+        let Some(difficulty) = ai_players.0.get(hitbox.player()) else {
+            continue;
+        };
+
+        let params = difficulty.params();
+        let target = ai_target(
+            &params,
+            &hitbox,
+            ball_tf.translation.xy(),
+            ball.movement_dir(),
+        );
+        move_msgs.write(MovePaddle {
+            player: hitbox.player(),
+            position: hitbox.position(),
+            dir: steer_towards(&hitbox, target, params.dead_zone),
+        });
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Functions
+
+//
+// Get the coordinate along its edge that a paddle should move towards, or None if it should
+// stay where it is. Players only react to a ball which is moving towards their paddle, and
+// is within their reaction distance.
+//
+fn ai_target(
+    params: &AiParams,
+    hitbox: &PaddleHitbox,
+    ball_pos: Vec2,
+    ball_dir: Option<Dir2>,
+) -> Option<f32> {
+    let normal = hitbox.plane_normal();
+    let distance = (ball_pos - hitbox.plane_origin()).dot(normal);
+    let Some(ball_dir) = ball_dir
+        .filter(|dir| dir.dot(normal) < 0f32)
+        .filter(|_| distance <= params.reaction_distance)
+    else {
+        return params.recenters.then_some(0f32);
+    };
+
+    let (axis, half_extent) = if hitbox.side().is_vertical() {
+        (Vec2::Y, ARENA_HEIGHT / 2f32)
+    } else {
+        (Vec2::X, ARENA_WIDTH / 2f32)
+    };
+    if !params.predicts {
+        return Some(ball_pos.dot(axis));
+    }
+
+    let intercept = ball_pos + (*ball_dir * (distance.max(0f32) / -ball_dir.dot(normal)));
+    Some(fold_into_range(intercept.dot(axis), half_extent))
+}
+
+//
+// Folds a coordinate back into the range -half_extent..=half_extent, as if it had bounced
+// off walls at either end of the range.
+//
+fn fold_into_range(value: f32, half_extent: f32) -> f32 {
+    let length = half_extent * 2f32;
+    let folded = (value + half_extent).rem_euclid(length * 2f32);
+    let folded = if folded > length {
+        (length * 2f32) - folded
+    } else {
+        folded
+    };
+
+    folded - half_extent
+}
+
+// Get the direction to move a paddle in, to bring its middle towards the target coordinate.
+fn steer_towards(hitbox: &PaddleHitbox, target: Option<f32>, dead_zone: f32) -> MoveDirection {
+    let Some(target) = target else {
+        return MoveDirection::None;
+    };
+
+    let offset = target - ((hitbox.face_min() + hitbox.face_max()) / 2f32);
+    match (offset.abs() <= dead_zone, hitbox.side().is_vertical()) {
+        (true, _) => MoveDirection::None,
+        (false, true) if offset > 0f32 => MoveDirection::Up,
+        (false, true) => MoveDirection::Down,
+        (false, false) if offset > 0f32 => MoveDirection::Right,
+        (false, false) => MoveDirection::Left,
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ball;
+    use crate::paddle::PaddlePosition;
+    use bevy_test_helpers::prelude::*;
+
+    #[test]
+    fn test_plugin_sys_added_drive_ai_paddles() {
+        validate_sys_in_plugin(
+            AiPlugin,
+            Update,
+            drive_ai_paddles,
+            Some(Systems::MovePaddleSndr),
+        );
+    }
+
+    #[test]
+    fn test_fold_into_range() {
+        for (value, exp_folded) in [
+            (0.0, 0.0),
+            (2.0, 2.0),
+            (5.0, 3.0),
+            (-5.0, -3.0),
+            (9.0, -1.0),
+            (17.0, 1.0),
+        ] {
+            let folded = fold_into_range(value, 4.0);
+            assert!(
+                (folded - exp_folded).abs() < 1e-5,
+                "Expected {value} to fold to {exp_folded} but got {folded}",
+            );
+        }
+    }
+
+    #[test]
+    fn test_drive_ai_paddles_follows_ball() {
+        // Ball approaches player 2 from above, and moves away from player 1
+        let dir = Dir2::new(Vec2::new(1.0, 0.2)).unwrap();
+        let msgs = run_drive_ai_scenario(AiDifficulty::Medium, Vec2::new(4.0, 2.0), Some(dir));
+        assert_eq!(
+            msgs,
+            vec![(Player1, MoveDirection::None), (Player2, MoveDirection::Up)],
+            "Expected P1 to stay in the middle and P2 to follow the ball up",
+        );
+    }
+
+    #[test]
+    fn test_drive_ai_paddles_easy_ignores_distant_ball() {
+        let dir = Dir2::new(Vec2::new(1.0, 0.2)).unwrap();
+        let msgs = run_drive_ai_scenario(AiDifficulty::Easy, Vec2::new(-4.0, 2.0), Some(dir));
+        assert_eq!(
+            msgs,
+            vec![
+                (Player1, MoveDirection::None),
+                (Player2, MoveDirection::None)
+            ],
+            "Expected easy players not to react to a distant ball",
+        );
+    }
+
+    #[test]
+    fn test_drive_ai_paddles_hard_predicts_bounce() {
+        // Ball is above player 2, but will bounce off the top wall and arrive below the middle
+        let dir = Dir2::new(Vec2::new(1.0, 1.0)).unwrap();
+        let msgs = run_drive_ai_scenario(AiDifficulty::Hard, Vec2::new(-2.0, 3.0), Some(dir));
+        assert_eq!(
+            msgs,
+            vec![
+                (Player1, MoveDirection::None),
+                (Player2, MoveDirection::Down)
+            ],
+            "Expected hard P2 to move towards where the ball will arrive",
+        );
+    }
+
+    #[test]
+    fn test_drive_ai_paddles_paused_ball() {
+        let msgs = run_drive_ai_scenario(AiDifficulty::Easy, Vec2::ZERO, None);
+        assert_eq!(
+            msgs,
+            vec![
+                (Player1, MoveDirection::None),
+                (Player2, MoveDirection::None)
+            ],
+            "Expected no movement while the ball is paused",
+        );
+    }
+
+    // --- Helper Functions ---
+
+    //
+    // Runs drive_ai_paddles with both players of a two player game controlled by the given
+    // difficulty, with centered paddles. Returns the (player, dir) of each MovePaddle message.
+    //
+    fn run_drive_ai_scenario(
+        difficulty: AiDifficulty,
+        ball_pos: Vec2,
+        ball_dir: Option<Dir2>,
+    ) -> Vec<(PlayerId, MoveDirection)> {
+        let mut world = World::default();
+        ball::tests::spawn_test_ball(&mut world, ball_pos, ball_dir);
+        paddle::tests::spawn_test_paddle(&mut world, 0.5, -0.5, Player1);
+        paddle::tests::spawn_test_paddle(&mut world, 0.5, -0.5, Player2);
+        world.insert_resource(AiPlayers(PerPlayer::filled(
+            GameMode::TwoPlayer.players(),
+            difficulty,
+        )));
+        world.init_resource::<Messages<MovePaddle>>();
+
+        let drive_sys = world.register_system(drive_ai_paddles);
+        world.run_system(drive_sys).unwrap();
+
+        let messages = world.resource::<Messages<MovePaddle>>();
+        let mut msgs: Vec<(PlayerId, MoveDirection)> = messages
+            .iter_current_update_messages()
+            .inspect(|msg| assert_eq!(msg.position, PaddlePosition::Back))
+            .map(|msg| (msg.player, msg.dir))
+            .collect();
+        msgs.sort_by_key(|(player, _)| player.index());
+        msgs
+    }
+}
@@ -1,7 +1,8 @@
 //!
 //! The arena module contains code to set up the environment in which the
 //! pong game is played. This includes the gameplay box itself, the dashed
-//! line down the middle, and the camera to render the scene.
+//! line down the middle, and the camera to render the scene, which can be
+//! shaken and zoomed by gameplay events.
 //!
 
 // -------------------------------------------------------------------------------------------------
@@ -13,6 +14,7 @@ use bevy::mesh::{Indices, PrimitiveTopology};
 use bevy::prelude::*;
 
 use crate::common::*;
+use crate::settings::Settings;
 
 // -------------------------------------------------------------------------------------------------
 // Constants
@@ -24,6 +26,19 @@ pub const MIDLINE_DASH_HEIGHT: f32 = MIDLINE_HEIGHT_AS_ARENA_HEIGHT_PCT * ARENA_
 pub const MIDLINE_X_MAG: f32 = MIDLINE_DASH_WIDTH / 2f32; // Magnitude of x coords of vertices
 pub const MIDLINE_Y_MAX: f32 = ARENA_HEIGHT / 2f32; // Max y coord value, end line here
 
+// Camera offset at full trauma. The offset scales with the square of the trauma.
+const SHAKE_MAX_OFFSET: f32 = 0.03 * ARENA_HEIGHT;
+const SHAKE_DECAY_PER_SEC: f32 = 1.6;
+// Angular frequencies of the x and y shake, which differ so the shake doesn't look diagonal.
+const SHAKE_FREQ_X: f32 = 47.0;
+const SHAKE_FREQ_Y: f32 = 59.0;
+
+// Largest zoom punch, as the fraction of the view zoomed in by.
+const ZOOM_MAX: f32 = 0.25;
+// Fraction of the zoom left after each second of easing back, and the zoom snapped to none.
+const ZOOM_RETURN_PER_SEC: f32 = 0.02;
+const ZOOM_SNAP: f32 = 0.0005;
+
 // -------------------------------------------------------------------------------------------------
 // Public API
 
@@ -33,12 +48,28 @@ pub const MIDLINE_Y_MAX: f32 = ARENA_HEIGHT / 2f32; // Max y coord value, end li
 /// common::ARENA_WIDTH x ARENA_HEIGHT, a dashed middle line, and a single 2d camera
 /// which is used to render the arena and its contents.
 ///
+/// The camera shakes when sent ShakeCamera messages, and punches in when sent ZoomCamera
+/// messages, smoothly returning to frame the whole arena as these wear off. Both only move
+/// and scale the camera Transform, leaving the camera Projection constant, so text sized by
+/// DynamicFontSize isn't resized over and over during each effect. These camera effects are
+/// skipped while turned off in the Settings.
+///
 pub struct ArenaPlugin;
 
 impl Plugin for ArenaPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup_camera.in_set(Systems::CameraSetup))
-            .add_systems(Startup, setup_arena.in_set(Systems::ArenaSetup));
+        app.init_resource::<Settings>()
+            .add_message::<ShakeCamera>()
+            .add_message::<ZoomCamera>()
+            .init_resource::<CameraEffects>()
+            .add_systems(Startup, setup_camera.in_set(Systems::CameraSetup))
+            .add_systems(Startup, setup_arena.in_set(Systems::ArenaSetup))
+            .add_systems(
+                Update,
+                apply_camera_effects
+                    .in_set(Systems::ShakeCameraRcvr)
+                    .in_set(Systems::ZoomCameraRcvr),
+            );
     }
 }
 
@@ -53,6 +84,47 @@ pub enum Systems {
     /// rectangle and dashed midline entities. Must be in Setup.
     ///
     ArenaSetup,
+
+    ///
+    /// Update systems which shake the camera on ShakeCamera messages. To shake the camera
+    /// in the same frame, the sender should be ordered before this set.
+    ///
+    ShakeCameraRcvr,
+
+    ///
+    /// Update systems which zoom the camera on ZoomCamera messages. To zoom the camera
+    /// in the same frame, the sender should be ordered before this set.
+    ///
+    ZoomCameraRcvr,
+}
+
+///
+/// This message should be sent by another module to shake the camera. It carries the amount
+/// of "trauma" to add, where 1.0 is the strongest shake. Trauma from several messages adds up,
+/// to at most 1.0, and wears off over time.
+///
+#[derive(Message, Clone, Copy, PartialEq, Debug)]
+pub struct ShakeCamera(pub f32);
+
+///
+/// This message should be sent by another module to punch the camera in, before it eases back
+/// out to frame the whole arena. It carries the fraction of the view to zoom in by, e.g. 0.1
+/// to zoom in by 10%, which is capped at 0.25. Punches don't add up, the largest one is used.
+///
+#[derive(Message, Clone, Copy, PartialEq, Debug)]
+pub struct ZoomCamera(pub f32);
+
+// -------------------------------------------------------------------------------------------------
+// Private Resources
+
+//
+// The current camera trauma, from 0.0 (still) to 1.0 (shaking as much as possible), and
+// fraction of the view the camera is zoomed in by.
+//
+#[derive(Resource, Default, Debug)]
+struct CameraEffects {
+    trauma: f32,
+    zoom: f32,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -72,6 +144,44 @@ fn setup_camera(mut commands: Commands) {
     ));
 }
 
+//
+// Lets the current shake and zoom wear off, then adds trauma from any ShakeCamera messages and
+// zoom from any ZoomCamera messages, and offsets and scales the camera by the result. With
+// neither, or while camera effects are turned off, the camera frames the arena as set up.
+//
+fn apply_camera_effects(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut effects: ResMut<CameraEffects>,
+    mut shake_msgs: MessageReader<ShakeCamera>,
+    mut zoom_msgs: MessageReader<ZoomCamera>,
+    camera_tf: Single<&mut Transform, With<Camera2d>>,
+) {
+    effects.trauma = (effects.trauma - (SHAKE_DECAY_PER_SEC * time.delta_secs())).max(0f32);
+    effects.zoom *= ZOOM_RETURN_PER_SEC.powf(time.delta_secs());
+    if effects.zoom < ZOOM_SNAP {
+        effects.zoom = 0f32;
+    }
+
+    for msg in shake_msgs.read() {
+        effects.trauma = (effects.trauma + msg.0.max(0f32)).min(1f32);
+    }
+    for msg in zoom_msgs.read() {
+        effects.zoom = effects.zoom.max(msg.0.clamp(0f32, ZOOM_MAX));
+    }
+    if !settings.camera_effects {
+        *effects = CameraEffects::default();
+    }
+
+    let offset = shake_offset(effects.trauma, time.elapsed_secs());
+    let scale = Vec2::splat(1f32 - effects.zoom);
+    let mut camera_tf = camera_tf.into_inner();
+    if (camera_tf.translation.xy() != offset) || (camera_tf.scale.xy() != scale) {
+        camera_tf.translation = offset.extend(camera_tf.translation.z);
+        camera_tf.scale = scale.extend(camera_tf.scale.z);
+    }
+}
+
 // Sets up the arena that the game is played in, including the dashed midline
 fn setup_arena(
     mut commands: Commands,
@@ -96,6 +206,18 @@ fn setup_arena(
 // -------------------------------------------------------------------------------------------------
 // Private Functions
 
+//
+// Get the camera offset for the given trauma at the given time. This follows fixed sine
+// waves rather than random numbers, so shaking never affects the game's random sequence.
+//
+fn shake_offset(trauma: f32, elapsed_secs: f32) -> Vec2 {
+    let magnitude = trauma.clamp(0f32, 1f32).powi(2) * SHAKE_MAX_OFFSET;
+    Vec2::new(
+        (elapsed_secs * SHAKE_FREQ_X).sin(),
+        (elapsed_secs * SHAKE_FREQ_Y).cos(),
+    ) * magnitude
+}
+
 //
 // Generates a mesh for a dashed vertical line whose height is equal to ARENA_HEIGHT
 // and adds it to the provided Assets<Mesh>, returning the handle.
@@ -165,6 +287,7 @@ mod tests {
     use bevy::ecs::query::QuerySingleError::{MultipleEntities, NoEntities};
     use bevy::mesh::VertexAttributeValues;
     use bevy_test_helpers::prelude::*;
+    use std::time::Duration;
 
     #[test]
     fn test_plugin_sys_added_setup_camera() {
@@ -181,6 +304,146 @@ mod tests {
         validate_sys_in_plugin(ArenaPlugin, Startup, setup_arena, Some(Systems::ArenaSetup));
     }
 
+    #[test]
+    fn test_plugin_sys_added_camera_effects() {
+        validate_sys_in_plugin(
+            ArenaPlugin,
+            Update,
+            apply_camera_effects,
+            Some(Systems::ShakeCameraRcvr),
+        );
+        validate_sys_in_plugin(
+            ArenaPlugin,
+            Update,
+            apply_camera_effects,
+            Some(Systems::ZoomCameraRcvr),
+        );
+    }
+
+    #[test]
+    fn test_shake_offset() {
+        assert_eq!(shake_offset(0f32, 1.234), Vec2::ZERO);
+        for elapsed_secs in [0.0, 0.1, 0.37, 2.5] {
+            let offset = shake_offset(1f32, elapsed_secs);
+            assert!(
+                offset.x.abs() <= SHAKE_MAX_OFFSET && offset.y.abs() <= SHAKE_MAX_OFFSET,
+                "Expected shake offset within max offset, got {offset}",
+            );
+            assert!(
+                shake_offset(0.5, elapsed_secs).length() < offset.length(),
+                "Expected less trauma to shake less",
+            );
+        }
+    }
+
+    #[test]
+    fn test_camera_effects_sys_shake() {
+        let mut world = setup_camera_effects_world(true);
+
+        // Still camera without trauma
+        advance_and_shake(&mut world, 0.1);
+        assert_eq!(camera_offset(&mut world), Vec2::ZERO);
+
+        // Trauma adds up to at most 1.0, and shakes the camera
+        world.write_message(ShakeCamera(0.7));
+        world.write_message(ShakeCamera(0.7));
+        advance_and_shake(&mut world, 0.1);
+        assert_eq!(
+            camera_offset(&mut world),
+            shake_offset(1f32, world.resource::<Time>().elapsed_secs()),
+            "Expected camera shaken by full trauma",
+        );
+        advance_and_shake(&mut world, 0.1);
+        let trauma = world.resource::<CameraEffects>().trauma;
+        assert!(trauma < 1f32, "Expected trauma to wear off, got {trauma}");
+
+        // Camera returns to the center once the trauma wears off
+        for _ in 0..20 {
+            advance_and_shake(&mut world, 0.1);
+        }
+        assert_eq!(world.resource::<CameraEffects>().trauma, 0f32);
+        assert_eq!(camera_offset(&mut world), Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_camera_effects_sys_zoom() {
+        let mut world = setup_camera_effects_world(true);
+
+        // The largest punch is used, capped at the max zoom
+        world.write_message(ZoomCamera(0.1));
+        world.write_message(ZoomCamera(0.05));
+        advance_and_shake(&mut world, 0.0);
+        assert_eq!(camera_scale(&mut world), Vec2::splat(0.9));
+        world.write_message(ZoomCamera(5.0));
+        advance_and_shake(&mut world, 0.0);
+        assert_eq!(camera_scale(&mut world), Vec2::splat(1.0 - ZOOM_MAX));
+
+        // The zoom eases back out smoothly
+        let mut last_scale = camera_scale(&mut world).x;
+        for _ in 0..5 {
+            advance_and_shake(&mut world, 0.1);
+            let scale = camera_scale(&mut world).x;
+            assert!(
+                scale > last_scale && scale < 1.0,
+                "Expected camera to ease out from scale {last_scale}, got {scale}",
+            );
+            last_scale = scale;
+        }
+
+        // Until the arena is framed as it was set up
+        for _ in 0..30 {
+            advance_and_shake(&mut world, 0.1);
+        }
+        assert_eq!(camera_scale(&mut world), Vec2::ONE);
+        assert_eq!(world.resource::<CameraEffects>().zoom, 0f32);
+    }
+
+    #[test]
+    fn test_camera_effects_sys_disabled() {
+        let mut world = setup_camera_effects_world(false);
+        world.write_message(ShakeCamera(1.0));
+        world.write_message(ZoomCamera(0.1));
+        advance_and_shake(&mut world, 0.1);
+        assert_eq!(camera_offset(&mut world), Vec2::ZERO);
+        assert_eq!(camera_scale(&mut world), Vec2::ONE);
+
+        // Turning effects off part way through returns the camera to rest at once
+        world.resource_mut::<Settings>().camera_effects = true;
+        world.write_message(ShakeCamera(1.0));
+        world.write_message(ZoomCamera(0.1));
+        advance_and_shake(&mut world, 0.1);
+        assert_ne!(camera_scale(&mut world), Vec2::ONE);
+        world.resource_mut::<Settings>().camera_effects = false;
+        advance_and_shake(&mut world, 0.1);
+        assert_eq!(camera_offset(&mut world), Vec2::ZERO);
+        assert_eq!(camera_scale(&mut world), Vec2::ONE);
+    }
+
+    #[test]
+    fn test_camera_effects_keep_projection() {
+        let mut world = setup_camera_effects_world(true);
+        let mut query = world.query_filtered::<Ref<Projection>, With<Camera2d>>();
+        let ticks_before: Vec<_> = query
+            .iter(&world)
+            .map(|projection| projection.last_changed())
+            .collect();
+
+        world.write_message(ShakeCamera(1.0));
+        world.write_message(ZoomCamera(0.2));
+        world.increment_change_tick();
+        world.run_system_cached(apply_camera_effects).unwrap();
+
+        assert_eq!(camera_scale(&mut world), Vec2::splat(0.8));
+        let ticks_after: Vec<_> = query
+            .iter(&world)
+            .map(|projection| projection.last_changed())
+            .collect();
+        assert_eq!(
+            ticks_before, ticks_after,
+            "Expected camera effects to leave the projection unchanged",
+        );
+    }
+
     #[test]
     fn test_camera_setup_system() {
         let mut world = World::default();
@@ -313,6 +576,40 @@ mod tests {
 
     // --- Helper Functions ---
 
+    // Create a world with a camera, and camera effects turned on or off in the settings.
+    fn setup_camera_effects_world(camera_effects: bool) -> World {
+        let mut world = World::default();
+        world.insert_resource(Settings {
+            camera_effects,
+            ..default()
+        });
+        world.init_resource::<Time>();
+        world.init_resource::<CameraEffects>();
+        world.init_resource::<Messages<ShakeCamera>>();
+        world.init_resource::<Messages<ZoomCamera>>();
+        world.spawn(Camera2d);
+        world
+    }
+
+    fn advance_and_shake(world: &mut World, secs: f32) {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(secs));
+        world.run_system_cached(apply_camera_effects).unwrap();
+        world.resource_mut::<Messages<ShakeCamera>>().update();
+        world.resource_mut::<Messages<ZoomCamera>>().update();
+    }
+
+    fn camera_offset(world: &mut World) -> Vec2 {
+        let mut query = world.query_filtered::<&Transform, With<Camera2d>>();
+        query.single(world).unwrap().translation.xy()
+    }
+
+    fn camera_scale(world: &mut World) -> Vec2 {
+        let mut query = world.query_filtered::<&Transform, With<Camera2d>>();
+        query.single(world).unwrap().scale.xy()
+    }
+
     fn validate_after_arena_setup(
         color_mat_res: Res<Assets<ColorMaterial>>,
         mesh_res: Res<Assets<Mesh>>,
@@ -358,7 +655,7 @@ mod tests {
     // Check whether a given set of 6 indices contains the necessary vertices/edges to
     // createa valid midline mesh dash between top_y and bot_y.
     //
-    fn validate_midline_mesh_dash(top_y: f32, bot_y: f32, indices: &[u16], verts: &Vec<[f32; 3]>) {
+    fn validate_midline_mesh_dash(top_y: f32, bot_y: f32, indices: &[u16], verts: &[[f32; 3]]) {
         assert_eq!(
             indices.len(),
             6,
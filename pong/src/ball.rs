@@ -7,14 +7,17 @@
 // -------------------------------------------------------------------------------------------------
 // Included Symbols
 
+use std::collections::VecDeque;
 use std::f32::consts::PI;
 use std::time::Duration;
 
 use bevy::prelude::*;
 use rand::Rng;
+use rand::seq::IndexedRandom;
 
 use crate::common::*;
-use crate::paddle::{self, AllPaddleHitboxes, Paddle, PaddleHitbox};
+use crate::paddle::{self, AllPaddleHitboxes, Paddle, PaddleHitbox, PaddlePosition};
+use crate::settings::Settings;
 
 // -------------------------------------------------------------------------------------------------
 // Constants
@@ -24,21 +27,33 @@ const BALL_SPEED_AS_SCREEN_WIDTH_PCT: f32 = 0.9;
 const BALL_SIZE: f32 = BALL_SIZE_AS_SCREEN_HEIGHT_PCT * ARENA_HEIGHT;
 const BALL_SPEED: f32 = BALL_SPEED_AS_SCREEN_WIDTH_PCT * ARENA_WIDTH;
 const BALL_OFF_SCREEN_X_MAG: f32 = (ARENA_WIDTH / 2f32) - (BALL_SIZE / 2f32);
+const BALL_OFF_SCREEN_Y_MAG: f32 = ARENA_HEIGHT / 2f32; // Beyond any wall bounce point
+/// Largest angle away from straight at an edge of the arena which the ball may be served at
+pub const BALL_SERVE_MAX_ANGLE: f32 = PI / 7f32;
+const BALL_TRAIL_MAX_LEN: usize = 12; // Longest trail_len of any curve level
+const BALL_TRAIL_SAMPLE_SECS: f32 = 1f32 / 60f32;
+const BALL_TRAIL_ALPHA: f32 = 0.6;
 
 const BALL_CURVE_CFG_NONE: CurveLevelCfg = CurveLevelCfg {
     color: BallColor::Solid(Color::srgb_u8(0, 255, 0)),
     rotate_rad_per_sec: 0.0,
     curve_rad_per_sec: 0.0,
+    trail_len: 4,
+    trail_width: 0.5,
 };
 const BALL_CURVE_CFG_L1: CurveLevelCfg = CurveLevelCfg {
     color: BallColor::Solid(Color::srgb_u8(0, 255, 0)),
     rotate_rad_per_sec: 2.0 * PI,
     curve_rad_per_sec: 0.1 * PI,
+    trail_len: 6,
+    trail_width: 0.6,
 };
 const BALL_CURVE_CFG_L2: CurveLevelCfg = CurveLevelCfg {
     color: BallColor::Solid(Color::srgb_u8(255, 255, 0)),
     rotate_rad_per_sec: 3.0 * PI,
     curve_rad_per_sec: 0.3 * PI,
+    trail_len: 9,
+    trail_width: 0.75,
 };
 const BALL_CURVE_CFG_L3: CurveLevelCfg = CurveLevelCfg {
     color: BallColor::Blinking {
@@ -47,6 +62,8 @@ const BALL_CURVE_CFG_L3: CurveLevelCfg = CurveLevelCfg {
     },
     rotate_rad_per_sec: 5.0 * PI,
     curve_rad_per_sec: 0.6 * PI,
+    trail_len: BALL_TRAIL_MAX_LEN,
+    trail_width: 0.9,
 };
 const BALL_CURVE_LEVELS: [CurveLevelCfg; 4] = [
     BALL_CURVE_CFG_NONE,
@@ -68,7 +85,13 @@ pub struct BallPlugin;
 
 impl Plugin for BallPlugin {
     fn build(&self, app: &mut App) {
-        app.add_message::<BallOffScreen>()
+        app.init_resource::<GameRng>()
+            .init_resource::<Settings>()
+            .init_resource::<NextServe>()
+            .init_resource::<BallTrail>()
+            .add_message::<BallOffScreen>()
+            .add_message::<BallHitPaddle>()
+            .add_message::<BallImpact>()
             .add_message::<ResetBall>()
             .add_message::<StartBall>()
             .add_systems(Startup, setup_ball.in_set(Systems::BallCreation))
@@ -76,6 +99,8 @@ impl Plugin for BallPlugin {
                 Update,
                 (
                     move_and_collide
+                        .in_set(Systems::BallHitPaddleSndr)
+                        .in_set(Systems::BallImpactSndr)
                         .before(detect_ball_off_screen)
                         .before(apply_curve_visuals),
                     detect_ball_off_screen.in_set(Systems::BallOffScreenSndr),
@@ -84,6 +109,10 @@ impl Plugin for BallPlugin {
                         .before(apply_curve_visuals),
                     handle_start_ball.in_set(Systems::StartBallRcvr),
                     apply_curve_visuals,
+                    update_ball_trail
+                        .after(apply_curve_visuals)
+                        .after(Systems::ResetBallRcvr)
+                        .after(Systems::StartBallRcvr),
                 ),
             )
             .configure_sets(
@@ -110,6 +139,18 @@ pub struct Ball {
     curve: CurveState,
 }
 
+impl Ball {
+    /// Get the direction the ball is currently moving in, or None while it is paused.
+    pub fn movement_dir(&self) -> Option<Dir2> {
+        (!self.paused).then_some(self.movement_dir)
+    }
+
+    /// Get the current curve level of the ball, from 0 (not curving) up to 3.
+    pub fn curve_level(&self) -> u8 {
+        self.curve.cfg_idx as u8
+    }
+}
+
 ///
 /// System sets to allow modules consuming this plugin to create ordering constraints
 /// based on functionality exposed in the API of the Plugin.
@@ -125,6 +166,18 @@ pub enum Systems {
     ///
     BallOffScreenSndr,
 
+    ///
+    /// Update systems which send BallHitPaddle messages. To react to these messages in the
+    /// same frame, the receiver should be ordered after this system set.
+    ///
+    BallHitPaddleSndr,
+
+    ///
+    /// Update systems which send BallImpact messages. To react to these messages in the
+    /// same frame, the receiver should be ordered after this system set.
+    ///
+    BallImpactSndr,
+
     ///
     /// Update systems which react to ResetBall messages. To react to these messages in the
     /// same frame, the sender should be ordered before this system set.
@@ -140,8 +193,9 @@ pub enum Systems {
 
 ///
 /// This message will be written by code in the BallPlugin to notify other modules
-/// that the ball has reached the edge of the screen on the left or right side, without
-/// bouncing off a paddle.
+/// that the ball has reached an edge of the screen, without bouncing off a paddle.
+/// The top and bottom edges are walls unless defended by a paddle, so those variants
+/// only occur in four player mode.
 ///
 /// If a system needs to react to this message in the same frame, it should be ordered
 /// before the BallOffScreenSndr SystemSet.
@@ -150,6 +204,58 @@ pub enum Systems {
 pub enum BallOffScreen {
     Left,
     Right,
+    Top,
+    Bottom,
+}
+
+impl BallOffScreen {
+    /// Get the edge of the arena which the ball left through.
+    pub fn side(self) -> ArenaSide {
+        match self {
+            BallOffScreen::Left => ArenaSide::Left,
+            BallOffScreen::Right => ArenaSide::Right,
+            BallOffScreen::Top => ArenaSide::Top,
+            BallOffScreen::Bottom => ArenaSide::Bottom,
+        }
+    }
+}
+
+///
+/// This message will be written by code in the BallPlugin each time the ball bounces off
+/// the face of a paddle, identifying the paddle which was hit. The curve level is that of the
+/// ball after the bounce, from 0 (not curving) up to the strongest curve of 3.
+///
+/// If a system needs to react to this message in the same frame, it should be ordered
+/// after the BallHitPaddleSndr SystemSet.
+///
+#[derive(Message, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BallHitPaddle {
+    /// The player who the paddle belongs to.
+    pub player: PlayerId,
+    /// Which of the player's paddles was hit.
+    pub position: PaddlePosition,
+    /// The curve level of the ball after bouncing off the paddle.
+    pub curve_level: u8,
+}
+
+///
+/// This message will be written by code in the BallPlugin each time the ball bounces off
+/// any surface, whether a wall or a paddle, giving where it made contact. Walled off paddles
+/// count as walls. The curve level is that of the ball after the bounce.
+///
+/// If a system needs to react to this message in the same frame, it should be ordered
+/// after the BallImpactSndr SystemSet.
+///
+#[derive(Message, Clone, Copy, PartialEq, Debug)]
+pub struct BallImpact {
+    /// The point on the surface which the edge of the ball touched.
+    pub point: Vec2,
+    /// The normal of the surface hit, pointing back into the arena.
+    pub normal: Dir2,
+    /// The curve level of the ball after the bounce.
+    pub curve_level: u8,
+    /// True if the surface was the face of a paddle in play, or false for a wall.
+    pub paddle: bool,
 }
 
 ///
@@ -159,12 +265,13 @@ pub enum BallOffScreen {
 /// If the reset needs to occur in the same frame as this message gets sent, the
 /// system generating the message should be ordered before ResetBallRcvr.
 ///
-#[derive(Message)]
+#[derive(Message, Debug)]
 pub struct ResetBall;
 
 ///
 /// This message should be sent by another module to signal that the ball should
-/// unpause and start moving in a random direction towards the left or right paddle.
+/// unpause and start moving towards one of the active paddles. The direction of the serve
+/// is taken from the NextServe resource, or chosen at random where it is left unset.
 ///
 /// If the start needs to occur in the same frame as this message is sent, the
 /// system generating the message should be ordered before StartBallRcvr.
@@ -172,6 +279,34 @@ pub struct ResetBall;
 #[derive(Message)]
 pub struct StartBall;
 
+///
+/// Resource which other modules may set to choose how the ball is served by the next StartBall
+/// message. Anything left as None is chosen at random, as is the receiver if their edge of the
+/// arena is no longer defended by an active paddle. It is not changed by serving the ball.
+///
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct NextServe {
+    /// The edge of the arena to serve the ball towards
+    pub receiver: Option<ArenaSide>,
+    ///
+    /// Angle of the serve away from straight at the receiver's edge, in radians. Positive is
+    /// counterclockwise, and it is limited to BALL_SERVE_MAX_ANGLE either way.
+    ///
+    pub angle: Option<f32>,
+}
+
+///
+/// Get the colors the ball is drawn in at the given curve level, which are shared by effects
+/// that reflect the curve level. A level beyond the strongest curve is treated as the strongest.
+///
+pub fn curve_level_colors(curve_level: u8) -> Vec<Color> {
+    let idx = (curve_level as usize).min(BALL_CURVE_LEVELS.len() - 1);
+    match BALL_CURVE_LEVELS[idx].color {
+        BallColor::Solid(color) => vec![color],
+        BallColor::Blinking { colors, .. } => colors.to_vec(),
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 // Private Types
 
@@ -191,6 +326,8 @@ struct CurveLevelCfg<'a> {
     color: BallColor<'a>,
     rotate_rad_per_sec: f32, // Should always be positive
     curve_rad_per_sec: f32,  // Should always be positive
+    trail_len: usize,        // Number of trail segments, at most BALL_TRAIL_MAX_LEN
+    trail_width: f32,        // Width of the newest trail segment, relative to the ball
 }
 
 // Represents the direction the ball is currently curving in, if any.
@@ -263,6 +400,12 @@ impl CurveState {
         }
     }
 
+    // Get the length and width of the trail behind the ball, for its current curve level.
+    fn get_trail_cfg(&self) -> (usize, f32) {
+        let cur_state = BALL_CURVE_LEVELS.get(self.cfg_idx).unwrap();
+        (cur_state.trail_len, cur_state.trail_width)
+    }
+
     //
     // Given the time_delta for the current frame, return how many radians the ball
     // should be rotated by according to its current curve state.
@@ -290,12 +433,39 @@ impl CurveState {
     }
 }
 
+// -------------------------------------------------------------------------------------------------
+// Private Resources
+
+// The recent positions of the ball drawn as its trail, newest first.
+#[derive(Resource)]
+struct BallTrail {
+    positions: VecDeque<Vec2>,
+    sample_timer: Timer,
+}
+
+impl Default for BallTrail {
+    fn default() -> Self {
+        BallTrail {
+            positions: VecDeque::with_capacity(BALL_TRAIL_MAX_LEN),
+            sample_timer: Timer::from_seconds(BALL_TRAIL_SAMPLE_SECS, TimerMode::Repeating),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Components
+
+// Component for one segment of the ball's trail, drawing the position at the given index.
+#[derive(Component)]
+struct BallTrailSegment(usize);
+
 // -------------------------------------------------------------------------------------------------
 // Private Systems
 
 //
 // Adds the Ball entity to the app with the appropriate on-screen size and color.
-// It initially starts paused in the center with no movement vector
+// It initially starts paused in the center with no movement vector. The segments of
+// the ball's trail are added alongside it, hidden until the ball is moving.
 //
 fn setup_ball(mut commands: Commands) {
     commands.spawn((
@@ -310,6 +480,18 @@ fn setup_ball(mut commands: Commands) {
         },
         Transform::from_scale(Vec3::new(BALL_SIZE, BALL_SIZE, 0f32)),
     ));
+
+    for idx in 0..BALL_TRAIL_MAX_LEN {
+        commands.spawn((
+            BallTrailSegment(idx),
+            Sprite {
+                custom_size: Some(Vec2::ONE),
+                ..default()
+            },
+            Transform::from_xyz(0f32, 0f32, Z_BEHIND_GAMEPLAY),
+            Visibility::Hidden,
+        ));
+    }
 }
 
 //
@@ -321,6 +503,8 @@ fn move_and_collide(
     time: Res<Time>,
     ball_q: Single<(&mut Ball, &mut Transform), Without<Paddle>>,
     paddles: Query<AllPaddleHitboxes>,
+    mut hit_msgs: MessageWriter<BallHitPaddle>,
+    mut impact_msgs: MessageWriter<BallImpact>,
 ) {
     let (mut ball, mut ball_tf) = ball_q.into_inner();
 
@@ -332,7 +516,14 @@ fn move_and_collide(
         // Move the ball along its trajectory and collide as needed
         let mut move_dist = time.delta_secs() * BALL_SPEED;
         loop {
-            let collision_dist = collide_once(move_dist, &mut ball, &mut ball_tf, paddles);
+            let collision_dist = collide_once(
+                move_dist,
+                &mut ball,
+                &mut ball_tf,
+                paddles,
+                &mut hit_msgs,
+                &mut impact_msgs,
+            );
             match collision_dist {
                 Some(dist) => move_dist -= dist,
                 None => break,
@@ -358,6 +549,55 @@ fn apply_curve_visuals(time: Res<Time>, ball_q: Single<(&mut Ball, &mut Sprite,
     ball_tf.rotation *= Quat::from_rotation_z(ball.curve.get_rotation_delta(time.delta()));
 }
 
+//
+// Updates the fading trail of recent positions drawn behind the ball. Its length, width and
+// color follow the current curve level of the ball. The trail is cleared whenever the ball is
+// paused, which includes each ResetBall, and is not drawn while turned off in the Settings.
+//
+fn update_ball_trail(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut trail: ResMut<BallTrail>,
+    ball_q: Single<(&Ball, &Sprite, &Transform), Without<BallTrailSegment>>,
+    segments: Query<
+        (
+            &BallTrailSegment,
+            &mut Sprite,
+            &mut Transform,
+            &mut Visibility,
+        ),
+        Without<Ball>,
+    >,
+) {
+    let (ball, ball_sprite, ball_tf) = ball_q.into_inner();
+    let (trail_len, trail_width) = ball.curve.get_trail_cfg();
+
+    if ball.paused || !settings.ball_trail {
+        trail.positions.clear();
+        trail.sample_timer.reset();
+    } else {
+        trail.sample_timer.tick(time.delta());
+        if trail.sample_timer.times_finished_this_tick() > 0 {
+            trail.positions.push_front(ball_tf.translation.truncate());
+        }
+        trail.positions.truncate(trail_len);
+    }
+
+    // Older segments are smaller and more transparent, fading out towards the end of the trail
+    for (segment, mut sprite, mut segment_tf, mut visibility) in segments {
+        let Some(&position) = trail.positions.get(segment.0) else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+        let fade = 1f32 - ((segment.0 + 1) as f32 / (trail_len + 1) as f32);
+        let size = BALL_SIZE * trail_width * (0.5 + (0.5 * fade));
+        sprite.color = ball_sprite.color.with_alpha(BALL_TRAIL_ALPHA * fade);
+        segment_tf.translation = position.extend(Z_BEHIND_GAMEPLAY);
+        segment_tf.scale = Vec3::new(size, size, 1f32);
+        visibility.set_if_neq(Visibility::Inherited);
+    }
+}
+
 //
 // Notifies other modules that the ball has reached the edge of the screen, by
 // dispatching BallOffScreen messages.
@@ -379,6 +619,13 @@ fn detect_ball_off_screen(
         } else {
             BallOffScreen::Left
         });
+    } else if ball_tf.translation.y.abs() > BALL_OFF_SCREEN_Y_MAG {
+        // Ball has passed a defended top/bottom edge! Write message
+        messages.write(if ball_tf.translation.y.is_sign_positive() {
+            BallOffScreen::Top
+        } else {
+            BallOffScreen::Bottom
+        });
     }
 }
 
@@ -404,23 +651,47 @@ fn handle_reset_ball(
 
 //
 // Handles StartBall messages sent by other modules, to unpause the Ball and
-// start it moving in a random direction towards the left or right wall.
+// start it moving towards one of the edges of the arena defended by a paddle
+// which has not been walled off. The edge and angle are those of the NextServe,
+// or random if it doesn't give them.
 //
-fn handle_start_ball(mut messages: MessageReader<StartBall>, ball_q: Single<&mut Ball>) {
+fn handle_start_ball(
+    mut messages: MessageReader<StartBall>,
+    ball_q: Single<&mut Ball>,
+    paddles: Query<AllPaddleHitboxes>,
+    next_serve: Res<NextServe>,
+    mut rng: ResMut<GameRng>,
+) {
     if !messages.is_empty() {
         messages.clear();
 
-        // Generate a random starting angle (w/ 50% change of each direction)
-        let mut rng = rand::rng();
-        let random_angle = rng.random_range(-(PI / 7f32)..(PI / 7f32));
-        let mut rotation_quat = Quat::from_rotation_z(random_angle);
-        if rng.random_bool(1.0 / 2.0) {
-            // flip rotation 180 degrees
-            rotation_quat *= Quat::from_rotation_z(PI);
+        let mut active_sides: Vec<ArenaSide> = Vec::new();
+        for hitbox in &paddles {
+            if !hitbox.is_walled() && !active_sides.contains(&hitbox.side()) {
+                active_sides.push(hitbox.side());
+            }
         }
 
+        // Unless chosen, pick a random defended edge (equal chance of each) and angle towards it
+        let rng = rng.rng();
+        let side = match next_serve.receiver {
+            Some(side) if active_sides.contains(&side) => side,
+            _ => {
+                let Some(&side) = active_sides.choose(rng) else {
+                    warn!("StartBall received with no active paddles to serve towards");
+                    return;
+                };
+                side
+            }
+        };
+        let angle = match next_serve.angle {
+            Some(angle) => angle.clamp(-BALL_SERVE_MAX_ANGLE, BALL_SERVE_MAX_ANGLE),
+            None => rng.random_range(-BALL_SERVE_MAX_ANGLE..BALL_SERVE_MAX_ANGLE),
+        };
+        let serve_dir = Rot2::radians(angle) * -side.inward_normal();
+
         let mut ball = ball_q.into_inner();
-        ball.movement_dir = Dir2::new_unchecked((rotation_quat * Vec3::X).xy());
+        ball.movement_dir = Dir2::new_unchecked(serve_dir);
         ball.paused = false;
     }
 }
@@ -434,116 +705,143 @@ fn handle_start_ball(mut messages: MessageReader<StartBall>, ball_q: Single<&mut
 // If a collision occurred, Some(f32) will be returned with the distance that
 // the ball has moved to reach this collision point. None is returned for no
 // collision. Ideally, this function should be called repeatedly until None is returned.
+// A BallHitPaddle message is written for each paddle the ball bounces off, and a
+// BallImpact message for every surface.
+//
+// Any edge of the arena which is not defended by a paddle is treated as a wall. A side may
+// be defended by several paddles (e.g. doubles mode), in which case the paddle face hit
+// earliest along the ball's ray is the one collided with. Paddles are only solid from the
+// front, so a ball travelling away from a paddle face passes through it.
 //
 fn collide_once(
     move_dist: f32,
     ball: &mut Ball,
     ball_tf: &mut Transform,
     paddles: Query<AllPaddleHitboxes>,
+    hit_msgs: &mut MessageWriter<BallHitPaddle>,
+    impact_msgs: &mut MessageWriter<BallImpact>,
 ) -> Option<f32> {
     // How far from center of ball should it "collide" with objects
     let ball_rad = ball_tf.scale.x / 2f32;
+    let ball_ray = Ray2d::new(ball_tf.translation.xy(), ball.movement_dir);
 
-    // (Plane origin offset for ball size, Plane)
-    let wall = if ball.movement_dir.y > 0f32 {
-        // Focus on collisions with top wall if moving up
-        (
-            Vec2::new(0f32, (ARENA_HEIGHT / 2f32) - ball_rad),
-            Plane2d::new(Vec2::NEG_Y),
-        )
-    } else {
-        // Otherwise, bottom wall
-        (
-            Vec2::new(0f32, (-ARENA_HEIGHT / 2f32) + ball_rad),
-            Plane2d::new(Vec2::Y),
-        )
-    };
-
-    // (
-    //     Plane origin offset for ball size,
-    //     Plane,
-    //     Paddle bot offset for ball size,
-    //     Paddle top offset for ball size,
-    //     Applied spin on ball,
-    // )
-    let paddle = if ball.movement_dir.x > 0f32 {
-        // Focus on collisions with p2 paddle if moving right
-        let hitbox = PaddleHitbox::from_query(paddles, Player2);
-        (
-            hitbox.plane_origin() - Vec2::new(ball_rad, 0f32),
-            Plane2d::new(Vec2::NEG_X),
-            hitbox.bot_y() - ball_rad,
-            hitbox.top_y() + ball_rad,
-            match hitbox.movement_dir() {
-                paddle::MoveDirection::Up => CurveDir::CounterClockwise,
-                paddle::MoveDirection::Down => CurveDir::Clockwise,
-                paddle::MoveDirection::None => CurveDir::None,
-            },
-        )
-    } else {
-        // Otherwise, focus on p1 paddle
-        let hitbox = PaddleHitbox::from_query(paddles, Player1);
-        (
-            hitbox.plane_origin() + Vec2::new(ball_rad, 0f32),
-            Plane2d::new(Vec2::X),
-            hitbox.bot_y() - ball_rad,
-            hitbox.top_y() + ball_rad,
-            match hitbox.movement_dir() {
-                paddle::MoveDirection::Up => CurveDir::Clockwise,
-                paddle::MoveDirection::Down => CurveDir::CounterClockwise,
-                paddle::MoveDirection::None => CurveDir::None,
-            },
-        )
-    };
+    // (Distance to impact point, Normal, CurveDir if applies, Paddle hit if applies)
+    struct Collision(f32, Plane2d, Option<CurveDir>, Option<BallHitPaddle>);
 
-    let ball_ray = Ray2d::new(ball_tf.translation.xy(), ball.movement_dir);
+    let mut collisions: Vec<Collision> = Vec::new();
+
+    // Collisions with walls on any undefended edges which the ball is moving towards
+    for side in ArenaSide::ALL {
+        let normal = side.inward_normal();
+        if (ball.movement_dir.dot(normal) >= 0f32)
+            || paddles.iter().any(|hitbox| hitbox.side() == side)
+        {
+            continue;
+        }
+
+        let edge = -normal * Vec2::new(ARENA_WIDTH / 2f32, ARENA_HEIGHT / 2f32);
+        let plane = Plane2d::new(normal);
+        if let Some(dist) = ball_ray.intersect_plane(edge + (normal * ball_rad), plane)
+            && dist <= move_dist
+        {
+            collisions.push(Collision(dist, plane, None, None));
+        }
+    }
 
-    // (Distance to impact point, Normal, CurveDir if applies, Cached impact point once computed)
-    struct Collision(f32, Plane2d, Option<CurveDir>, Option<Vec2>);
+    // Collisions with the face of any paddles which the ball is moving towards
+    for hitbox in &paddles {
+        let normal = hitbox.plane_normal();
+        if ball.movement_dir.dot(normal) >= 0f32 {
+            continue;
+        }
 
-    let mut paddle_collision: Option<Collision> = None;
-    if let Some(dist) = ball_ray.intersect_plane(paddle.0, paddle.1) {
-        if dist <= move_dist {
+        let plane = Plane2d::new(normal);
+        let plane_origin = hitbox.plane_origin() + (normal * ball_rad);
+        if let Some(dist) = ball_ray.intersect_plane(plane_origin, plane)
+            && dist <= move_dist
+        {
             let impact_point = ball_ray.get_point(dist);
-            if (impact_point.y >= paddle.2) && (impact_point.y <= paddle.3) {
-                paddle_collision = Some(Collision(
+            let impact_on_face = if hitbox.side().is_vertical() {
+                impact_point.y
+            } else {
+                impact_point.x
+            };
+            if (impact_on_face >= hitbox.face_min() - ball_rad)
+                && (impact_on_face <= hitbox.face_max() + ball_rad)
+            {
+                // Walled off paddles belong to eliminated players, so don't count as hits
+                let hit = (!hitbox.is_walled()).then(|| BallHitPaddle {
+                    player: hitbox.player(),
+                    position: hitbox.position(),
+                    curve_level: 0,
+                });
+                collisions.push(Collision(
                     dist,
-                    paddle.1,
-                    Some(paddle.4),
-                    Some(impact_point),
+                    plane,
+                    Some(curve_from_paddle(&hitbox)),
+                    hit,
                 ));
             }
         }
     }
 
-    let mut wall_collision: Option<Collision> = None;
-    if let Some(dist) = ball_ray.intersect_plane(wall.0, wall.1) {
-        if dist <= move_dist {
-            wall_collision = Some(Collision(dist, wall.1, None, None));
+    let impact_dist = collisions
+        .iter()
+        .map(|collision| collision.0)
+        .reduce(f32::min)?;
+    ball_tf.translation = ball_ray.get_point(impact_dist).extend(0f32);
+
+    //
+    // Apply every collision at the nearest distance (e.g. wall and paddle in a corner). Each
+    // surface normal is only reflected off once, in case several paddles share a plane.
+    //
+    let mut reflected: Vec<Vec2> = Vec::new();
+    for collision in collisions
+        .iter()
+        .filter(|collision| collision.0 == impact_dist)
+    {
+        let normal = collision.1.normal.as_vec2();
+        if reflected.contains(&normal) {
+            continue;
         }
-    }
+        reflected.push(normal);
 
-    let mut apply_collision = |collision: Collision| {
-        let impact_point = collision.3.unwrap_or(ball_ray.get_point(collision.0));
-        ball_tf.translation = impact_point.extend(0f32);
-        ball.movement_dir =
-            Dir2::new_unchecked(ball.movement_dir.reflect(collision.1.normal.as_vec2()));
+        ball.movement_dir = Dir2::new_unchecked(ball.movement_dir.reflect(normal));
         if let Some(curve_dir) = collision.2 {
             ball.curve.apply_curve(curve_dir);
         }
-        Some(collision.0)
-    };
-
-    match (paddle_collision, wall_collision) {
-        (Some(pad_imp), Some(wall_imp)) if pad_imp.0 < wall_imp.0 => apply_collision(pad_imp),
-        (Some(pad_imp), Some(wall_imp)) if wall_imp.0 < pad_imp.0 => apply_collision(wall_imp),
-        (Some(pad_imp), Some(wall_imp)) => {
-            // Hitting wall and paddle at same dist (corner)
-            apply_collision(wall_imp);
-            apply_collision(pad_imp)
+        if let Some(hit) = collision.3 {
+            hit_msgs.write(BallHitPaddle {
+                curve_level: ball.curve.cfg_idx as u8,
+                ..hit
+            });
         }
-        (None, Some(imp)) | (Some(imp), None) => apply_collision(imp),
-        (None, None) => None,
+        impact_msgs.write(BallImpact {
+            point: ball_tf.translation.xy() - (normal * ball_rad),
+            normal: collision.1.normal,
+            curve_level: ball.curve.cfg_idx as u8,
+            paddle: collision.3.is_some(),
+        });
+    }
+
+    Some(impact_dist)
+}
+
+//
+// Get the curve direction applied to the ball when it bounces off the given paddle,
+// based on which way the paddle is moving relative to its face.
+//
+fn curve_from_paddle(hitbox: &PaddleHitbox) -> CurveDir {
+    let spin = hitbox
+        .plane_normal()
+        .perp_dot(hitbox.movement_dir().as_vec2());
+
+    if spin > 0f32 {
+        CurveDir::Clockwise
+    } else if spin < 0f32 {
+        CurveDir::CounterClockwise
+    } else {
+        CurveDir::None
     }
 }
 
@@ -551,7 +849,7 @@ fn collide_once(
 // Unit Tests
 
 #[cfg(test)]
-mod tests {
+pub mod tests {
     use super::*;
     use bevy::ecs::schedule::AnonymousSet;
     use bevy::sprite::Anchor;
@@ -568,10 +866,22 @@ mod tests {
             world.is_resource_added::<Messages<BallOffScreen>>(),
             "Expected BallOffScreen messages to be added by BallPlugin",
         );
+        assert!(
+            world.is_resource_added::<Messages<BallHitPaddle>>(),
+            "Expected BallHitPaddle messages to be added by BallPlugin",
+        );
+        assert!(
+            world.is_resource_added::<Messages<BallImpact>>(),
+            "Expected BallImpact messages to be added by BallPlugin",
+        );
         assert!(
             world.is_resource_added::<Messages<StartBall>>(),
             "Expected StartBall messages to be added by BallPlugin",
         );
+        assert!(
+            world.is_resource_added::<NextServe>(),
+            "Expected NextServe resource to be added by BallPlugin",
+        );
         assert!(
             world.is_resource_added::<Messages<ResetBall>>(),
             "Expected ResetBall messages to be added by BallPlugin",
@@ -589,7 +899,13 @@ mod tests {
             BallPlugin,
             Update,
             move_and_collide,
-            Option::<AnonymousSet>::None,
+            Some(Systems::BallHitPaddleSndr),
+        );
+        validate_sys_in_plugin(
+            BallPlugin,
+            Update,
+            move_and_collide,
+            Some(Systems::BallImpactSndr),
         );
     }
 
@@ -613,6 +929,11 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_plugin_added_sys_trail() {
+        validate_sys_in_plugin(BallPlugin, Update, update_ball_trail, None::<AnonymousSet>);
+    }
+
     #[test]
     fn test_plugin_added_sys_handle_start() {
         validate_sys_in_plugin(
@@ -670,6 +991,103 @@ mod tests {
             "Expected Ball to be BALL_SIZE x BALL_SIZE x 0, but got {}",
             ball_tf.scale,
         );
+
+        // Validate the trail segments were created, hidden until the ball moves
+        let mut query = world.query::<(&BallTrailSegment, &Visibility)>();
+        assert_eq!(query.iter(&world).len(), BALL_TRAIL_MAX_LEN);
+        assert!(
+            query
+                .iter(&world)
+                .all(|(_, vis)| *vis == Visibility::Hidden),
+            "Expected ball trail to start hidden",
+        );
+    }
+
+    #[test]
+    fn test_curve_levels_trail() {
+        for (lvl, pair) in BALL_CURVE_LEVELS.windows(2).enumerate() {
+            assert!(
+                pair[1].trail_len > pair[0].trail_len && pair[1].trail_width > pair[0].trail_width,
+                "Expected curve level {} to have a longer and wider trail than level {lvl}",
+                lvl + 1,
+            );
+        }
+        assert_eq!(
+            BALL_CURVE_LEVELS.iter().map(|cfg| cfg.trail_len).max(),
+            Some(BALL_TRAIL_MAX_LEN),
+            "Expected BALL_TRAIL_MAX_LEN to be the longest trail of any curve level",
+        );
+    }
+
+    #[test]
+    fn test_curve_level_colors() {
+        assert_eq!(
+            curve_level_colors(0),
+            vec![Color::srgb_u8(0, 255, 0)],
+            "Expected a single solid color with no curve",
+        );
+        assert_eq!(curve_level_colors(2), vec![Color::srgb_u8(255, 255, 0)]);
+        let BallColor::Blinking { colors, .. } = BALL_CURVE_CFG_L3.color else {
+            panic!("Expected the strongest curve level to blink");
+        };
+        assert_eq!(curve_level_colors(3), colors.to_vec());
+        assert_eq!(
+            curve_level_colors(200),
+            colors.to_vec(),
+            "Expected levels beyond the strongest to use the strongest colors",
+        );
+    }
+
+    #[test]
+    fn test_update_ball_trail_sys() {
+        let mut world = setup_trail_world();
+
+        // No trail while the ball waits to be served
+        run_trail_frames(&mut world, 3);
+        assert_eq!(visible_trail_len(&mut world), 0);
+
+        // Trail grows behind a moving ball, up to the length of its curve level
+        set_test_ball_curve(&mut world, false, 0);
+        run_trail_frames(&mut world, 2);
+        assert_eq!(visible_trail_len(&mut world), 2);
+        run_trail_frames(&mut world, 20);
+        assert_eq!(visible_trail_len(&mut world), BALL_CURVE_CFG_NONE.trail_len);
+
+        set_test_ball_curve(&mut world, false, 3);
+        run_trail_frames(&mut world, 20);
+        assert_eq!(visible_trail_len(&mut world), BALL_CURVE_CFG_L3.trail_len);
+
+        // Segments fade and shrink along the trail
+        let mut query = world.query::<(&BallTrailSegment, &Sprite, &Transform)>();
+        let mut segments: Vec<_> = query
+            .iter(&world)
+            .map(|(segment, sprite, tf)| (segment.0, sprite.color.alpha(), tf.scale.x))
+            .collect();
+        segments.sort_by_key(|(idx, ..)| *idx);
+        for pair in segments.windows(2) {
+            assert!(
+                pair[1].1 < pair[0].1 && pair[1].2 < pair[0].2,
+                "Expected trail segment {} to be fainter and smaller than {}",
+                pair[1].0,
+                pair[0].0,
+            );
+        }
+
+        // Trail is cleared when the ball is reset
+        world.write_message(ResetBall);
+        world.run_system_cached(handle_reset_ball).unwrap();
+        run_trail_frames(&mut world, 1);
+        assert_eq!(visible_trail_len(&mut world), 0);
+        assert!(world.resource::<BallTrail>().positions.is_empty());
+    }
+
+    #[test]
+    fn test_update_ball_trail_disabled() {
+        let mut world = setup_trail_world();
+        world.resource_mut::<Settings>().ball_trail = false;
+        set_test_ball_curve(&mut world, false, 2);
+        run_trail_frames(&mut world, 10);
+        assert_eq!(visible_trail_len(&mut world), 0);
     }
 
     #[test]
@@ -1090,6 +1508,257 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_move_collide_top_paddle() {
+        // Solid collision with the top paddle in four player mode, imparting curve
+        let exp_collision_x = 0.0;
+        let exp_collision_y =
+            (ARENA_HEIGHT / 2.0) - paddle::tests::get_paddle_width() - (BALL_SIZE / 2.0);
+
+        let mut world = World::default();
+        paddle::tests::spawn_test_paddle(&mut world, 1.0, -1.0, Player1);
+        paddle::tests::spawn_test_paddle(&mut world, 1.0, -1.0, Player2);
+        paddle::tests::spawn_test_paddle(&mut world, 1.0, -1.0, Player3);
+        paddle::tests::spawn_test_paddle(&mut world, 1.0, -1.0, Player4);
+        paddle::tests::set_test_paddle_movement(&mut world, Player3, paddle::MoveDirection::Right);
+        world.spawn((
+            Ball {
+                movement_dir: Dir2::from_xy(3.0, 4.0).unwrap(),
+                paused: false,
+                curve: CurveState::default(),
+            },
+            Transform {
+                translation: Vec3::new(exp_collision_x - 3.0, exp_collision_y - 4.0, 0.0),
+                scale: Vec2::splat(BALL_SIZE).extend(0f32),
+                ..default()
+            },
+        ));
+        world.init_resource::<Time>();
+        world.init_resource::<Messages<BallHitPaddle>>();
+        world.init_resource::<Messages<BallImpact>>();
+        world
+            .get_resource_mut::<Time>()
+            .unwrap()
+            .advance_by(Duration::from_secs_f32((5.0 / BALL_SPEED) * 1.5));
+
+        let move_sys = world.register_system(move_and_collide);
+        world.run_system(move_sys).unwrap();
+
+        // Post reflection vector should be half of pre-collision 3/4/5 triangle.
+        // Note the clockwise curve will also slightly alter trajectory after the bounce.
+        let mut query = world.query::<(&Ball, &Transform)>();
+        let (ball, ball_tf) = query.single(&world).unwrap();
+        assert!(
+            ball_tf.translation.y < exp_collision_y,
+            "Expected ball to bounce back down off the top paddle, but it is at {}",
+            ball_tf.translation,
+        );
+        assert!(
+            ball.movement_dir.y < 0.0,
+            "Expected ball moving downwards after bounce, got {}",
+            ball.movement_dir,
+        );
+        assert_eq!(
+            (ball.curve.dir, ball.curve.cfg_idx),
+            (CurveDir::Clockwise, 1),
+            "Expected top paddle moving right to apply level 1 clockwise curve",
+        );
+        let hits: Vec<BallHitPaddle> = world
+            .resource::<Messages<BallHitPaddle>>()
+            .iter_current_update_messages()
+            .copied()
+            .collect();
+        assert_eq!(
+            hits,
+            vec![BallHitPaddle {
+                player: Player3,
+                position: PaddlePosition::Back,
+                curve_level: 1,
+            }],
+            "Expected a single BallHitPaddle message for the top paddle",
+        );
+        assert_eq!(
+            impacts(&world),
+            vec![BallImpact {
+                point: Vec2::new(exp_collision_x, exp_collision_y + (BALL_SIZE / 2.0),),
+                normal: Dir2::NEG_Y,
+                curve_level: 1,
+                paddle: true,
+            }],
+            "Expected a single BallImpact message for the top paddle face",
+        );
+    }
+
+    #[test]
+    fn test_move_collide_doubles_forward_paddle_first() {
+        // The forward paddle is hit before the back paddle behind it
+        let exp_collision_x = (-ARENA_WIDTH / 2.0)
+            + paddle::tests::get_forward_offset()
+            + paddle::tests::get_paddle_width()
+            + (BALL_SIZE / 2.0);
+
+        let mut world = World::default();
+        paddle::tests::spawn_test_paddle(&mut world, 1.0, -1.0, Player1);
+        paddle::tests::spawn_test_paddle_at(
+            &mut world,
+            1.0,
+            -1.0,
+            Player1,
+            PaddlePosition::Forward,
+        );
+        world.spawn((
+            Ball {
+                movement_dir: Dir2::NEG_X,
+                paused: false,
+                curve: CurveState::default(),
+            },
+            Transform {
+                translation: Vec3::new(exp_collision_x + 2.0, 0.0, 0.0),
+                scale: Vec2::splat(BALL_SIZE).extend(0f32),
+                ..default()
+            },
+        ));
+        world.init_resource::<Time>();
+        world.init_resource::<Messages<BallHitPaddle>>();
+        world.init_resource::<Messages<BallImpact>>();
+        world
+            .get_resource_mut::<Time>()
+            .unwrap()
+            .advance_by(Duration::from_secs_f32(3.0 / BALL_SPEED));
+
+        let move_sys = world.register_system(move_and_collide);
+        world.run_system(move_sys).unwrap();
+
+        let mut query = world.query::<(&Ball, &Transform)>();
+        let (ball, ball_tf) = query.single(&world).unwrap();
+        assert!(
+            (ball_tf.translation.x - (exp_collision_x + 1.0)).abs() < 0.00001,
+            "Expected ball to bounce off forward paddle face and end at x {}, got {}",
+            exp_collision_x + 1.0,
+            ball_tf.translation.x,
+        );
+        assert_eq!(
+            ball.movement_dir,
+            Dir2::X,
+            "Expected ball reflected by forward paddle",
+        );
+    }
+
+    #[test]
+    fn test_move_doubles_passes_behind_forward_paddle() {
+        // A ball moving away from the face of a paddle passes through it from behind
+        let forward_face_x = (-ARENA_WIDTH / 2.0)
+            + paddle::tests::get_forward_offset()
+            + paddle::tests::get_paddle_width();
+
+        let mut world = World::default();
+        paddle::tests::spawn_test_paddle(&mut world, 1.0, -1.0, Player1);
+        paddle::tests::spawn_test_paddle_at(
+            &mut world,
+            1.0,
+            -1.0,
+            Player1,
+            PaddlePosition::Forward,
+        );
+        world.spawn((
+            Ball {
+                movement_dir: Dir2::X,
+                paused: false,
+                curve: CurveState::default(),
+            },
+            Transform {
+                translation: Vec3::new(forward_face_x - 1.0, 0.0, 0.0),
+                scale: Vec2::splat(BALL_SIZE).extend(0f32),
+                ..default()
+            },
+        ));
+        world.init_resource::<Time>();
+        world.init_resource::<Messages<BallHitPaddle>>();
+        world.init_resource::<Messages<BallImpact>>();
+        world
+            .get_resource_mut::<Time>()
+            .unwrap()
+            .advance_by(Duration::from_secs_f32(2.0 / BALL_SPEED));
+
+        let move_sys = world.register_system(move_and_collide);
+        world.run_system(move_sys).unwrap();
+
+        let mut query = world.query::<(&Ball, &Transform)>();
+        let (ball, ball_tf) = query.single(&world).unwrap();
+        assert!(
+            (ball_tf.translation.x - (forward_face_x + 1.0)).abs() < 0.00001,
+            "Expected ball to pass through back of forward paddle to x {}, got {}",
+            forward_face_x + 1.0,
+            ball_tf.translation.x,
+        );
+        assert_eq!(
+            ball.movement_dir,
+            Dir2::X,
+            "Expected ball direction unchanged when passing behind forward paddle",
+        );
+    }
+
+    #[test]
+    fn test_move_collide_walled_paddle() {
+        // Walled paddles cover their entire edge, so the ball always bounces
+        let exp_collision_x =
+            (-ARENA_WIDTH / 2.0) + paddle::tests::get_paddle_width() + (BALL_SIZE / 2.0);
+        let exp_collision_y = 0.0;
+
+        let mut world = World::default();
+        paddle::tests::spawn_test_paddle(&mut world, 0.0, 0.0, Player1);
+        paddle::tests::spawn_test_paddle(&mut world, 0.0, 0.0, Player2);
+        paddle::tests::wall_off_test_paddles(&mut world, &[Player1]);
+        world.spawn((
+            Ball {
+                movement_dir: Dir2::from_xy(-4.0, 3.0).unwrap(),
+                paused: false,
+                curve: CurveState::default(),
+            },
+            Transform {
+                translation: Vec3::new(exp_collision_x + 4.0, exp_collision_y - 3.0, 0.0),
+                scale: Vec2::splat(BALL_SIZE).extend(0f32),
+                ..default()
+            },
+        ));
+        world.init_resource::<Time>();
+        world.init_resource::<Messages<BallHitPaddle>>();
+        world.init_resource::<Messages<BallImpact>>();
+        world
+            .get_resource_mut::<Time>()
+            .unwrap()
+            .advance_by(Duration::from_secs_f32((5.0 / BALL_SPEED) * 1.5));
+
+        let move_sys = world.register_system(move_and_collide);
+        world.run_system(move_sys).unwrap();
+
+        let mut query = world.query::<(&Ball, &Transform)>();
+        let (ball, ball_tf) = query.single(&world).unwrap();
+        let exp_pos = Vec2::new(exp_collision_x + 2.0, exp_collision_y + 1.5);
+        assert!(
+            (ball_tf.translation.xy() - exp_pos).length() < 0.00001,
+            "Expected ball at {exp_pos} after bouncing off walled paddle, got {}",
+            ball_tf.translation,
+        );
+        assert!(
+            (ball.movement_dir.as_vec2() - Vec2::new(0.8, 0.6)).length() < 0.00001,
+            "Expected reflected direction after bouncing off walled paddle, got {}",
+            ball.movement_dir,
+        );
+        // A walled paddle doesn't count as a hit
+        assert_message_count::<BallHitPaddle>(&world, 0);
+        assert_eq!(
+            impacts(&world),
+            vec![BallImpact {
+                point: Vec2::new(exp_collision_x - (BALL_SIZE / 2.0), exp_collision_y),
+                normal: Dir2::X,
+                curve_level: 0,
+                paddle: false,
+            }],
+            "Expected a walled paddle impact to count as a wall",
+        );
+    }
+
     #[test]
     fn test_move_collide_with_curve() {
         // Time to allow the ball to propagate 5 units
@@ -1319,6 +1988,53 @@ mod tests {
         test_ball_off_screen_helper(false, BALL_OFF_SCREEN_X_MAG - 1f32, None);
     }
 
+    #[test]
+    fn test_ball_off_screen_sys_top_bottom() {
+        for (ball_y, exp_message) in [
+            (ARENA_HEIGHT, BallOffScreen::Top),
+            (-ARENA_HEIGHT, BallOffScreen::Bottom),
+        ] {
+            let mut world = World::default();
+            world.spawn((
+                Ball {
+                    movement_dir: Dir2::Y,
+                    paused: false,
+                    curve: CurveState::default(),
+                },
+                Transform::from_translation(Vec3::new(0f32, ball_y, 0f32)),
+            ));
+            world.init_resource::<Messages<BallOffScreen>>();
+
+            let detect_sys = world.register_system(detect_ball_off_screen);
+            world.run_system(detect_sys).unwrap();
+            assert_messages_eq(&world, &[exp_message]);
+        }
+    }
+
+    #[test]
+    fn test_ball_off_screen_sys_wall_bounce_point() {
+        // Resting against the top wall (two player mode) is not off screen
+        let mut world = World::default();
+        world.spawn((
+            Ball {
+                movement_dir: Dir2::Y,
+                paused: false,
+                curve: CurveState::default(),
+            },
+            Transform::from_translation(Vec3::new(
+                0f32,
+                (ARENA_HEIGHT / 2f32) - (BALL_SIZE / 2f32),
+                0f32,
+            )),
+        ));
+        world.init_resource::<Messages<BallOffScreen>>();
+
+        let detect_sys = world.register_system(detect_ball_off_screen);
+        world.run_system(detect_sys).unwrap();
+
+        assert_message_count::<BallOffScreen>(&world, 0);
+    }
+
     #[test]
     fn test_reset_ball_sys() {
         let mut world = World::default();
@@ -1341,10 +2057,8 @@ mod tests {
             },
         ));
 
-        // Create message and resource containing it, for system to receive
-        let mut messages = Messages::<ResetBall>::default();
-        messages.write(ResetBall);
-        world.insert_resource(messages);
+        // Send the message for the system to receive
+        send_messages(&mut world, [ResetBall]);
 
         // Run the system
         let reset_sys = world.register_system(handle_reset_ball);
@@ -1393,16 +2107,20 @@ mod tests {
             Transform::default(),
         ));
 
-        // Create message and resource containing it, for system to receive
-        let mut messages = Messages::<StartBall>::default();
-        messages.write(StartBall);
-        world.insert_resource(messages);
+        // Spawn paddles for the ball to be served towards
+        paddle::tests::spawn_test_paddle(&mut world, 1.0, -1.0, Player1);
+        paddle::tests::spawn_test_paddle(&mut world, 1.0, -1.0, Player2);
+
+        // Send the message for the system to receive
+        send_messages(&mut world, [StartBall]);
+        world.init_resource::<GameRng>();
+        world.init_resource::<NextServe>();
 
         // Run the system
         let start_sys = world.register_system(handle_start_ball);
         world.run_system(start_sys).unwrap();
 
-        // Validate Ball was started (note we ignore direction part, since it's random)
+        // Validate Ball was started towards one of the paddles (exact direction is random)
         let mut query = world.query::<&Ball>();
         let ball = query.single(&world).unwrap_or_else(|err| {
             panic!("Attempt to query single Ball failed with err {err}");
@@ -1411,6 +2129,135 @@ mod tests {
             !ball.paused,
             "Expected ball to be unpaused after start message"
         );
+        assert!(
+            ball.movement_dir.x.abs() >= BALL_SERVE_MAX_ANGLE.cos(),
+            "Expected ball served towards left or right paddle, but got dir {}",
+            ball.movement_dir,
+        );
+    }
+
+    #[test]
+    fn test_start_ball_sys_skips_walled_paddles() {
+        let mut world = World::default();
+
+        world.spawn((
+            Ball {
+                movement_dir: Dir2::X,
+                paused: true,
+                curve: CurveState::default(),
+            },
+            Transform::default(),
+        ));
+
+        // Only the bottom paddle remains active. Wall off the rest.
+        world.init_resource::<Messages<StartBall>>();
+        world.init_resource::<GameRng>();
+        world.init_resource::<NextServe>();
+        paddle::tests::spawn_test_paddle(&mut world, 1.0, -1.0, Player1);
+        paddle::tests::spawn_test_paddle(&mut world, 1.0, -1.0, Player2);
+        paddle::tests::spawn_test_paddle(&mut world, 1.0, -1.0, Player3);
+        paddle::tests::spawn_test_paddle(&mut world, 1.0, -1.0, Player4);
+        paddle::tests::wall_off_test_paddles(&mut world, &[Player1, Player2, Player3]);
+
+        // Run the system
+        world.write_message(StartBall);
+        let start_sys = world.register_system(handle_start_ball);
+        world.run_system(start_sys).unwrap();
+
+        let mut query = world.query::<&Ball>();
+        let ball = query.single(&world).unwrap();
+        assert!(
+            !ball.paused,
+            "Expected ball to be unpaused after start message"
+        );
+        assert!(
+            -ball.movement_dir.y >= BALL_SERVE_MAX_ANGLE.cos(),
+            "Expected ball served towards bottom paddle, but got dir {}",
+            ball.movement_dir,
+        );
+    }
+
+    #[test]
+    fn test_start_ball_sys_seeded() {
+        // Serve the ball in two separate worlds seeded identically
+        let serve_dirs: Vec<Dir2> = (0..2)
+            .map(|_| {
+                let mut world = World::default();
+                world.spawn((
+                    Ball {
+                        movement_dir: Dir2::X,
+                        paused: true,
+                        curve: CurveState::default(),
+                    },
+                    Transform::default(),
+                ));
+                paddle::tests::spawn_test_paddle(&mut world, 1.0, -1.0, Player1);
+                paddle::tests::spawn_test_paddle(&mut world, 1.0, -1.0, Player2);
+                world.init_resource::<Messages<StartBall>>();
+                world.insert_resource(GameRng::seeded(7));
+                world.init_resource::<NextServe>();
+
+                world.write_message(StartBall);
+                let start_sys = world.register_system(handle_start_ball);
+                world.run_system(start_sys).unwrap();
+
+                let mut query = world.query::<&Ball>();
+                query.single(&world).unwrap().movement_dir
+            })
+            .collect();
+
+        assert_eq!(
+            serve_dirs[0], serve_dirs[1],
+            "Expected identically seeded games to serve the ball the same way",
+        );
+    }
+
+    #[test]
+    fn test_start_ball_sys_next_serve() {
+        let mut world = World::default();
+        world.spawn((
+            Ball {
+                movement_dir: Dir2::X,
+                paused: true,
+                curve: CurveState::default(),
+            },
+            Transform::default(),
+        ));
+        paddle::tests::spawn_test_paddle(&mut world, 1.0, -1.0, Player1);
+        paddle::tests::spawn_test_paddle(&mut world, 1.0, -1.0, Player2);
+        world.init_resource::<Messages<StartBall>>();
+        world.init_resource::<GameRng>();
+        world.insert_resource(NextServe {
+            receiver: Some(ArenaSide::Left),
+            angle: Some(PI),
+        });
+
+        // The chosen receiver is served towards, with the angle limited to the serve cone
+        world.write_message(StartBall);
+        world.run_system_cached(handle_start_ball).unwrap();
+        let mut query = world.query::<&Ball>();
+        let ball = query.single(&world).unwrap();
+        let exp_dir = Rot2::radians(BALL_SERVE_MAX_ANGLE) * Vec2::NEG_X;
+        assert!(
+            ball.movement_dir.as_vec2().abs_diff_eq(exp_dir, 0.0001),
+            "Expected ball served left at the largest angle, but got dir {}",
+            ball.movement_dir,
+        );
+
+        // An undefended receiver is replaced by a random defended edge
+        world.insert_resource(NextServe {
+            receiver: Some(ArenaSide::Top),
+            angle: Some(0f32),
+        });
+        world.write_message(StartBall);
+        world.run_system_cached(handle_start_ball).unwrap();
+        let ball = query.single(&world).unwrap();
+        assert_eq!(
+            ball.movement_dir.x.abs(),
+            1f32,
+            "Expected ball served straight at the left or right paddle, but got dir {}",
+            ball.movement_dir,
+        );
     }
 
     #[test]
@@ -1484,6 +2331,53 @@ mod tests {
 
     // --- Helper Functions ---
 
+    // Creates a world with the ball and its trail set up, with the ball waiting to be served.
+    fn setup_trail_world() -> World {
+        let mut world = World::default();
+        world.init_resource::<Settings>();
+        world.init_resource::<Time>();
+        world.init_resource::<BallTrail>();
+        world.init_resource::<Messages<ResetBall>>();
+        world.run_system_cached(setup_ball).unwrap();
+        world
+    }
+
+    // Sets whether the test ball is paused, and the curve level it's at.
+    fn set_test_ball_curve(world: &mut World, paused: bool, cfg_idx: usize) {
+        let mut query = world.query::<&mut Ball>();
+        let mut ball = query.single_mut(world).unwrap();
+        ball.paused = paused;
+        ball.curve.cfg_idx = cfg_idx;
+    }
+
+    // Runs the trail system for the given number of frames, moving the ball along each frame.
+    fn run_trail_frames(world: &mut World, frames: usize) {
+        for _ in 0..frames {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(20));
+            let mut query = world.query_filtered::<&mut Transform, With<Ball>>();
+            query.single_mut(world).unwrap().translation.x += 5f32;
+            world.run_system_cached(update_ball_trail).unwrap();
+        }
+    }
+
+    fn visible_trail_len(world: &mut World) -> usize {
+        let mut query = world.query::<(&BallTrailSegment, &Visibility)>();
+        query
+            .iter(world)
+            .filter(|(_, vis)| **vis != Visibility::Hidden)
+            .count()
+    }
+
+    fn impacts(world: &World) -> Vec<BallImpact> {
+        world
+            .resource::<Messages<BallImpact>>()
+            .iter_current_update_messages()
+            .copied()
+            .collect()
+    }
+
     fn test_move_and_collide_helper(cfg: &TestMoveCollideCfg) {
         let mut world = World::default();
 
@@ -1517,6 +2411,8 @@ mod tests {
             },
         ));
         world.init_resource::<Time>();
+        world.init_resource::<Messages<BallHitPaddle>>();
+        world.init_resource::<Messages<BallImpact>>();
         let move_sys = world.register_system(move_and_collide);
 
         for delta in cfg.time_deltas {
@@ -1587,25 +2483,20 @@ mod tests {
         world.run_system(detect_sys).unwrap();
 
         // Validate if the message was written or not
-        let messages = world.get_resource::<Messages<BallOffScreen>>().unwrap();
-        let mut msg_cursor = messages.get_cursor();
-        let mut msg_iter = msg_cursor.read(&messages);
-        if expected_message.is_none() {
-            assert!(
-                msg_iter.next().is_none(),
-                "Expected no BallOffScreen message, but got one",
-            );
-        } else {
-            let received_msg = *msg_iter
-                .next()
-                .expect("Expected a BallOffScreen message, but got none");
-            assert_eq!(
-                received_msg,
-                expected_message.unwrap(),
-                "Expected message {:?} but got message {:?}",
-                expected_message.unwrap(),
-                received_msg,
-            );
-        }
+        assert_messages_eq(&world, expected_message.as_slice());
+    }
+
+    // --- External API For Other Test Suites ---
+
+    // Spawns a ball at the given position, moving in the given direction (paused if None).
+    pub fn spawn_test_ball(world: &mut World, position: Vec2, movement_dir: Option<Dir2>) {
+        world.spawn((
+            Ball {
+                movement_dir: movement_dir.unwrap_or(Dir2::X),
+                paused: movement_dir.is_none(),
+                curve: CurveState::default(),
+            },
+            Transform::from_translation(position.extend(0f32)),
+        ));
     }
 }
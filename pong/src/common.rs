@@ -3,6 +3,20 @@
 //! and will be included by many of the core modules.
 //!
 
+// -------------------------------------------------------------------------------------------------
+// Included Symbols
+
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::ops::{Index, IndexMut};
+use std::path::Path;
+
 // -------------------------------------------------------------------------------------------------
 // Constants
 
@@ -17,76 +31,483 @@ pub const Z_BACKGROUND: f32 = -2f32;
 pub const Z_BEHIND_GAMEPLAY: f32 = -1f32;
 /// Z index for components in the foreground, in front of core gameplay
 pub const Z_FOREGROUND: f32 = 1f32;
+/// Z index for menus overlayed on top of everything else
+pub const Z_OVERLAY: f32 = 2f32;
+
+/// Name of the game's own directory within the platform's config and data directories
+pub const APP_DIR_NAME: &str = "rust_pong";
 
 // -------------------------------------------------------------------------------------------------
 // Re-Exports
 
 pub use PlayerId::Player1;
 pub use PlayerId::Player2;
+pub use PlayerId::Player3;
+pub use PlayerId::Player4;
 
 // -------------------------------------------------------------------------------------------------
 // Public Types
 
-/// PlayerId to differentiate between players 1 and 2 throughout game logic
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+///
+/// PlayerId to differentiate between players throughout game logic. Players 1 and 2
+/// defend the left and right edges of the arena (each as a team of two in doubles mode).
+/// Players 3 and 4 only take part in the four player mode, and defend the top and bottom
+/// edges respectively.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum PlayerId {
     Player1,
     Player2,
+    Player3,
+    Player4,
 }
 
-// -------------------------------------------------------------------------------------------------
-// Public Traits and Blanket Impls
+impl PlayerId {
+    /// Total number of distinct PlayerId values
+    pub const COUNT: usize = 4;
+
+    /// All PlayerId values, in order
+    pub const ALL: [PlayerId; PlayerId::COUNT] = [Player1, Player2, Player3, Player4];
+
+    /// Get a unique index for this player, in the range 0..PlayerId::COUNT
+    pub fn index(self) -> usize {
+        match self {
+            Player1 => 0,
+            Player2 => 1,
+            Player3 => 2,
+            Player4 => 3,
+        }
+    }
+
+    /// Get the side of the arena which this player is defending
+    pub fn side(self) -> ArenaSide {
+        match self {
+            Player1 => ArenaSide::Left,
+            Player2 => ArenaSide::Right,
+            Player3 => ArenaSide::Top,
+            Player4 => ArenaSide::Bottom,
+        }
+    }
+}
+
+/// Represents one of the four edges of the arena
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ArenaSide {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl ArenaSide {
+    /// All ArenaSide values, in order
+    pub const ALL: [ArenaSide; 4] = [
+        ArenaSide::Left,
+        ArenaSide::Right,
+        ArenaSide::Top,
+        ArenaSide::Bottom,
+    ];
+
+    /// Get the unit vector normal to this edge of the arena, pointing into the arena.
+    pub fn inward_normal(self) -> Vec2 {
+        match self {
+            ArenaSide::Left => Vec2::X,
+            ArenaSide::Right => Vec2::NEG_X,
+            ArenaSide::Top => Vec2::NEG_Y,
+            ArenaSide::Bottom => Vec2::Y,
+        }
+    }
+
+    /// Returns true if this is the left or right edge of the arena, false otherwise.
+    pub fn is_vertical(self) -> bool {
+        matches!(self, ArenaSide::Left | ArenaSide::Right)
+    }
+
+    /// Get the edge of the arena across from this one.
+    pub fn opposite(self) -> ArenaSide {
+        match self {
+            ArenaSide::Left => ArenaSide::Right,
+            ArenaSide::Right => ArenaSide::Left,
+            ArenaSide::Top => ArenaSide::Bottom,
+            ArenaSide::Bottom => ArenaSide::Top,
+        }
+    }
+
+    /// Get the PlayerId of the player defending this edge of the arena in four player mode.
+    pub fn player(self) -> PlayerId {
+        match self {
+            ArenaSide::Left => Player1,
+            ArenaSide::Right => Player2,
+            ArenaSide::Top => Player3,
+            ArenaSide::Bottom => Player4,
+        }
+    }
+}
 
 ///
-/// A trait that can be implemented for any type that contains some data T for both
-/// of the 2 players in the game. It allows retrieval of the data per-player.
+/// Resource selecting which variant of pong is being played. This is inserted into the app by
+/// the PongPlugin, and read by the other plugins when setting up and running the game.
 ///
-pub trait AsPerPlayerData<T> {
+#[derive(Resource, Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum GameMode {
+    /// Classic pong. Players 1 and 2 defend the left and right edges of the arena.
+    #[default]
+    TwoPlayer,
+
     ///
-    /// Required Method:
-    /// Consumes the value and returns a tuple of T types for players 1 and 2.
-    /// The first tuple item is for player 1. The second is for player 2.
+    /// Every edge of the arena is defended by a player. A player who concedes too many
+    /// goals is eliminated, and their edge is walled off. The last player standing wins.
     ///
-    fn as_per_player(self) -> (T, T);
-}
+    FourPlayer,
 
-impl<T, U> AsPerPlayerData<T> for U
-where
-    U: Iterator<Item = (PlayerId, T)>,
-{
     ///
-    /// Consumes the iterator (assuming it contains exactly 1 entry for each player)
-    /// and identifies which player each item T belongs to. Then returns the appropriate tuple.
+    /// Two versus two. Players 1 and 2 each represent a team defending the left and right
+    /// edges of the arena, with a back and a forward paddle each controlled separately.
     ///
-    /// This is intended for common use in query results that exist for each player. The
-    /// query iterator can be mapped to the appropriate iterator type (PlayerId, T) and
-    /// then this function can be called to return the p1 and p2 data as a tuple.
+    Doubles,
+}
+
+impl GameMode {
+    /// Get the list of players taking part in a game of this mode
+    pub fn players(self) -> &'static [PlayerId] {
+        match self {
+            GameMode::TwoPlayer | GameMode::Doubles => &PlayerId::ALL[..2],
+            GameMode::FourPlayer => &PlayerId::ALL,
+        }
+    }
+}
+
+///
+/// Collection holding at most one item of data for each player, indexed by PlayerId.
+///
+/// This is intended for common use in query results that exist for each player. The query
+/// iterator can be mapped to an iterator of (PlayerId, T) and collected with try_from_iter,
+/// which validates that every expected player has exactly one entry. Indexing by a player
+/// without an entry will panic, so use get when the player may be absent.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct PerPlayer<T>([Option<T>; PlayerId::COUNT]);
+
+impl<T> PerPlayer<T> {
+    /// Create an empty collection, without data for any player
+    pub fn new() -> Self {
+        Self(std::array::from_fn(|_| None))
+    }
+
+    /// Create a collection holding a clone of value for each of the given players
+    pub fn filled(players: &[PlayerId], value: T) -> Self
+    where
+        T: Clone,
+    {
+        let mut per_player = Self::new();
+        for &player in players {
+            per_player.insert(player, value.clone());
+        }
+        per_player
+    }
+
     ///
-    fn as_per_player(mut self) -> (T, T) {
-        let item1 = self.next();
-        let item2 = self.next();
-        assert!(
-            self.next().is_none(),
-            "Expected 1 iterator entry for each player. Got more than 2."
-        );
+    /// Collect the (PlayerId, T) items of an iterator, validating that there is exactly one
+    /// item for each of the expected players, and none for any other player.
+    ///
+    pub fn try_from_iter<I>(players: &[PlayerId], iter: I) -> Result<Self, PerPlayerError>
+    where
+        I: IntoIterator<Item = (PlayerId, T)>,
+    {
+        let mut per_player = Self::new();
+        for (player, item) in iter {
+            if !players.contains(&player) {
+                return Err(PerPlayerError::Unexpected(player));
+            }
+            if per_player.insert(player, item).is_some() {
+                return Err(PerPlayerError::Duplicate(player));
+            }
+        }
+
+        match players.iter().find(|&&player| !per_player.contains(player)) {
+            Some(&player) => Err(PerPlayerError::Missing(player)),
+            None => Ok(per_player),
+        }
+    }
+
+    /// Set the data for the given player, returning their previous data if any
+    pub fn insert(&mut self, player: PlayerId, item: T) -> Option<T> {
+        self.0[player.index()].replace(item)
+    }
+
+    /// Get the data for the given player, if any
+    pub fn get(&self, player: PlayerId) -> Option<&T> {
+        self.0[player.index()].as_ref()
+    }
+
+    /// Get the data for the given player mutably, if any
+    pub fn get_mut(&mut self, player: PlayerId) -> Option<&mut T> {
+        self.0[player.index()].as_mut()
+    }
+
+    /// Returns true if the collection holds data for the given player
+    pub fn contains(&self, player: PlayerId) -> bool {
+        self.0[player.index()].is_some()
+    }
+
+    /// Iterate over the (PlayerId, &T) entries of each player with data, in PlayerId order
+    pub fn iter(&self) -> impl Iterator<Item = (PlayerId, &T)> {
+        PlayerId::ALL
+            .into_iter()
+            .zip(&self.0)
+            .filter_map(|(player, item)| item.as_ref().map(|item| (player, item)))
+    }
+}
+
+impl<T> Default for PerPlayer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<[T; PlayerId::COUNT]> for PerPlayer<T> {
+    fn from(items: [T; PlayerId::COUNT]) -> Self {
+        Self(items.map(Some))
+    }
+}
+
+impl<T> Index<PlayerId> for PerPlayer<T> {
+    type Output = T;
 
-        match (item1, item2) {
-            (Some(item1), Some(item2)) => {
-                if item1.0 == Player1 {
-                    assert!(
-                        item2.0 == Player2,
-                        "Expected 1 iterator entry for each player. Got 2 for Player 1"
-                    );
-                    (item1.1, item2.1)
-                } else {
-                    assert!(
-                        item2.0 == Player1,
-                        "Expected 1 iterator entry for each player. Got 2 for Player 2"
-                    );
-                    (item2.1, item1.1)
-                }
+    fn index(&self, player: PlayerId) -> &T {
+        self.get(player)
+            .unwrap_or_else(|| panic!("PerPlayer collection has no data for {player:?}"))
+    }
+}
+
+impl<T> IndexMut<PlayerId> for PerPlayer<T> {
+    fn index_mut(&mut self, player: PlayerId) -> &mut T {
+        self.get_mut(player)
+            .unwrap_or_else(|| panic!("PerPlayer collection has no data for {player:?}"))
+    }
+}
+
+/// Reasons for which a PerPlayer collection could not be built from an iterator
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PerPlayerError {
+    /// An expected player had no item
+    Missing(PlayerId),
+    /// A player had more than one item
+    Duplicate(PlayerId),
+    /// An item was given for a player who was not expected
+    Unexpected(PlayerId),
+}
+
+impl fmt::Display for PerPlayerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PerPlayerError::Missing(player) => write!(f, "expected an entry for {player:?}"),
+            PerPlayerError::Duplicate(player) => {
+                write!(f, "expected one entry for {player:?}, but got more")
             }
-            _ => panic!("Expected 1 iterator entry for each player. Got less than 2."),
+            PerPlayerError::Unexpected(player) => write!(f, "unexpected entry for {player:?}"),
+        }
+    }
+}
+
+impl std::error::Error for PerPlayerError {}
+
+///
+/// Resource holding the random number generator used for all gameplay randomness (e.g. the
+/// direction the ball is served in). The generator is always created from a seed, which can
+/// be retrieved to reproduce the same game later. By default the seed is chosen at random.
+///
+#[derive(Resource, Debug, Clone)]
+pub struct GameRng {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl GameRng {
+    /// Create a generator which always produces the same sequence for the given seed
+    pub fn seeded(seed: u64) -> Self {
+        GameRng {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Get the seed this generator was created from
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Get the generator itself, to draw random values from
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        GameRng::seeded(rand::random())
+    }
+}
+
+///
+/// Resource holding the name of each player, used to identify them in the match history and
+/// leaderboard. By default, each player is simply named after their number (e.g. "Player 1").
+///
+#[derive(Resource, Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerNames(pub PerPlayer<String>);
+
+impl PlayerNames {
+    /// Get the name of the given player, falling back to the default name if they have none.
+    pub fn name(&self, player: PlayerId) -> String {
+        self.0
+            .get(player)
+            .cloned()
+            .unwrap_or_else(|| format!("Player {}", player.index() + 1))
+    }
+}
+
+/// Errors which may occur when loading or saving a RON file (e.g. the settings or profiles)
+#[derive(Debug)]
+pub enum RonFileError {
+    /// The file could not be read or written
+    Io(io::Error),
+    /// The contents of the file are not valid for what it holds
+    Parse(ron::error::SpannedError),
+    /// The value could not be converted to the file format
+    Serialize(ron::Error),
+    /// The file was parsed, but the value it holds is not valid (e.g. a malformed bracket)
+    Invalid(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl fmt::Display for RonFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RonFileError::Io(err) => write!(f, "file i/o failed: {err}"),
+            RonFileError::Parse(err) => write!(f, "file is invalid: {err}"),
+            RonFileError::Serialize(err) => write!(f, "value could not be serialized: {err}"),
+            RonFileError::Invalid(err) => write!(f, "file is invalid: {err}"),
         }
     }
 }
+
+impl std::error::Error for RonFileError {}
+
+// -------------------------------------------------------------------------------------------------
+// Public API
+
+///
+/// Load a value from the RON file at the given path. If there is no file there yet,
+/// None is returned.
+///
+pub fn load_ron_file<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, RonFileError> {
+    match fs::read_to_string(path) {
+        Ok(contents) => ron::from_str(&contents)
+            .map(Some)
+            .map_err(RonFileError::Parse),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(RonFileError::Io(err)),
+    }
+}
+
+/// Save a value to the RON file at the given path, creating its directory if needed.
+pub fn save_ron_file<T: Serialize>(path: &Path, value: &T) -> Result<(), RonFileError> {
+    let contents = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+        .map_err(RonFileError::Serialize)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(RonFileError::Io)?;
+    }
+    fs::write(path, contents).map_err(RonFileError::Io)
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_per_player_try_from_iter() {
+        let per_player =
+            PerPlayer::try_from_iter(&[Player1, Player2], [(Player2, "b"), (Player1, "a")])
+                .unwrap();
+        assert_eq!(per_player[Player1], "a");
+        assert_eq!(per_player[Player2], "b");
+        assert_eq!(per_player.get(Player3), None);
+        assert_eq!(
+            per_player.iter().collect::<Vec<_>>(),
+            vec![(Player1, &"a"), (Player2, &"b")],
+        );
+    }
+
+    #[test]
+    fn test_per_player_try_from_iter_errors() {
+        let players = GameMode::TwoPlayer.players();
+        assert_eq!(
+            PerPlayer::try_from_iter(players, [(Player1, 1)]),
+            Err(PerPlayerError::Missing(Player2)),
+        );
+        assert_eq!(
+            PerPlayer::try_from_iter(players, [(Player1, 1), (Player2, 2), (Player1, 3)]),
+            Err(PerPlayerError::Duplicate(Player1)),
+        );
+        assert_eq!(
+            PerPlayer::try_from_iter(players, [(Player1, 1), (Player2, 2), (Player3, 3)]),
+            Err(PerPlayerError::Unexpected(Player3)),
+        );
+        assert_eq!(
+            PerPlayer::try_from_iter(GameMode::FourPlayer.players(), [(Player4, 4)]),
+            Err(PerPlayerError::Missing(Player1)),
+        );
+    }
+
+    #[test]
+    fn test_per_player_mutation() {
+        let mut per_player = PerPlayer::filled(GameMode::FourPlayer.players(), 0u8);
+        per_player[Player3] += 2;
+        if let Some(item) = per_player.get_mut(Player4) {
+            *item += 3;
+        }
+        assert_eq!(per_player, PerPlayer::from([0, 0, 2, 3]));
+        assert_eq!(per_player.insert(Player1, 7), Some(0));
+        assert_eq!(per_player[Player1], 7);
+    }
+
+    #[test]
+    fn test_arena_side_opposite() {
+        for side in ArenaSide::ALL {
+            assert_eq!(
+                side.opposite().inward_normal(),
+                -side.inward_normal(),
+                "Expected the opposite of {side:?} to face it across the arena",
+            );
+        }
+    }
+
+    #[test]
+    fn test_player_names() {
+        let mut names = PlayerNames::default();
+        names.0.insert(Player2, "Ada".into());
+        assert_eq!(names.name(Player1), "Player 1");
+        assert_eq!(names.name(Player2), "Ada");
+    }
+
+    #[test]
+    fn test_game_rng_seeded() {
+        use rand::Rng;
+
+        let mut rng_a = GameRng::seeded(42);
+        let mut rng_b = GameRng::seeded(42);
+        let seq_a: Vec<u32> = (0..8).map(|_| rng_a.rng().random()).collect();
+        let seq_b: Vec<u32> = (0..8).map(|_| rng_b.rng().random()).collect();
+        assert_eq!(
+            seq_a, seq_b,
+            "Expected equal seeds to produce equal sequences"
+        );
+        assert_eq!(
+            rng_a.seed(),
+            42,
+            "Expected seed to be retrievable from the generator"
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//!
+//! The countdown module contains the CountdownPlugin, which shows a "3-2-1" countdown in the
+//! middle of the arena before each round starts, optionally with an audio tick for each number.
+//!
+
+// -------------------------------------------------------------------------------------------------
+// Included Symbols
+
+use std::time::Duration;
+
+use bevy::audio::{AudioPlayer, Pitch, PlaybackSettings};
+use bevy::prelude::*;
+
+use bevy_dyn_fontsize::{DynamicFontSize, DynamicFontsizePlugin};
+
+use crate::common::*;
+use crate::settings::Settings;
+
+// -------------------------------------------------------------------------------------------------
+// Constants
+
+// The number the countdown starts from. Longer waits show nothing until this number is reached.
+const COUNTDOWN_FROM: u32 = 3;
+
+const COUNTDOWN_FONT_SIZE_AS_SCREEN_PCT: f32 = 0.15;
+const COUNTDOWN_HEIGHT: f32 = COUNTDOWN_FONT_SIZE_AS_SCREEN_PCT * ARENA_HEIGHT;
+const COUNTDOWN_Y: f32 = 0.18 * ARENA_HEIGHT;
+
+const TICK_FREQUENCY_HZ: f32 = 660.0;
+const TICK_DURATION: Duration = Duration::from_millis(80);
+
+// -------------------------------------------------------------------------------------------------
+// Public API
+
+///
+/// The CountdownPlugin shows the whole seconds left until the next round starts, counting down
+/// from three, as given by the RoundCountdown resource. Whenever the number shown changes, a
+/// tick is played if countdown ticks are turned on in the Settings and audio is available.
+///
+/// This plugin will only work properly if the app contains a single Camera2d entity.
+///
+pub struct CountdownPlugin;
+
+impl Plugin for CountdownPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<DynamicFontsizePlugin>() {
+            app.add_plugins(DynamicFontsizePlugin::default());
+        }
+
+        app.init_resource::<Settings>()
+            .init_resource::<RoundCountdown>()
+            .add_systems(Startup, setup_countdown.in_set(Systems::SetupAfterCamera))
+            .add_systems(Update, update_countdown.in_set(Systems::RoundCountdownRcvr));
+    }
+}
+
+///
+/// System sets to allow modules consuming this plugin to create ordering constraints
+/// based on functionality exposed in the API of the Plugin.
+///
+#[derive(SystemSet, Debug, Clone, Hash, PartialEq, Eq)]
+pub enum Systems {
+    ///
+    /// Startup systems which spawn the countdown text. These require the camera to exist,
+    /// so the camera setup should be ordered before this set.
+    ///
+    SetupAfterCamera,
+
+    ///
+    /// Update systems which show the RoundCountdown. To show a change to it in the same
+    /// frame, the system making the change should be ordered before this set.
+    ///
+    RoundCountdownRcvr,
+}
+
+///
+/// Resource which should be kept up to date by another module with the time left until the
+/// next round starts, or None while a round is being played.
+///
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct RoundCountdown(pub Option<Duration>);
+
+// -------------------------------------------------------------------------------------------------
+// Private Resources
+
+// The tone played for each number of the countdown, if audio is available.
+#[derive(Resource)]
+struct TickSound(Handle<Pitch>);
+
+// -------------------------------------------------------------------------------------------------
+// Private Components
+
+// Component for the text entity displaying the countdown.
+#[derive(Component)]
+struct CountdownText;
+
+// -------------------------------------------------------------------------------------------------
+// Private Systems
+
+//
+// Spawns the countdown text, which starts empty, and creates the tick sound when the app
+// has audio.
+//
+fn setup_countdown(
+    mut commands: Commands,
+    camera_entity: Single<Entity, With<Camera2d>>,
+    pitches: Option<ResMut<Assets<Pitch>>>,
+) {
+    commands.spawn((
+        CountdownText,
+        DynamicFontSize {
+            height_in_world: COUNTDOWN_HEIGHT,
+            render_camera: camera_entity.entity(),
+            max_width_in_world: None,
+        },
+        Text2d::default(),
+        Transform::from_xyz(0f32, COUNTDOWN_Y, Z_FOREGROUND),
+    ));
+
+    if let Some(mut pitches) = pitches {
+        let tick = pitches.add(Pitch::new(TICK_FREQUENCY_HZ, TICK_DURATION));
+        commands.insert_resource(TickSound(tick));
+    }
+}
+
+//
+// Updates the countdown text whenever the number to show changes, playing a tick for each
+// new number if enabled.
+//
+fn update_countdown(
+    mut commands: Commands,
+    countdown: Res<RoundCountdown>,
+    settings: Res<Settings>,
+    tick: Option<Res<TickSound>>,
+    mut shown: Local<Option<u32>>,
+    texts: Query<&mut Text2d, With<CountdownText>>,
+) {
+    let number = countdown.0.and_then(countdown_number);
+    if number == *shown {
+        return;
+    }
+    *shown = number;
+
+    for mut text in texts {
+        text.0 = number.map(|number| number.to_string()).unwrap_or_default();
+    }
+
+    if let (Some(_), true, Some(tick)) = (number, settings.countdown_ticks, tick) {
+        commands.spawn((AudioPlayer(tick.0.clone()), PlaybackSettings::DESPAWN));
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Functions
+
+//
+// Get the number to show with the given time left until the round starts. It counts whole
+// seconds, rounding up, so is None once no time is left or while more than COUNTDOWN_FROM
+// seconds are left.
+//
+fn countdown_number(remaining: Duration) -> Option<u32> {
+    let number = remaining.as_secs_f32().ceil() as u32;
+    (1..=COUNTDOWN_FROM).contains(&number).then_some(number)
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_test_helpers::prelude::*;
+
+    #[test]
+    fn test_plugin_sys_added_setup() {
+        validate_sys_in_plugin(
+            CountdownPlugin,
+            Startup,
+            setup_countdown,
+            Some(Systems::SetupAfterCamera),
+        );
+    }
+
+    #[test]
+    fn test_plugin_sys_added_update() {
+        validate_sys_in_plugin(
+            CountdownPlugin,
+            Update,
+            update_countdown,
+            Some(Systems::RoundCountdownRcvr),
+        );
+    }
+
+    #[test]
+    fn test_countdown_number() {
+        for (remaining_secs, exp_number) in [
+            (0.0, None),
+            (0.01, Some(1)),
+            (1.0, Some(1)),
+            (1.5, Some(2)),
+            (3.0, Some(3)),
+            (3.2, None),
+            (8.0, None),
+        ] {
+            assert_eq!(
+                countdown_number(Duration::from_secs_f32(remaining_secs)),
+                exp_number,
+                "Expected {exp_number:?} shown with {remaining_secs}s left",
+            );
+        }
+    }
+
+    #[test]
+    fn test_update_countdown_text() {
+        let mut world = setup_countdown_world(true);
+
+        for (remaining, exp_text) in [
+            (None, ""),
+            (Some(2.5), "3"),
+            (Some(1.7), "2"),
+            (Some(0.2), "1"),
+            (Some(0.0), ""),
+            (Some(5.0), ""),
+        ] {
+            world.resource_mut::<RoundCountdown>().0 = remaining.map(Duration::from_secs_f32);
+            world.run_system_cached(update_countdown).unwrap();
+            assert_eq!(
+                countdown_text(&mut world),
+                exp_text,
+                "Expected countdown text {exp_text:?} with {remaining:?}s left",
+            );
+        }
+    }
+
+    #[test]
+    fn test_update_countdown_ticks() {
+        let mut world = setup_countdown_world(true);
+        for remaining in [2.5, 2.4, 1.5, 0.5, 0.0] {
+            world.resource_mut::<RoundCountdown>().0 = Some(Duration::from_secs_f32(remaining));
+            world.run_system_cached(update_countdown).unwrap();
+        }
+        assert_eq!(
+            tick_count(&mut world),
+            3,
+            "Expected one tick per number shown"
+        );
+
+        // No ticks when turned off in the settings
+        let mut world = setup_countdown_world(false);
+        world.resource_mut::<RoundCountdown>().0 = Some(Duration::from_secs(3));
+        world.run_system_cached(update_countdown).unwrap();
+        assert_eq!(countdown_text(&mut world), "3");
+        assert_eq!(tick_count(&mut world), 0, "Expected no ticks when disabled");
+    }
+
+    #[test]
+    fn test_update_countdown_without_audio() {
+        let mut world = setup_countdown_world(true);
+        world.remove_resource::<TickSound>();
+        world.resource_mut::<RoundCountdown>().0 = Some(Duration::from_secs(1));
+        world.run_system_cached(update_countdown).unwrap();
+        assert_eq!(countdown_text(&mut world), "1");
+        assert_eq!(tick_count(&mut world), 0);
+    }
+
+    // --- Helper Functions ---
+
+    // Create a world with the countdown set up, and ticks turned on or off in the settings.
+    fn setup_countdown_world(countdown_ticks: bool) -> World {
+        let mut world = World::default();
+        world.insert_resource(Settings {
+            countdown_ticks,
+            ..default()
+        });
+        world.init_resource::<RoundCountdown>();
+        world.init_resource::<Assets<Pitch>>();
+        world.spawn(Camera2d);
+        world.run_system_cached(setup_countdown).unwrap();
+        world
+    }
+
+    fn countdown_text(world: &mut World) -> String {
+        let mut query = world.query_filtered::<&Text2d, With<CountdownText>>();
+        query.single(world).unwrap().0.clone()
+    }
+
+    fn tick_count(world: &mut World) -> usize {
+        let mut query = world.query::<&AudioPlayer<Pitch>>();
+        query.iter(world).len()
+    }
+}
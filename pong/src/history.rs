@@ -0,0 +1,588 @@
+//!
+//! The history module keeps a persistent record of every completed match, and ranks the
+//! players found in it on a leaderboard by their wins, win rate and Elo rating.
+//!
+
+// -------------------------------------------------------------------------------------------------
+// Included Symbols
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::common::*;
+use crate::score::MaxScoreReached;
+use crate::stats::{self, MatchStats};
+
+// -------------------------------------------------------------------------------------------------
+// Constants
+
+const HISTORY_FILE_NAME: &str = "history.jsonl";
+
+const INITIAL_ELO: f32 = 1500.0;
+const ELO_K_FACTOR: f32 = 32.0;
+const ELO_SCALE: f32 = 400.0;
+
+// -------------------------------------------------------------------------------------------------
+// Public API
+
+///
+/// The HistoryPlugin adds a MatchRecord to the MatchHistory resource whenever a match ends,
+/// naming each player from the PlayerNames resource. If a path is given, the history is
+/// loaded from that file when the plugin is built, and each new record is appended to it.
+///
+pub struct HistoryPlugin {
+    /// The file the match history is stored in, if it should be persisted.
+    pub path: Option<PathBuf>,
+}
+
+impl Default for HistoryPlugin {
+    fn default() -> Self {
+        HistoryPlugin {
+            path: default_history_path(),
+        }
+    }
+}
+
+impl Plugin for HistoryPlugin {
+    fn build(&self, app: &mut App) {
+        let records = match &self.path {
+            Some(path) => load_history(path).unwrap_or_else(|err| {
+                warn!(
+                    "Failed to load match history from {}: {err}",
+                    path.display()
+                );
+                Vec::new()
+            }),
+            None => Vec::new(),
+        };
+
+        app.init_resource::<GameMode>()
+            .init_resource::<GameRng>()
+            .init_resource::<PlayerNames>()
+            .init_resource::<MatchStats>()
+            .insert_resource(MatchHistory(records))
+            .insert_resource(HistoryPath(self.path.clone()))
+            .add_message::<MaxScoreReached>()
+            .add_systems(
+                Update,
+                record_finished_match
+                    .in_set(Systems::RecordMatch)
+                    .after(stats::Systems::TrackStats),
+            );
+    }
+}
+
+/// These SystemSets are used to control any system ordering dependencies on this plugin
+#[derive(SystemSet, Debug, Clone, Hash, PartialEq, Eq)]
+pub enum Systems {
+    ///
+    /// Update systems which add finished matches to the MatchHistory. A system reading the
+    /// MatchHistory in the same frame as a match ends should be ordered after this set.
+    ///
+    RecordMatch,
+}
+
+/// Resource holding the record of every completed match, oldest first.
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct MatchHistory(pub Vec<MatchRecord>);
+
+/// The record of a single completed match.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchRecord {
+    /// When the match ended, in seconds since the Unix epoch
+    pub played_at: u64,
+    /// The variant of pong played
+    pub mode: GameMode,
+    /// Seed of the random number generator the game was started with
+    pub seed: u64,
+    /// The name of each player who took part
+    pub players: PerPlayer<String>,
+    /// The final score of each player. In four player mode, this is the goals they conceded.
+    pub scores: PerPlayer<u8>,
+    /// The player who won the match
+    pub winner: PlayerId,
+    /// The statistics of the match, including its duration
+    pub stats: MatchStats,
+}
+
+impl MatchRecord {
+    /// Get the name of the player who won the match.
+    pub fn winner_name(&self) -> &str {
+        self.players.get(self.winner).map_or("", String::as_str)
+    }
+}
+
+/// A player's standing on the leaderboard, across every match they played under their name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeaderboardEntry {
+    /// The name the player played under
+    pub name: String,
+    /// Number of matches played
+    pub played: u32,
+    /// Number of matches won
+    pub wins: u32,
+    /// Elo rating, starting from 1500 before the first match
+    pub elo: f32,
+}
+
+impl LeaderboardEntry {
+    /// Get the fraction of played matches which were won, from 0 to 1.
+    pub fn win_rate(&self) -> f32 {
+        self.wins as f32 / self.played.max(1) as f32
+    }
+}
+
+/// The orders the leaderboard can be ranked in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LeaderboardSort {
+    /// Most matches won first
+    Wins,
+    /// Highest fraction of matches won first
+    WinRate,
+    /// Highest Elo rating first
+    #[default]
+    Elo,
+}
+
+/// Errors which can occur while loading or saving the match history.
+#[derive(Debug)]
+pub enum HistoryError {
+    /// The history file could not be read or written
+    Io(io::Error),
+    /// The given line (counting from 1) of the history file was not a valid match record
+    Parse(usize, serde_json::Error),
+    /// A match record could not be converted to JSON
+    Serialize(serde_json::Error),
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryError::Io(err) => write!(f, "file error: {err}"),
+            HistoryError::Parse(line, err) => write!(f, "invalid record on line {line}: {err}"),
+            HistoryError::Serialize(err) => write!(f, "failed to serialize match record: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {}
+
+///
+/// Get the default location of the match history file, in the platform's data directory.
+/// Returns None if the platform has no data directory.
+///
+pub fn default_history_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(APP_DIR_NAME).join(HISTORY_FILE_NAME))
+}
+
+///
+/// Load every match record from the history file at the given path, oldest first. The file
+/// holds one JSON record per line. If there is no file there yet, the history is empty.
+///
+pub fn load_history(path: &Path) -> Result<Vec<MatchRecord>, HistoryError> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(HistoryError::Io(err)),
+    };
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            serde_json::from_str(line).map_err(|err| HistoryError::Parse(idx + 1, err))
+        })
+        .collect()
+}
+
+/// Append a match record to the history file at the given path, creating it if needed.
+pub fn append_match_to(path: &Path, record: &MatchRecord) -> Result<(), HistoryError> {
+    let line = serde_json::to_string(record).map_err(HistoryError::Serialize)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(HistoryError::Io)?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(HistoryError::Io)?;
+    writeln!(file, "{line}").map_err(HistoryError::Io)
+}
+
+///
+/// Rank every player found in the given match records, which must be oldest first. Elo
+/// ratings are updated after each match as if the winner had beaten every other player of
+/// the match in turn. Ties in the chosen order are broken by Elo rating, then by name.
+///
+pub fn leaderboard(records: &[MatchRecord], sort: LeaderboardSort) -> Vec<LeaderboardEntry> {
+    let mut entries: Vec<LeaderboardEntry> = Vec::new();
+
+    for record in records {
+        let mut participants: Vec<(usize, bool)> = Vec::new();
+        for (player, name) in record.players.iter() {
+            let idx = match entries.iter().position(|entry| entry.name == *name) {
+                Some(idx) => idx,
+                None => {
+                    entries.push(LeaderboardEntry {
+                        name: name.clone(),
+                        played: 0,
+                        wins: 0,
+                        elo: INITIAL_ELO,
+                    });
+                    entries.len() - 1
+                }
+            };
+            if participants.iter().all(|&(other, _)| other != idx) {
+                participants.push((idx, player == record.winner));
+            }
+        }
+
+        // Work out every rating change from the ratings before the match, then apply them
+        let mut elo_changes = vec![0f32; participants.len()];
+        for (winner_pos, &(winner_idx, _)) in participants.iter().enumerate() {
+            if !participants[winner_pos].1 {
+                continue;
+            }
+            for (loser_pos, &(loser_idx, _)) in participants.iter().enumerate() {
+                if loser_pos == winner_pos {
+                    continue;
+                }
+                let change = elo_change(entries[winner_idx].elo, entries[loser_idx].elo);
+                elo_changes[winner_pos] += change;
+                elo_changes[loser_pos] -= change;
+            }
+        }
+
+        for (&(idx, won), change) in participants.iter().zip(elo_changes) {
+            let entry = &mut entries[idx];
+            entry.played += 1;
+            entry.wins += won as u32;
+            entry.elo += change;
+        }
+    }
+
+    entries.sort_by(|a, b| {
+        let primary = match sort {
+            LeaderboardSort::Wins => b.wins.cmp(&a.wins),
+            LeaderboardSort::WinRate => b.win_rate().total_cmp(&a.win_rate()),
+            LeaderboardSort::Elo => Ordering::Equal,
+        };
+        primary
+            .then(b.elo.total_cmp(&a.elo))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    entries
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Resources
+
+// Location the match history is persisted to, if any.
+#[derive(Resource)]
+struct HistoryPath(Option<PathBuf>);
+
+// -------------------------------------------------------------------------------------------------
+// Private Systems
+
+//
+// System to add a record of each match to the MatchHistory once it ends, and append it to the
+// history file too if there is one.
+//
+fn record_finished_match(
+    mut max_score_msgs: MessageReader<MaxScoreReached>,
+    stats: Res<MatchStats>,
+    names: Res<PlayerNames>,
+    rng: Res<GameRng>,
+    path: Res<HistoryPath>,
+    mut history: ResMut<MatchHistory>,
+) {
+    let Some(&MaxScoreReached(winner)) = max_score_msgs.read().last() else {
+        return;
+    };
+
+    let played_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let record = match_record(&stats, &names, rng.seed(), winner, played_at);
+
+    if let Some(path) = &path.0
+        && let Err(err) = append_match_to(path, &record)
+    {
+        warn!("Failed to save match to {}: {err}", path.display());
+    }
+    history.0.push(record);
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Functions
+
+// Create the record of a finished match, from its statistics.
+fn match_record(
+    stats: &MatchStats,
+    names: &PlayerNames,
+    seed: u64,
+    winner: PlayerId,
+    played_at: u64,
+) -> MatchRecord {
+    let mut players = PerPlayer::new();
+    let mut scores = PerPlayer::new();
+    for &player in stats.mode.players() {
+        players.insert(player, names.name(player));
+        scores.insert(player, 0u8);
+    }
+
+    // Scores count the points won, or in four player mode the goals conceded
+    for point in &stats.points {
+        let scored = match stats.mode {
+            GameMode::TwoPlayer | GameMode::Doubles => point.scorer,
+            GameMode::FourPlayer => point.conceded_by,
+        };
+        if let Some(score) = scored.and_then(|player| scores.get_mut(player)) {
+            *score = score.saturating_add(1);
+        }
+    }
+
+    MatchRecord {
+        played_at,
+        mode: stats.mode,
+        seed,
+        players,
+        scores,
+        winner,
+        stats: stats.clone(),
+    }
+}
+
+// Get the Elo rating change for a player of winner_elo beating a player of loser_elo.
+fn elo_change(winner_elo: f32, loser_elo: f32) -> f32 {
+    let expected_win = 1f32 / (1f32 + 10f32.powf((loser_elo - winner_elo) / ELO_SCALE));
+    ELO_K_FACTOR * (1f32 - expected_win)
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::PointStats;
+    use bevy_test_helpers::prelude::*;
+
+    #[test]
+    fn test_plugin_build_loads_history() {
+        let path = test_history_path("plugin_build");
+        let record = test_record(&[("Ada", 5), ("Bob", 3)], Player1);
+        append_match_to(&path, &record).unwrap();
+
+        let mut app = App::new();
+        app.add_plugins(HistoryPlugin {
+            path: Some(path.clone()),
+        });
+        assert_eq!(
+            app.world().resource::<MatchHistory>().0,
+            vec![record],
+            "Expected the history to be loaded from the file",
+        );
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_plugin_sys_added_record_finished_match() {
+        validate_sys_in_plugin(
+            HistoryPlugin { path: None },
+            Update,
+            record_finished_match,
+            Some(Systems::RecordMatch),
+        );
+    }
+
+    #[test]
+    fn test_append_and_load_history() {
+        let path = test_history_path("append_and_load");
+        assert!(
+            load_history(&path).unwrap().is_empty(),
+            "Expected an empty history without a file",
+        );
+
+        let first = test_record(&[("Ada", 5), ("Bob", 3)], Player1);
+        let second = test_record(&[("Ada", 1), ("Cy", 5)], Player2);
+        append_match_to(&path, &first).unwrap();
+        append_match_to(&path, &second).unwrap();
+        assert_eq!(load_history(&path).unwrap(), vec![first, second]);
+
+        fs::write(&path, "\n{\"played_at\": \"yesterday\"}\n").unwrap();
+        assert!(
+            matches!(load_history(&path), Err(HistoryError::Parse(2, _))),
+            "Expected a parse error on line 2",
+        );
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_record_finished_match() {
+        let mut world = World::default();
+        world.init_resource::<Messages<MaxScoreReached>>();
+        world.insert_resource(GameRng::seeded(42));
+        world.insert_resource(HistoryPath(None));
+        world.init_resource::<MatchHistory>();
+        let mut names = PlayerNames::default();
+        names.0.insert(Player2, "Bob".into());
+        world.insert_resource(names);
+
+        let mut stats = MatchStats::new(GameMode::TwoPlayer);
+        for scorer in [Player2, Player1, Player2] {
+            stats.points.push(PointStats {
+                scorer: Some(scorer),
+                ..default()
+            });
+        }
+        world.insert_resource(stats.clone());
+        let record_sys = world.register_system(record_finished_match);
+
+        // Nothing is recorded until the match ends
+        world.run_system(record_sys).unwrap();
+        assert!(world.resource::<MatchHistory>().0.is_empty());
+
+        world.write_message(MaxScoreReached(Player2));
+        world.run_system(record_sys).unwrap();
+        let history = &world.resource::<MatchHistory>().0;
+        assert_eq!(history.len(), 1, "Expected the match to be recorded");
+        let record = &history[0];
+        assert_eq!(record.seed, 42);
+        assert_eq!(record.winner_name(), "Bob");
+        assert_eq!(
+            record.players.iter().collect::<Vec<_>>(),
+            vec![(Player1, &"Player 1".to_string()), (Player2, &"Bob".into())],
+        );
+        assert_eq!(
+            record.scores.iter().collect::<Vec<_>>(),
+            vec![(Player1, &1), (Player2, &2)],
+        );
+        assert_eq!(record.stats, stats);
+    }
+
+    #[test]
+    fn test_match_record_four_player_scores() {
+        let mut stats = MatchStats::new(GameMode::FourPlayer);
+        for conceded_by in [Player3, Player3, Player1] {
+            stats.points.push(PointStats {
+                conceded_by: Some(conceded_by),
+                ..default()
+            });
+        }
+        let record = match_record(&stats, &PlayerNames::default(), 1, Player4, 0);
+        assert_eq!(
+            record.scores,
+            PerPlayer::from([1, 0, 2, 0]),
+            "Expected four player scores to count goals conceded",
+        );
+    }
+
+    #[test]
+    fn test_leaderboard() {
+        let records = [
+            test_record(&[("Ada", 5), ("Bob", 3)], Player1),
+            test_record(&[("Bob", 5), ("Cy", 1)], Player1),
+            test_record(&[("Cy", 5), ("Ada", 4)], Player1),
+            test_record(&[("Ada", 5), ("Cy", 0)], Player1),
+        ];
+
+        // First match: equal ratings, so the winner gains half the K factor
+        let board = leaderboard(&records[..1], LeaderboardSort::Elo);
+        assert_eq!(board[0].name, "Ada");
+        assert_eq!(board[0].elo, INITIAL_ELO + (ELO_K_FACTOR / 2.0));
+        assert_eq!(board[1].elo, INITIAL_ELO - (ELO_K_FACTOR / 2.0));
+
+        let summary = |sort| -> Vec<(String, u32, u32)> {
+            leaderboard(&records, sort)
+                .into_iter()
+                .map(|entry| (entry.name, entry.wins, entry.played))
+                .collect()
+        };
+        assert_eq!(
+            summary(LeaderboardSort::Wins),
+            vec![
+                ("Ada".into(), 2, 3),
+                ("Bob".into(), 1, 2),
+                ("Cy".into(), 1, 3)
+            ],
+        );
+        assert_eq!(
+            summary(LeaderboardSort::WinRate),
+            vec![
+                ("Ada".into(), 2, 3),
+                ("Bob".into(), 1, 2),
+                ("Cy".into(), 1, 3)
+            ],
+        );
+
+        let board = leaderboard(&records, LeaderboardSort::Elo);
+        assert!(
+            board.windows(2).all(|pair| pair[0].elo >= pair[1].elo),
+            "Expected entries ranked by Elo, got {board:?}",
+        );
+        let total_elo: f32 = board.iter().map(|entry| entry.elo).sum();
+        assert!(
+            (total_elo - (INITIAL_ELO * 3.0)).abs() < 0.01,
+            "Expected Elo changes to balance out, got total {total_elo}",
+        );
+    }
+
+    #[test]
+    fn test_leaderboard_four_player() {
+        let record = MatchRecord {
+            mode: GameMode::FourPlayer,
+            ..test_record(&[("Ada", 5), ("Bob", 5), ("Cy", 5), ("Di", 2)], Player4)
+        };
+        let board = leaderboard(&[record], LeaderboardSort::Elo);
+        assert_eq!(board[0].name, "Di");
+        assert_eq!(board[0].elo, INITIAL_ELO + (ELO_K_FACTOR * 1.5));
+        assert!(
+            board[1..]
+                .iter()
+                .all(|entry| entry.elo == INITIAL_ELO - (ELO_K_FACTOR / 2.0)),
+            "Expected each loser to lose to the winner only, got {board:?}",
+        );
+    }
+
+    // --- Helper Functions ---
+
+    // Create a record of a match between the named players, with the given scores.
+    fn test_record(players: &[(&str, u8)], winner: PlayerId) -> MatchRecord {
+        let mut record = MatchRecord {
+            played_at: 1_700_000_000,
+            mode: GameMode::TwoPlayer,
+            seed: 7,
+            players: PerPlayer::new(),
+            scores: PerPlayer::new(),
+            winner,
+            stats: MatchStats::new(GameMode::TwoPlayer),
+        };
+        for (&(name, score), player) in players.iter().zip(PlayerId::ALL) {
+            record.players.insert(player, name.to_string());
+            record.scores.insert(player, score);
+        }
+        record
+    }
+
+    // Get a history file path unique to the given test, in the temp directory.
+    fn test_history_path(test_name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!(
+                "rust_pong_history_{}_{test_name}",
+                std::process::id()
+            ))
+            .join(HISTORY_FILE_NAME)
+    }
+}
@@ -0,0 +1,375 @@
+//!
+//! The leaderboard module contains the in-game leaderboard screen, which ranks every player
+//! in the match history by their Elo rating.
+//!
+
+// -------------------------------------------------------------------------------------------------
+// Included Symbols
+
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+use bevy_dyn_fontsize::{DynamicFontSize, DynamicFontsizePlugin};
+
+use crate::common::*;
+use crate::history::{self, LeaderboardEntry, LeaderboardSort, MatchHistory, leaderboard};
+use crate::settings::{PaddleKeyOverrides, Settings};
+
+// -------------------------------------------------------------------------------------------------
+// Constants
+
+const TOGGLE_LEADERBOARD_KEY: KeyCode = KeyCode::Tab;
+
+const LEADERBOARD_ROWS: usize = 10;
+const BACKGROUND_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.9);
+const ROW_FONT_SIZE_AS_SCREEN_PCT: f32 = 0.04;
+const ROW_SPACING_AS_SCREEN_PCT: f32 = 0.06;
+const ROW_HEIGHT: f32 = ROW_FONT_SIZE_AS_SCREEN_PCT * ARENA_HEIGHT;
+const ROW_SPACING: f32 = ROW_SPACING_AS_SCREEN_PCT * ARENA_HEIGHT;
+const TITLE_Y: f32 = ARENA_HEIGHT * 0.42;
+const TITLE_TEXT: &str = "Leaderboard (Tab to close)";
+const NO_MATCHES_TEXT: &str = "No matches played yet";
+
+// Left edge of each column, as a fraction of the arena width, and its heading.
+const COLUMNS: [(f32, &str); 6] = [
+    (-0.35, "#"),
+    (-0.3, "Name"),
+    (0.05, "Elo"),
+    (0.15, "Wins"),
+    (0.25, "Played"),
+    (0.35, "Win %"),
+];
+
+// -------------------------------------------------------------------------------------------------
+// Public API
+
+///
+/// The LeaderboardPlugin adds a leaderboard screen, which is hidden until the leaderboard key
+/// is pressed. It ranks the top players of the MatchHistory by Elo rating, and is kept up to
+/// date as matches finish. Unlike the options screen, the game keeps running while it is open.
+///
+/// This plugin will only work properly if the app contains a single Camera2d entity.
+///
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<DynamicFontsizePlugin>() {
+            app.add_plugins(DynamicFontsizePlugin::default());
+        }
+
+        app.init_resource::<MatchHistory>()
+            .init_resource::<Settings>()
+            .init_resource::<PaddleKeyOverrides>()
+            .add_systems(
+                Startup,
+                setup_leaderboard_screen.in_set(Systems::SetupAfterCamera),
+            )
+            .add_systems(
+                Update,
+                (
+                    toggle_leaderboard_screen,
+                    update_leaderboard_text.after(history::Systems::RecordMatch),
+                ),
+            );
+    }
+}
+
+/// These SystemSets are used to control any system ordering dependencies on this plugin
+#[derive(SystemSet, Debug, Clone, Hash, PartialEq, Eq)]
+pub enum Systems {
+    ///
+    /// Startup systems which spawn the leaderboard screen entities. These require the camera
+    /// to exist, so the camera setup should be ordered before this set.
+    ///
+    SetupAfterCamera,
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Components
+
+// Component for the root entity of the leaderboard screen, which is its background.
+#[derive(Component)]
+struct LeaderboardRoot;
+
+// Component for the text entity displaying one column of one ranked row of the leaderboard.
+#[derive(Component)]
+struct LeaderboardCell {
+    row: usize,
+    column: usize,
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Systems
+
+//
+// Startup system to spawn the leaderboard screen. It is a background covering the arena,
+// with a title, column headings and a text entity for each cell of the ranked rows as
+// children. It starts hidden, and the cells are filled in from the match history.
+//
+fn setup_leaderboard_screen(mut commands: Commands, camera_entity: Single<Entity, With<Camera2d>>) {
+    let camera_entity = camera_entity.entity();
+    let text_bundle = move |text: &str, anchor: Anchor, x: f32, y: f32| {
+        (
+            DynamicFontSize {
+                height_in_world: ROW_HEIGHT,
+                render_camera: camera_entity,
+                max_width_in_world: None,
+            },
+            Text2d::new(text),
+            anchor,
+            Transform::from_xyz(x, y, 1f32),
+        )
+    };
+
+    commands
+        .spawn((
+            LeaderboardRoot,
+            Sprite {
+                color: BACKGROUND_COLOR,
+                custom_size: Some(Vec2::new(ARENA_WIDTH, ARENA_HEIGHT)),
+                ..default()
+            },
+            Transform::from_xyz(0f32, 0f32, Z_OVERLAY),
+            Visibility::Hidden,
+        ))
+        .with_children(|parent| {
+            parent.spawn(text_bundle(TITLE_TEXT, Anchor::CENTER, 0f32, TITLE_Y));
+
+            let heading_y = TITLE_Y - (ROW_SPACING * 2f32);
+            for (column_x, heading) in COLUMNS {
+                let x = column_x * ARENA_WIDTH;
+                parent.spawn(text_bundle(heading, Anchor::CENTER_LEFT, x, heading_y));
+            }
+
+            for row in 0..LEADERBOARD_ROWS {
+                let y = heading_y - (ROW_SPACING * (row + 1) as f32);
+                for (column, (column_x, _)) in COLUMNS.into_iter().enumerate() {
+                    let x = column_x * ARENA_WIDTH;
+                    parent.spawn((
+                        LeaderboardCell { row, column },
+                        text_bundle("", Anchor::CENTER_LEFT, x, y),
+                    ));
+                }
+            }
+        });
+}
+
+//
+// Opens or closes the leaderboard screen when the toggle key is pressed. If a player has
+// bound the toggle key to their paddle or serve (even just for this run, e.g. through their
+// profile), it is left to them instead.
+//
+fn toggle_leaderboard_screen(
+    keys: Res<ButtonInput<KeyCode>>,
+    settings: Res<Settings>,
+    key_overrides: Res<PaddleKeyOverrides>,
+    mut root_vis: Query<&mut Visibility, With<LeaderboardRoot>>,
+) {
+    if !keys.just_pressed(TOGGLE_LEADERBOARD_KEY) {
+        return;
+    }
+    let mut bindings = settings.key_bindings.clone();
+    key_overrides.apply(&mut bindings);
+    if bindings.is_bound(TOGGLE_LEADERBOARD_KEY) {
+        return;
+    }
+
+    for mut vis in root_vis.iter_mut() {
+        *vis = match *vis {
+            Visibility::Hidden => Visibility::Visible,
+            _ => Visibility::Hidden,
+        };
+    }
+}
+
+// Updates the text of each cell whenever the match history changes (including at startup).
+fn update_leaderboard_text(
+    history: Res<MatchHistory>,
+    cells: Query<(&mut Text2d, &LeaderboardCell)>,
+) {
+    if !history.is_changed() {
+        return;
+    }
+
+    let entries = ranked_entries(&history);
+    for (mut text, cell) in cells {
+        text.0 = cell_text(&entries, cell.row, cell.column);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Functions
+
+//
+// Get the text of the given column of a ranked row. The first row reports an empty history
+// instead, when no matches have been played.
+//
+fn cell_text(entries: &[LeaderboardEntry], row: usize, column: usize) -> String {
+    let Some(entry) = entries.get(row) else {
+        return if entries.is_empty() && (row, column) == (0, 1) {
+            NO_MATCHES_TEXT.to_string()
+        } else {
+            String::new()
+        };
+    };
+
+    match column {
+        0 => (row + 1).to_string(),
+        1 => entry.name.clone(),
+        2 => format!("{:.0}", entry.elo),
+        3 => entry.wins.to_string(),
+        4 => entry.played.to_string(),
+        _ => format!("{:.0}%", entry.win_rate() * 100f32),
+    }
+}
+
+// Get the leaderboard entries to display, ranked by Elo rating.
+fn ranked_entries(history: &MatchHistory) -> Vec<LeaderboardEntry> {
+    let mut entries = leaderboard(&history.0, LeaderboardSort::Elo);
+    entries.truncate(LEADERBOARD_ROWS);
+    entries
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::MatchRecord;
+    use crate::paddle::PaddlePosition;
+    use crate::settings::PaddleKeys;
+    use crate::stats::MatchStats;
+    use bevy_test_helpers::prelude::*;
+
+    #[test]
+    fn test_plugin_sys_added_setup() {
+        validate_sys_in_plugin(
+            LeaderboardPlugin,
+            Startup,
+            setup_leaderboard_screen,
+            Some(Systems::SetupAfterCamera),
+        );
+    }
+
+    #[test]
+    fn test_plugin_sys_added_update() {
+        validate_sys_in_plugin(
+            LeaderboardPlugin,
+            Update,
+            toggle_leaderboard_screen,
+            None::<bevy::ecs::schedule::AnonymousSet>,
+        );
+        validate_sys_in_plugin(
+            LeaderboardPlugin,
+            Update,
+            update_leaderboard_text,
+            None::<bevy::ecs::schedule::AnonymousSet>,
+        );
+    }
+
+    #[test]
+    fn test_leaderboard_screen() {
+        let mut world = World::default();
+        world.spawn(Camera2d);
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<MatchHistory>();
+        world.init_resource::<Settings>();
+        world.init_resource::<PaddleKeyOverrides>();
+
+        let setup_sys = world.register_system(setup_leaderboard_screen);
+        let toggle_sys = world.register_system(toggle_leaderboard_screen);
+        let update_sys = world.register_system(update_leaderboard_text);
+        world.run_system(setup_sys).unwrap();
+        world.run_system(update_sys).unwrap();
+        assert_eq!(
+            cell_texts(&mut world, 0),
+            vec!["", NO_MATCHES_TEXT, "", "", "", ""],
+            "Expected the first row to report an empty history",
+        );
+
+        // Once a match is recorded, its players are ranked
+        let mut players = PerPlayer::new();
+        players.insert(Player1, "Ada".to_string());
+        players.insert(Player2, "Bob".to_string());
+        world.resource_mut::<MatchHistory>().0.push(MatchRecord {
+            played_at: 0,
+            mode: GameMode::TwoPlayer,
+            seed: 0,
+            players,
+            scores: PerPlayer::filled(GameMode::TwoPlayer.players(), 0),
+            winner: Player2,
+            stats: MatchStats::default(),
+        });
+        world.run_system(update_sys).unwrap();
+        assert_eq!(
+            cell_texts(&mut world, 0),
+            vec!["1", "Bob", "1516", "1", "1", "100%"],
+        );
+        assert_eq!(
+            cell_texts(&mut world, 1),
+            vec!["2", "Ada", "1484", "0", "1", "0%"],
+        );
+        assert_eq!(cell_texts(&mut world, 2), vec![""; COLUMNS.len()]);
+
+        // The screen is shown and hidden by the toggle key
+        let mut root_vis = world.query_filtered::<&Visibility, With<LeaderboardRoot>>();
+        assert_eq!(root_vis.single(&world).unwrap(), Visibility::Hidden);
+        for exp_vis in [Visibility::Visible, Visibility::Hidden] {
+            let mut keys = world.resource_mut::<ButtonInput<KeyCode>>();
+            keys.release(TOGGLE_LEADERBOARD_KEY);
+            keys.clear();
+            keys.press(TOGGLE_LEADERBOARD_KEY);
+            world.run_system(toggle_sys).unwrap();
+            assert_eq!(root_vis.single(&world).unwrap(), exp_vis);
+        }
+    }
+
+    #[test]
+    fn test_toggle_key_bound_to_paddle() {
+        let mut world = World::default();
+        world.spawn(Camera2d);
+        world.init_resource::<MatchHistory>();
+        let mut settings = Settings::default();
+        settings.key_bindings.player3.positive = TOGGLE_LEADERBOARD_KEY;
+        world.insert_resource(settings);
+        world.init_resource::<PaddleKeyOverrides>();
+        let mut keys = ButtonInput::<KeyCode>::default();
+        keys.press(TOGGLE_LEADERBOARD_KEY);
+        world.insert_resource(keys);
+        world.run_system_cached(setup_leaderboard_screen).unwrap();
+
+        // Moving the paddle with the toggle key shouldn't open the leaderboard
+        world.run_system_cached(toggle_leaderboard_screen).unwrap();
+        let mut root_vis = world.query_filtered::<&Visibility, With<LeaderboardRoot>>();
+        assert_eq!(root_vis.single(&world).unwrap(), Visibility::Hidden);
+
+        // Nor should it when the key is only bound for this run, e.g. by a player's profile
+        world.insert_resource(Settings::default());
+        world.insert_resource(PaddleKeyOverrides(vec![(
+            Player2,
+            PaddlePosition::Back,
+            PaddleKeys {
+                positive: TOGGLE_LEADERBOARD_KEY,
+                negative: KeyCode::KeyL,
+            },
+        )]));
+        world.run_system_cached(toggle_leaderboard_screen).unwrap();
+        assert_eq!(root_vis.single(&world).unwrap(), Visibility::Hidden);
+    }
+
+    // --- Helper Functions ---
+
+    // Get the text of every cell in the given row of the leaderboard, in column order.
+    fn cell_texts(world: &mut World, row: usize) -> Vec<String> {
+        let mut cells: Vec<(usize, String)> = world
+            .query::<(&Text2d, &LeaderboardCell)>()
+            .iter(world)
+            .filter(|(_, cell)| cell.row == row)
+            .map(|(text, cell)| (cell.column, text.0.clone()))
+            .collect();
+        cells.sort();
+        cells.into_iter().map(|(_, text)| text).collect()
+    }
+}
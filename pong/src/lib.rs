@@ -6,31 +6,89 @@
 // -------------------------------------------------------------------------------------------------
 // Module Declarations
 
+mod ai;
 mod arena;
 mod ball;
 mod common;
+mod countdown;
+mod history;
+mod leaderboard;
+mod options;
 mod paddle;
+mod particles;
+mod profiles;
+mod replay;
 mod score;
+mod serve;
+mod settings;
+mod stats;
+mod tournament;
 mod window;
 
 // -------------------------------------------------------------------------------------------------
 // Included Symbols
 
+use std::path::PathBuf;
+
 use bevy::prelude::*;
 
-use arena::ArenaPlugin;
-use ball::{BallOffScreen, BallPlugin, ResetBall, StartBall};
+use ai::{AiPlayers, AiPlugin};
+use arena::{ArenaPlugin, ShakeCamera, ZoomCamera};
+use ball::{BallHitPaddle, BallOffScreen, BallPlugin, ResetBall};
 use common::*;
-use paddle::PaddlePlugin;
-use score::{ClearScores, MaxScoreReached, PlayerScored, ScorePlugin};
+use countdown::{CountdownPlugin, RoundCountdown};
+use history::HistoryPlugin;
+use leaderboard::LeaderboardPlugin;
+use options::OptionsPlugin;
+use paddle::{PaddleColors, PaddlePlugin, PaddlePosition, RestorePaddles, WallOffPaddle};
+use particles::ParticlesPlugin;
+use replay::ReplayPlugin;
+use score::{
+    ClearScores, GamePoint, GameWon, GoalConceded, MaxScoreReached, PlayerEliminated, PlayerScored,
+    ScorePlugin, StartNextGame,
+};
+use serve::{ServeBall, ServePlugin};
+use settings::{SettingsPlugin, default_settings_path};
+use stats::StatsPlugin;
+use tournament::{TournamentPlugin, tournament_finished};
 use window::PongWindowPlugin;
 
+// -------------------------------------------------------------------------------------------------
+// Re-Exports
+
+pub use ai::AiDifficulty;
+pub use common::{GameMode, PerPlayer, PlayerId, RonFileError};
+pub use history::{
+    HistoryError, LeaderboardEntry, LeaderboardSort, MatchRecord, default_history_path,
+    leaderboard, load_history,
+};
+pub use profiles::{
+    PlayerProfile, Profiles, default_profiles_path, load_profiles, save_profiles_to,
+};
+pub use replay::{Recording, ReplayMode, load_recording};
+pub use settings::{
+    DisplayMode, GameplaySettings, KeyBindings, PaddleKeys, ParticleSettings, ServeRule, Settings,
+    SettingsOverrides, WinCondition,
+};
+pub use stats::{MatchStats, PointStats, StatsError, save_match_stats_to};
+pub use tournament::{
+    MatchResult, Standing, Tournament, TournamentError, TournamentFormat, TournamentMatch,
+    default_tournament_path, load_tournament, save_tournament_to,
+};
+pub use window::RenderBackend;
+
 // -------------------------------------------------------------------------------------------------
 // Constants
 
 const TIME_BEFORE_FIRST_ROUND_SECS: f32 = 2.0;
 const TIME_BETWEEN_ROUNDS_SECS: f32 = 1.0;
 const TIME_BETWEEN_GAMES_SECS: f32 = 3.0;
+const TIME_SHOWING_BRACKET_SECS: f32 = 8.0;
+
+// Camera trauma added by a paddle hit at each curve level. Only the strongest curves shake.
+const CURVE_HIT_SHAKE_TRAUMA: [f32; 4] = [0.0, 0.0, 0.25, 0.45];
+// Fraction of the view the camera punches in by when a player reaches game point.
+const GAME_POINT_ZOOM: f32 = 0.06;
 
 // -------------------------------------------------------------------------------------------------
 // Public API
@@ -40,36 +98,132 @@ const TIME_BETWEEN_GAMES_SECS: f32 = 3.0;
 /// implements the Pong game in its entirety, including the game window,
 /// entity setup, and all runtime game logic.
 ///
-pub struct PongPlugin;
+/// When replaying a recording, the recorded game mode, computer players, seed and settings
+/// are used in place of those given here, and the user's settings file and match history
+/// are left untouched.
+///
+/// When playing a tournament, each game is a two player match between the entrants seated by
+/// the tournament, whose names and paddle colors take the place of those given here. The
+/// bracket is shown between games, and no more games are started once it has a champion.
+///
+#[derive(Default)]
+pub struct PongPlugin {
+    /// The variant of pong to play. Defaults to classic two player pong.
+    pub mode: GameMode,
+    /// Settings to use in place of the saved settings, for this run only.
+    pub settings: SettingsOverrides,
+    /// The graphics API used to render the game.
+    pub backend: RenderBackend,
+    /// Whether to run the game without a window or renderer.
+    pub headless: bool,
+    /// The players controlled by the computer, and how well each of them plays.
+    pub ai_players: PerPlayer<AiDifficulty>,
+    /// Seed for all gameplay randomness, to make the game reproducible. Random if None.
+    pub seed: Option<u64>,
+    /// Whether to record the game, or replay a recorded game, if either.
+    pub replay: Option<ReplayMode>,
+    /// Number of frames to run before exiting, if the game should stop by itself.
+    pub frame_limit: Option<u32>,
+    /// Directory to export the statistics of each finished match to as JSON, if any.
+    pub stats_dir: Option<PathBuf>,
+    /// The name of each player, recorded in the match history. Defaults to "Player N" for
+    /// players, and to the difficulty for computer players (e.g. "Computer (Hard)").
+    pub player_names: PerPlayer<String>,
+    /// The profile each player chose to play under, if any. A profile's name takes the place
+    /// of any name given above, and its paddle color and keys are used for that player.
+    pub profiles: PerPlayer<PlayerProfile>,
+    /// The file to store the match history in. Defaults to the platform's data directory.
+    pub history_path: Option<PathBuf>,
+    /// The file of a tournament to play the remaining matches of, if any.
+    pub tournament_path: Option<PathBuf>,
+}
 
 impl Plugin for PongPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(PongWindowPlugin)
-            .add_plugins(ArenaPlugin)
-            .add_plugins(BallPlugin)
-            .add_plugins(PaddlePlugin)
-            .add_plugins(ScorePlugin)
-            .init_resource::<RoundStartTimer>()
-            .init_resource::<IsBetweenGames>()
-            .add_systems(PostStartup, start_first_round_timer)
-            .add_systems(
-                Update,
-                (
-                    update_round_timer.before(score::Systems::ClearScoresRcvr),
-                    handle_ball_off_screen
-                        .before(ball::Systems::ResetBallRcvr)
-                        .before(score::Systems::PlayerScoredRcvr),
-                    handle_game_end,
-                ),
-            )
-            .configure_sets(
-                Startup,
-                (arena::Systems::CameraSetup.before(score::Systems::SetupAfterCamera),),
-            )
-            .configure_sets(
-                Update,
-                (ball::Systems::BallOffScreenSndr.before(handle_ball_off_screen),),
-            );
+        let replaying = matches!(self.replay, Some(ReplayMode::Replay(_)));
+        let mode = match self.tournament_path {
+            Some(_) => GameMode::TwoPlayer,
+            None => self.mode,
+        };
+        let mut player_names = self.player_names.clone();
+        let mut paddle_colors = PaddleColors::default();
+        let mut settings = self.settings.clone();
+        for (player, profile) in self.profiles.iter() {
+            player_names.insert(player, profile.name.clone());
+            if let Some(color) = profile.color() {
+                paddle_colors.0.insert(player, color);
+            }
+            if let Some(keys) = profile.keys {
+                settings
+                    .paddle_keys
+                    .push((player, PaddlePosition::Back, keys));
+            }
+            if let (GameMode::Doubles, Some(keys)) = (mode, profile.forward_keys) {
+                settings
+                    .paddle_keys
+                    .push((player, PaddlePosition::Forward, keys));
+            }
+        }
+        for (player, difficulty) in self.ai_players.iter() {
+            if !player_names.contains(player) {
+                player_names.insert(player, format!("Computer ({difficulty:?})"));
+            }
+        }
+
+        app.insert_resource(mode)
+            .insert_resource(AiPlayers(self.ai_players.clone()))
+            .insert_resource(PlayerNames(player_names))
+            .insert_resource(paddle_colors)
+            .insert_resource(self.seed.map_or_else(GameRng::default, GameRng::seeded))
+            .add_plugins(SettingsPlugin {
+                path: if replaying {
+                    None
+                } else {
+                    default_settings_path()
+                },
+                overrides: settings,
+            })
+            .add_plugins(HistoryPlugin {
+                path: if replaying {
+                    None
+                } else {
+                    self.history_path.clone().or_else(default_history_path)
+                },
+            });
+
+        if let Some(replay_mode) = &self.replay {
+            app.add_plugins(ReplayPlugin {
+                mode: replay_mode.clone(),
+            });
+        }
+        if let Some(frame_limit) = self.frame_limit {
+            app.insert_resource(FrameLimit(frame_limit))
+                .add_systems(Update, exit_at_frame_limit);
+        }
+
+        app.add_plugins(PongWindowPlugin {
+            backend: self.backend,
+            headless: self.headless,
+        })
+        .add_plugins(ArenaPlugin)
+        .add_plugins(BallPlugin)
+        .add_plugins(PaddlePlugin)
+        .add_plugins(AiPlugin)
+        .add_plugins(ScorePlugin)
+        .add_plugins(ServePlugin)
+        .add_plugins(CountdownPlugin)
+        .add_plugins(ParticlesPlugin)
+        .add_plugins(StatsPlugin {
+            export_dir: self.stats_dir.clone(),
+        })
+        .add_plugins(OptionsPlugin)
+        .add_plugins(LeaderboardPlugin);
+
+        if let Some(path) = &self.tournament_path {
+            app.add_plugins(TournamentPlugin { path: path.clone() });
+        }
+
+        add_glue_systems(app);
     }
 }
 
@@ -80,9 +234,19 @@ impl Plugin for PongPlugin {
 #[derive(Resource, Default)]
 struct RoundStartTimer(Timer);
 
-// Boolean state resource signifying if we are between games (true) or just rounds (false).
-#[derive(Resource, Default)]
-struct IsBetweenGames(bool);
+// State resource signifying what the next round starts: another rally, the next game of
+// the match, or a new match.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+enum NextRound {
+    #[default]
+    Rally,
+    Game,
+    Match,
+}
+
+// Number of frames to run the game for, before exiting.
+#[derive(Resource)]
+struct FrameLimit(u32);
 
 // -------------------------------------------------------------------------------------------------
 // Private Systems
@@ -94,71 +258,223 @@ fn start_first_round_timer(mut round_timer: ResMut<RoundStartTimer>) {
 
 //
 // System to handle expiring round timer (i.e. time to start a round).
-// Should start the ball moving and if it's a new game, clear the scoreboard.
+// Should serve the ball and if it's the next game of a match, reset the game scores.
+// If it's a new match, clear the scoreboard and bring back any paddles which were walled off.
+// Until then, the time left is shown by the round countdown.
 //
+#[allow(clippy::too_many_arguments)]
 fn update_round_timer(
     time: Res<Time>,
     mut round_timer: ResMut<RoundStartTimer>,
-    mut between_games: ResMut<IsBetweenGames>,
+    mut countdown: ResMut<RoundCountdown>,
+    mut next_round: ResMut<NextRound>,
+    mut next_game_msgs: MessageWriter<StartNextGame>,
     mut clear_score_msgs: MessageWriter<ClearScores>,
-    mut start_ball_msgs: MessageWriter<StartBall>,
+    mut restore_paddle_msgs: MessageWriter<RestorePaddles>,
+    mut serve_msgs: MessageWriter<ServeBall>,
 ) {
     round_timer.0.tick(time.delta());
+    let remaining = (!round_timer.0.is_finished()).then(|| round_timer.0.remaining());
+    countdown.set_if_neq(RoundCountdown(remaining));
+
     if round_timer.0.just_finished() {
-        // Reset for new game if needed
-        if between_games.0 {
-            between_games.0 = false;
-            clear_score_msgs.write(ClearScores);
+        // Reset for next game or new match if needed
+        match std::mem::take(&mut *next_round) {
+            NextRound::Rally => {}
+            NextRound::Game => {
+                next_game_msgs.write(StartNextGame);
+            }
+            NextRound::Match => {
+                clear_score_msgs.write(ClearScores);
+                restore_paddle_msgs.write(RestorePaddles);
+            }
         }
 
         // Start round
-        start_ball_msgs.write(StartBall);
+        serve_msgs.write(ServeBall);
     }
 }
 
 //
 // System to handle ball off screen messages from ball plugin, and trigger associated
-// actions to reset the ball, increment score, and start the timer until the next round.
+// actions to reset the ball, update score, and start the timer until the next round.
+// In two player mode the opposing player scores. In four player mode, the player
+// defending the edge the ball went off concedes a goal.
 //
 fn handle_ball_off_screen(
     mut off_screen_msgs: MessageReader<BallOffScreen>,
     mut score_msgs: MessageWriter<PlayerScored>,
+    mut conceded_msgs: MessageWriter<GoalConceded>,
     mut reset_msgs: MessageWriter<ResetBall>,
     mut round_timer: ResMut<RoundStartTimer>,
+    mode: Res<GameMode>,
 ) {
     if let Some(off_screen_msg) = off_screen_msgs.read().next() {
-        score_msgs.write(PlayerScored(match off_screen_msg {
-            BallOffScreen::Left => Player2,
-            BallOffScreen::Right => Player1,
-        }));
+        match (*mode, off_screen_msg.side()) {
+            (GameMode::TwoPlayer | GameMode::Doubles, ArenaSide::Left) => {
+                score_msgs.write(PlayerScored(Player2));
+            }
+            (GameMode::TwoPlayer | GameMode::Doubles, ArenaSide::Right) => {
+                score_msgs.write(PlayerScored(Player1));
+            }
+            (_, side) => {
+                conceded_msgs.write(GoalConceded(side.player()));
+            }
+        }
         reset_msgs.write(ResetBall);
         round_timer.0 = Timer::from_seconds(TIME_BETWEEN_ROUNDS_SECS, TimerMode::Once);
         off_screen_msgs.clear();
     }
 }
 
+// System to wall off the paddle of any player who has been eliminated from the game.
+fn handle_player_eliminated(
+    mut eliminated_msgs: MessageReader<PlayerEliminated>,
+    mut wall_off_msgs: MessageWriter<WallOffPaddle>,
+) {
+    for &PlayerEliminated(player) in eliminated_msgs.read() {
+        wall_off_msgs.write(WallOffPaddle(player));
+    }
+}
+
 //
-// System to handle 'end of game' scenario when a player has reached the winning score.
-// Essentially just note we are between games and extend the between-round timer duration.
+// System to handle 'end of game' scenario when a player has won a game, or the whole match.
+// Essentially just note what the next round starts and extend the between-round timer
+// duration. At the end of a match during a tournament, the pause is longer to give time
+// to read the bracket. The ball is reset too, as a timed game can end mid-rally.
 //
 fn handle_game_end(
-    mut messages: MessageReader<MaxScoreReached>,
+    mut game_won_msgs: MessageReader<GameWon>,
+    mut max_score_msgs: MessageReader<MaxScoreReached>,
+    mut reset_msgs: MessageWriter<ResetBall>,
     mut round_timer: ResMut<RoundStartTimer>,
-    mut between_games: ResMut<IsBetweenGames>,
+    mut next_round: ResMut<NextRound>,
+    tournament: Option<Res<Tournament>>,
 ) {
-    if !messages.is_empty() {
-        messages.clear();
-        between_games.0 = true;
+    if !max_score_msgs.is_empty() {
+        max_score_msgs.clear();
+        game_won_msgs.clear();
+        *next_round = NextRound::Match;
+        let pause_secs = match tournament {
+            Some(_) => TIME_SHOWING_BRACKET_SECS,
+            None => TIME_BETWEEN_GAMES_SECS,
+        };
+        round_timer.0 = Timer::from_seconds(pause_secs, TimerMode::Once);
+        reset_msgs.write(ResetBall);
+    } else if !game_won_msgs.is_empty() {
+        game_won_msgs.clear();
+        *next_round = NextRound::Game;
         round_timer.0 = Timer::from_seconds(TIME_BETWEEN_GAMES_SECS, TimerMode::Once);
+        reset_msgs.write(ResetBall);
     }
 }
 
+// System to shake the camera when the ball comes off a paddle with one of the strongest curves.
+fn shake_on_curve_hit(
+    mut hit_msgs: MessageReader<BallHitPaddle>,
+    mut shake_msgs: MessageWriter<ShakeCamera>,
+) {
+    for hit in hit_msgs.read() {
+        let level = usize::from(hit.curve_level).min(CURVE_HIT_SHAKE_TRAUMA.len() - 1);
+        if CURVE_HIT_SHAKE_TRAUMA[level] > 0f32 {
+            shake_msgs.write(ShakeCamera(CURVE_HIT_SHAKE_TRAUMA[level]));
+        }
+    }
+}
+
+// System to punch the camera in when a player reaches game point.
+fn zoom_on_game_point(
+    mut game_point_msgs: MessageReader<GamePoint>,
+    mut zoom_msgs: MessageWriter<ZoomCamera>,
+) {
+    if !game_point_msgs.is_empty() {
+        game_point_msgs.clear();
+        zoom_msgs.write(ZoomCamera(GAME_POINT_ZOOM));
+    }
+}
+
+// System to exit the app once it has run for the number of frames in the FrameLimit.
+fn exit_at_frame_limit(
+    limit: Res<FrameLimit>,
+    mut frames: Local<u32>,
+    mut exit_msgs: MessageWriter<AppExit>,
+) {
+    *frames += 1;
+    if *frames == limit.0 {
+        exit_msgs.write(AppExit::Success);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Functions
+
+//
+// Add the resources and systems gluing the plugins together, along with the orderings they
+// need between the plugins' sets.
+//
+fn add_glue_systems(app: &mut App) {
+    app.init_resource::<RoundStartTimer>()
+        .init_resource::<NextRound>()
+        .add_systems(PostStartup, start_first_round_timer)
+        .add_systems(
+            Update,
+            (
+                update_round_timer
+                    .before(score::Systems::StartNextGameRcvr)
+                    .before(score::Systems::ClearScoresRcvr)
+                    .before(paddle::Systems::RestorePaddlesRcvr)
+                    .before(tournament::Systems::PrepareMatch)
+                    .before(serve::Systems::ServeBallRcvr)
+                    .before(countdown::Systems::RoundCountdownRcvr)
+                    .run_if(not(tournament_finished)),
+                handle_ball_off_screen
+                    .before(ball::Systems::ResetBallRcvr)
+                    .before(score::Systems::PlayerScoredRcvr)
+                    .before(score::Systems::GoalConcededRcvr),
+                handle_player_eliminated
+                    .after(score::Systems::PlayerEliminatedSndr)
+                    .before(paddle::Systems::WallOffPaddleRcvr),
+                handle_game_end
+                    .after(score::Systems::GameWonSndr)
+                    .after(score::Systems::MaxScoreReachedSndr)
+                    .before(ball::Systems::ResetBallRcvr),
+                shake_on_curve_hit
+                    .after(ball::Systems::BallHitPaddleSndr)
+                    .before(arena::Systems::ShakeCameraRcvr),
+                zoom_on_game_point
+                    .after(score::Systems::GamePointSndr)
+                    .before(arena::Systems::ZoomCameraRcvr),
+            ),
+        )
+        .configure_sets(
+            Startup,
+            (
+                arena::Systems::CameraSetup.before(score::Systems::SetupAfterCamera),
+                arena::Systems::CameraSetup.before(serve::Systems::SetupAfterCamera),
+                arena::Systems::CameraSetup.before(countdown::Systems::SetupAfterCamera),
+                arena::Systems::CameraSetup.before(stats::Systems::SetupAfterCamera),
+                arena::Systems::CameraSetup.before(options::Systems::SetupAfterCamera),
+                arena::Systems::CameraSetup.before(leaderboard::Systems::SetupAfterCamera),
+                arena::Systems::CameraSetup.before(tournament::Systems::SetupAfterCamera),
+            ),
+        )
+        .configure_sets(
+            Update,
+            (
+                ball::Systems::BallOffScreenSndr.before(handle_ball_off_screen),
+                tournament::Systems::PrepareMatch.before(paddle::Systems::RestorePaddlesRcvr),
+                particles::Systems::ShakeCameraSndr.before(arena::Systems::ShakeCameraRcvr),
+            ),
+        );
+}
+
 // -------------------------------------------------------------------------------------------------
 // Unit Tests
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bevy_test_helpers::prelude::*;
     use std::time::Duration;
 
     #[test]
@@ -189,10 +505,11 @@ mod tests {
     fn test_update_timer_sys_no_trigger() {
         test_update_timer_sys_helper(&UpdateTimerSysHelperCfg {
             timer_expires: false,
-            between_games_before: false,
+            next_round_before: NextRound::Rally,
+            exp_next_game: false,
             exp_score_clear: false,
-            exp_start_ball: false,
-            exp_between_games_after: false,
+            exp_serve_ball: false,
+            exp_next_round_after: NextRound::Rally,
         });
     }
 
@@ -200,10 +517,11 @@ mod tests {
     fn test_update_timer_sys_w_trigger() {
         test_update_timer_sys_helper(&UpdateTimerSysHelperCfg {
             timer_expires: true,
-            between_games_before: false,
+            next_round_before: NextRound::Rally,
+            exp_next_game: false,
             exp_score_clear: false,
-            exp_start_ball: true,
-            exp_between_games_after: false,
+            exp_serve_ball: true,
+            exp_next_round_after: NextRound::Rally,
         });
     }
 
@@ -211,10 +529,11 @@ mod tests {
     fn test_update_timer_sys_between_no_trigger() {
         test_update_timer_sys_helper(&UpdateTimerSysHelperCfg {
             timer_expires: false,
-            between_games_before: true,
+            next_round_before: NextRound::Match,
+            exp_next_game: false,
             exp_score_clear: false,
-            exp_start_ball: false,
-            exp_between_games_after: true,
+            exp_serve_ball: false,
+            exp_next_round_after: NextRound::Match,
         });
     }
 
@@ -222,18 +541,33 @@ mod tests {
     fn test_update_timer_sys_between_w_trigger() {
         test_update_timer_sys_helper(&UpdateTimerSysHelperCfg {
             timer_expires: true,
-            between_games_before: true,
+            next_round_before: NextRound::Match,
+            exp_next_game: false,
             exp_score_clear: true,
-            exp_start_ball: true,
-            exp_between_games_after: false,
+            exp_serve_ball: true,
+            exp_next_round_after: NextRound::Rally,
+        });
+    }
+
+    #[test]
+    fn test_update_timer_sys_next_game_w_trigger() {
+        test_update_timer_sys_helper(&UpdateTimerSysHelperCfg {
+            timer_expires: true,
+            next_round_before: NextRound::Game,
+            exp_next_game: true,
+            exp_score_clear: false,
+            exp_serve_ball: true,
+            exp_next_round_after: NextRound::Rally,
         });
     }
 
     #[test]
     fn test_ball_off_screen_left() {
         test_ball_off_screen_sys_helper(&BallOffScreenSysHelperCfg {
+            mode: GameMode::TwoPlayer,
             input_messages: &[BallOffScreen::Left],
             exp_player_score: Some(PlayerScored(Player2)),
+            exp_goal_conceded: None,
             exp_reset_ball: true,
             exp_timer_started: true,
         });
@@ -242,8 +576,10 @@ mod tests {
     #[test]
     fn test_ball_off_screen_right() {
         test_ball_off_screen_sys_helper(&BallOffScreenSysHelperCfg {
+            mode: GameMode::TwoPlayer,
             input_messages: &[BallOffScreen::Right],
             exp_player_score: Some(PlayerScored(Player1)),
+            exp_goal_conceded: None,
             exp_reset_ball: true,
             exp_timer_started: true,
         });
@@ -252,12 +588,14 @@ mod tests {
     #[test]
     fn test_ball_off_screen_multi() {
         test_ball_off_screen_sys_helper(&BallOffScreenSysHelperCfg {
+            mode: GameMode::TwoPlayer,
             input_messages: &[
                 BallOffScreen::Right,
                 BallOffScreen::Right,
                 BallOffScreen::Left,
             ],
             exp_player_score: Some(PlayerScored(Player1)),
+            exp_goal_conceded: None,
             exp_reset_ball: true,
             exp_timer_started: true,
         });
@@ -266,34 +604,204 @@ mod tests {
     #[test]
     fn test_ball_off_screen_no_input() {
         test_ball_off_screen_sys_helper(&BallOffScreenSysHelperCfg {
+            mode: GameMode::TwoPlayer,
             input_messages: &[],
             exp_player_score: None,
+            exp_goal_conceded: None,
             exp_reset_ball: false,
             exp_timer_started: false,
         });
     }
 
+    #[test]
+    fn test_ball_off_screen_four_player() {
+        test_ball_off_screen_sys_helper(&BallOffScreenSysHelperCfg {
+            mode: GameMode::FourPlayer,
+            input_messages: &[BallOffScreen::Top],
+            exp_player_score: None,
+            exp_goal_conceded: Some(GoalConceded(Player3)),
+            exp_reset_ball: true,
+            exp_timer_started: true,
+        });
+    }
+
+    #[test]
+    fn test_ball_off_screen_four_player_left() {
+        test_ball_off_screen_sys_helper(&BallOffScreenSysHelperCfg {
+            mode: GameMode::FourPlayer,
+            input_messages: &[BallOffScreen::Left],
+            exp_player_score: None,
+            exp_goal_conceded: Some(GoalConceded(Player1)),
+            exp_reset_ball: true,
+            exp_timer_started: true,
+        });
+    }
+
+    #[test]
+    fn test_player_eliminated_system() {
+        let mut world = World::default();
+        world.init_resource::<Messages<WallOffPaddle>>();
+        send_messages(
+            &mut world,
+            [PlayerEliminated(Player2), PlayerEliminated(Player4)],
+        );
+
+        let eliminated_sys = world.register_system(handle_player_eliminated);
+        world.run_system(eliminated_sys).unwrap();
+
+        // Expect a WallOffPaddle message for each eliminated player
+        assert_messages_eq(&world, &[WallOffPaddle(Player2), WallOffPaddle(Player4)]);
+    }
+
+    #[test]
+    fn test_shake_on_curve_hit_system() {
+        let mut world = World::default();
+        world.init_resource::<Messages<ShakeCamera>>();
+        send_messages(
+            &mut world,
+            (0..=3).map(|curve_level| BallHitPaddle {
+                player: Player1,
+                position: PaddlePosition::Back,
+                curve_level,
+            }),
+        );
+        world.run_system_cached(shake_on_curve_hit).unwrap();
+
+        // Expect only hits with the strongest curves to shake the camera
+        assert_messages_eq(
+            &world,
+            &[
+                ShakeCamera(CURVE_HIT_SHAKE_TRAUMA[2]),
+                ShakeCamera(CURVE_HIT_SHAKE_TRAUMA[3]),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_zoom_on_game_point_system() {
+        let mut world = World::default();
+        world.init_resource::<Messages<GamePoint>>();
+        world.init_resource::<Messages<ZoomCamera>>();
+
+        world.run_system_cached(zoom_on_game_point).unwrap();
+        assert_message_count::<ZoomCamera>(&world, 0);
+
+        // Sudden death puts both players on game point, but the camera only punches in once
+        send_messages(&mut world, [GamePoint(Player1), GamePoint(Player2)]);
+        world.run_system_cached(zoom_on_game_point).unwrap();
+        assert_eq!(
+            drain_messages::<ZoomCamera>(&mut world),
+            [ZoomCamera(GAME_POINT_ZOOM)],
+        );
+    }
+
+    #[test]
+    fn test_glue_system_ordering() {
+        // The round timer starts each round before any plugin reacts to it
+        assert_runs_before(
+            add_glue_systems,
+            Update,
+            update_round_timer,
+            score::Systems::ClearScoresRcvr,
+        );
+        assert_runs_before(
+            add_glue_systems,
+            Update,
+            update_round_timer,
+            serve::Systems::ServeBallRcvr,
+        );
+
+        // Goals are handled after the ball goes off screen, and before it is reset
+        assert_runs_before(
+            add_glue_systems,
+            Update,
+            handle_ball_off_screen,
+            ball::Systems::ResetBallRcvr,
+        );
+        assert_runs_after(
+            add_glue_systems,
+            Update,
+            handle_ball_off_screen,
+            ball::Systems::BallOffScreenSndr,
+        );
+        assert_runs_before(
+            add_glue_systems,
+            Update,
+            ball::Systems::BallOffScreenSndr,
+            score::Systems::PlayerScoredRcvr,
+        );
+
+        // Camera effects are triggered in the same frame as the hits and game points causing them
+        assert_runs_after(
+            add_glue_systems,
+            Update,
+            arena::Systems::ShakeCameraRcvr,
+            ball::Systems::BallHitPaddleSndr,
+        );
+        assert_runs_after(
+            add_glue_systems,
+            Update,
+            arena::Systems::ZoomCameraRcvr,
+            score::Systems::GamePointSndr,
+        );
+
+        // The match is prepared before paddles are restored, through the round timer or not
+        assert_runs_before(
+            add_glue_systems,
+            Update,
+            tournament::Systems::PrepareMatch,
+            paddle::Systems::RestorePaddlesRcvr,
+        );
+        assert_runs_before(
+            add_glue_systems,
+            Startup,
+            arena::Systems::CameraSetup,
+            score::Systems::SetupAfterCamera,
+        );
+    }
+
+    #[test]
+    fn test_frame_limit_system() {
+        let mut world = World::default();
+        world.insert_resource(FrameLimit(3));
+        world.init_resource::<Messages<AppExit>>();
+
+        // Only the third frame should trigger an exit
+        let limit_sys = world.register_system(exit_at_frame_limit);
+        for frame in 1..=3 {
+            world.run_system(limit_sys).unwrap();
+            let exit_msgs = world.resource::<Messages<AppExit>>();
+            assert_eq!(
+                exit_msgs.iter_current_update_messages().count(),
+                usize::from(frame == 3),
+                "Expected an AppExit message only on frame 3, checking frame {frame}",
+            );
+        }
+    }
+
     #[test]
     fn test_game_end_system() {
         let mut world = World::default();
 
         // Get our resources in place to run the system
-        let mut max_score_messages = Messages::<MaxScoreReached>::default();
-        max_score_messages.write(MaxScoreReached);
-        world.insert_resource(max_score_messages);
-        world.insert_resource(IsBetweenGames(false));
+        send_messages(&mut world, [MaxScoreReached(Player1)]);
+        world.init_resource::<Messages<GameWon>>();
+        world.init_resource::<Messages<ResetBall>>();
+        world.insert_resource(NextRound::Rally);
         world.init_resource::<RoundStartTimer>();
 
         // Run the system
         let game_end_sys = world.register_system(handle_game_end);
         world.run_system(game_end_sys).unwrap();
 
-        // Validate IsBetweenGames state afterwards
-        let is_between_games = world.get_resource::<IsBetweenGames>().unwrap();
-        assert!(
-            is_between_games.0,
-            "Expected IsBetweenGames=true but it was false"
+        // Validate NextRound state afterwards
+        assert_eq!(
+            *world.resource::<NextRound>(),
+            NextRound::Match,
+            "Expected a new match to start after the match was won",
         );
+        assert_message_count::<ResetBall>(&world, 1);
+        drain_messages::<ResetBall>(&mut world);
 
         // Validate Timer was set as expected
         let round_timer = world.get_resource::<RoundStartTimer>().unwrap();
@@ -304,21 +812,50 @@ mod tests {
             Timer::from_seconds(TIME_BETWEEN_GAMES_SECS, TimerMode::Once),
             round_timer.0,
         );
+
+        // Winning a game which doesn't end the match leads to the next game instead
+        world.insert_resource(NextRound::Rally);
+        world.write_message(GameWon(Player2));
+        world.run_system(game_end_sys).unwrap();
+        assert_eq!(
+            *world.resource::<NextRound>(),
+            NextRound::Game,
+            "Expected the next game to start after a game was won",
+        );
+        assert_message_count::<ResetBall>(&world, 1);
+        assert_eq!(
+            world.resource::<RoundStartTimer>().0,
+            Timer::from_seconds(TIME_BETWEEN_GAMES_SECS, TimerMode::Once),
+        );
+
+        // During a tournament, the pause is extended to show the bracket
+        let entrants = vec![PlayerProfile::new("Ada"), PlayerProfile::new("Bob")];
+        world.insert_resource(Tournament::new(TournamentFormat::RoundRobin, entrants).unwrap());
+        world.write_message(MaxScoreReached(Player2));
+        world.run_system(game_end_sys).unwrap();
+        assert_eq!(
+            world.resource::<RoundStartTimer>().0,
+            Timer::from_seconds(TIME_SHOWING_BRACKET_SECS, TimerMode::Once),
+            "Expected the pause to last long enough to show the bracket",
+        );
     }
 
     // --- Helper Types ---
 
     struct UpdateTimerSysHelperCfg {
         timer_expires: bool,
-        between_games_before: bool,
+        next_round_before: NextRound,
+        exp_next_game: bool,
         exp_score_clear: bool,
-        exp_start_ball: bool,
-        exp_between_games_after: bool,
+        exp_serve_ball: bool,
+        exp_next_round_after: NextRound,
     }
 
     struct BallOffScreenSysHelperCfg<'a> {
+        mode: GameMode,
         input_messages: &'a [BallOffScreen],
         exp_player_score: Option<PlayerScored>,
+        exp_goal_conceded: Option<GoalConceded>,
         exp_reset_ball: bool,
         exp_timer_started: bool,
     }
@@ -336,68 +873,50 @@ mod tests {
             Duration::from_millis(500)
         });
         world.insert_resource(time);
-        world.insert_resource(IsBetweenGames(cfg.between_games_before));
+        world.insert_resource(cfg.next_round_before);
+        world.init_resource::<Messages<StartNextGame>>();
         world.init_resource::<Messages<ClearScores>>();
-        world.init_resource::<Messages<StartBall>>();
+        world.init_resource::<Messages<RestorePaddles>>();
+        world.init_resource::<Messages<ServeBall>>();
+        world.init_resource::<RoundCountdown>();
         world.insert_resource(RoundStartTimer(Timer::from_seconds(1f32, TimerMode::Once)));
 
         // Run the system
         let update_sys = world.register_system(update_round_timer);
         world.run_system(update_sys).unwrap();
 
-        // Validate ClearScores messages
-        let clear_messages = world.get_resource::<Messages<ClearScores>>().unwrap();
-        if cfg.exp_score_clear {
-            assert!(
-                !clear_messages.is_empty(),
-                "Expected a ClearScores message but got none"
-            );
-        } else {
-            assert!(
-                clear_messages.is_empty(),
-                "Expected no ClearScores but got one"
-            );
-        }
+        // Validate StartNextGame, ClearScores and ServeBall messages. RestorePaddles is sent
+        // alongside ClearScores for a new match.
+        assert_message_count::<StartNextGame>(&world, cfg.exp_next_game as usize);
+        assert_message_count::<ClearScores>(&world, cfg.exp_score_clear as usize);
+        assert_message_count::<RestorePaddles>(&world, cfg.exp_score_clear as usize);
+        assert_message_count::<ServeBall>(&world, cfg.exp_serve_ball as usize);
 
-        // Validate StartBall messages
-        let start_messages = world.get_resource::<Messages<StartBall>>().unwrap();
-        if cfg.exp_start_ball {
-            assert!(
-                !start_messages.is_empty(),
-                "Expected one StartBall message but got none"
-            );
-        } else {
-            assert!(
-                start_messages.is_empty(),
-                "Expected no StartBall but got one"
-            );
-        }
+        // Validate the countdown shows the time left, until the round starts
+        let exp_countdown = (!cfg.timer_expires).then_some(Duration::from_millis(500));
+        assert_eq!(
+            *world.resource::<RoundCountdown>(),
+            RoundCountdown(exp_countdown),
+            "Expected RoundCountdown to match the time left on the round timer",
+        );
 
-        // Validate IsBetweenGames state afterwards
-        let is_between_games = world.get_resource::<IsBetweenGames>().unwrap();
-        if cfg.exp_between_games_after {
-            assert!(
-                is_between_games.0,
-                "Expected IsBetweenGames=true but it was false"
-            );
-        } else {
-            assert!(
-                !is_between_games.0,
-                "Expected IsBetweenGames=false but is was true"
-            );
-        }
+        // Validate NextRound state afterwards
+        assert_eq!(
+            *world.resource::<NextRound>(),
+            cfg.exp_next_round_after,
+            "Expected NextRound {:?} afterwards",
+            cfg.exp_next_round_after,
+        );
     }
 
     fn test_ball_off_screen_sys_helper(cfg: &BallOffScreenSysHelperCfg) {
         let mut world = World::default();
 
         // Get our resources in place based on the config given
-        let mut input_messages = Messages::<BallOffScreen>::default();
-        for input_message in cfg.input_messages {
-            input_messages.write(*input_message);
-        }
-        world.insert_resource(input_messages);
+        send_messages(&mut world, cfg.input_messages.iter().copied());
+        world.insert_resource(cfg.mode);
         world.init_resource::<Messages<PlayerScored>>();
+        world.init_resource::<Messages<GoalConceded>>();
         world.init_resource::<Messages<ResetBall>>();
         world.init_resource::<RoundStartTimer>();
 
@@ -405,43 +924,10 @@ mod tests {
         let ball_sys = world.register_system(handle_ball_off_screen);
         world.run_system(ball_sys).unwrap();
 
-        // Validate expected PlayerScored message
-        let score_messages = world.get_resource::<Messages<PlayerScored>>().unwrap();
-        let mut message_cursor = score_messages.get_cursor();
-        let mut message_iter = message_cursor.read(score_messages);
-        if let Some(exp_score_message) = &cfg.exp_player_score {
-            let score_message = message_iter
-                .next()
-                .expect("Expected a PlayerScored message but got none");
-            assert_eq!(
-                *score_message, *exp_score_message,
-                "Expected message {:?} but got {:?}",
-                *exp_score_message, *score_message,
-            );
-            assert!(
-                message_iter.next().is_none(),
-                "Expected one PlayerScored message but got more"
-            );
-        } else {
-            assert!(
-                message_iter.next().is_none(),
-                "Expected no PlayerScored messages but got one"
-            );
-        }
-
-        // Validate ResetBall messages
-        let reset_messages = world.get_resource_mut::<Messages<ResetBall>>().unwrap();
-        if cfg.exp_reset_ball {
-            assert!(
-                !reset_messages.is_empty(),
-                "Expected one ResetBall message but got none"
-            );
-        } else {
-            assert!(
-                reset_messages.is_empty(),
-                "Expected no ResetBall messages but got one"
-            );
-        }
+        // Validate expected PlayerScored, GoalConceded and ResetBall messages
+        assert_messages_eq(&world, cfg.exp_player_score.as_slice());
+        assert_messages_eq(&world, cfg.exp_goal_conceded.as_slice());
+        assert_message_count::<ResetBall>(&world, cfg.exp_reset_ball as usize);
 
         // Validate Timer was started if expected
         let round_timer = world.get_resource::<RoundStartTimer>().unwrap();
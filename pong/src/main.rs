@@ -1,7 +1,1405 @@
-use bevy::prelude::App;
+//!
+//! Entry point for the pong binary. Parses and validates the command line arguments, and
+//! translates them into the configuration of the PongPlugin which runs the game. Subcommands
+//! query the match history, or manage the player profiles and tournament, without starting
+//! the game.
+//!
 
-use pong::PongPlugin;
+// -------------------------------------------------------------------------------------------------
+// Included Symbols
 
-fn main() {
-    App::new().add_plugins(PongPlugin).run();
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use bevy::prelude::{App, AppExit};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+
+use pong::{
+    AiDifficulty, DisplayMode, GameMode, LeaderboardEntry, LeaderboardSort, MatchRecord,
+    PaddleKeys, PerPlayer, PlayerId, PlayerProfile, PongPlugin, Profiles, RenderBackend,
+    ReplayMode, ServeRule, SettingsOverrides, Tournament, TournamentFormat, WinCondition,
+    default_history_path, default_profiles_path, default_tournament_path, leaderboard,
+    load_history, load_profiles, load_recording, load_tournament, save_profiles_to,
+    save_tournament_to,
+};
+
+// -------------------------------------------------------------------------------------------------
+// Command Line Arguments
+
+/// A spin on classic pong, for up to four players.
+#[derive(Parser, Debug)]
+#[command(version, after_help = AFTER_HELP, args_conflicts_with_subcommands = true)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Width of the game window, in pixels [default: saved setting]
+    #[arg(long, value_name = "PIXELS", value_parser = clap::value_parser!(u32).range(160..=7680))]
+    width: Option<u32>,
+
+    /// Height of the game window, in pixels [default: saved setting]
+    #[arg(long, value_name = "PIXELS", value_parser = clap::value_parser!(u32).range(90..=4320))]
+    height: Option<u32>,
+
+    /// Play fullscreen, instead of the saved display mode
+    #[arg(long, conflicts_with = "windowed")]
+    fullscreen: bool,
+
+    /// Play in a window, instead of the saved display mode
+    #[arg(long)]
+    windowed: bool,
+
+    /// Graphics API used to render the game
+    #[arg(long, value_enum, default_value_t = Backend::Dx12)]
+    backend: Backend,
+
+    /// Score a player needs to win a game [default: saved setting]
+    #[arg(long, value_name = "SCORE", value_parser = clap::value_parser!(u8).range(1..=99))]
+    winning_score: Option<u8>,
+
+    /// Only win a game with a lead of two points, playing on past the winning score if needed
+    #[arg(long, conflicts_with = "time_limit")]
+    win_by_two: bool,
+
+    /// Play timed games, won by whoever leads once the ball has been in play for SECS
+    #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u32).range(10..=3600))]
+    time_limit: Option<u32>,
+
+    /// Number of games in a match, won by the first player to win most of them
+    /// [default: saved setting]
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u8).range(1..=99))]
+    best_of: Option<u8>,
+
+    /// Who each serve goes to [default: saved setting]
+    #[arg(long, value_enum)]
+    serve: Option<Serve>,
+
+    /// Make each player launch their own serves with the serve key, aiming with their paddle keys
+    #[arg(long)]
+    manual_serve: bool,
+
+    /// Variant of pong to play
+    #[arg(long, value_enum, default_value_t = Mode::TwoPlayer)]
+    mode: Mode,
+
+    /// Let the computer control a player, e.g. "2=hard". May be repeated for several players
+    #[arg(long, value_name = "PLAYER=DIFFICULTY", value_parser = parse_ai_player)]
+    ai: Vec<(PlayerId, AiDifficulty)>,
+
+    /// Name a player in the match history, e.g. "1=Ada". May be repeated for several players
+    #[arg(long, value_name = "PLAYER=NAME", value_parser = parse_player_name)]
+    name: Vec<(PlayerId, String)>,
+
+    /// Play under a saved player profile, e.g. "1=Ada". May be repeated for several players
+    #[arg(long, value_name = "PLAYER=PROFILE", value_parser = parse_player_name)]
+    profile: Vec<(PlayerId, String)>,
+
+    /// Seed for all gameplay randomness, to make games reproducible [default: random]
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Record the game to FILE, saved when the game exits
+    #[arg(long, value_name = "FILE", conflicts_with = "replay")]
+    record: Option<PathBuf>,
+
+    /// Replay a game previously recorded to FILE, exiting once it ends
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = [
+            "mode",
+            "ai",
+            "seed",
+            "winning_score",
+            "win_by_two",
+            "time_limit",
+            "best_of",
+            "serve",
+            "manual_serve",
+        ],
+    )]
+    replay: Option<PathBuf>,
+
+    /// Play the next matches of the saved tournament, see `pong tournament new`
+    #[arg(long, conflicts_with_all = ["mode", "name", "profile", "replay"])]
+    tournament: bool,
+
+    /// Run without a window or renderer, e.g. to simulate games between computer players
+    #[arg(long)]
+    headless: bool,
+
+    /// Save the statistics of each finished match to DIR, as JSON
+    #[arg(long, value_name = "DIR")]
+    stats: Option<PathBuf>,
+
+    /// Exit after running this many frames
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    frames: Option<u32>,
+
+    /// File the match history is stored in [default: in the platform's data directory]
+    #[arg(long, value_name = "FILE", global = true)]
+    history_file: Option<PathBuf>,
+
+    /// File the player profiles are stored in [default: in the platform's config directory]
+    #[arg(long, value_name = "FILE", global = true)]
+    profiles_file: Option<PathBuf>,
+
+    /// File the tournament is stored in [default: in the platform's data directory]
+    #[arg(long, value_name = "FILE", global = true)]
+    tournament_file: Option<PathBuf>,
+}
+
+//
+// Subcommands which query the match history, or manage the profiles and tournament, instead
+// of starting the game.
+//
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List the most recent matches in the match history
+    History {
+        /// Number of matches to list, most recent first
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+
+        /// Only list matches which this player took part in
+        #[arg(long, value_name = "NAME")]
+        player: Option<String>,
+    },
+
+    /// Rank every player in the match history
+    Leaderboard {
+        /// Order to rank the players in
+        #[arg(long, value_enum, default_value_t = Sort::Elo)]
+        sort: Sort,
+    },
+
+    /// List, add or remove the saved player profiles
+    Profile {
+        #[command(subcommand)]
+        action: ProfileAction,
+    },
+
+    /// Start a new tournament between player profiles, or show the current one
+    Tournament {
+        #[command(subcommand)]
+        action: TournamentAction,
+    },
+}
+
+// Actions which manage the saved player profiles.
+#[derive(Subcommand, Debug)]
+enum ProfileAction {
+    /// List every profile, with its preferences and standing in the match history
+    List,
+
+    /// Add a profile, or update the preferences of an existing one
+    Add {
+        /// Name to play under, which identifies the player in the match history
+        name: String,
+
+        /// Preferred paddle color, as a hex code, e.g. "ff8800"
+        #[arg(long, value_name = "RRGGBB", value_parser = parse_color)]
+        color: Option<[u8; 3]>,
+
+        /// Preferred keys for the back paddle, e.g. "KeyW,KeyS" (up/right first)
+        #[arg(long, value_name = "POSITIVE,NEGATIVE")]
+        keys: Option<PaddleKeys>,
+
+        /// Preferred keys for the forward paddle in doubles mode, e.g. "KeyT,KeyG"
+        #[arg(long, value_name = "POSITIVE,NEGATIVE")]
+        forward_keys: Option<PaddleKeys>,
+    },
+
+    /// Remove a profile. Matches played under it stay in the match history
+    Remove {
+        /// Name of the profile to remove
+        name: String,
+    },
+}
+
+// Actions which manage the saved tournament.
+#[derive(Subcommand, Debug)]
+enum TournamentAction {
+    /// Draw a new tournament between profiles, listed from the top seed down
+    New {
+        /// Names of the profiles taking part
+        #[arg(required = true, num_args = 2..)]
+        entrants: Vec<String>,
+
+        /// How entrants are drawn into matches
+        #[arg(long, value_enum, default_value_t = Format::SingleElimination)]
+        format: Format,
+
+        /// Replace the current tournament, even if it hasn't crowned a champion yet
+        #[arg(long)]
+        replace: bool,
+    },
+
+    /// Show the bracket of the current tournament
+    Show,
+}
+
+const AFTER_HELP: &str = "\
+Players 1 and 2 defend the left and right edges of the arena. Players 3 and 4 defend the top
+and bottom edges, and only play in four-player mode.
+
+Examples:
+  pong --mode doubles --winning-score 5
+  pong --winning-score 11 --win-by-two --best-of 3
+  pong --time-limit 120
+  pong --serve toward-loser --manual-serve
+  pong --ai 2=medium --record game.ron
+  pong --headless --ai 1=easy --ai 2=hard --seed 7 --frames 3600 --stats stats
+  pong --name 1=Ada --name 2=Bob
+  pong profile add Ada --color ff8800 --keys KeyQ,KeyA
+  pong --profile 1=Ada --ai 2=hard
+  pong tournament new Ada Bob Cy Di --format round-robin
+  pong --tournament
+  pong leaderboard --sort win-rate";
+
+// The variants of pong which can be played.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Mode {
+    /// Classic pong, one player against another
+    TwoPlayer,
+    /// Every edge of the arena is defended, last player standing wins
+    FourPlayer,
+    /// Two versus two, with a back and a forward paddle on each side
+    Doubles,
+}
+
+// The rules for who each serve goes to.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Serve {
+    /// Serve towards a random player
+    Random,
+    /// Serve towards each player in turn
+    Alternate,
+    /// Serve towards the player who conceded the last point
+    TowardLoser,
+}
+
+// The graphics APIs which can be used to render the game.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Backend {
+    /// Let the renderer choose the best API for the platform
+    Auto,
+    Vulkan,
+    Dx12,
+    Metal,
+    Gl,
+}
+
+// The ways a tournament's entrants can be drawn into matches.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Format {
+    /// Losers are knocked out, until one entrant is left
+    SingleElimination,
+    /// Every entrant plays every other entrant once
+    RoundRobin,
+}
+
+// The orders the leaderboard can be ranked in.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Sort {
+    /// Most matches won first
+    Wins,
+    /// Highest fraction of matches won first
+    WinRate,
+    /// Highest Elo rating first
+    Elo,
+}
+
+// -------------------------------------------------------------------------------------------------
+// Main
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    if let Some(command) = &args.command {
+        let lines = run_command(command, &args).unwrap_or_else(|(kind, message)| {
+            Args::command().error(kind, message).exit();
+        });
+        for line in lines {
+            println!("{line}");
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    let plugin = pong_plugin(&args).unwrap_or_else(|(kind, message)| {
+        Args::command().error(kind, message).exit();
+    });
+
+    match App::new().add_plugins(plugin).run() {
+        AppExit::Success => ExitCode::SUCCESS,
+        AppExit::Error(code) => ExitCode::from(code.get()),
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Functions
+
+//
+// Validates the combination of arguments given, and translates them into the configuration of
+// the PongPlugin. Returns the kind of error and a message describing it for invalid arguments.
+//
+fn pong_plugin(args: &Args) -> Result<PongPlugin, (ErrorKind, String)> {
+    let mode = match args.mode {
+        Mode::TwoPlayer => GameMode::TwoPlayer,
+        Mode::FourPlayer => GameMode::FourPlayer,
+        Mode::Doubles => GameMode::Doubles,
+    };
+
+    let mut ai_players = PerPlayer::new();
+    for &(player, difficulty) in &args.ai {
+        if !mode.players().contains(&player) {
+            return Err((
+                ErrorKind::ValueValidation,
+                format!("--ai: {player:?} does not take part in {mode:?} mode"),
+            ));
+        }
+        if ai_players.insert(player, difficulty).is_some() {
+            return Err((
+                ErrorKind::ArgumentConflict,
+                format!("--ai: {player:?} was given more than once"),
+            ));
+        }
+    }
+
+    let mut player_names = PerPlayer::new();
+    for (player, name) in &args.name {
+        if !mode.players().contains(player) {
+            return Err((
+                ErrorKind::ValueValidation,
+                format!("--name: {player:?} does not take part in {mode:?} mode"),
+            ));
+        }
+        if player_names.insert(*player, name.clone()).is_some() {
+            return Err((
+                ErrorKind::ArgumentConflict,
+                format!("--name: {player:?} was given more than once"),
+            ));
+        }
+    }
+
+    let mut profiles: PerPlayer<PlayerProfile> = PerPlayer::new();
+    if !args.profile.is_empty() {
+        let (_, saved) = load_profiles_arg(args)?;
+        for (player, name) in &args.profile {
+            if !mode.players().contains(player) {
+                return Err((
+                    ErrorKind::ValueValidation,
+                    format!("--profile: {player:?} does not take part in {mode:?} mode"),
+                ));
+            }
+            if player_names.contains(*player) {
+                return Err((
+                    ErrorKind::ArgumentConflict,
+                    format!("--profile: {player:?} was already given a --name"),
+                ));
+            }
+            if profiles.iter().any(|(_, profile)| profile.name == *name) {
+                return Err((
+                    ErrorKind::ArgumentConflict,
+                    format!("--profile: '{name}' can only be played by one player"),
+                ));
+            }
+            let profile = saved.get(name).ok_or_else(|| {
+                (
+                    ErrorKind::ValueValidation,
+                    format!("--profile: no profile named '{name}', see `pong profile add`"),
+                )
+            })?;
+            if profiles.insert(*player, profile.clone()).is_some() {
+                return Err((
+                    ErrorKind::ArgumentConflict,
+                    format!("--profile: {player:?} was given more than once"),
+                ));
+            }
+        }
+    }
+
+    let tournament_path = if args.tournament {
+        let (path, tournament) = load_tournament_arg(args)?;
+        match tournament {
+            None => {
+                return Err((
+                    ErrorKind::ValueValidation,
+                    "--tournament: no tournament to play, see `pong tournament new`".to_string(),
+                ));
+            }
+            Some(tournament) => {
+                if let Some(champion) = tournament.champion() {
+                    return Err((
+                        ErrorKind::ValueValidation,
+                        format!("--tournament: the tournament is over, {champion} is the champion"),
+                    ));
+                }
+            }
+        }
+        Some(path)
+    } else {
+        None
+    };
+
+    let replay = match (&args.record, &args.replay) {
+        (Some(path), _) => Some(ReplayMode::Record(path.clone())),
+        (None, Some(path)) => {
+            let recording = load_recording(path).map_err(|err| {
+                (
+                    ErrorKind::Io,
+                    format!("--replay: failed to load {}: {err}", path.display()),
+                )
+            })?;
+            Some(ReplayMode::Replay(Box::new(recording)))
+        }
+        (None, None) => None,
+    };
+
+    let win_condition = match (args.win_by_two, args.time_limit) {
+        (true, _) => Some(WinCondition::WinByTwo),
+        (false, Some(secs)) => Some(WinCondition::Timed { secs }),
+        (false, None) => None,
+    };
+
+    let display_mode = match (args.fullscreen, args.windowed) {
+        (true, _) => Some(DisplayMode::Fullscreen),
+        (false, true) => Some(DisplayMode::Windowed),
+        (false, false) => None,
+    };
+
+    Ok(PongPlugin {
+        mode,
+        settings: SettingsOverrides {
+            display_mode,
+            window_width: args.width,
+            window_height: args.height,
+            winning_score: args.winning_score,
+            win_condition,
+            best_of: args.best_of,
+            serve_rule: args.serve.map(|serve| match serve {
+                Serve::Random => ServeRule::Random,
+                Serve::Alternate => ServeRule::Alternate,
+                Serve::TowardLoser => ServeRule::TowardLoser,
+            }),
+            manual_serve: args.manual_serve.then_some(true),
+            ..Default::default()
+        },
+        backend: match args.backend {
+            Backend::Auto => RenderBackend::Auto,
+            Backend::Vulkan => RenderBackend::Vulkan,
+            Backend::Dx12 => RenderBackend::Dx12,
+            Backend::Metal => RenderBackend::Metal,
+            Backend::Gl => RenderBackend::Gl,
+        },
+        headless: args.headless,
+        ai_players,
+        seed: args.seed,
+        replay,
+        frame_limit: args.frames,
+        stats_dir: args.stats.clone(),
+        player_names,
+        profiles,
+        history_path: args.history_file.clone(),
+        tournament_path,
+    })
+}
+
+// Parses a computer controlled player argument, in the form PLAYER=DIFFICULTY (e.g. 2=hard).
+fn parse_ai_player(arg: &str) -> Result<(PlayerId, AiDifficulty), String> {
+    let (player, difficulty) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("expected PLAYER=DIFFICULTY, e.g. 2=hard, but got '{arg}'"))?;
+
+    let difficulty = match difficulty.trim().to_ascii_lowercase().as_str() {
+        "easy" => AiDifficulty::Easy,
+        "medium" => AiDifficulty::Medium,
+        "hard" => AiDifficulty::Hard,
+        other => {
+            return Err(format!(
+                "expected a difficulty of easy, medium or hard, but got '{other}'"
+            ));
+        }
+    };
+
+    Ok((parse_player(player)?, difficulty))
+}
+
+// Parses a player name argument, in the form PLAYER=NAME (e.g. 1=Ada).
+fn parse_player_name(arg: &str) -> Result<(PlayerId, String), String> {
+    let (player, name) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("expected PLAYER=NAME, e.g. 1=Ada, but got '{arg}'"))?;
+
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(format!("expected a name for player '{}'", player.trim()));
+    }
+
+    Ok((parse_player(player)?, name.to_string()))
+}
+
+// Parses a color given as a hex code, in the form RRGGBB (optionally prefixed with #).
+fn parse_color(arg: &str) -> Result<[u8; 3], String> {
+    let hex = arg.trim().trim_start_matches('#');
+    let component = |i: usize| {
+        hex.get(i..i + 2)
+            .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+    };
+    match (hex.len(), component(0), component(2), component(4)) {
+        (6, Some(red), Some(green), Some(blue)) => Ok([red, green, blue]),
+        _ => Err(format!(
+            "expected a hex color code RRGGBB, e.g. ff8800, but got '{arg}'"
+        )),
+    }
+}
+
+// Parses the number of a player, from 1 to 4.
+fn parse_player(player: &str) -> Result<PlayerId, String> {
+    match player.trim() {
+        "1" => Ok(PlayerId::Player1),
+        "2" => Ok(PlayerId::Player2),
+        "3" => Ok(PlayerId::Player3),
+        "4" => Ok(PlayerId::Player4),
+        other => Err(format!("expected a player from 1 to 4, but got '{other}'")),
+    }
+}
+
+//
+// Runs a subcommand, returning the lines to print. Returns the kind of error and a message
+// describing it if the subcommand fails, e.g. because the history or profiles can't be loaded.
+//
+fn run_command(command: &Command, args: &Args) -> Result<Vec<String>, (ErrorKind, String)> {
+    match command {
+        Command::History { limit, player } => Ok(history_lines(
+            &load_history_arg(args)?,
+            *limit,
+            player.as_deref(),
+        )),
+        Command::Leaderboard { sort } => {
+            let sort = match sort {
+                Sort::Wins => LeaderboardSort::Wins,
+                Sort::WinRate => LeaderboardSort::WinRate,
+                Sort::Elo => LeaderboardSort::Elo,
+            };
+            Ok(leaderboard_lines(&leaderboard(
+                &load_history_arg(args)?,
+                sort,
+            )))
+        }
+        Command::Profile { action } => manage_profiles(action, args),
+        Command::Tournament { action } => manage_tournament(action, args),
+    }
+}
+
+//
+// Runs a profile subcommand, saving any change to the profiles file. Returns the lines to
+// print, or the kind of error and a message describing it.
+//
+fn manage_profiles(
+    action: &ProfileAction,
+    args: &Args,
+) -> Result<Vec<String>, (ErrorKind, String)> {
+    let (path, mut profiles) = load_profiles_arg(args)?;
+
+    let line = match action {
+        ProfileAction::List => return Ok(profile_lines(&profiles, &load_history_arg(args)?)),
+        ProfileAction::Add {
+            name,
+            color,
+            keys,
+            forward_keys,
+        } => {
+            let name = name.trim();
+            if name.is_empty() {
+                return Err((
+                    ErrorKind::ValueValidation,
+                    "expected a profile name".to_string(),
+                ));
+            }
+
+            // Only the given preferences are changed, when updating an existing profile
+            let mut profile = profiles
+                .get(name)
+                .cloned()
+                .unwrap_or_else(|| PlayerProfile::new(name));
+            profile.paddle_color = color.or(profile.paddle_color);
+            profile.keys = keys.or(profile.keys);
+            profile.forward_keys = forward_keys.or(profile.forward_keys);
+            match profiles.insert(profile) {
+                Some(_) => format!("Updated profile '{name}'"),
+                None => format!("Added profile '{name}'"),
+            }
+        }
+        ProfileAction::Remove { name } => match profiles.remove(name) {
+            Some(_) => format!("Removed profile '{name}'"),
+            None => {
+                return Err((
+                    ErrorKind::ValueValidation,
+                    format!("no profile named '{name}'"),
+                ));
+            }
+        },
+    };
+
+    save_profiles_to(&path, &profiles).map_err(|err| {
+        (
+            ErrorKind::Io,
+            format!("failed to save profiles to {}: {err}", path.display()),
+        )
+    })?;
+    Ok(vec![line])
+}
+
+//
+// Runs a tournament subcommand, saving any new tournament to the tournament file. Returns the
+// lines to print, or the kind of error and a message describing it.
+//
+fn manage_tournament(
+    action: &TournamentAction,
+    args: &Args,
+) -> Result<Vec<String>, (ErrorKind, String)> {
+    let (path, current) = load_tournament_arg(args)?;
+
+    let (mut lines, tournament) = match action {
+        TournamentAction::Show => match current {
+            Some(tournament) => (Vec::new(), tournament),
+            None => {
+                return Ok(vec![
+                    "No tournament yet, start one with `pong tournament new`".to_string(),
+                ]);
+            }
+        },
+        TournamentAction::New {
+            entrants,
+            format,
+            replace,
+        } => {
+            if let Some(current) = current
+                && current.champion().is_none()
+                && !replace
+            {
+                return Err((
+                    ErrorKind::ArgumentConflict,
+                    format!(
+                        "the current tournament isn't over yet ({}), use --replace to start anyway",
+                        current.status_line()
+                    ),
+                ));
+            }
+
+            let (_, profiles) = load_profiles_arg(args)?;
+            let entrants = entrants
+                .iter()
+                .map(|name| {
+                    profiles.get(name).cloned().ok_or_else(|| {
+                        (
+                            ErrorKind::ValueValidation,
+                            format!("no profile named '{name}', see `pong profile add`"),
+                        )
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let format = match format {
+                Format::SingleElimination => TournamentFormat::SingleElimination,
+                Format::RoundRobin => TournamentFormat::RoundRobin,
+            };
+            let tournament = Tournament::new(format, entrants)
+                .map_err(|err| (ErrorKind::ValueValidation, err.to_string()))?;
+            save_tournament_to(&path, &tournament).map_err(|err| {
+                (
+                    ErrorKind::Io,
+                    format!("failed to save tournament to {}: {err}", path.display()),
+                )
+            })?;
+            (vec!["Started a new tournament".to_string()], tournament)
+        }
+    };
+
+    lines.extend(tournament.bracket_lines());
+    lines.push(tournament.status_line());
+    Ok(lines)
+}
+
+// Loads the match history from the file given in the arguments, or the default file.
+fn load_history_arg(args: &Args) -> Result<Vec<MatchRecord>, (ErrorKind, String)> {
+    let path = args
+        .history_file
+        .clone()
+        .or_else(default_history_path)
+        .ok_or((
+            ErrorKind::MissingRequiredArgument,
+            "no data directory to find the match history in, use --history-file".to_string(),
+        ))?;
+    load_history(&path).map_err(|err| {
+        (
+            ErrorKind::Io,
+            format!(
+                "failed to load match history from {}: {err}",
+                path.display()
+            ),
+        )
+    })
+}
+
+//
+// Loads the player profiles from the file given in the arguments, or the default file.
+// Returns the path of the file along with the profiles, so changes can be saved to it.
+//
+fn load_profiles_arg(args: &Args) -> Result<(PathBuf, Profiles), (ErrorKind, String)> {
+    let path = args
+        .profiles_file
+        .clone()
+        .or_else(default_profiles_path)
+        .ok_or((
+            ErrorKind::MissingRequiredArgument,
+            "no config directory to find the profiles in, use --profiles-file".to_string(),
+        ))?;
+    let profiles = load_profiles(&path).map_err(|err| {
+        (
+            ErrorKind::Io,
+            format!("failed to load profiles from {}: {err}", path.display()),
+        )
+    })?;
+    Ok((path, profiles))
+}
+
+//
+// Get a line describing each of the most recent matches (up to the limit), most recent first.
+// If a player is given, only matches they took part in are listed.
+//
+fn history_lines(records: &[MatchRecord], limit: usize, player: Option<&str>) -> Vec<String> {
+    let lines: Vec<String> = records
+        .iter()
+        .rev()
+        .filter(|record| player.is_none_or(|name| record.players.iter().any(|(_, n)| n == name)))
+        .take(limit)
+        .map(|record| {
+            let scores: Vec<String> = record
+                .players
+                .iter()
+                .map(|(player, name)| {
+                    let score = record.scores.get(player).copied().unwrap_or_default();
+                    format!("{name} {score}")
+                })
+                .collect();
+            let duration_secs = record.stats.duration_secs.max(0f32).round() as u32;
+            format!(
+                "{}  {:?}  {}  ({}:{:02}, won by {})",
+                format_timestamp(record.played_at),
+                record.mode,
+                scores.join(" vs "),
+                duration_secs / 60,
+                duration_secs % 60,
+                record.winner_name(),
+            )
+        })
+        .collect();
+
+    if lines.is_empty() {
+        vec!["No matches found".to_string()]
+    } else {
+        lines
+    }
+}
+
+// Get the lines of a table ranking the leaderboard entries in order.
+fn leaderboard_lines(entries: &[LeaderboardEntry]) -> Vec<String> {
+    if entries.is_empty() {
+        return vec!["No matches played yet".to_string()];
+    }
+
+    let name_width = entries
+        .iter()
+        .map(|entry| entry.name.chars().count())
+        .max()
+        .unwrap_or_default()
+        .max(4);
+    let mut lines = vec![format!(
+        "{:>3}  {:<name_width$}  {:>5}  {:>4}  {:>6}  {:>5}",
+        "#", "Name", "Elo", "Wins", "Played", "Win %"
+    )];
+    for (rank, entry) in entries.iter().enumerate() {
+        lines.push(format!(
+            "{:>3}  {:<name_width$}  {:>5.0}  {:>4}  {:>6}  {:>4.0}%",
+            rank + 1,
+            entry.name,
+            entry.elo,
+            entry.wins,
+            entry.played,
+            entry.win_rate() * 100f32,
+        ));
+    }
+    lines
+}
+
+//
+// Loads the tournament from the file given in the arguments, or the default file. Returns the
+// path of the file along with the tournament, if there is one, so it can be saved there.
+//
+fn load_tournament_arg(args: &Args) -> Result<(PathBuf, Option<Tournament>), (ErrorKind, String)> {
+    let path = args
+        .tournament_file
+        .clone()
+        .or_else(default_tournament_path)
+        .ok_or((
+            ErrorKind::MissingRequiredArgument,
+            "no data directory to find the tournament in, use --tournament-file".to_string(),
+        ))?;
+    let tournament = load_tournament(&path).map_err(|err| {
+        (
+            ErrorKind::Io,
+            format!("failed to load tournament from {}: {err}", path.display()),
+        )
+    })?;
+    Ok((path, tournament))
+}
+
+//
+// Get the lines of a table listing each profile, with its preferences and its standing on the
+// leaderboard of the match history. Preferences which aren't set are shown as "-".
+//
+fn profile_lines(profiles: &Profiles, records: &[MatchRecord]) -> Vec<String> {
+    if profiles.0.is_empty() {
+        return vec!["No profiles yet, add one with `pong profile add NAME`".to_string()];
+    }
+
+    let name_width = profiles
+        .0
+        .iter()
+        .map(|profile| profile.name.chars().count())
+        .max()
+        .unwrap_or_default()
+        .max(4);
+    let keys_text = |keys: Option<PaddleKeys>| keys.map_or("-".to_string(), |k| k.to_string());
+    let mut lines = vec![format!(
+        "{:<name_width$}  {:<7}  {:<20}  {:<20}  {:>5}  {:>4}  {:>6}",
+        "Name", "Color", "Keys", "Forward keys", "Elo", "Wins", "Played"
+    )];
+    for profile in &profiles.0 {
+        let color = profile.paddle_color.map_or("-".to_string(), |[r, g, b]| {
+            format!("#{r:02x}{g:02x}{b:02x}")
+        });
+        let (elo, wins, played) = match profile.standing(records) {
+            Some(entry) => (format!("{:.0}", entry.elo), entry.wins, entry.played),
+            None => ("-".to_string(), 0, 0),
+        };
+        lines.push(format!(
+            "{:<name_width$}  {:<7}  {:<20}  {:<20}  {:>5}  {:>4}  {:>6}",
+            profile.name,
+            color,
+            keys_text(profile.keys),
+            keys_text(profile.forward_keys),
+            elo,
+            wins,
+            played,
+        ));
+    }
+    lines
+}
+
+// Format a time in seconds since the Unix epoch as a UTC date and time, e.g. 2024-03-01 14:05.
+fn format_timestamp(secs: u64) -> String {
+    // Convert days since the epoch to a civil date, using the proleptic Gregorian calendar
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+
+    let secs_of_day = secs % 86_400;
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        secs_of_day / 3_600,
+        (secs_of_day % 3_600) / 60,
+    )
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_args_definition() {
+        Args::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_ai_player() {
+        assert_eq!(
+            parse_ai_player("2=hard"),
+            Ok((PlayerId::Player2, AiDifficulty::Hard))
+        );
+        assert_eq!(
+            parse_ai_player("4 = Easy"),
+            Ok((PlayerId::Player4, AiDifficulty::Easy))
+        );
+        assert!(
+            parse_ai_player("hard").is_err(),
+            "Expected missing '=' to fail"
+        );
+        assert!(
+            parse_ai_player("5=hard").is_err(),
+            "Expected player 5 to fail"
+        );
+        assert!(
+            parse_ai_player("1=expert").is_err(),
+            "Expected unknown difficulty to fail"
+        );
+    }
+
+    #[test]
+    fn test_parse_player_name() {
+        assert_eq!(
+            parse_player_name("1= Ada Lovelace "),
+            Ok((PlayerId::Player1, "Ada Lovelace".to_string()))
+        );
+        assert!(
+            parse_player_name("1=").is_err(),
+            "Expected empty name to fail"
+        );
+        assert!(
+            parse_player_name("0=Ada").is_err(),
+            "Expected player 0 to fail"
+        );
+    }
+
+    #[test]
+    fn test_pong_plugin_from_args() {
+        let args = Args::try_parse_from([
+            "pong",
+            "--mode",
+            "four-player",
+            "--ai",
+            "3=medium",
+            "--fullscreen",
+            "--width",
+            "800",
+            "--seed",
+            "5",
+            "--frames",
+            "60",
+            "--stats",
+            "out",
+            "--name",
+            "4=Di",
+            "--time-limit",
+            "90",
+            "--best-of",
+            "3",
+            "--serve",
+            "toward-loser",
+            "--manual-serve",
+        ])
+        .unwrap();
+        let plugin = pong_plugin(&args).unwrap();
+
+        assert_eq!(plugin.mode, GameMode::FourPlayer);
+        assert_eq!(
+            plugin.ai_players.get(PlayerId::Player3),
+            Some(&AiDifficulty::Medium)
+        );
+        assert!(!plugin.ai_players.contains(PlayerId::Player1));
+        assert_eq!(
+            plugin.settings,
+            SettingsOverrides {
+                display_mode: Some(DisplayMode::Fullscreen),
+                window_width: Some(800),
+                win_condition: Some(WinCondition::Timed { secs: 90 }),
+                best_of: Some(3),
+                serve_rule: Some(ServeRule::TowardLoser),
+                manual_serve: Some(true),
+                ..Default::default()
+            },
+        );
+        assert_eq!(plugin.seed, Some(5));
+        assert_eq!(plugin.frame_limit, Some(60));
+        assert_eq!(plugin.stats_dir, Some(PathBuf::from("out")));
+        assert_eq!(
+            plugin.player_names.iter().collect::<Vec<_>>(),
+            vec![(PlayerId::Player4, &"Di".to_string())],
+        );
+        assert_eq!(plugin.history_path, None);
+        assert_eq!(plugin.backend, RenderBackend::Dx12);
+        assert!(plugin.replay.is_none());
+    }
+
+    #[test]
+    fn test_pong_plugin_invalid_args() {
+        // Out of range values, and conflicting options, are rejected while parsing
+        for invalid in [
+            ["pong", "--width", "100"].as_slice(),
+            &["pong", "--winning-score", "0"],
+            &["pong", "--frames", "0"],
+            &["pong", "--best-of", "0"],
+            &["pong", "--win-by-two", "--time-limit", "60"],
+            &["pong", "--replay", "a.ron", "--best-of", "3"],
+            &["pong", "--fullscreen", "--windowed"],
+            &["pong", "--record", "a.ron", "--replay", "b.ron"],
+            &["pong", "--replay", "a.ron", "--seed", "1"],
+            &["pong", "--backend", "software"],
+            &["pong", "--serve", "winner"],
+            &["pong", "--replay", "a.ron", "--manual-serve"],
+            &["pong", "--mode", "doubles", "history"],
+            &["pong", "leaderboard", "--sort", "losses"],
+        ] {
+            assert!(
+                Args::try_parse_from(invalid).is_err(),
+                "Expected {invalid:?} to be rejected",
+            );
+        }
+
+        // Combinations which depend on each other are rejected when validated
+        for (invalid, exp_kind) in [
+            (
+                ["pong", "--ai", "3=easy"].as_slice(),
+                ErrorKind::ValueValidation,
+            ),
+            (
+                &["pong", "--ai", "1=easy", "--ai", "1=hard"],
+                ErrorKind::ArgumentConflict,
+            ),
+            (
+                &["pong", "--replay", "/nonexistent/pong.ron"],
+                ErrorKind::Io,
+            ),
+            (&["pong", "--name", "3=Cy"], ErrorKind::ValueValidation),
+            (
+                &["pong", "--name", "1=Ada", "--name", "1=Bob"],
+                ErrorKind::ArgumentConflict,
+            ),
+        ] {
+            let args = Args::try_parse_from(invalid).unwrap();
+            assert_eq!(
+                pong_plugin(&args).err().map(|(kind, _)| kind),
+                Some(exp_kind),
+                "Expected {invalid:?} to fail validation",
+            );
+        }
+    }
+
+    #[test]
+    fn test_run_command_history() {
+        let path = std::env::temp_dir()
+            .join(format!("rust_pong_main_{}", std::process::id()))
+            .join("history.jsonl");
+        let path_arg = path.to_str().unwrap();
+
+        // A missing history file is simply empty
+        let args = Args::try_parse_from(["pong", "history", "--history-file", path_arg]).unwrap();
+        let command = args.command.as_ref().unwrap();
+        assert_eq!(
+            run_command(command, &args),
+            Ok(vec!["No matches found".to_string()]),
+        );
+
+        // An invalid history file is reported as an error
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "not json").unwrap();
+        let args =
+            Args::try_parse_from(["pong", "leaderboard", "--history-file", path_arg]).unwrap();
+        let command = args.command.as_ref().unwrap();
+        assert_eq!(
+            run_command(command, &args).err().map(|(kind, _)| kind),
+            Some(ErrorKind::Io),
+        );
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_run_command_profiles() {
+        let dir =
+            std::env::temp_dir().join(format!("rust_pong_main_profiles_{}", std::process::id()));
+        let profiles_file = dir.join("profiles.ron");
+        let history_file = dir.join("history.jsonl");
+        let run = |command: &[&str]| {
+            let mut argv = vec!["pong"];
+            argv.extend_from_slice(command);
+            argv.extend([
+                "--profiles-file",
+                profiles_file.to_str().unwrap(),
+                "--history-file",
+                history_file.to_str().unwrap(),
+            ]);
+            let args = Args::try_parse_from(argv).unwrap();
+            run_command(args.command.as_ref().unwrap(), &args)
+        };
+
+        assert_eq!(
+            run(&["profile", "list"]),
+            Ok(vec![
+                "No profiles yet, add one with `pong profile add NAME`".to_string()
+            ]),
+        );
+        assert_eq!(
+            run(&["profile", "add", "Ada", "--color", "#FF8800"]),
+            Ok(vec!["Added profile 'Ada'".to_string()]),
+        );
+
+        // Updating a profile only changes the preferences given
+        assert_eq!(
+            run(&["profile", "add", "Ada", "--keys", "KeyQ,KeyA"]),
+            Ok(vec!["Updated profile 'Ada'".to_string()]),
+        );
+        let profiles = load_profiles(&profiles_file).unwrap();
+        let ada = profiles.get("Ada").unwrap();
+        assert_eq!(ada.paddle_color, Some([255, 136, 0]));
+        assert_eq!(ada.keys, Some("KeyQ,KeyA".parse().unwrap()));
+        assert_eq!(ada.forward_keys, None);
+
+        assert_eq!(
+            run(&["profile", "list"]),
+            Ok(vec![
+                "Name  Color    Keys                  Forward keys            Elo  Wins  Played"
+                    .to_string(),
+                "Ada   #ff8800  KeyQ,KeyA             -                         -     0       0"
+                    .to_string(),
+            ]),
+        );
+
+        // Profiles are chosen by name, for players in the game
+        let args = Args::try_parse_from([
+            "pong",
+            "--profile",
+            "2=Ada",
+            "--profiles-file",
+            profiles_file.to_str().unwrap(),
+        ])
+        .unwrap();
+        let plugin = pong_plugin(&args).unwrap();
+        assert_eq!(
+            plugin.profiles.iter().collect::<Vec<_>>(),
+            vec![(PlayerId::Player2, ada)],
+        );
+        for (invalid, exp_kind) in [
+            (
+                ["--profile", "1=Bob"].as_slice(),
+                ErrorKind::ValueValidation,
+            ),
+            (&["--profile", "3=Ada"], ErrorKind::ValueValidation),
+            (
+                &["--profile", "1=Ada", "--profile", "2=Ada"],
+                ErrorKind::ArgumentConflict,
+            ),
+            (
+                &["--profile", "1=Ada", "--name", "1=Ann"],
+                ErrorKind::ArgumentConflict,
+            ),
+        ] {
+            let mut argv = vec!["pong", "--profiles-file", profiles_file.to_str().unwrap()];
+            argv.extend_from_slice(invalid);
+            let args = Args::try_parse_from(&argv).unwrap();
+            assert_eq!(
+                pong_plugin(&args).err().map(|(kind, _)| kind),
+                Some(exp_kind),
+                "Expected {invalid:?} to fail validation",
+            );
+        }
+
+        assert_eq!(
+            run(&["profile", "remove", "Ada"]),
+            Ok(vec!["Removed profile 'Ada'".to_string()]),
+        );
+        assert_eq!(
+            run(&["profile", "remove", "Ada"])
+                .err()
+                .map(|(kind, _)| kind),
+            Some(ErrorKind::ValueValidation),
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_run_command_tournament() {
+        let dir =
+            std::env::temp_dir().join(format!("rust_pong_main_tournament_{}", std::process::id()));
+        let profiles_file = dir.join("profiles.ron");
+        let tournament_file = dir.join("tournament.ron");
+        let file_args = [
+            "--profiles-file",
+            profiles_file.to_str().unwrap(),
+            "--tournament-file",
+            tournament_file.to_str().unwrap(),
+        ];
+        let parse = |argv: &[&str]| {
+            let mut argv = argv.to_vec();
+            argv.extend(file_args);
+            Args::try_parse_from(argv).unwrap()
+        };
+        let run = |command: &[&str]| {
+            let mut argv = vec!["pong"];
+            argv.extend_from_slice(command);
+            let args = parse(&argv);
+            run_command(args.command.as_ref().unwrap(), &args)
+        };
+        let profiles = Profiles(["Ada", "Bob", "Cy"].map(PlayerProfile::new).to_vec());
+        save_profiles_to(&profiles_file, &profiles).unwrap();
+
+        // There's no tournament to show or play until one is started
+        assert_eq!(
+            run(&["tournament", "show"]),
+            Ok(vec![
+                "No tournament yet, start one with `pong tournament new`".to_string()
+            ]),
+        );
+        assert_eq!(
+            pong_plugin(&parse(&["pong", "--tournament"]))
+                .err()
+                .map(|(kind, _)| kind),
+            Some(ErrorKind::ValueValidation),
+        );
+
+        // Every entrant must have a profile
+        assert_eq!(
+            run(&["tournament", "new", "Ada", "Di"])
+                .err()
+                .map(|(kind, _)| kind),
+            Some(ErrorKind::ValueValidation),
+        );
+        assert_eq!(
+            run(&["tournament", "new", "Ada", "Bob", "Cy"]),
+            Ok([
+                "Started a new tournament",
+                "Semifinals",
+                "Ada (bye)",
+                "Bob vs Cy",
+                "Final",
+                "Ada vs ?",
+                "Next match: Bob vs Cy",
+            ]
+            .map(String::from)
+            .to_vec()),
+        );
+
+        // An unfinished tournament is only replaced when asked to
+        assert_eq!(
+            run(&["tournament", "new", "Ada", "Bob", "--format", "round-robin"])
+                .err()
+                .map(|(kind, _)| kind),
+            Some(ErrorKind::ArgumentConflict),
+        );
+        assert!(
+            run(&[
+                "tournament",
+                "new",
+                "Ada",
+                "Bob",
+                "--format",
+                "round-robin",
+                "--replace"
+            ])
+            .is_ok()
+        );
+        assert_eq!(
+            run(&["tournament", "show"]).unwrap().last().unwrap(),
+            "Next match: Ada vs Bob",
+        );
+
+        // The tournament is played as two player games
+        let plugin = pong_plugin(&parse(&["pong", "--tournament", "--ai", "2=easy"])).unwrap();
+        assert_eq!(plugin.tournament_path, Some(tournament_file.clone()));
+        assert!(
+            Args::try_parse_from(["pong", "--tournament", "--mode", "doubles"]).is_err(),
+            "Expected a tournament not to be played in another mode",
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("ff8800"), Ok([255, 136, 0]));
+        assert_eq!(parse_color("#00A0fF"), Ok([0, 160, 255]));
+        for invalid in ["fff", "ff88000", "gg8800", "#ff88"] {
+            assert!(
+                parse_color(invalid).is_err(),
+                "Expected '{invalid}' to fail"
+            );
+        }
+    }
+
+    #[test]
+    fn test_history_lines() {
+        let records = [
+            test_record("Ada", "Bob", PlayerId::Player1, 1_700_000_000),
+            test_record("Cy", "Ada", PlayerId::Player1, 1_700_000_100),
+            test_record("Bob", "Cy", PlayerId::Player2, 1_700_000_200),
+        ];
+
+        assert_eq!(
+            history_lines(&records, 2, None),
+            vec![
+                "2023-11-14 22:16  TwoPlayer  Bob 3 vs Cy 5  (1:05, won by Cy)",
+                "2023-11-14 22:15  TwoPlayer  Cy 5 vs Ada 3  (1:05, won by Cy)",
+            ],
+        );
+        assert_eq!(
+            history_lines(&records, 10, Some("Ada")).len(),
+            2,
+            "Expected only the matches Ada played in",
+        );
+        assert_eq!(
+            history_lines(&records, 10, Some("Di")),
+            vec!["No matches found"],
+        );
+    }
+
+    #[test]
+    fn test_leaderboard_lines() {
+        assert_eq!(leaderboard_lines(&[]), vec!["No matches played yet"]);
+
+        let records = [test_record("Ada", "Bob", PlayerId::Player1, 0)];
+        assert_eq!(
+            leaderboard_lines(&leaderboard(&records, LeaderboardSort::Elo)),
+            vec![
+                "  #  Name    Elo  Wins  Played  Win %",
+                "  1  Ada    1516     1       1   100%",
+                "  2  Bob    1484     0       1     0%",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+        assert_eq!(format_timestamp(1_709_301_900), "2024-03-01 14:05");
+    }
+
+    // --- Helper Functions ---
+
+    // Create the record of a two player match, won 5 to 3 and lasting 65 seconds.
+    fn test_record(p1: &str, p2: &str, winner: PlayerId, played_at: u64) -> MatchRecord {
+        let mut players = PerPlayer::new();
+        players.insert(PlayerId::Player1, p1.to_string());
+        players.insert(PlayerId::Player2, p2.to_string());
+        let mut scores = PerPlayer::filled(GameMode::TwoPlayer.players(), 3);
+        scores.insert(winner, 5);
+        let mut stats = pong::MatchStats::new(GameMode::TwoPlayer);
+        stats.duration_secs = 65.2;
+
+        MatchRecord {
+            played_at,
+            mode: GameMode::TwoPlayer,
+            seed: 0,
+            players,
+            scores,
+            winner,
+            stats,
+        }
+    }
 }
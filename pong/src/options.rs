@@ -0,0 +1,783 @@
+//!
+//! The options module contains the in-game options screen, which displays the current game
+//! Settings and allows them to be changed (including rebinding paddle keys) at runtime.
+//!
+
+// -------------------------------------------------------------------------------------------------
+// Included Symbols
+
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+use bevy_dyn_fontsize::{DynamicFontSize, DynamicFontsizePlugin};
+
+use crate::common::*;
+use crate::paddle::PaddlePosition;
+use crate::settings::{DisplayMode, KeyBindings, ServeRule, Settings, WinCondition};
+
+// -------------------------------------------------------------------------------------------------
+// Constants
+
+const TOGGLE_OPTIONS_KEY: KeyCode = KeyCode::KeyO;
+const SELECT_PREV_KEY: KeyCode = KeyCode::ArrowUp;
+const SELECT_NEXT_KEY: KeyCode = KeyCode::ArrowDown;
+const DECREASE_KEY: KeyCode = KeyCode::ArrowLeft;
+const INCREASE_KEY: KeyCode = KeyCode::ArrowRight;
+const REBIND_KEY: KeyCode = KeyCode::Enter;
+
+const WINDOW_SIZE_PRESETS: [(u32, u32); 4] = [(1280, 720), (1600, 900), (1920, 1080), (2560, 1440)];
+const VOLUME_STEP: f32 = 0.1;
+const MIN_WINNING_SCORE: u8 = 1;
+const MAX_WINNING_SCORE: u8 = 99;
+const WIN_CONDITION_PRESETS: [WinCondition; 5] = [
+    WinCondition::FirstTo,
+    WinCondition::WinByTwo,
+    WinCondition::Timed { secs: 60 },
+    WinCondition::Timed { secs: 180 },
+    WinCondition::Timed { secs: 300 },
+];
+const BEST_OF_STEP: u8 = 2;
+const MIN_BEST_OF: u8 = 1;
+const MAX_BEST_OF: u8 = 9;
+const SERVE_RULES: [ServeRule; 3] = [
+    ServeRule::Random,
+    ServeRule::Alternate,
+    ServeRule::TowardLoser,
+];
+
+const BACKGROUND_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.9);
+const ROW_FONT_SIZE_AS_SCREEN_PCT: f32 = 0.028;
+const ROW_SPACING_AS_SCREEN_PCT: f32 = 0.03;
+const ROW_HEIGHT: f32 = ROW_FONT_SIZE_AS_SCREEN_PCT * ARENA_HEIGHT;
+const ROW_SPACING: f32 = ROW_SPACING_AS_SCREEN_PCT * ARENA_HEIGHT;
+const ROWS_LEFT_X: f32 = -ARENA_WIDTH * 0.3;
+const TITLE_Y: f32 = ARENA_HEIGHT * 0.46;
+const TITLE_TEXT: &str = "Options (O to close, arrows to change, Enter to rebind)";
+
+// -------------------------------------------------------------------------------------------------
+// Public API
+
+///
+/// The OptionsPlugin adds an options screen, which is hidden until the options key is
+/// pressed. While it is open, the game is paused, and the arrow keys select and change
+/// each of the game Settings. Selecting a key binding and pressing Enter rebinds it to
+/// the next key pressed.
+///
+/// This plugin will only work properly if the app contains a single Camera2d entity.
+///
+pub struct OptionsPlugin;
+
+impl Plugin for OptionsPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<DynamicFontsizePlugin>() {
+            app.add_plugins(DynamicFontsizePlugin::default());
+        }
+
+        app.init_resource::<Settings>()
+            .init_resource::<OptionsScreen>()
+            .add_systems(
+                Startup,
+                setup_options_screen.in_set(Systems::SetupAfterCamera),
+            )
+            .add_systems(
+                Update,
+                (
+                    toggle_options_screen,
+                    rebind_key,
+                    navigate_options,
+                    update_options_text,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// These SystemSets are used to control any system ordering dependencies on this plugin
+#[derive(SystemSet, Debug, Clone, Hash, PartialEq, Eq)]
+pub enum Systems {
+    ///
+    /// Startup systems which spawn the options screen entities. These require the camera
+    /// to exist, so the camera setup should be ordered before this set.
+    ///
+    SetupAfterCamera,
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Resources
+
+// Tracks the state of the options screen
+#[derive(Resource, Default, Debug)]
+struct OptionsScreen {
+    // Whether the options screen is currently shown (and the game paused)
+    open: bool,
+    // Index of the currently selected row in option_items
+    selected: usize,
+    // Whether the next key pressed should be bound to the selected key binding row
+    rebinding: bool,
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Components
+
+// Component for the root entity of the options screen, which is its background.
+#[derive(Component)]
+struct OptionsRoot;
+
+// Component for the text entity displaying the given row of option_items.
+#[derive(Component)]
+struct OptionsRow(usize);
+
+// -------------------------------------------------------------------------------------------------
+// Private Types
+
+// Each of the rows on the options screen, in display order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OptionItem {
+    DisplayMode,
+    Vsync,
+    WindowSize,
+    Volume,
+    CountdownTicks,
+    BallTrail,
+    Particles,
+    CameraEffects,
+    WinningScore,
+    WinCondition,
+    BestOf,
+    ServeRule,
+    ManualServe,
+    // Key binding for the given paddle. The bool is true for the positive direction key.
+    Key(PlayerId, PaddlePosition, bool),
+    // Key binding the given player launches manual serves with.
+    ServeKey(PlayerId),
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Systems
+
+//
+// Startup system to spawn the options screen. It is a background covering the arena, with
+// a title and a text row for each option as children. It starts hidden.
+//
+fn setup_options_screen(
+    mut commands: Commands,
+    camera_entity: Single<Entity, With<Camera2d>>,
+    settings: Res<Settings>,
+) {
+    let camera_entity = camera_entity.entity();
+    commands
+        .spawn((
+            OptionsRoot,
+            Sprite {
+                color: BACKGROUND_COLOR,
+                custom_size: Some(Vec2::new(ARENA_WIDTH, ARENA_HEIGHT)),
+                ..default()
+            },
+            Transform::from_xyz(0f32, 0f32, Z_OVERLAY),
+            Visibility::Hidden,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                DynamicFontSize {
+                    height_in_world: ROW_HEIGHT,
+                    render_camera: camera_entity,
+                    max_width_in_world: None,
+                },
+                Text2d::new(TITLE_TEXT),
+                Anchor::CENTER,
+                Transform::from_xyz(0f32, TITLE_Y, 1f32),
+            ));
+
+            for (idx, item) in option_items().into_iter().enumerate() {
+                let y = TITLE_Y - (ROW_SPACING * (idx + 2) as f32);
+                parent.spawn((
+                    OptionsRow(idx),
+                    DynamicFontSize {
+                        height_in_world: ROW_HEIGHT,
+                        render_camera: camera_entity,
+                        max_width_in_world: None,
+                    },
+                    Text2d::new(row_text(item, &settings, false, false)),
+                    Anchor::CENTER_LEFT,
+                    Transform::from_xyz(ROWS_LEFT_X, y, 1f32),
+                ));
+            }
+        });
+}
+
+//
+// Opens or closes the options screen when the toggle key is pressed. The game is paused
+// (via virtual time) while the options screen is open.
+//
+fn toggle_options_screen(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut screen: ResMut<OptionsScreen>,
+    mut time: ResMut<Time<Virtual>>,
+    mut root_vis: Query<&mut Visibility, With<OptionsRoot>>,
+) {
+    if screen.rebinding || !keys.just_pressed(TOGGLE_OPTIONS_KEY) {
+        return;
+    }
+
+    screen.open = !screen.open;
+    if screen.open {
+        time.pause();
+    } else {
+        time.unpause();
+    }
+
+    for mut vis in root_vis.iter_mut() {
+        *vis = if screen.open {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+// While waiting for a key to rebind, binds the next key pressed to the selected row.
+fn rebind_key(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut screen: ResMut<OptionsScreen>,
+    mut settings: ResMut<Settings>,
+) {
+    if !screen.rebinding {
+        return;
+    }
+
+    // Unidentified keys can't be saved in the settings file, so they are ignored
+    let Some(&key) = keys
+        .get_just_pressed()
+        .find(|key| !matches!(key, KeyCode::Unidentified(_)))
+    else {
+        return;
+    };
+
+    match option_items().get(screen.selected).copied() {
+        Some(OptionItem::Key(player, position, positive)) => {
+            let paddle_keys = settings.key_bindings.paddle_mut(player, position);
+            if positive {
+                paddle_keys.positive = key;
+            } else {
+                paddle_keys.negative = key;
+            }
+        }
+        Some(OptionItem::ServeKey(player)) => {
+            *settings.key_bindings.serve_mut(player) = key;
+        }
+        _ => {}
+    }
+    screen.rebinding = false;
+}
+
+//
+// While the options screen is open, moves the selection between rows, changes the value of
+// the selected option, or starts rebinding the selected key binding.
+//
+fn navigate_options(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut screen: ResMut<OptionsScreen>,
+    mut settings: ResMut<Settings>,
+) {
+    if !screen.open || screen.rebinding {
+        return;
+    }
+
+    let num_items = option_items().len();
+    if keys.just_pressed(SELECT_PREV_KEY) {
+        screen.selected = (screen.selected + num_items - 1) % num_items;
+    }
+    if keys.just_pressed(SELECT_NEXT_KEY) {
+        screen.selected = (screen.selected + 1) % num_items;
+    }
+
+    let item = option_items()[screen.selected];
+    if keys.just_pressed(DECREASE_KEY) {
+        adjust_option(item, &mut settings, false);
+    }
+    if keys.just_pressed(INCREASE_KEY) {
+        adjust_option(item, &mut settings, true);
+    }
+    if keys.just_pressed(REBIND_KEY)
+        && matches!(item, OptionItem::Key(..) | OptionItem::ServeKey(_))
+    {
+        screen.rebinding = true;
+    }
+}
+
+// Updates the text of each row whenever the settings or options screen state change.
+fn update_options_text(
+    screen: Res<OptionsScreen>,
+    settings: Res<Settings>,
+    rows: Query<(&mut Text2d, &OptionsRow)>,
+) {
+    if !screen.is_changed() && !settings.is_changed() {
+        return;
+    }
+
+    let items = option_items();
+    for (mut text, row) in rows {
+        if let Some(&item) = items.get(row.0) {
+            let selected = row.0 == screen.selected;
+            text.0 = row_text(item, &settings, selected, selected && screen.rebinding);
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Functions
+
+// Get every row of the options screen, in display order.
+fn option_items() -> Vec<OptionItem> {
+    let mut items = vec![
+        OptionItem::DisplayMode,
+        OptionItem::Vsync,
+        OptionItem::WindowSize,
+        OptionItem::Volume,
+        OptionItem::CountdownTicks,
+        OptionItem::BallTrail,
+        OptionItem::Particles,
+        OptionItem::CameraEffects,
+        OptionItem::WinningScore,
+        OptionItem::WinCondition,
+        OptionItem::BestOf,
+        OptionItem::ServeRule,
+        OptionItem::ManualServe,
+    ];
+    for (player, position) in KeyBindings::PADDLES {
+        items.push(OptionItem::Key(player, position, true));
+        items.push(OptionItem::Key(player, position, false));
+    }
+    for player in PlayerId::ALL {
+        items.push(OptionItem::ServeKey(player));
+    }
+    items
+}
+
+//
+// Change the value of the given option to the next (increase) or previous value. Key
+// bindings are unaffected, as they are changed by rebinding instead.
+//
+fn adjust_option(item: OptionItem, settings: &mut Settings, increase: bool) {
+    match item {
+        OptionItem::DisplayMode => {
+            settings.display_mode = match settings.display_mode {
+                DisplayMode::Windowed => DisplayMode::Fullscreen,
+                DisplayMode::Fullscreen => DisplayMode::Windowed,
+            };
+        }
+        OptionItem::Vsync => settings.vsync = !settings.vsync,
+        OptionItem::WindowSize => {
+            let size = (settings.window_width, settings.window_height);
+            let num_presets = WINDOW_SIZE_PRESETS.len();
+            let idx = match WINDOW_SIZE_PRESETS
+                .iter()
+                .position(|&preset| preset == size)
+            {
+                Some(idx) if increase => (idx + 1) % num_presets,
+                Some(idx) => (idx + num_presets - 1) % num_presets,
+                None => 0,
+            };
+            (settings.window_width, settings.window_height) = WINDOW_SIZE_PRESETS[idx];
+        }
+        OptionItem::Volume => {
+            let step = if increase { VOLUME_STEP } else { -VOLUME_STEP };
+            let steps = ((settings.volume + step) / VOLUME_STEP).round();
+            settings.volume = (steps * VOLUME_STEP).clamp(0f32, 1f32);
+        }
+        OptionItem::CountdownTicks => settings.countdown_ticks = !settings.countdown_ticks,
+        OptionItem::BallTrail => settings.ball_trail = !settings.ball_trail,
+        OptionItem::Particles => {
+            settings.particles.enabled = !settings.particles.enabled;
+        }
+        OptionItem::CameraEffects => settings.camera_effects = !settings.camera_effects,
+        OptionItem::WinningScore => {
+            let score = &mut settings.gameplay.winning_score;
+            *score = if increase {
+                score.saturating_add(1).min(MAX_WINNING_SCORE)
+            } else {
+                score.saturating_sub(1).max(MIN_WINNING_SCORE)
+            };
+        }
+        OptionItem::WinCondition => {
+            let current = settings.gameplay.win_condition;
+            let num_presets = WIN_CONDITION_PRESETS.len();
+            let idx = match WIN_CONDITION_PRESETS
+                .iter()
+                .position(|&preset| preset == current)
+            {
+                Some(idx) if increase => (idx + 1) % num_presets,
+                Some(idx) => (idx + num_presets - 1) % num_presets,
+                None => 0,
+            };
+            settings.gameplay.win_condition = WIN_CONDITION_PRESETS[idx];
+        }
+        OptionItem::BestOf => {
+            let best_of = &mut settings.gameplay.best_of;
+            *best_of = if increase {
+                best_of.saturating_add(BEST_OF_STEP).min(MAX_BEST_OF)
+            } else {
+                best_of.saturating_sub(BEST_OF_STEP).max(MIN_BEST_OF)
+            };
+        }
+        OptionItem::ServeRule => {
+            let current = settings.gameplay.serve_rule;
+            let num_rules = SERVE_RULES.len();
+            let idx = match SERVE_RULES.iter().position(|&rule| rule == current) {
+                Some(idx) if increase => (idx + 1) % num_rules,
+                Some(idx) => (idx + num_rules - 1) % num_rules,
+                None => 0,
+            };
+            settings.gameplay.serve_rule = SERVE_RULES[idx];
+        }
+        OptionItem::ManualServe => {
+            settings.gameplay.manual_serve = !settings.gameplay.manual_serve;
+        }
+        OptionItem::Key(..) | OptionItem::ServeKey(_) => {}
+    }
+}
+
+// Get the text displayed for an option row, given its current value.
+fn row_text(item: OptionItem, settings: &Settings, selected: bool, rebinding: bool) -> String {
+    let value = match item {
+        OptionItem::Key(..) | OptionItem::ServeKey(_) if rebinding => "Press a key...".to_string(),
+        OptionItem::DisplayMode => format!("{:?}", settings.display_mode),
+        OptionItem::Vsync => on_off(settings.vsync),
+        OptionItem::WindowSize => format!("{}x{}", settings.window_width, settings.window_height),
+        OptionItem::Volume => format!("{:.0}%", settings.volume * 100f32),
+        OptionItem::CountdownTicks => on_off(settings.countdown_ticks),
+        OptionItem::BallTrail => on_off(settings.ball_trail),
+        OptionItem::Particles => on_off(settings.particles.enabled),
+        OptionItem::CameraEffects => on_off(settings.camera_effects),
+        OptionItem::WinningScore => settings.gameplay.winning_score.to_string(),
+        OptionItem::WinCondition => settings.gameplay.win_condition.to_string(),
+        OptionItem::BestOf => match settings.gameplay.best_of {
+            0 | 1 => "Single Game".to_string(),
+            best_of => format!("Best of {best_of}"),
+        },
+        OptionItem::Key(player, position, positive) => {
+            let keys = settings.key_bindings.paddle(player, position);
+            let key = if positive {
+                keys.positive
+            } else {
+                keys.negative
+            };
+            format!("{key:?}")
+        }
+        OptionItem::ServeRule => settings.gameplay.serve_rule.to_string(),
+        OptionItem::ManualServe => on_off(settings.gameplay.manual_serve),
+        OptionItem::ServeKey(player) => format!("{:?}", settings.key_bindings.serve(player)),
+    };
+    let marker = if selected { "> " } else { "  " };
+
+    format!("{marker}{}: {value}", row_label(item))
+}
+
+// Get the text displayed for the value of an option which is turned on or off.
+fn on_off(value: bool) -> String {
+    (if value { "On" } else { "Off" }).to_string()
+}
+
+// Get the label naming an option row.
+fn row_label(item: OptionItem) -> String {
+    match item {
+        OptionItem::DisplayMode => "Display Mode".to_string(),
+        OptionItem::Vsync => "Vsync".to_string(),
+        OptionItem::WindowSize => "Window Size".to_string(),
+        OptionItem::Volume => "Volume".to_string(),
+        OptionItem::CountdownTicks => "Countdown Ticks".to_string(),
+        OptionItem::BallTrail => "Ball Trail".to_string(),
+        OptionItem::Particles => "Particles".to_string(),
+        OptionItem::CameraEffects => "Screen Shake".to_string(),
+        OptionItem::WinningScore => "Winning Score".to_string(),
+        OptionItem::WinCondition => "Win Condition".to_string(),
+        OptionItem::BestOf => "Match Length".to_string(),
+        OptionItem::ServeRule => "Serve To".to_string(),
+        OptionItem::ManualServe => "Manual Serve".to_string(),
+        OptionItem::ServeKey(player) => format!("Player {} Serve", player.index() + 1),
+        OptionItem::Key(player, position, positive) => {
+            let dir = match (player.side().is_vertical(), positive) {
+                (true, true) => "Up",
+                (true, false) => "Down",
+                (false, true) => "Right",
+                (false, false) => "Left",
+            };
+            let position = match position {
+                PaddlePosition::Back => "",
+                PaddlePosition::Forward => " Forward",
+            };
+            format!("Player {}{position} {dir}", player.index() + 1)
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::schedule::AnonymousSet;
+    use bevy_test_helpers::prelude::*;
+
+    #[test]
+    fn test_plugin_sys_added_setup() {
+        validate_sys_in_plugin(
+            OptionsPlugin,
+            Startup,
+            setup_options_screen,
+            Some(Systems::SetupAfterCamera),
+        );
+    }
+
+    #[test]
+    fn test_plugin_sys_added_update() {
+        validate_sys_in_plugin(
+            OptionsPlugin,
+            Update,
+            toggle_options_screen,
+            None::<AnonymousSet>,
+        );
+        validate_sys_in_plugin(OptionsPlugin, Update, rebind_key, None::<AnonymousSet>);
+        validate_sys_in_plugin(
+            OptionsPlugin,
+            Update,
+            navigate_options,
+            None::<AnonymousSet>,
+        );
+        validate_sys_in_plugin(
+            OptionsPlugin,
+            Update,
+            update_options_text,
+            None::<AnonymousSet>,
+        );
+    }
+
+    #[test]
+    fn test_setup_options_screen() {
+        let mut world = setup_test_world();
+
+        let mut root_query = world.query_filtered::<&Visibility, With<OptionsRoot>>();
+        assert_eq!(
+            *root_query.single(&world).unwrap(),
+            Visibility::Hidden,
+            "Expected options screen to start hidden",
+        );
+
+        let mut row_query = world.query::<(&Text2d, &OptionsRow)>();
+        assert_eq!(
+            row_query.iter(&world).len(),
+            option_items().len(),
+            "Expected a text row for each option",
+        );
+        for (text, row) in row_query.iter(&world) {
+            assert_eq!(
+                text.0,
+                row_text(option_items()[row.0], &Settings::default(), false, false),
+                "Expected row {} to display its default setting",
+                row.0,
+            );
+        }
+    }
+
+    #[test]
+    fn test_toggle_options_screen_pauses() {
+        let mut world = setup_test_world();
+        press_keys(&mut world, &[TOGGLE_OPTIONS_KEY]);
+        run_update_systems(&mut world);
+
+        assert!(world.resource::<OptionsScreen>().open);
+        assert!(
+            world.resource::<Time<Virtual>>().is_paused(),
+            "Expected game to be paused while options are open",
+        );
+        let mut root_query = world.query_filtered::<&Visibility, With<OptionsRoot>>();
+        assert_eq!(*root_query.single(&world).unwrap(), Visibility::Visible);
+
+        press_keys(&mut world, &[TOGGLE_OPTIONS_KEY]);
+        run_update_systems(&mut world);
+        assert!(!world.resource::<OptionsScreen>().open);
+        assert!(
+            !world.resource::<Time<Virtual>>().is_paused(),
+            "Expected game to resume when options are closed",
+        );
+        assert_eq!(*root_query.single(&world).unwrap(), Visibility::Hidden);
+    }
+
+    #[test]
+    fn test_navigate_options_changes_settings() {
+        let mut world = setup_test_world();
+        world.resource_mut::<OptionsScreen>().open = true;
+
+        // Select the volume row and turn it down
+        press_keys(&mut world, &[SELECT_NEXT_KEY]);
+        run_update_systems(&mut world);
+        press_keys(&mut world, &[SELECT_NEXT_KEY]);
+        run_update_systems(&mut world);
+        press_keys(&mut world, &[SELECT_NEXT_KEY]);
+        run_update_systems(&mut world);
+        press_keys(&mut world, &[DECREASE_KEY]);
+        run_update_systems(&mut world);
+
+        assert_eq!(world.resource::<OptionsScreen>().selected, 3);
+        assert!(
+            (world.resource::<Settings>().volume - 0.9).abs() < 0.00001,
+            "Expected volume to be decreased by one step",
+        );
+
+        // Selected row should show the new value
+        let mut row_query = world.query::<(&Text2d, &OptionsRow)>();
+        let (text, _) = row_query.iter(&world).find(|(_, row)| row.0 == 3).unwrap();
+        assert_eq!(text.0, "> Volume: 90%");
+
+        // Wrap around to the last row, and check that closed options don't respond
+        world.resource_mut::<OptionsScreen>().selected = 0;
+        press_keys(&mut world, &[SELECT_PREV_KEY]);
+        run_update_systems(&mut world);
+        assert_eq!(
+            world.resource::<OptionsScreen>().selected,
+            option_items().len() - 1,
+        );
+        world.resource_mut::<OptionsScreen>().open = false;
+        press_keys(&mut world, &[SELECT_PREV_KEY]);
+        run_update_systems(&mut world);
+        assert_eq!(
+            world.resource::<OptionsScreen>().selected,
+            option_items().len() - 1,
+            "Expected closed options screen to ignore navigation",
+        );
+    }
+
+    #[test]
+    fn test_rebind_key() {
+        let mut world = setup_test_world();
+        let row = option_items()
+            .iter()
+            .position(|&item| item == OptionItem::Key(Player2, PaddlePosition::Back, false))
+            .unwrap();
+        {
+            let mut screen = world.resource_mut::<OptionsScreen>();
+            screen.open = true;
+            screen.selected = row;
+        }
+
+        press_keys(&mut world, &[REBIND_KEY]);
+        run_update_systems(&mut world);
+        assert!(world.resource::<OptionsScreen>().rebinding);
+
+        press_keys(&mut world, &[KeyCode::KeyM]);
+        run_update_systems(&mut world);
+        assert!(!world.resource::<OptionsScreen>().rebinding);
+        assert_eq!(
+            world.resource::<Settings>().key_bindings.player2.negative,
+            KeyCode::KeyM,
+            "Expected player 2 down key to be rebound",
+        );
+
+        // Serve keys are rebound in the same way
+        let row = option_items()
+            .iter()
+            .position(|&item| item == OptionItem::ServeKey(Player3))
+            .unwrap();
+        world.resource_mut::<OptionsScreen>().selected = row;
+        press_keys(&mut world, &[REBIND_KEY]);
+        run_update_systems(&mut world);
+        press_keys(&mut world, &[KeyCode::KeyN]);
+        run_update_systems(&mut world);
+        assert_eq!(
+            world.resource::<Settings>().key_bindings.serve(Player3),
+            KeyCode::KeyN,
+            "Expected player 3 serve key to be rebound",
+        );
+    }
+
+    #[test]
+    fn test_adjust_option_limits() {
+        let mut settings = Settings {
+            volume: 1.0,
+            ..default()
+        };
+        adjust_option(OptionItem::Volume, &mut settings, true);
+        assert_eq!(settings.volume, 1.0, "Expected volume capped at 100%");
+
+        settings.gameplay.winning_score = MIN_WINNING_SCORE;
+        adjust_option(OptionItem::WinningScore, &mut settings, false);
+        assert_eq!(settings.gameplay.winning_score, MIN_WINNING_SCORE);
+
+        settings.gameplay.best_of = MAX_BEST_OF;
+        adjust_option(OptionItem::BestOf, &mut settings, true);
+        assert_eq!(settings.gameplay.best_of, MAX_BEST_OF);
+        adjust_option(OptionItem::BestOf, &mut settings, false);
+        assert_eq!(settings.gameplay.best_of, MAX_BEST_OF - BEST_OF_STEP);
+
+        // Win conditions cycle through the presets, in either direction
+        adjust_option(OptionItem::WinCondition, &mut settings, false);
+        assert_eq!(
+            settings.gameplay.win_condition,
+            WIN_CONDITION_PRESETS[WIN_CONDITION_PRESETS.len() - 1],
+        );
+        adjust_option(OptionItem::WinCondition, &mut settings, true);
+        assert_eq!(settings.gameplay.win_condition, WinCondition::FirstTo);
+        assert_eq!(
+            row_text(OptionItem::WinCondition, &settings, false, false),
+            "  Win Condition: First to Score",
+        );
+
+        // Serve rules cycle, and manual serves toggle
+        adjust_option(OptionItem::ServeRule, &mut settings, false);
+        assert_eq!(settings.gameplay.serve_rule, ServeRule::TowardLoser);
+        adjust_option(OptionItem::ServeRule, &mut settings, true);
+        assert_eq!(settings.gameplay.serve_rule, ServeRule::Random);
+        adjust_option(OptionItem::ManualServe, &mut settings, true);
+        assert_eq!(
+            row_text(OptionItem::ManualServe, &settings, false, false),
+            "  Manual Serve: On",
+        );
+
+        settings.window_width = 123;
+        adjust_option(OptionItem::WindowSize, &mut settings, true);
+        assert_eq!(
+            (settings.window_width, settings.window_height),
+            WINDOW_SIZE_PRESETS[0],
+            "Expected custom window size to snap to first preset",
+        );
+        adjust_option(OptionItem::WindowSize, &mut settings, false);
+        assert_eq!(
+            (settings.window_width, settings.window_height),
+            WINDOW_SIZE_PRESETS[WINDOW_SIZE_PRESETS.len() - 1],
+        );
+    }
+
+    // --- Helper Functions ---
+
+    // Create a world with the options screen set up, ready for update systems to be run.
+    fn setup_test_world() -> World {
+        let mut world = World::default();
+        world.init_resource::<Settings>();
+        world.init_resource::<OptionsScreen>();
+        world.init_resource::<Time<Virtual>>();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.spawn(Camera2d);
+
+        let setup_sys = world.register_system(setup_options_screen);
+        world.run_system(setup_sys).unwrap();
+        world
+    }
+
+    // Set the given keys as just pressed (and all others released) for the next update.
+    fn press_keys(world: &mut World, keys: &[KeyCode]) {
+        let mut input = world.resource_mut::<ButtonInput<KeyCode>>();
+        input.reset_all();
+        for &key in keys {
+            input.press(key);
+        }
+    }
+
+    // Run each of the options screen update systems once, in order.
+    fn run_update_systems(world: &mut World) {
+        world.run_system_cached(toggle_options_screen).unwrap();
+        world.run_system_cached(rebind_key).unwrap();
+        world.run_system_cached(navigate_options).unwrap();
+        world.run_system_cached(update_options_text).unwrap();
+    }
+}
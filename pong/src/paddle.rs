@@ -1,5 +1,5 @@
 //!
-//! Contains code to setup and manage the paddles on either side of the pong screen,
+//! Contains code to setup and manage the paddles on the edges of the pong screen,
 //! and allow other code to query for paddle positional data for use in collision logic.
 //!
 
@@ -11,6 +11,7 @@ use bevy::prelude::*;
 use bevy::sprite::Anchor;
 
 use crate::common::*;
+use crate::settings::{PaddleKeyOverrides, Settings};
 
 // -------------------------------------------------------------------------------------------------
 // Constants
@@ -21,13 +22,21 @@ const PADDLE_MOVE_SPEED: f32 = ARENA_HEIGHT * 1.5;
 const PADDLE_HEIGHT: f32 = PADDLE_HEIGHT_AS_SCREEN_PCT * ARENA_HEIGHT;
 const PADDLE_WIDTH: f32 = PADDLE_HEIGHT * PADDLE_ASPECT_RATIO;
 const PADDLE_CLAMP_Y: f32 = (ARENA_HEIGHT / 2f32) - (PADDLE_HEIGHT / 2f32);
+const PADDLE_CLAMP_X: f32 = (ARENA_WIDTH / 2f32) - PADDLE_WIDTH - (PADDLE_HEIGHT / 2f32);
+const PADDLE_FORWARD_OFFSET: f32 = ARENA_WIDTH * 0.2;
+
+const PADDLE_COLOR: Color = Color::WHITE;
+const WALLED_PADDLE_COLOR: Color = Color::srgb_u8(100, 100, 100);
 
 // -------------------------------------------------------------------------------------------------
 // Public API
 
 ///
-/// The PaddlePlugin adds 2 paddles to the screen, one on each side.
-/// It also handles user input to move the paddles up and down using W/S and ^/v keys.
+/// The PaddlePlugin adds paddles to the screen for each player in the current GameMode.
+/// In doubles mode, each side has both a back and a forward paddle, otherwise there is just
+/// a single paddle per player, drawn in that player's color from the PaddleColors resource.
+/// It also handles user input to move each paddle using its own
+/// movement keys, as configured in the key bindings of the game Settings.
 /// There is also a read-only API exposed to query positional data about the paddles
 /// for use in collision computation.
 ///
@@ -35,10 +44,21 @@ pub struct PaddlePlugin;
 
 impl Plugin for PaddlePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup_paddles.in_set(Systems::PaddleCreation))
+        app.init_resource::<GameMode>()
+            .init_resource::<Settings>()
+            .init_resource::<PaddleKeyOverrides>()
+            .init_resource::<PaddleColors>()
+            .add_message::<MovePaddle>()
+            .add_message::<WallOffPaddle>()
+            .add_message::<RestorePaddles>()
+            .add_systems(Startup, setup_paddles.in_set(Systems::PaddleCreation))
             .add_systems(
                 Update,
-                handle_input_move_paddles.in_set(Systems::HandleInput),
+                (
+                    handle_input_move_paddles.in_set(Systems::HandleInput),
+                    handle_wall_off_paddle.in_set(Systems::WallOffPaddleRcvr),
+                    handle_restore_paddles.in_set(Systems::RestorePaddlesRcvr),
+                ),
             );
     }
 }
@@ -50,10 +70,24 @@ pub enum Systems {
     PaddleCreation,
 
     ///
-    /// Implements all logic to retrieve user input events and update
-    /// the paddle hitbox and latest movement data accordingly. Must be in Update.
+    /// Implements all logic to retrieve user input events (and MovePaddle messages) and
+    /// update the paddle hitbox and latest movement data accordingly. Must be in Update.
+    /// To react to MovePaddle messages in the same frame, the sender should be ordered
+    /// before this system set.
     ///
     HandleInput,
+
+    ///
+    /// Update systems which react to WallOffPaddle messages. To react to these messages in the
+    /// same frame, the sender should be ordered before this system set.
+    ///
+    WallOffPaddleRcvr,
+
+    ///
+    /// Update systems which react to RestorePaddles messages. To react to these messages in the
+    /// same frame, the sender should be ordered before this system set.
+    ///
+    RestorePaddlesRcvr,
 }
 
 ///
@@ -61,26 +95,99 @@ pub enum Systems {
 /// Intended for use by other code modules to help avoid query component conflicts,
 /// by using Without<Paddle> in Query filters as needed.
 ///
-#[derive(Component)]
+#[derive(Component, Debug)]
 pub struct Paddle {
     player: PlayerId,
+    position: PaddlePosition,
     move_dir: MoveDirection,
+    walled: bool,
 }
 
 impl Paddle {
-    // Private constructor to easily create a Paddle with given player Id and other defaults.
-    fn new(player: PlayerId) -> Self {
+    // Private constructor to easily create a Paddle with given player Id, position and defaults.
+    fn new(player: PlayerId, position: PaddlePosition) -> Self {
         Paddle {
             player,
+            position,
             move_dir: MoveDirection::None,
+            walled: false,
         }
     }
 }
 
+///
+/// Represents how far a paddle sits from the edge of the arena it defends. Every player has a
+/// Back paddle against their edge. In doubles mode, each side also has a Forward paddle which
+/// sits further into the arena and is controlled by the second player on that side.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PaddlePosition {
+    /// Paddle sits against the edge of the arena.
+    Back,
+    /// Paddle sits further into the arena, in front of the Back paddle.
+    Forward,
+}
+
+///
+/// This message should be sent by another module to move a paddle without user input
+/// (e.g. for a computer controlled player). A paddle which receives this message in a frame
+/// moves in the given direction and ignores its keys for that frame. Directions which don't
+/// apply to the paddle (e.g. Left for a paddle on the left edge) leave it stationary.
+///
+/// If the paddle needs to move in the same frame as this message gets sent, the
+/// system generating the message should be ordered before HandleInput.
+///
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MovePaddle {
+    /// The player whose paddle should move
+    pub player: PlayerId,
+    /// Which of the player's paddles should move
+    pub position: PaddlePosition,
+    /// The direction to move the paddle in
+    pub dir: MoveDirection,
+}
+
+///
+/// This message should be sent by another module to signal that a player has been
+/// eliminated. Their paddle will be stretched across their entire edge of the arena,
+/// acting as a wall, and will no longer respond to input.
+///
+/// If the change needs to occur in the same frame as this message gets sent, the
+/// system generating the message should be ordered before WallOffPaddleRcvr.
+///
+#[derive(Message, Debug, PartialEq, Eq)]
+pub struct WallOffPaddle(pub PlayerId);
+
+///
+/// This message should be sent by another module to signal that all paddles should be
+/// returned to their initial state (e.g. at the start of a new game), undoing any
+/// previous WallOffPaddle messages.
+///
+/// If the change needs to occur in the same frame as this message gets sent, the
+/// system generating the message should be ordered before RestorePaddlesRcvr.
+///
+#[derive(Message, Debug)]
+pub struct RestorePaddles;
+
+///
+/// Resource holding the color each player's paddles are drawn in, e.g. as chosen in their
+/// player profile. Players without a color get the default paddle color. The colors are
+/// read when paddles are created or restored, so changes show from the next game on.
+///
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct PaddleColors(pub PerPlayer<Color>);
+
+impl PaddleColors {
+    /// Get the color of the given player's paddles, falling back to the default color.
+    pub fn color(&self, player: PlayerId) -> Color {
+        self.0.get(player).copied().unwrap_or(PADDLE_COLOR)
+    }
+}
+
 ///
 /// A custom QueryData which allows read-only access to the hitbox API.
 /// The entrypoint for the API is a system with parameter Query<AllPaddleHitboxes>.
-/// From there, the API allows an individual player hitbox to be selected,
+/// From there, each paddle hitbox in the game can be iterated over,
 /// and relevant data for the hitbox can be retrieved via the API.
 ///
 #[derive(QueryData)]
@@ -95,30 +202,38 @@ pub struct AllPaddleHitboxes(&'static Paddle, &'static Transform);
 pub type PaddleHitbox<'w, 's> = AllPaddleHitboxesItem<'w, 's>;
 
 impl<'w, 's> PaddleHitbox<'w, 's> {
+    /// Get the player controlling this paddle.
+    pub fn player(&self) -> PlayerId {
+        self.0.player
+    }
+
+    /// Get the position of this paddle, relative to the edge it defends.
+    pub fn position(&self) -> PaddlePosition {
+        self.0.position
+    }
+
+    /// Get the edge of the arena this paddle is defending.
+    pub fn side(&self) -> ArenaSide {
+        self.0.player.side()
+    }
+
     ///
-    /// Given the query for all paddle hitboxes, retrieve the one specific to a
-    /// particular PlayerId.
+    /// Get the normal of the collision plane of this paddle. It is perpendicular
+    /// to the edge of the arena the paddle defends, and points into the arena.
     ///
-    pub fn from_query(query: Query<'w, 's, AllPaddleHitboxes>, player: PlayerId) -> Self {
-        for item in query {
-            if item.0.player == player {
-                return item as PaddleHitbox;
-            }
-        }
-        panic!("PlayerId {player:?} was not found in AllPaddleHitboxes query.");
+    pub fn plane_normal(&self) -> Vec2 {
+        self.side().inward_normal()
     }
 
     ///
     /// Get an origin point for the collision plane of this paddle. The plane
-    /// is on the vertical face of the paddle nearest the center line of the arena.
+    /// is on the face of the paddle nearest the center of the arena.
     ///
     pub fn plane_origin(&self) -> Vec2 {
-        let x_offset = match self.0.player {
-            Player1 => self.1.scale.x,
-            Player2 => -self.1.scale.x,
-        };
+        let normal = self.plane_normal();
+        let depth = (self.1.scale.xy() * normal).length();
 
-        self.1.translation.xy() + Vec2::new(x_offset, 0f32)
+        self.1.translation.xy() + (normal * depth)
     }
 
     /// Get the topmost Y coordinate of the collision surface of the paddle.
@@ -131,15 +246,48 @@ impl<'w, 's> PaddleHitbox<'w, 's> {
         self.1.translation.y - (self.1.scale.y / 2f32)
     }
 
+    ///
+    /// Get the minimum coordinate of the collision surface of the paddle, along the edge
+    /// it defends. This is the bottom Y for left/right paddles and the left X for top/bottom.
+    ///
+    pub fn face_min(&self) -> f32 {
+        if self.side().is_vertical() {
+            self.bot_y()
+        } else {
+            self.1.translation.x - (self.1.scale.x / 2f32)
+        }
+    }
+
+    ///
+    /// Get the maximum coordinate of the collision surface of the paddle, along the edge
+    /// it defends. This is the top Y for left/right paddles and the right X for top/bottom.
+    ///
+    pub fn face_max(&self) -> f32 {
+        if self.side().is_vertical() {
+            self.top_y()
+        } else {
+            self.1.translation.x + (self.1.scale.x / 2f32)
+        }
+    }
+
     /// Get the direction this paddle moved in the most recent update.
     pub fn movement_dir(&self) -> MoveDirection {
         self.0.move_dir
     }
+
+    ///
+    /// Returns true if this paddle's player has been eliminated and the paddle now walls
+    /// off their entire edge of the arena.
+    ///
+    pub fn is_walled(&self) -> bool {
+        self.0.walled
+    }
 }
 
 ///
 /// Represents any of the possible directions that a paddle may have moved in the most
-/// recent frame of the game.
+/// recent frame of the game. Paddles on the left and right edges move Up and Down, while
+/// paddles on the top and bottom edges move Left and Right.
 ///
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum MoveDirection {
@@ -149,118 +297,213 @@ pub enum MoveDirection {
     Up,
     /// Paddle moved towards negative Y direction last update.
     Down,
+    /// Paddle moved towards negative X direction last update.
+    Left,
+    /// Paddle moved towards positive X direction last update.
+    Right,
+}
+
+impl MoveDirection {
+    /// Get the unit vector for this direction, or zero for no movement.
+    pub fn as_vec2(self) -> Vec2 {
+        match self {
+            MoveDirection::None => Vec2::ZERO,
+            MoveDirection::Up => Vec2::Y,
+            MoveDirection::Down => Vec2::NEG_Y,
+            MoveDirection::Left => Vec2::NEG_X,
+            MoveDirection::Right => Vec2::X,
+        }
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
 // Private Systems
 
 //
-// Creates two paddles - one for each player. One paddle is against the left edge of
-// the screen, one is against the right edge. They are vertically centered to start.
+// Creates the paddles for each player in the current GameMode. Back paddles are against the
+// edge of the screen defended by that player, while forward paddles (doubles mode only) are
+// offset into the arena. All paddles are centered along their edge to start.
 //
-fn setup_paddles(mut commands: Commands) {
-    let paddle_size = Vec3::new(PADDLE_WIDTH, PADDLE_HEIGHT, 0f32);
-
-    commands.spawn((
-        Paddle::new(Player1),
-        Sprite {
-            color: Color::WHITE,
-            custom_size: Some(Vec2::ONE),
-            ..default()
-        },
-        Anchor::CENTER_LEFT,
-        Transform {
-            translation: Vec3 {
-                x: -ARENA_WIDTH / 2f32,
-                y: 0f32,
-                z: Z_FOREGROUND,
-            },
-            scale: paddle_size,
-            ..default()
-        },
-    ));
-
-    commands.spawn((
-        Paddle::new(Player2),
-        Sprite {
-            color: Color::WHITE,
-            custom_size: Some(Vec2::ONE),
-            ..default()
-        },
-        Anchor::CENTER_RIGHT,
-        Transform {
-            translation: Vec3 {
-                x: ARENA_WIDTH / 2f32,
-                y: 0f32,
-                z: Z_FOREGROUND,
-            },
-            scale: paddle_size,
-            ..default()
-        },
-    ));
+fn setup_paddles(mut commands: Commands, mode: Res<GameMode>, colors: Res<PaddleColors>) {
+    for &player in mode.players() {
+        for &position in paddle_positions(*mode) {
+            let side = player.side();
+            commands.spawn((
+                Paddle::new(player, position),
+                Sprite {
+                    color: colors.color(player),
+                    custom_size: Some(Vec2::ONE),
+                    ..default()
+                },
+                paddle_anchor(side),
+                initial_paddle_transform(side, position),
+            ));
+        }
+    }
 }
 
-// Checks relevant user inputs and updates positions of paddles accordingly.
+//
+// Checks relevant user inputs and updates positions of paddles accordingly. Paddles which
+// were sent a MovePaddle message this frame follow that instead of their keys.
+//
 fn handle_input_move_paddles(
     paddles: Query<(&mut Transform, &mut Paddle)>,
+    mut move_msgs: MessageReader<MovePaddle>,
     keys: Res<ButtonInput<KeyCode>>,
+    settings: Res<Settings>,
+    key_overrides: Res<PaddleKeyOverrides>,
     time: Res<Time>,
 ) {
     let distance = time.delta_secs() * PADDLE_MOVE_SPEED;
-    let ((p1_trans, p1_move_dir), (p2_trans, p2_move_dir)) = paddles
-        .into_iter()
-        .map(|(t, pad)| {
+    let move_msgs: Vec<MovePaddle> = move_msgs.read().copied().collect();
+
+    for (mut paddle_tf, mut paddle) in paddles {
+        if paddle.walled {
+            // Walled off paddles no longer respond to input
+            paddle.move_dir = MoveDirection::None;
+            continue;
+        }
+
+        let paddle_keys =
+            key_overrides.paddle(&settings.key_bindings, paddle.player, paddle.position);
+        let (pos, clamp, pos_dir, neg_dir) = if paddle.player.side().is_vertical() {
             (
-                pad.player,
-                (
-                    &mut t.into_inner().translation,
-                    &mut pad.into_inner().move_dir,
-                ),
+                &mut paddle_tf.translation.y,
+                PADDLE_CLAMP_Y,
+                MoveDirection::Up,
+                MoveDirection::Down,
             )
-        })
-        .as_per_player();
-
-    match (keys.pressed(KeyCode::KeyW), keys.pressed(KeyCode::KeyS)) {
-        (true, false) => {
-            if p1_trans.y < PADDLE_CLAMP_Y {
-                p1_trans.y = (p1_trans.y + distance).min(PADDLE_CLAMP_Y);
-                *p1_move_dir = MoveDirection::Up;
-            } else {
-                *p1_move_dir = MoveDirection::None;
+        } else {
+            (
+                &mut paddle_tf.translation.x,
+                PADDLE_CLAMP_X,
+                MoveDirection::Right,
+                MoveDirection::Left,
+            )
+        };
+
+        let requested_dir = move_msgs
+            .iter()
+            .rfind(|msg| msg.player == paddle.player && msg.position == paddle.position)
+            .map(|msg| msg.dir);
+        let pressed = match requested_dir {
+            Some(dir) => (dir == pos_dir, dir == neg_dir),
+            None => (
+                keys.pressed(paddle_keys.positive),
+                keys.pressed(paddle_keys.negative),
+            ),
+        };
+
+        paddle.move_dir = match pressed {
+            (true, false) if *pos < clamp => {
+                *pos = (*pos + distance).min(clamp);
+                pos_dir
             }
-        }
-        (false, true) => {
-            if p1_trans.y > -PADDLE_CLAMP_Y {
-                p1_trans.y = (p1_trans.y - distance).max(-PADDLE_CLAMP_Y);
-                *p1_move_dir = MoveDirection::Down;
-            } else {
-                *p1_move_dir = MoveDirection::None;
+            (false, true) if *pos > -clamp => {
+                *pos = (*pos - distance).max(-clamp);
+                neg_dir
             }
-        }
-        _ => *p1_move_dir = MoveDirection::None, // No p1 movement if neither or both are pressed
+            _ => MoveDirection::None, // No movement if at the limit, or neither/both are pressed
+        };
     }
+}
 
-    match (
-        keys.pressed(KeyCode::ArrowUp),
-        keys.pressed(KeyCode::ArrowDown),
-    ) {
-        (true, false) => {
-            if p2_trans.y < PADDLE_CLAMP_Y {
-                p2_trans.y = (p2_trans.y + distance).min(PADDLE_CLAMP_Y);
-                *p2_move_dir = MoveDirection::Up;
-            } else {
-                *p2_move_dir = MoveDirection::None;
-            }
+//
+// Handles WallOffPaddle messages, stretching the eliminated player's paddle across their
+// entire edge of the arena and greying it out.
+//
+fn handle_wall_off_paddle(
+    mut messages: MessageReader<WallOffPaddle>,
+    paddles: Query<(&mut Paddle, &mut Transform, &mut Sprite)>,
+) {
+    if messages.is_empty() {
+        return;
+    }
+
+    let players: Vec<PlayerId> = messages.read().map(|msg| msg.0).collect();
+    for (mut paddle, mut paddle_tf, mut sprite) in paddles {
+        if !players.contains(&paddle.player) {
+            continue;
         }
-        (false, true) => {
-            if p2_trans.y > -PADDLE_CLAMP_Y {
-                p2_trans.y = (p2_trans.y - distance).max(-PADDLE_CLAMP_Y);
-                *p2_move_dir = MoveDirection::Down;
-            } else {
-                *p2_move_dir = MoveDirection::None;
-            }
+
+        wall_off(&mut paddle, &mut paddle_tf);
+        sprite.color = WALLED_PADDLE_COLOR;
+    }
+}
+
+// Handles RestorePaddles messages, returning every paddle to its initial state.
+fn handle_restore_paddles(
+    mut messages: MessageReader<RestorePaddles>,
+    colors: Res<PaddleColors>,
+    paddles: Query<(&mut Paddle, &mut Transform, &mut Sprite)>,
+) {
+    if !messages.is_empty() {
+        messages.clear();
+
+        for (mut paddle, mut paddle_tf, mut sprite) in paddles {
+            *paddle = Paddle::new(paddle.player, paddle.position);
+            *paddle_tf = initial_paddle_transform(paddle.player.side(), paddle.position);
+            sprite.color = colors.color(paddle.player);
         }
-        _ => *p2_move_dir = MoveDirection::None, // No p2 movement if neither or both are pressed
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Functions
+
+// Get the positions of the paddles each player defends their edge with in the given GameMode.
+fn paddle_positions(mode: GameMode) -> &'static [PaddlePosition] {
+    match mode {
+        GameMode::Doubles => &[PaddlePosition::Back, PaddlePosition::Forward],
+        GameMode::TwoPlayer | GameMode::FourPlayer => &[PaddlePosition::Back],
+    }
+}
+
+// Stretches a paddle across its entire edge of the arena, so it acts as a wall.
+fn wall_off(paddle: &mut Paddle, paddle_tf: &mut Transform) {
+    paddle.walled = true;
+    paddle.move_dir = MoveDirection::None;
+    if paddle.player.side().is_vertical() {
+        paddle_tf.translation.y = 0f32;
+        paddle_tf.scale.y = ARENA_HEIGHT;
+    } else {
+        paddle_tf.translation.x = 0f32;
+        paddle_tf.scale.x = ARENA_WIDTH;
+    }
+}
+
+// Get the sprite anchor for a paddle, such that its translation sits on the arena edge.
+fn paddle_anchor(side: ArenaSide) -> Anchor {
+    match side {
+        ArenaSide::Left => Anchor::CENTER_LEFT,
+        ArenaSide::Right => Anchor::CENTER_RIGHT,
+        ArenaSide::Top => Anchor::TOP_CENTER,
+        ArenaSide::Bottom => Anchor::BOTTOM_CENTER,
+    }
+}
+
+//
+// Get the initial transform for a paddle defending the given side of the arena. Its
+// translation is at the center of that edge (offset into the arena for forward paddles),
+// and its scale is the real on-screen size.
+//
+fn initial_paddle_transform(side: ArenaSide, position: PaddlePosition) -> Transform {
+    let offset = match position {
+        PaddlePosition::Back => 0f32,
+        PaddlePosition::Forward => PADDLE_FORWARD_OFFSET,
+    };
+    let edge = (-side.inward_normal() * Vec2::new(ARENA_WIDTH / 2f32, ARENA_HEIGHT / 2f32))
+        + (side.inward_normal() * offset);
+    let scale = if side.is_vertical() {
+        Vec3::new(PADDLE_WIDTH, PADDLE_HEIGHT, 0f32)
+    } else {
+        Vec3::new(PADDLE_HEIGHT, PADDLE_WIDTH, 0f32)
+    };
+
+    Transform {
+        translation: edge.extend(Z_FOREGROUND),
+        scale,
+        ..default()
     }
 }
 
@@ -296,6 +539,8 @@ pub mod tests {
     #[test]
     fn test_setup_paddles_system() {
         let mut world = World::default();
+        world.init_resource::<GameMode>();
+        world.init_resource::<PaddleColors>();
 
         // Run the system and let it create entities we expect
         let setup_sys = world.register_system(setup_paddles);
@@ -385,10 +630,131 @@ pub mod tests {
                         tf.translation.x,
                     );
                 }
+                _ => panic!("Expected only P1 and P2 paddles in two player mode, got {pid:?}"),
             }
         }
     }
 
+    #[test]
+    fn test_setup_paddles_system_four_player() {
+        let mut world = World::default();
+        world.insert_resource(GameMode::FourPlayer);
+        world.init_resource::<PaddleColors>();
+
+        // Run the system and let it create entities we expect
+        let setup_sys = world.register_system(setup_paddles);
+        world.run_system(setup_sys).unwrap();
+
+        // Validate there is exactly one paddle per player
+        let mut query = world.query::<(&Paddle, &Anchor, &Transform)>();
+        assert_eq!(
+            query.iter(&world).len(),
+            4,
+            "Expected 4 paddles to be added by setup system in four player mode",
+        );
+        for player in PlayerId::ALL {
+            let count = query
+                .iter(&world)
+                .filter(|(paddle, ..)| paddle.player == player)
+                .count();
+            assert_eq!(count, 1, "Expected exactly one paddle for {player:?}");
+        }
+
+        // Validate the top and bottom paddles are horizontal and on the correct edges
+        for (paddle, anchor, tf) in query.iter(&world) {
+            let (exp_anchor, exp_y) = match paddle.player {
+                Player3 => (Anchor::TOP_CENTER, ARENA_HEIGHT / 2f32),
+                Player4 => (Anchor::BOTTOM_CENTER, -ARENA_HEIGHT / 2f32),
+                _ => continue,
+            };
+            assert_eq!(
+                *anchor, exp_anchor,
+                "Expected {:?} paddle anchored at {exp_anchor:?}, got {anchor:?}",
+                paddle.player,
+            );
+            assert_eq!(
+                tf.translation.xy(),
+                Vec2::new(0f32, exp_y),
+                "Expected {:?} paddle centered on its edge at y {exp_y}, got {}",
+                paddle.player,
+                tf.translation,
+            );
+            assert_eq!(
+                tf.scale,
+                Vec3::new(PADDLE_HEIGHT, PADDLE_WIDTH, 0f32),
+                "Expected {:?} paddle to be horizontal, but got size {}",
+                paddle.player,
+                tf.scale,
+            );
+        }
+    }
+
+    #[test]
+    fn test_setup_paddles_system_doubles() {
+        let mut world = World::default();
+        world.insert_resource(GameMode::Doubles);
+        let mut colors = PaddleColors::default();
+        colors.0.insert(Player2, Color::srgb_u8(0, 128, 255));
+        world.insert_resource(colors);
+
+        // Run the system and let it create entities we expect
+        let setup_sys = world.register_system(setup_paddles);
+        world.run_system(setup_sys).unwrap();
+
+        // Validate there is a back and forward paddle on each side
+        let mut query = world.query::<(&Paddle, &Transform)>();
+        assert_eq!(
+            query.iter(&world).len(),
+            4,
+            "Expected 4 paddles to be added by setup system in doubles mode",
+        );
+        for (paddle, tf) in query.iter(&world) {
+            let offset = match paddle.position {
+                PaddlePosition::Back => 0f32,
+                PaddlePosition::Forward => PADDLE_FORWARD_OFFSET,
+            };
+            let exp_x = match paddle.player {
+                Player1 => (-ARENA_WIDTH / 2f32) + offset,
+                Player2 => (ARENA_WIDTH / 2f32) - offset,
+                _ => panic!("Expected only P1 and P2 paddles in doubles mode, got {paddle:?}"),
+            };
+            assert_eq!(
+                tf.translation.xy(),
+                Vec2::new(exp_x, 0f32),
+                "Expected {:?} {:?} paddle at x {exp_x}, got {}",
+                paddle.player,
+                paddle.position,
+                tf.translation,
+            );
+        }
+        for player in [Player1, Player2] {
+            for position in [PaddlePosition::Back, PaddlePosition::Forward] {
+                let count = query
+                    .iter(&world)
+                    .filter(|(paddle, _)| (paddle.player, paddle.position) == (player, position))
+                    .count();
+                assert_eq!(
+                    count, 1,
+                    "Expected exactly one {position:?} paddle for {player:?}"
+                );
+            }
+        }
+
+        // Both of a player's paddles are drawn in their color
+        let mut query = world.query::<(&Paddle, &Sprite)>();
+        for (paddle, sprite) in query.iter(&world) {
+            let exp_color = match paddle.player {
+                Player2 => Color::srgb_u8(0, 128, 255),
+                _ => PADDLE_COLOR,
+            };
+            assert_eq!(
+                sprite.color, exp_color,
+                "Expected {:?} {:?} paddle to be drawn in {exp_color:?}",
+                paddle.player, paddle.position,
+            );
+        }
+    }
+
     #[test]
     fn test_handle_input_no_keys_down() {
         run_handle_input_scenario(
@@ -507,6 +873,235 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_handle_input_horizontal_paddles() {
+        let mut world = World::default();
+        spawn_test_paddle(&mut world, 1f32, -1f32, Player3);
+        spawn_test_paddle(&mut world, 1f32, -1f32, Player4);
+
+        let mut time: Time<()> = Time::default();
+        time.advance_by(Duration::from_millis(5));
+        world.insert_resource(time);
+
+        let mut button_input = ButtonInput::<KeyCode>::default();
+        button_input.press(KeyCode::KeyL);
+        button_input.press(KeyCode::Numpad4);
+        world.insert_resource(button_input);
+        world.init_resource::<Settings>();
+        world.init_resource::<PaddleKeyOverrides>();
+        world.init_resource::<Messages<MovePaddle>>();
+
+        let handle_input_sys = world.register_system(handle_input_move_paddles);
+        world.run_system(handle_input_sys).unwrap();
+
+        // Player 3 should have moved right, and player 4 left
+        let mut query = world.query::<(&Paddle, &Transform)>();
+        for (paddle, tf) in query.iter(&world) {
+            let (exp_x, exp_dir) = match paddle.player {
+                Player3 => (0.005 * PADDLE_MOVE_SPEED, MoveDirection::Right),
+                _ => (-0.005 * PADDLE_MOVE_SPEED, MoveDirection::Left),
+            };
+            assert_eq!(
+                tf.translation.x, exp_x,
+                "Expected {:?} x to be {exp_x} but it was {}",
+                paddle.player, tf.translation.x,
+            );
+            assert_eq!(
+                paddle.move_dir, exp_dir,
+                "Expected {:?} dir of {exp_dir:?}",
+                paddle.player,
+            );
+        }
+    }
+
+    #[test]
+    fn test_handle_input_doubles_paddles() {
+        let mut world = World::default();
+        spawn_test_paddle(&mut world, 1f32, -1f32, Player1);
+        spawn_test_paddle_at(&mut world, 1f32, -1f32, Player1, PaddlePosition::Forward);
+
+        let mut time: Time<()> = Time::default();
+        time.advance_by(Duration::from_millis(5));
+        world.insert_resource(time);
+
+        let mut button_input = ButtonInput::<KeyCode>::default();
+        button_input.press(KeyCode::KeyW);
+        button_input.press(KeyCode::KeyG);
+        world.insert_resource(button_input);
+        world.init_resource::<Settings>();
+        world.init_resource::<PaddleKeyOverrides>();
+        world.init_resource::<Messages<MovePaddle>>();
+
+        let handle_input_sys = world.register_system(handle_input_move_paddles);
+        world.run_system(handle_input_sys).unwrap();
+
+        // Each paddle on the side should move independently, using its own keys
+        let mut query = world.query::<(&Paddle, &Transform)>();
+        for (paddle, tf) in query.iter(&world) {
+            let (exp_y, exp_dir) = match paddle.position {
+                PaddlePosition::Back => (0.005 * PADDLE_MOVE_SPEED, MoveDirection::Up),
+                PaddlePosition::Forward => (-0.005 * PADDLE_MOVE_SPEED, MoveDirection::Down),
+            };
+            assert_eq!(
+                tf.translation.y, exp_y,
+                "Expected {:?} paddle y to be {exp_y} but it was {}",
+                paddle.position, tf.translation.y,
+            );
+            assert_eq!(
+                paddle.move_dir, exp_dir,
+                "Expected {:?} paddle dir of {exp_dir:?}",
+                paddle.position,
+            );
+        }
+    }
+
+    #[test]
+    fn test_handle_input_move_paddle_msgs() {
+        let mut world = World::default();
+        spawn_test_paddle(&mut world, 1f32, -1f32, Player1);
+        spawn_test_paddle(&mut world, 1f32, -1f32, Player2);
+        spawn_test_paddle(&mut world, 1f32, -1f32, Player3);
+
+        let mut time: Time<()> = Time::default();
+        time.advance_by(Duration::from_millis(5));
+        world.insert_resource(time);
+
+        // Player 1 keys are pressed, but the message should take priority over them
+        let mut button_input = ButtonInput::<KeyCode>::default();
+        button_input.press(KeyCode::KeyW);
+        world.insert_resource(button_input);
+        world.init_resource::<Settings>();
+        world.init_resource::<PaddleKeyOverrides>();
+        world.init_resource::<Messages<MovePaddle>>();
+        for (player, dir) in [
+            (Player1, MoveDirection::None),
+            (Player2, MoveDirection::Down),
+            (Player3, MoveDirection::Up),
+        ] {
+            world.write_message(MovePaddle {
+                player,
+                position: PaddlePosition::Back,
+                dir,
+            });
+        }
+
+        let handle_input_sys = world.register_system(handle_input_move_paddles);
+        world.run_system(handle_input_sys).unwrap();
+
+        // Player 3 paddle can't move up, since it moves horizontally
+        let mut query = world.query::<(&Paddle, &Transform)>();
+        for (paddle, tf) in query.iter(&world) {
+            let (exp_pos, exp_dir) = match paddle.player {
+                Player2 => (-0.005 * PADDLE_MOVE_SPEED, MoveDirection::Down),
+                _ => (0f32, MoveDirection::None),
+            };
+            let pos = if paddle.player.side().is_vertical() {
+                tf.translation.y
+            } else {
+                tf.translation.x
+            };
+            assert_eq!(
+                pos, exp_pos,
+                "Expected {:?} paddle at {exp_pos} but it was at {pos}",
+                paddle.player,
+            );
+            assert_eq!(
+                paddle.move_dir, exp_dir,
+                "Expected {:?} dir of {exp_dir:?}",
+                paddle.player,
+            );
+        }
+    }
+
+    #[test]
+    fn test_wall_off_and_restore_paddles() {
+        let mut world = World::default();
+        world.insert_resource(GameMode::FourPlayer);
+        let mut colors = PaddleColors::default();
+        colors.0.insert(Player1, Color::srgb_u8(255, 0, 0));
+        world.insert_resource(colors.clone());
+        world.init_resource::<Messages<WallOffPaddle>>();
+        world.init_resource::<Messages<RestorePaddles>>();
+
+        let setup_sys = world.register_system(setup_paddles);
+        let wall_sys = world.register_system(handle_wall_off_paddle);
+        let restore_sys = world.register_system(handle_restore_paddles);
+        world.run_system(setup_sys).unwrap();
+
+        // Wall off players 1 and 3. Their paddles should cover their entire edges.
+        world.write_message(WallOffPaddle(Player1));
+        world.write_message(WallOffPaddle(Player3));
+        world.run_system(wall_sys).unwrap();
+        let mut query = world.query::<AllPaddleHitboxes>();
+        for hitbox in query.iter(&world) {
+            match hitbox.0.player {
+                Player1 => {
+                    assert!(hitbox.is_walled(), "Expected P1 paddle to be walled off");
+                    assert_eq!(
+                        (hitbox.face_min(), hitbox.face_max()),
+                        (-ARENA_HEIGHT / 2f32, ARENA_HEIGHT / 2f32),
+                        "Expected walled P1 paddle to cover the full left edge",
+                    );
+                }
+                Player3 => {
+                    assert!(hitbox.is_walled(), "Expected P3 paddle to be walled off");
+                    assert_eq!(
+                        (hitbox.face_min(), hitbox.face_max()),
+                        (-ARENA_WIDTH / 2f32, ARENA_WIDTH / 2f32),
+                        "Expected walled P3 paddle to cover the full top edge",
+                    );
+                }
+                player => assert!(
+                    !hitbox.is_walled(),
+                    "Expected {player:?} paddle not to be walled off"
+                ),
+            }
+        }
+
+        // Walled paddles should ignore input
+        let mut time: Time<()> = Time::default();
+        time.advance_by(Duration::from_millis(5));
+        world.insert_resource(time);
+        let mut button_input = ButtonInput::<KeyCode>::default();
+        button_input.press(KeyCode::KeyW);
+        world.insert_resource(button_input);
+        world.init_resource::<Settings>();
+        world.init_resource::<PaddleKeyOverrides>();
+        world.init_resource::<Messages<MovePaddle>>();
+        let handle_input_sys = world.register_system(handle_input_move_paddles);
+        world.run_system(handle_input_sys).unwrap();
+        let p1_hitbox = get_test_hitbox(query.query(&world), Player1);
+        assert_eq!(
+            p1_hitbox.movement_dir(),
+            MoveDirection::None,
+            "Expected walled P1 paddle not to move",
+        );
+
+        // Restoring should return all paddles to their initial state
+        world.write_message(RestorePaddles);
+        world.run_system(restore_sys).unwrap();
+        let mut query = world.query::<(&Paddle, &Transform, &Sprite)>();
+        for (paddle, tf, sprite) in query.iter(&world) {
+            assert!(
+                !paddle.walled,
+                "Expected {:?} to be restored",
+                paddle.player
+            );
+            assert_eq!(
+                *tf,
+                initial_paddle_transform(paddle.player.side(), paddle.position),
+                "Expected {:?} paddle to return to its initial transform",
+                paddle.player,
+            );
+            assert_eq!(
+                sprite.color,
+                colors.color(paddle.player),
+                "Expected {:?} paddle color to be restored",
+                paddle.player,
+            );
+        }
+    }
+
     #[test]
     fn test_hitbox_api() {
         let mut world = World::default();
@@ -516,7 +1111,9 @@ pub mod tests {
             commands.spawn((
                 Paddle {
                     player: Player2,
+                    position: PaddlePosition::Back,
                     move_dir: MoveDirection::Up,
+                    walled: false,
                 },
                 Transform {
                     translation: Vec3::new(10f32, 3f32, 0f32),
@@ -527,7 +1124,9 @@ pub mod tests {
             commands.spawn((
                 Paddle {
                     player: Player1,
+                    position: PaddlePosition::Back,
                     move_dir: MoveDirection::Down,
+                    walled: false,
                 },
                 Transform {
                     translation: Vec3::new(-5f32, 8f32, 0f32),
@@ -543,7 +1142,7 @@ pub mod tests {
         let hitbox_query = query_state.query(&world);
 
         // Validate player 1 hitbox parameters
-        let p1_hitbox = PaddleHitbox::from_query(hitbox_query, Player1);
+        let p1_hitbox = get_test_hitbox(hitbox_query, Player1);
         let exp_top_y = 8f32 + (PADDLE_HEIGHT / 2f32);
         let exp_bot_y = 8f32 - (PADDLE_HEIGHT / 2f32);
         let exp_plane_x = -5f32 + PADDLE_WIDTH;
@@ -582,7 +1181,7 @@ pub mod tests {
         );
 
         // Validate player 2 hitbox parameters
-        let p2_hitbox = PaddleHitbox::from_query(hitbox_query, Player2);
+        let p2_hitbox = get_test_hitbox(hitbox_query, Player2);
         let exp_top_y = 3f32 + (PADDLE_HEIGHT / 2f32);
         let exp_bot_y = 3f32 - (PADDLE_HEIGHT / 2f32);
         let exp_plane_x = 10f32 - PADDLE_WIDTH;
@@ -621,8 +1220,38 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_hitbox_api_horizontal() {
+        let mut world = World::default();
+        spawn_test_paddle(&mut world, 3f32, 1f32, Player3);
+
+        let mut query_state = world.query::<AllPaddleHitboxes>();
+        let p3_hitbox = get_test_hitbox(query_state.query(&world), Player3);
+        assert_eq!(
+            p3_hitbox.side(),
+            ArenaSide::Top,
+            "Expected p3 to defend the top edge"
+        );
+        assert_eq!(
+            p3_hitbox.plane_normal(),
+            Vec2::NEG_Y,
+            "Expected p3 plane normal to point down into the arena",
+        );
+        assert_eq!(
+            p3_hitbox.plane_origin().y,
+            (ARENA_HEIGHT / 2f32) - PADDLE_WIDTH,
+            "Expected p3 plane origin on bottom face of paddle",
+        );
+        assert_eq!(
+            (p3_hitbox.face_min(), p3_hitbox.face_max()),
+            (1f32, 3f32),
+            "Expected p3 face to span x coords 1 to 3",
+        );
+    }
+
     // ----- Helper Functions -----
 
+    #[allow(clippy::too_many_arguments)]
     fn run_handle_input_scenario(
         init_p1_y: f32,
         init_p2_y: f32,
@@ -660,17 +1289,17 @@ pub mod tests {
             button_input.press(*key);
         }
         world.insert_resource(button_input);
+        world.init_resource::<Settings>();
+        world.init_resource::<PaddleKeyOverrides>();
+        world.init_resource::<Messages<MovePaddle>>();
 
         // Run system to move paddles
         let handle_input_sys = world.register_system(handle_input_move_paddles);
         world.run_system(handle_input_sys).unwrap();
 
         // Validate y positions are updated to expected values
-        let mut query = world.query::<(&Paddle, &Transform)>();
-        let (p1_tf, p2_tf) = query
-            .iter(&world)
-            .map(|(p, tf)| (p.player, tf))
-            .as_per_player();
+        let (p1_tf, p1_dir) = get_test_paddle(&mut world, Player1);
+        let (p2_tf, p2_dir) = get_test_paddle(&mut world, Player2);
         assert_eq!(
             p1_tf.translation.y, exp_p1_y,
             "Expected p1 y to be {exp_p1_y} but it was {}",
@@ -683,42 +1312,98 @@ pub mod tests {
         );
 
         // Validate movement directions are updated to expected values
-        let mut query = world.query::<&Paddle>();
-        let (p1_dir, p2_dir) = query
-            .iter(&world)
-            .map(|p| (p.player, p.move_dir))
-            .as_per_player();
         assert_eq!(exp_p1_dir, p1_dir, "Expected p1 dir of {:?}", exp_p1_dir);
         assert_eq!(exp_p2_dir, p2_dir, "Expected p2 dir of {:?}", exp_p2_dir);
     }
 
+    // Get the hitbox of the single paddle belonging to the given player.
+    fn get_test_hitbox<'w, 's>(
+        query: Query<'w, 's, AllPaddleHitboxes>,
+        player: PlayerId,
+    ) -> PaddleHitbox<'w, 's> {
+        let mut hitboxes = query.into_iter().filter(|hitbox| hitbox.0.player == player);
+        let hitbox = hitboxes
+            .next()
+            .unwrap_or_else(|| panic!("Expected a paddle for {player:?} but found none"));
+        assert!(
+            hitboxes.next().is_none(),
+            "Expected one paddle for {player:?} but found more"
+        );
+        hitbox
+    }
+
+    // Get the (Transform, MoveDirection) of the single paddle belonging to the given player.
+    fn get_test_paddle(world: &mut World, player: PlayerId) -> (Transform, MoveDirection) {
+        let mut query = world.query::<AllPaddleHitboxes>();
+        let hitbox = get_test_hitbox(query.query(world), player);
+        (*hitbox.1, hitbox.movement_dir())
+    }
+
     // --- External API For Other Test Suites ---
-    pub fn spawn_test_paddle(world: &mut World, top_y: f32, bot_y: f32, player: PlayerId) {
-        let x = match player {
-            Player1 => -ARENA_WIDTH / 2f32,
-            Player2 => ARENA_WIDTH / 2f32,
-        };
 
-        assert!(top_y >= bot_y, "Expected top_y to be greater than bot_y");
+    //
+    // Spawns a paddle on the edge of the arena defended by the given player. The face_max
+    // and face_min are the paddle ends along that edge (i.e. top/bottom y for left and
+    // right paddles, or right/left x for top and bottom paddles).
+    //
+    pub fn spawn_test_paddle(world: &mut World, face_max: f32, face_min: f32, player: PlayerId) {
+        spawn_test_paddle_at(world, face_max, face_min, player, PaddlePosition::Back);
+    }
 
-        let paddle_height = top_y - bot_y;
-        let paddle_y = bot_y + (paddle_height / 2f32);
+    // Same as spawn_test_paddle, but spawns the player's paddle at the given position.
+    pub fn spawn_test_paddle_at(
+        world: &mut World,
+        face_max: f32,
+        face_min: f32,
+        player: PlayerId,
+        position: PaddlePosition,
+    ) {
+        assert!(
+            face_max >= face_min,
+            "Expected face_max to be greater than face_min"
+        );
 
-        world.spawn((
-            Paddle::new(player),
-            Transform {
-                translation: Vec3 {
-                    x: x,
-                    y: paddle_y,
-                    z: 0f32,
-                },
-                scale: Vec3::new(PADDLE_WIDTH, paddle_height, 0f32),
-                ..default()
-            },
-        ));
+        let side = player.side();
+        let paddle_len = face_max - face_min;
+        let paddle_mid = face_min + (paddle_len / 2f32);
+        let mut transform = initial_paddle_transform(side, position);
+        transform.translation.z = 0f32;
+        if side.is_vertical() {
+            transform.translation.y = paddle_mid;
+            transform.scale.y = paddle_len;
+        } else {
+            transform.translation.x = paddle_mid;
+            transform.scale.x = paddle_len;
+        }
+
+        world.spawn((Paddle::new(player, position), transform));
+    }
+
+    // Walls off the paddles of the given players, as if they had been eliminated.
+    pub fn wall_off_test_paddles(world: &mut World, players: &[PlayerId]) {
+        let mut query = world.query::<(&mut Paddle, &mut Transform)>();
+        for (mut paddle, mut paddle_tf) in query.iter_mut(world) {
+            if players.contains(&paddle.player) {
+                wall_off(&mut paddle, &mut paddle_tf);
+            }
+        }
+    }
+
+    // Sets the most recent movement direction of the given player's paddle.
+    pub fn set_test_paddle_movement(world: &mut World, player: PlayerId, dir: MoveDirection) {
+        let mut query = world.query::<&mut Paddle>();
+        for mut paddle in query.iter_mut(world) {
+            if paddle.player == player {
+                paddle.move_dir = dir;
+            }
+        }
     }
 
     pub fn get_paddle_width() -> f32 {
-        return PADDLE_WIDTH;
+        PADDLE_WIDTH
+    }
+
+    pub fn get_forward_offset() -> f32 {
+        PADDLE_FORWARD_OFFSET
     }
 }
@@ -0,0 +1,563 @@
+//!
+//! The particles module contains the ParticlesPlugin, which throws off sparks wherever the ball
+//! hits a wall or paddle, and a larger burst whenever a goal is scored, along with a shake of
+//! the camera.
+//!
+
+// -------------------------------------------------------------------------------------------------
+// Included Symbols
+
+use std::f32::consts::PI;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::arena::ShakeCamera;
+use crate::ball::{self, Ball, BallImpact, BallOffScreen, curve_level_colors};
+use crate::common::*;
+use crate::settings::{ParticleSettings, Settings};
+
+// -------------------------------------------------------------------------------------------------
+// Constants
+
+// Limits on the particle settings, however large the configured values.
+const MAX_PARTICLES_CAP: u32 = 2000;
+const MIN_LIFETIME_SECS: f32 = 0.05;
+const MAX_LIFETIME_SECS: f32 = 3.0;
+
+// Sparks thrown off by an impact spread up to this angle either side of the surface normal.
+const IMPACT_SPREAD_RAD: f32 = 0.45 * PI;
+const IMPACT_SPEED_MIN: f32 = 0.15 * ARENA_HEIGHT;
+const IMPACT_SPEED_MAX: f32 = 0.45 * ARENA_HEIGHT;
+const IMPACT_SPARK_SIZE: f32 = 0.012 * ARENA_HEIGHT;
+
+// The goal burst is larger, faster and spreads wider than an impact.
+const GOAL_SPREAD_RAD: f32 = 0.4 * PI;
+const GOAL_SPEED_MIN: f32 = 0.2 * ARENA_HEIGHT;
+const GOAL_SPEED_MAX: f32 = 0.9 * ARENA_HEIGHT;
+const GOAL_SPARK_SIZE: f32 = 0.02 * ARENA_HEIGHT;
+const GOAL_SHAKE_TRAUMA: f32 = 0.6;
+
+// Fraction of its speed a spark keeps after each second.
+const SPARK_DRAG_PER_SEC: f32 = 0.05;
+
+// -------------------------------------------------------------------------------------------------
+// Public API
+
+///
+/// The ParticlesPlugin spawns sparks at each BallImpact, colored by the curve level of the
+/// ball, and a larger burst at the edge the ball leaves through for each BallOffScreen, which
+/// also shakes the camera. Sparks spread out from the surface, slow down, shrink and fade away.
+///
+/// How many sparks are shown, and for how long, is taken from the particle Settings, capped at
+/// limits of this plugin. Sparks are purely cosmetic, so don't draw on the game's random numbers.
+///
+pub struct ParticlesPlugin;
+
+impl Plugin for ParticlesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Settings>()
+            .add_message::<BallImpact>()
+            .add_message::<BallOffScreen>()
+            .add_message::<ShakeCamera>()
+            .add_systems(
+                Update,
+                (
+                    emit_impact_sparks.after(ball::Systems::BallImpactSndr),
+                    emit_goal_burst
+                        .in_set(Systems::ShakeCameraSndr)
+                        .after(ball::Systems::BallOffScreenSndr)
+                        .before(ball::Systems::ResetBallRcvr),
+                    update_particles,
+                ),
+            );
+    }
+}
+
+///
+/// System sets to allow modules consuming this plugin to create ordering constraints
+/// based on functionality exposed in the API of the Plugin.
+///
+#[derive(SystemSet, Debug, Clone, Hash, PartialEq, Eq)]
+pub enum Systems {
+    ///
+    /// Update systems which send ShakeCamera messages. To react to these messages in the
+    /// same frame, the receiver should be ordered after this system set.
+    ///
+    ShakeCameraSndr,
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Types
+
+// How a burst of sparks is thrown off: in which directions, how fast, and how big.
+struct Burst {
+    origin: Vec2,
+    normal: Vec2,
+    spread_rad: f32,
+    speed_min: f32,
+    speed_max: f32,
+    size: f32,
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Components
+
+// Component for a single spark, which moves, shrinks and fades until its lifetime is over.
+#[derive(Component, Clone)]
+struct Particle {
+    velocity: Vec2,
+    lifetime: Timer,
+    size: f32,
+    color: Color,
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Systems
+
+//
+// Throws off sparks from the point of each impact, colored by the curve level of the ball.
+//
+fn emit_impact_sparks(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    mut impact_msgs: MessageReader<BallImpact>,
+    particles: Query<(), With<Particle>>,
+) {
+    let mut room = room_for_particles(&settings.particles, particles.count());
+    for impact in impact_msgs.read() {
+        let burst = Burst {
+            origin: impact.point,
+            normal: impact.normal.as_vec2(),
+            spread_rad: IMPACT_SPREAD_RAD,
+            speed_min: IMPACT_SPEED_MIN,
+            speed_max: IMPACT_SPEED_MAX,
+            size: IMPACT_SPARK_SIZE,
+        };
+        let count = settings.particles.impact_sparks.min(room);
+        spawn_burst(
+            &mut commands,
+            &settings.particles,
+            &burst,
+            count,
+            impact.curve_level,
+        );
+        room -= count;
+    }
+}
+
+//
+// Throws off a large burst of sparks back into the arena where the ball left it, and shakes
+// the camera, for each goal. This must run before the ball is reset to the center.
+//
+fn emit_goal_burst(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    mut off_screen_msgs: MessageReader<BallOffScreen>,
+    mut shake_msgs: MessageWriter<ShakeCamera>,
+    ball_q: Single<(&Ball, &Transform)>,
+    particles: Query<(), With<Particle>>,
+) {
+    let (ball, ball_tf) = ball_q.into_inner();
+    let mut room = room_for_particles(&settings.particles, particles.count());
+    for off_screen in off_screen_msgs.read() {
+        let half_arena = Vec2::new(ARENA_WIDTH, ARENA_HEIGHT) / 2f32;
+        let burst = Burst {
+            origin: ball_tf.translation.xy().clamp(-half_arena, half_arena),
+            normal: off_screen.side().inward_normal(),
+            spread_rad: GOAL_SPREAD_RAD,
+            speed_min: GOAL_SPEED_MIN,
+            speed_max: GOAL_SPEED_MAX,
+            size: GOAL_SPARK_SIZE,
+        };
+        let count = settings.particles.goal_sparks.min(room);
+        spawn_burst(
+            &mut commands,
+            &settings.particles,
+            &burst,
+            count,
+            ball.curve_level(),
+        );
+        room -= count;
+
+        shake_msgs.write(ShakeCamera(GOAL_SHAKE_TRAUMA));
+    }
+}
+
+//
+// Moves each spark along, slowing it down, and shrinks and fades it over its lifetime.
+// Sparks are despawned once their lifetime is over.
+//
+fn update_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    particles: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+) {
+    let drag = SPARK_DRAG_PER_SEC.powf(time.delta_secs());
+    for (entity, mut particle, mut particle_tf, mut sprite) in particles {
+        particle.lifetime.tick(time.delta());
+        if particle.lifetime.is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        particle_tf.translation += (particle.velocity * time.delta_secs()).extend(0f32);
+        particle.velocity *= drag;
+
+        let life_left = 1f32 - particle.lifetime.fraction();
+        sprite.color = particle.color.with_alpha(life_left);
+        sprite.custom_size = Some(Vec2::splat(particle.size * life_left));
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Functions
+
+//
+// Get the number of sparks which may be added with the given number already shown. None may
+// be added if particles are turned off.
+//
+fn room_for_particles(settings: &ParticleSettings, existing: usize) -> u32 {
+    if !settings.enabled {
+        return 0;
+    }
+    let max = settings.max_particles.min(MAX_PARTICLES_CAP);
+    max.saturating_sub(existing.try_into().unwrap_or(u32::MAX))
+}
+
+//
+// Spawns the given number of sparks, in random directions within the spread either side of
+// the normal of the burst, and in the colors of the given curve level.
+//
+fn spawn_burst(
+    commands: &mut Commands,
+    settings: &ParticleSettings,
+    burst: &Burst,
+    count: u32,
+    curve_level: u8,
+) {
+    let colors = curve_level_colors(curve_level);
+    let lifetime = spark_lifetime(settings);
+    let mut rng = rand::rng();
+
+    for _ in 0..count {
+        let angle = rng.random_range(-burst.spread_rad..=burst.spread_rad);
+        let speed = rng.random_range(burst.speed_min..=burst.speed_max);
+        let color = colors[rng.random_range(0..colors.len())];
+        commands.spawn((
+            Particle {
+                velocity: Rot2::radians(angle) * burst.normal * speed,
+                lifetime: Timer::new(lifetime, TimerMode::Once),
+                size: burst.size,
+                color,
+            },
+            Sprite {
+                color,
+                custom_size: Some(Vec2::splat(burst.size)),
+                ..default()
+            },
+            Transform::from_translation(burst.origin.extend(Z_FOREGROUND)),
+        ));
+    }
+}
+
+//
+// Get the lifetime of each spark from the settings, within limits. A lifetime which isn't
+// finite (e.g. NaN in a hand-edited settings file) is replaced by the default lifetime.
+//
+fn spark_lifetime(settings: &ParticleSettings) -> Duration {
+    let lifetime_secs = if settings.lifetime_secs.is_finite() {
+        settings.lifetime_secs
+    } else {
+        ParticleSettings::default().lifetime_secs
+    };
+    Duration::from_secs_f32(lifetime_secs.clamp(MIN_LIFETIME_SECS, MAX_LIFETIME_SECS))
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::schedule::AnonymousSet;
+    use bevy_test_helpers::prelude::*;
+
+    #[test]
+    fn test_plugin_sys_added_impact_sparks() {
+        validate_sys_in_plugin(
+            ParticlesPlugin,
+            Update,
+            emit_impact_sparks,
+            None::<AnonymousSet>,
+        );
+    }
+
+    #[test]
+    fn test_plugin_sys_added_goal_burst() {
+        validate_sys_in_plugin(
+            ParticlesPlugin,
+            Update,
+            emit_goal_burst,
+            Some(Systems::ShakeCameraSndr),
+        );
+    }
+
+    #[test]
+    fn test_plugin_sys_added_update() {
+        validate_sys_in_plugin(
+            ParticlesPlugin,
+            Update,
+            update_particles,
+            None::<AnonymousSet>,
+        );
+    }
+
+    #[test]
+    fn test_room_for_particles() {
+        let settings = ParticleSettings {
+            max_particles: 100,
+            ..default()
+        };
+        assert_eq!(room_for_particles(&settings, 0), 100);
+        assert_eq!(room_for_particles(&settings, 70), 30);
+        assert_eq!(room_for_particles(&settings, 150), 0);
+
+        let settings = ParticleSettings {
+            max_particles: u32::MAX,
+            ..default()
+        };
+        assert_eq!(
+            room_for_particles(&settings, 0),
+            MAX_PARTICLES_CAP,
+            "Expected the configured maximum to be capped",
+        );
+
+        let settings = ParticleSettings {
+            enabled: false,
+            ..default()
+        };
+        assert_eq!(
+            room_for_particles(&settings, 0),
+            0,
+            "Expected no room when off"
+        );
+    }
+
+    #[test]
+    fn test_emit_impact_sparks() {
+        let mut world = setup_particles_world(ParticleSettings::default());
+        world.write_message(BallImpact {
+            point: Vec2::new(1.0, ARENA_HEIGHT / 2.0),
+            normal: Dir2::NEG_Y,
+            curve_level: 3,
+            paddle: false,
+        });
+        world.run_system_cached(emit_impact_sparks).unwrap();
+
+        let sparks = sparks(&mut world);
+        assert_eq!(
+            sparks.len(),
+            ParticleSettings::default().impact_sparks as usize
+        );
+        let colors = curve_level_colors(3);
+        for (particle, particle_tf) in sparks {
+            assert_eq!(
+                particle_tf.translation.xy(),
+                Vec2::new(1.0, ARENA_HEIGHT / 2.0)
+            );
+            assert!(
+                particle.velocity.y < 0.0,
+                "Expected sparks thrown back into the arena, got velocity {}",
+                particle.velocity,
+            );
+            assert!(
+                colors.contains(&particle.color),
+                "Expected spark colored by the curve level, got {:?}",
+                particle.color,
+            );
+        }
+    }
+
+    #[test]
+    fn test_emit_impact_sparks_capped() {
+        let mut world = setup_particles_world(ParticleSettings {
+            impact_sparks: 8,
+            max_particles: 20,
+            ..default()
+        });
+        for _ in 0..3 {
+            world.write_message(BallImpact {
+                point: Vec2::ZERO,
+                normal: Dir2::X,
+                curve_level: 0,
+                paddle: true,
+            });
+        }
+        world.run_system_cached(emit_impact_sparks).unwrap();
+        assert_eq!(sparks(&mut world).len(), 20, "Expected sparks capped at 20");
+
+        // No more sparks while the maximum are shown
+        world.write_message(BallImpact {
+            point: Vec2::ZERO,
+            normal: Dir2::X,
+            curve_level: 0,
+            paddle: true,
+        });
+        world.run_system_cached(emit_impact_sparks).unwrap();
+        assert_eq!(sparks(&mut world).len(), 20);
+    }
+
+    #[test]
+    fn test_emit_impact_sparks_disabled() {
+        let mut world = setup_particles_world(ParticleSettings {
+            enabled: false,
+            ..default()
+        });
+        world.write_message(BallImpact {
+            point: Vec2::ZERO,
+            normal: Dir2::X,
+            curve_level: 2,
+            paddle: true,
+        });
+        world.run_system_cached(emit_impact_sparks).unwrap();
+        assert!(sparks(&mut world).is_empty(), "Expected no sparks when off");
+    }
+
+    #[test]
+    fn test_emit_goal_burst() {
+        let mut world = setup_particles_world(ParticleSettings::default());
+        ball::tests::spawn_test_ball(&mut world, Vec2::new(ARENA_WIDTH, 1.0), Some(Dir2::X));
+        world.write_message(BallOffScreen::Right);
+        world.run_system_cached(emit_goal_burst).unwrap();
+
+        let sparks = sparks(&mut world);
+        assert_eq!(
+            sparks.len(),
+            ParticleSettings::default().goal_sparks as usize
+        );
+        for (particle, particle_tf) in sparks {
+            assert_eq!(
+                particle_tf.translation.xy(),
+                Vec2::new(ARENA_WIDTH / 2.0, 1.0),
+                "Expected burst from the edge of the arena the ball left through",
+            );
+            assert!(
+                particle.velocity.x < 0.0,
+                "Expected burst back into the arena, got velocity {}",
+                particle.velocity,
+            );
+        }
+
+        let shakes: Vec<ShakeCamera> = world
+            .resource::<Messages<ShakeCamera>>()
+            .iter_current_update_messages()
+            .copied()
+            .collect();
+        assert_eq!(shakes, vec![ShakeCamera(GOAL_SHAKE_TRAUMA)]);
+    }
+
+    #[test]
+    fn test_emit_goal_burst_disabled_still_shakes() {
+        let mut world = setup_particles_world(ParticleSettings {
+            enabled: false,
+            ..default()
+        });
+        ball::tests::spawn_test_ball(&mut world, Vec2::new(0.0, -ARENA_HEIGHT), None);
+        world.write_message(BallOffScreen::Bottom);
+        world.run_system_cached(emit_goal_burst).unwrap();
+        assert!(sparks(&mut world).is_empty(), "Expected no sparks when off");
+        assert_eq!(world.resource::<Messages<ShakeCamera>>().len(), 1);
+    }
+
+    #[test]
+    fn test_update_particles() {
+        let mut world = setup_particles_world(ParticleSettings {
+            lifetime_secs: 0.5,
+            ..default()
+        });
+        world.write_message(BallImpact {
+            point: Vec2::ZERO,
+            normal: Dir2::Y,
+            curve_level: 0,
+            paddle: false,
+        });
+        world.run_system_cached(emit_impact_sparks).unwrap();
+
+        // Sparks move away from the impact, shrinking and fading
+        advance_and_update(&mut world, 0.25);
+        let mut query = world.query::<(&Particle, &Transform, &Sprite)>();
+        for (particle, particle_tf, sprite) in query.iter(&world) {
+            assert!(particle_tf.translation.y > 0.0, "Expected spark to move");
+            assert!((sprite.color.alpha() - 0.5).abs() < 0.00001);
+            assert!(sprite.custom_size.unwrap().x < particle.size);
+        }
+
+        // Sparks are despawned when their lifetime is over
+        advance_and_update(&mut world, 0.3);
+        assert!(sparks(&mut world).is_empty(), "Expected sparks despawned");
+    }
+
+    #[test]
+    fn test_spark_lifetime_clamped() {
+        let default_secs = ParticleSettings::default().lifetime_secs;
+        for (lifetime_secs, exp_secs) in [
+            (60.0, MAX_LIFETIME_SECS),
+            (0.0, MIN_LIFETIME_SECS),
+            (f32::NAN, default_secs),
+            (f32::INFINITY, default_secs),
+        ] {
+            let mut world = setup_particles_world(ParticleSettings {
+                lifetime_secs,
+                ..default()
+            });
+            world.write_message(BallImpact {
+                point: Vec2::ZERO,
+                normal: Dir2::Y,
+                curve_level: 0,
+                paddle: false,
+            });
+            world.run_system_cached(emit_impact_sparks).unwrap();
+            let sparks = sparks(&mut world);
+            assert!(!sparks.is_empty(), "Expected sparks for {lifetime_secs}");
+            for (particle, _) in sparks {
+                assert_eq!(
+                    particle.lifetime.duration(),
+                    Duration::from_secs_f32(exp_secs),
+                    "Expected spark lifetime of {exp_secs} for setting of {lifetime_secs}",
+                );
+            }
+        }
+    }
+
+    // --- Helper Functions ---
+
+    fn setup_particles_world(particles: ParticleSettings) -> World {
+        let mut world = World::default();
+        world.insert_resource(Settings {
+            particles,
+            ..default()
+        });
+        world.init_resource::<Time>();
+        world.init_resource::<Messages<BallImpact>>();
+        world.init_resource::<Messages<BallOffScreen>>();
+        world.init_resource::<Messages<ShakeCamera>>();
+        world
+    }
+
+    fn sparks(world: &mut World) -> Vec<(Particle, Transform)> {
+        let mut query = world.query::<(&Particle, &Transform)>();
+        query
+            .iter(world)
+            .map(|(particle, particle_tf)| (particle.clone(), *particle_tf))
+            .collect()
+    }
+
+    fn advance_and_update(world: &mut World, secs: f32) {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(secs));
+        world.run_system_cached(update_particles).unwrap();
+    }
+}
@@ -0,0 +1,237 @@
+//!
+//! The profiles module contains the named player profiles, which players choose before a
+//! match to play under their own name, paddle color and keys. Profiles are persisted to a
+//! file in the platform's config directory, and are linked to the match history by name.
+//!
+
+// -------------------------------------------------------------------------------------------------
+// Included Symbols
+
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::common::{APP_DIR_NAME, RonFileError, load_ron_file, save_ron_file};
+use crate::history::{LeaderboardEntry, LeaderboardSort, MatchRecord, leaderboard};
+use crate::settings::PaddleKeys;
+
+// -------------------------------------------------------------------------------------------------
+// Constants
+
+const PROFILES_FILE_NAME: &str = "profiles.ron";
+
+// -------------------------------------------------------------------------------------------------
+// Public API
+
+///
+/// A named player profile. Playing under a profile names the player after it in the scoreboard
+/// and match history, and applies any of its preferences in place of the defaults. Any field
+/// missing from the profiles file is left unset, so older files keep loading.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PlayerProfile {
+    /// Name the player plays under, which identifies them in the match history
+    pub name: String,
+    /// Preferred color of the player's paddles, as red, green and blue components
+    #[serde(default)]
+    pub paddle_color: Option<[u8; 3]>,
+    /// Preferred keys for the player's back paddle (their only paddle outside of doubles)
+    #[serde(default)]
+    pub keys: Option<PaddleKeys>,
+    /// Preferred keys for the player's forward paddle, in doubles mode
+    #[serde(default)]
+    pub forward_keys: Option<PaddleKeys>,
+}
+
+impl PlayerProfile {
+    /// Create a profile with the given name, without any preferences.
+    pub fn new(name: impl Into<String>) -> Self {
+        PlayerProfile {
+            name: name.into(),
+            paddle_color: None,
+            keys: None,
+            forward_keys: None,
+        }
+    }
+
+    /// Get the preferred paddle color of the profile, if it has one.
+    pub fn color(&self) -> Option<Color> {
+        self.paddle_color
+            .map(|[red, green, blue]| Color::srgb_u8(red, green, blue))
+    }
+
+    ///
+    /// Get the standing of the profile on the leaderboard of the given match history. Returns
+    /// None if no match has been played under the profile's name yet.
+    ///
+    pub fn standing(&self, records: &[MatchRecord]) -> Option<LeaderboardEntry> {
+        leaderboard(records, LeaderboardSort::Elo)
+            .into_iter()
+            .find(|entry| entry.name == self.name)
+    }
+}
+
+/// Every saved player profile, in the order they were created.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct Profiles(pub Vec<PlayerProfile>);
+
+impl Profiles {
+    /// Get the profile with the given name, if there is one.
+    pub fn get(&self, name: &str) -> Option<&PlayerProfile> {
+        self.0.iter().find(|profile| profile.name == name)
+    }
+
+    ///
+    /// Add the given profile, replacing any existing profile with the same name in place.
+    /// Returns the replaced profile, if there was one.
+    ///
+    pub fn insert(&mut self, profile: PlayerProfile) -> Option<PlayerProfile> {
+        match self.0.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => Some(std::mem::replace(existing, profile)),
+            None => {
+                self.0.push(profile);
+                None
+            }
+        }
+    }
+
+    /// Remove the profile with the given name, returning it if there was one.
+    pub fn remove(&mut self, name: &str) -> Option<PlayerProfile> {
+        let index = self.0.iter().position(|profile| profile.name == name)?;
+        Some(self.0.remove(index))
+    }
+}
+
+///
+/// Get the default location of the profiles file, in the platform's config directory.
+/// Returns None if the platform has no config directory.
+///
+pub fn default_profiles_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_DIR_NAME).join(PROFILES_FILE_NAME))
+}
+
+///
+/// Load the profiles from the file at the given path. If there is no file there yet,
+/// there are no profiles.
+///
+pub fn load_profiles(path: &Path) -> Result<Profiles, RonFileError> {
+    load_ron_file(path).map(Option::unwrap_or_default)
+}
+
+/// Save the profiles to the file at the given path, creating its directory if needed.
+pub fn save_profiles_to(path: &Path, profiles: &Profiles) -> Result<(), RonFileError> {
+    save_ron_file(path, profiles)
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::*;
+    use crate::stats::MatchStats;
+    use std::fs;
+
+    #[test]
+    fn test_profiles_insert_and_remove() {
+        let mut profiles = Profiles::default();
+        assert_eq!(profiles.insert(PlayerProfile::new("Ada")), None);
+        assert_eq!(profiles.insert(PlayerProfile::new("Bob")), None);
+
+        // Adding a profile with an existing name replaces it, keeping its place
+        let recolored = PlayerProfile {
+            paddle_color: Some([255, 0, 0]),
+            ..PlayerProfile::new("Ada")
+        };
+        assert_eq!(
+            profiles.insert(recolored.clone()),
+            Some(PlayerProfile::new("Ada"))
+        );
+        assert_eq!(
+            profiles.0,
+            vec![recolored.clone(), PlayerProfile::new("Bob")]
+        );
+        assert_eq!(profiles.get("Ada"), Some(&recolored));
+        assert_eq!(
+            recolored.color(),
+            Some(Color::srgb_u8(255, 0, 0)),
+            "Expected paddle color to convert to an sRGB color",
+        );
+
+        assert_eq!(profiles.remove("Bob"), Some(PlayerProfile::new("Bob")));
+        assert_eq!(profiles.remove("Bob"), None);
+        assert_eq!(profiles.get("Bob"), None);
+    }
+
+    #[test]
+    fn test_save_and_load_profiles() {
+        let path = test_profiles_path("save_load");
+        assert_eq!(
+            load_profiles(&path).unwrap(),
+            Profiles::default(),
+            "Expected no profiles when the file doesn't exist",
+        );
+
+        let profiles = Profiles(vec![
+            PlayerProfile {
+                name: "Ada".to_string(),
+                paddle_color: Some([0, 128, 255]),
+                keys: Some("KeyQ,KeyA".parse().unwrap()),
+                forward_keys: Some("KeyE,KeyD".parse().unwrap()),
+            },
+            PlayerProfile::new("Bob"),
+        ]);
+        save_profiles_to(&path, &profiles).unwrap();
+        assert_eq!(load_profiles(&path).unwrap(), profiles);
+
+        // Preferences missing from the file are left unset
+        fs::write(&path, r#"([(name: "Cy")])"#).unwrap();
+        assert_eq!(
+            load_profiles(&path).unwrap(),
+            Profiles(vec![PlayerProfile::new("Cy")])
+        );
+
+        fs::write(&path, "not a profile").unwrap();
+        assert!(matches!(load_profiles(&path), Err(RonFileError::Parse(_))));
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_profile_standing() {
+        let mut players = PerPlayer::new();
+        players.insert(Player1, "Ada".to_string());
+        players.insert(Player2, "Bob".to_string());
+        let records = [MatchRecord {
+            played_at: 0,
+            mode: GameMode::TwoPlayer,
+            seed: 0,
+            players,
+            scores: PerPlayer::filled(GameMode::TwoPlayer.players(), 0),
+            winner: Player1,
+            stats: MatchStats::default(),
+        }];
+
+        let standing = PlayerProfile::new("Ada").standing(&records).unwrap();
+        assert_eq!((standing.played, standing.wins), (1, 1));
+        assert_eq!(
+            PlayerProfile::new("Cy").standing(&records),
+            None,
+            "Expected no standing for a profile without matches",
+        );
+    }
+
+    // --- Helper Functions ---
+
+    // Get a profiles file path unique to the given test, in the temp directory.
+    fn test_profiles_path(test_name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!(
+                "rust_pong_profiles_{}_{test_name}",
+                std::process::id()
+            ))
+            .join(PROFILES_FILE_NAME)
+    }
+}
@@ -0,0 +1,572 @@
+//!
+//! The replay module records the inputs of a game to a file, and plays them back later.
+//! Along with the inputs, a recording holds everything else needed to reproduce the game
+//! exactly (e.g. the random seed and settings), so the replayed game plays out the same.
+//!
+
+// -------------------------------------------------------------------------------------------------
+// Included Symbols
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use bevy::input::InputSystems;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use serde::{Deserialize, Serialize};
+
+use crate::ai::AiPlayers;
+use crate::common::*;
+use crate::settings::{PaddleKeyOverrides, Settings, key_code_serde};
+
+// -------------------------------------------------------------------------------------------------
+// Public API
+
+///
+/// The ReplayPlugin either records a game to a file, or replays a previously recorded game.
+///
+/// When recording, the keys pressed and the time elapsed in every frame are written to the
+/// file when the app exits. When replaying, the recorded game mode, computer players, random
+/// seed and settings replace those of the app (dropping any paddle key overrides, which are
+/// recorded as part of the key bindings), so this plugin must be added after anything
+/// else inserting those resources, and before any plugin reading them while building (e.g.
+/// the window plugin). Each frame then reproduces the recorded keys and elapsed time, and the
+/// app exits once every recorded frame has been played.
+///
+pub struct ReplayPlugin {
+    /// Whether to record or replay the game.
+    pub mode: ReplayMode,
+}
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        match &self.mode {
+            ReplayMode::Record(path) => {
+                app.insert_resource(Recorder {
+                    path: path.clone(),
+                    recording: None,
+                })
+                .add_systems(Startup, start_recording)
+                .add_systems(Last, (record_frame, save_recording_on_exit).chain());
+            }
+            ReplayMode::Replay(recording) => {
+                let first_delta = recording
+                    .frames
+                    .first()
+                    .map_or(Duration::ZERO, |frame| frame.delta);
+                app.insert_resource(recording.mode)
+                    .insert_resource(recording.ai_players.clone())
+                    .insert_resource(recording.settings.clone())
+                    .insert_resource(PaddleKeyOverrides::default())
+                    .insert_resource(GameRng::seeded(recording.seed))
+                    .insert_resource(TimeUpdateStrategy::ManualDuration(first_delta))
+                    .insert_resource(Replayer {
+                        recording: Recording::clone(recording),
+                        frame: 0,
+                    })
+                    .add_systems(PreUpdate, replay_frame_input.after(InputSystems))
+                    .add_systems(Last, advance_replay);
+            }
+        }
+    }
+}
+
+/// Selects whether the ReplayPlugin records or replays a game.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayMode {
+    /// Record the game, saving it to the given file when the app exits.
+    Record(PathBuf),
+    /// Replay the given recording, which can be loaded using load_recording.
+    Replay(Box<Recording>),
+}
+
+///
+/// A recorded game. This holds the state the game started from, followed by the keys which
+/// were pressed and the time which elapsed in every frame of the game.
+///
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recording {
+    /// Seed of the random number generator when the game started
+    pub seed: u64,
+    /// The variant of pong which was played
+    pub mode: GameMode,
+    /// The computer controlled players of the game
+    pub ai_players: AiPlayers,
+    /// The settings when the game started
+    pub settings: Settings,
+    // The recorded input of every frame, in order
+    frames: Vec<RecordedFrame>,
+}
+
+impl Recording {
+    /// Get the number of frames in the recording.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns true if the recording has no frames.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+/// Load a recording from the given file. Unlike other files, a missing recording is an error.
+pub fn load_recording(path: &Path) -> Result<Recording, RonFileError> {
+    load_ron_file(path)?.ok_or_else(|| RonFileError::Io(io::ErrorKind::NotFound.into()))
+}
+
+/// Save a recording to the given file, creating its parent directory if needed.
+pub fn save_recording_to(path: &Path, recording: &Recording) -> Result<(), RonFileError> {
+    save_ron_file(path, recording)
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Types
+
+// The input of a single recorded frame.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct RecordedFrame {
+    // Time elapsed since the previous frame.
+    delta: Duration,
+
+    // Keys held down during the frame.
+    pressed: Vec<RecordedKey>,
+}
+
+// A key in a recorded frame, stored by name.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct RecordedKey(#[serde(with = "key_code_serde")] KeyCode);
+
+// -------------------------------------------------------------------------------------------------
+// Private Resources
+
+// The recording in progress (once the game has started), and where to save it.
+#[derive(Resource)]
+struct Recorder {
+    path: PathBuf,
+    recording: Option<Recording>,
+}
+
+// The recording being replayed, and the index of the frame being replayed.
+#[derive(Resource)]
+struct Replayer {
+    recording: Recording,
+    frame: usize,
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Systems
+
+//
+// Starts the recording from the state of the game once everything has been set up. Any paddle
+// key overrides for this run are recorded as part of the key bindings, so the recorded keys
+// move the same paddles when the game is replayed.
+//
+fn start_recording(
+    mut recorder: ResMut<Recorder>,
+    rng: Res<GameRng>,
+    mode: Res<GameMode>,
+    ai_players: Option<Res<AiPlayers>>,
+    settings: Res<Settings>,
+    key_overrides: Option<Res<PaddleKeyOverrides>>,
+) {
+    let mut settings = settings.clone();
+    if let Some(key_overrides) = key_overrides {
+        key_overrides.apply(&mut settings.key_bindings);
+    }
+
+    recorder.recording = Some(Recording {
+        seed: rng.seed(),
+        mode: *mode,
+        ai_players: ai_players.map(|ai| ai.clone()).unwrap_or_default(),
+        settings,
+        frames: Vec::new(),
+    });
+}
+
+// Adds the keys held down and time elapsed this frame to the recording.
+fn record_frame(
+    mut recorder: ResMut<Recorder>,
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time<Real>>,
+) {
+    let Some(recording) = &mut recorder.recording else {
+        return;
+    };
+
+    // Unidentified keys can't be stored by name, and don't control anything anyway
+    let pressed = keys
+        .get_pressed()
+        .filter(|key| !matches!(key, KeyCode::Unidentified(_)))
+        .map(|&key| RecordedKey(key))
+        .collect();
+    recording.frames.push(RecordedFrame {
+        delta: time.delta(),
+        pressed,
+    });
+}
+
+// Saves the recording to its file when the app exits.
+fn save_recording_on_exit(mut exit_msgs: MessageReader<AppExit>, recorder: Res<Recorder>) {
+    if exit_msgs.is_empty() {
+        return;
+    }
+    exit_msgs.clear();
+
+    if let Some(recording) = &recorder.recording {
+        match save_recording_to(&recorder.path, recording) {
+            Ok(()) => info!("Saved recording to {}", recorder.path.display()),
+            Err(err) => error!(
+                "Failed to save recording to {}: {err}",
+                recorder.path.display()
+            ),
+        }
+    }
+}
+
+//
+// Replaces the keyboard input of this frame with the recorded keys. The recorded keys of the
+// previous frame are used to work out which keys were just pressed or released, so actual
+// keyboard input is ignored completely.
+//
+fn replay_frame_input(mut keys: ResMut<ButtonInput<KeyCode>>, replayer: Res<Replayer>) {
+    let frames = &replayer.recording.frames;
+    let Some(frame) = frames.get(replayer.frame) else {
+        return;
+    };
+    let previous: &[RecordedKey] = match replayer.frame.checked_sub(1) {
+        Some(previous) => &frames[previous].pressed,
+        None => &[],
+    };
+
+    keys.reset_all();
+    for &RecordedKey(key) in previous {
+        keys.press(key);
+        if !frame.pressed.contains(&RecordedKey(key)) {
+            keys.release(key);
+        }
+    }
+    for &RecordedKey(key) in &frame.pressed {
+        keys.press(key);
+    }
+    for &RecordedKey(key) in previous {
+        keys.clear_just_pressed(key);
+    }
+}
+
+//
+// Moves on to the next recorded frame, setting up the time which should elapse in it. Once
+// every frame has been replayed, the app exits.
+//
+fn advance_replay(
+    mut replayer: ResMut<Replayer>,
+    mut time_strategy: ResMut<TimeUpdateStrategy>,
+    mut exit_msgs: MessageWriter<AppExit>,
+) {
+    replayer.frame += 1;
+    match replayer.recording.frames.get(replayer.frame) {
+        Some(frame) => *time_strategy = TimeUpdateStrategy::ManualDuration(frame.delta),
+        None => {
+            info!("Replay finished");
+            exit_msgs.write(AppExit::Success);
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::AiDifficulty;
+    use crate::paddle::PaddlePosition;
+    use crate::settings::PaddleKeys;
+    use bevy_test_helpers::prelude::*;
+    use std::fs;
+
+    #[test]
+    fn test_plugin_sys_added_record() {
+        let path = test_recording_path("sys_added_record");
+        validate_sys_in_plugin(
+            ReplayPlugin {
+                mode: ReplayMode::Record(path.clone()),
+            },
+            Startup,
+            start_recording,
+            None::<bevy::ecs::schedule::AnonymousSet>,
+        );
+        validate_sys_in_plugin(
+            ReplayPlugin {
+                mode: ReplayMode::Record(path),
+            },
+            Last,
+            record_frame,
+            None::<bevy::ecs::schedule::AnonymousSet>,
+        );
+    }
+
+    #[test]
+    fn test_plugin_sys_added_replay() {
+        validate_sys_in_plugin(
+            ReplayPlugin {
+                mode: ReplayMode::Replay(Box::new(test_recording(vec![]))),
+            },
+            PreUpdate,
+            replay_frame_input,
+            None::<bevy::ecs::schedule::AnonymousSet>,
+        );
+    }
+
+    #[test]
+    fn test_plugin_build_replay() {
+        let mut recording = test_recording(vec![test_frame(20, &[])]);
+        recording.mode = GameMode::FourPlayer;
+        recording.settings.gameplay.winning_score = 4;
+
+        let mut app = App::new();
+        app.insert_resource(GameMode::TwoPlayer)
+            .insert_resource(GameRng::seeded(1))
+            .insert_resource(PaddleKeyOverrides(vec![(
+                Player1,
+                PaddlePosition::Back,
+                PaddleKeys {
+                    positive: KeyCode::KeyO,
+                    negative: KeyCode::KeyL,
+                },
+            )]))
+            .add_plugins(ReplayPlugin {
+                mode: ReplayMode::Replay(Box::new(recording.clone())),
+            });
+
+        let world = app.world();
+        assert_eq!(*world.resource::<GameMode>(), GameMode::FourPlayer);
+        assert_eq!(world.resource::<GameRng>().seed(), recording.seed);
+        assert_eq!(*world.resource::<Settings>(), recording.settings);
+        assert_eq!(
+            *world.resource::<PaddleKeyOverrides>(),
+            PaddleKeyOverrides::default()
+        );
+        assert_eq!(*world.resource::<AiPlayers>(), recording.ai_players);
+        assert_time_strategy(world, 20);
+    }
+
+    #[test]
+    fn test_record_frames() {
+        let mut world = World::default();
+        world.insert_resource(Recorder {
+            path: test_recording_path("record_frames"),
+            recording: None,
+        });
+        world.insert_resource(GameRng::seeded(99));
+        world.insert_resource(GameMode::Doubles);
+        world.init_resource::<Settings>();
+        let profile_keys = PaddleKeys {
+            positive: KeyCode::KeyO,
+            negative: KeyCode::KeyL,
+        };
+        world.insert_resource(PaddleKeyOverrides(vec![(
+            Player2,
+            PaddlePosition::Forward,
+            profile_keys,
+        )]));
+        let start_sys = world.register_system(start_recording);
+        world.run_system(start_sys).unwrap();
+
+        let mut time = Time::<Real>::default();
+        time.advance_by(Duration::from_millis(16));
+        world.insert_resource(time);
+        let mut keys = ButtonInput::<KeyCode>::default();
+        keys.press(KeyCode::KeyW);
+        keys.press(KeyCode::Unidentified(
+            bevy::input::keyboard::NativeKeyCode::Unidentified,
+        ));
+        world.insert_resource(keys);
+        let record_sys = world.register_system(record_frame);
+        world.run_system(record_sys).unwrap();
+
+        let recording = world.resource::<Recorder>().recording.clone().unwrap();
+        assert_eq!(recording.seed, 99, "Expected the seed to be recorded");
+        assert_eq!(
+            recording.mode,
+            GameMode::Doubles,
+            "Expected the mode to be recorded"
+        );
+        assert_eq!(
+            recording.settings.key_bindings.player2_forward, profile_keys,
+            "Expected the paddle key overrides to be recorded in the key bindings",
+        );
+        assert_eq!(
+            recording.frames,
+            vec![test_frame(16, &[KeyCode::KeyW])],
+            "Expected one frame with only the identified keys recorded",
+        );
+    }
+
+    #[test]
+    fn test_replay_frames() {
+        let recording = test_recording(vec![
+            test_frame(10, &[KeyCode::KeyW]),
+            test_frame(20, &[KeyCode::KeyW, KeyCode::KeyS]),
+            test_frame(30, &[KeyCode::KeyS]),
+        ]);
+        let mut world = World::default();
+        world.insert_resource(Replayer {
+            recording,
+            frame: 0,
+        });
+        world.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            10,
+        )));
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<Messages<AppExit>>();
+        let input_sys = world.register_system(replay_frame_input);
+        let advance_sys = world.register_system(advance_replay);
+
+        // The W key is held down in both of the first two frames, while real input is ignored
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Escape);
+        world.run_system(input_sys).unwrap();
+        assert_keys(&world, &[KeyCode::KeyW], &[KeyCode::KeyW], &[]);
+        world.run_system(advance_sys).unwrap();
+        assert_time_strategy(&world, 20);
+
+        world.run_system(input_sys).unwrap();
+        assert_keys(
+            &world,
+            &[KeyCode::KeyW, KeyCode::KeyS],
+            &[KeyCode::KeyS],
+            &[],
+        );
+        world.run_system(advance_sys).unwrap();
+
+        world.run_system(input_sys).unwrap();
+        assert_keys(&world, &[KeyCode::KeyS], &[], &[KeyCode::KeyW]);
+        assert!(
+            world.resource::<Messages<AppExit>>().is_empty(),
+            "Expected no exit before the last frame is finished",
+        );
+
+        // After the final frame, the app should exit
+        world.run_system(advance_sys).unwrap();
+        assert!(
+            !world.resource::<Messages<AppExit>>().is_empty(),
+            "Expected an exit once every frame was replayed",
+        );
+    }
+
+    #[test]
+    fn test_save_and_load_recording() {
+        let path = test_recording_path("save_and_load");
+        let mut recording = test_recording(vec![
+            test_frame(16, &[KeyCode::ArrowUp, KeyCode::Numpad4]),
+            test_frame(17, &[]),
+        ]);
+        recording.ai_players.0.insert(Player2, AiDifficulty::Hard);
+
+        save_recording_to(&path, &recording).unwrap();
+        let loaded = load_recording(&path).unwrap();
+        assert_eq!(
+            loaded, recording,
+            "Expected loaded recording to match saved"
+        );
+        assert_eq!(loaded.len(), 2, "Expected 2 frames in the loaded recording");
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_load_recording_invalid() {
+        let path = test_recording_path("invalid");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "(seed: \"not a number\")").unwrap();
+        assert!(
+            matches!(load_recording(&path), Err(RonFileError::Parse(_))),
+            "Expected a parse error for an invalid recording",
+        );
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+        assert!(
+            matches!(load_recording(&path), Err(RonFileError::Io(_))),
+            "Expected a file error for a missing recording",
+        );
+    }
+
+    // --- Helper Functions ---
+
+    // Validates the time update strategy is set to the given milliseconds per frame.
+    fn assert_time_strategy(world: &World, exp_delta_ms: u64) {
+        let exp_delta = Duration::from_millis(exp_delta_ms);
+        assert!(
+            matches!(
+                world.resource::<TimeUpdateStrategy>(),
+                TimeUpdateStrategy::ManualDuration(delta) if *delta == exp_delta
+            ),
+            "Expected frame to take the recorded {exp_delta:?}",
+        );
+    }
+
+    // Validates the pressed, just pressed and just released keys.
+    fn assert_keys(
+        world: &World,
+        exp_pressed: &[KeyCode],
+        exp_just_pressed: &[KeyCode],
+        exp_just_released: &[KeyCode],
+    ) {
+        let keys = world.resource::<ButtonInput<KeyCode>>();
+        for (desc, actual, expected) in [
+            (
+                "pressed",
+                keys.get_pressed().collect::<Vec<_>>(),
+                exp_pressed,
+            ),
+            (
+                "just pressed",
+                keys.get_just_pressed().collect(),
+                exp_just_pressed,
+            ),
+            (
+                "just released",
+                keys.get_just_released().collect(),
+                exp_just_released,
+            ),
+        ] {
+            let mut actual: Vec<KeyCode> = actual.into_iter().copied().collect();
+            let mut expected = expected.to_vec();
+            actual.sort();
+            expected.sort();
+            assert_eq!(actual, expected, "Expected {desc} keys {expected:?}");
+        }
+    }
+
+    // Create a recording of a two player game with the given frames.
+    fn test_recording(frames: Vec<RecordedFrame>) -> Recording {
+        Recording {
+            seed: 1234,
+            mode: GameMode::TwoPlayer,
+            ai_players: AiPlayers::default(),
+            settings: Settings::default(),
+            frames,
+        }
+    }
+
+    // Create a recorded frame lasting the given milliseconds, with the given keys held down.
+    fn test_frame(delta_ms: u64, pressed: &[KeyCode]) -> RecordedFrame {
+        RecordedFrame {
+            delta: Duration::from_millis(delta_ms),
+            pressed: pressed.iter().map(|&key| RecordedKey(key)).collect(),
+        }
+    }
+
+    // Get a recording file path unique to the given test, in the temp directory.
+    fn test_recording_path(test_name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!(
+                "rust_pong_replay_{}_{test_name}",
+                std::process::id()
+            ))
+            .join("recording.ron")
+    }
+}
@@ -7,25 +7,35 @@
 // -------------------------------------------------------------------------------------------------
 // Included Symbols
 
+use std::ops::{Index, IndexMut};
+
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
+use bevy::time::Stopwatch;
 
 use bevy_dyn_fontsize::{DynamicFontSize, DynamicFontsizePlugin};
 
+use crate::ball::StartBall;
 use crate::common::*;
+use crate::settings::{GameplaySettings, Settings, WinCondition};
 
 // -------------------------------------------------------------------------------------------------
 // Constants
 
 const SCORE_FONT_SIZE_AS_SCREEN_PCT: f32 = 0.2;
 const WIN_FONT_SIZE_AS_SCREEN_PCT: f32 = 0.04;
+const NAME_FONT_SIZE_AS_SCREEN_PCT: f32 = 0.04;
 const PADDING_UNDER_SCORE_AS_SCREEN_PCT: f32 = 0.02;
-const WINNING_SCORE: u8 = 10;
-
-const P1_WIN_TEXT: &str = "Player 1 Wins!";
-const P2_WIN_TEXT: &str = "Player 2 Wins!";
-
-const SCORE_TEXT_Y: f32 = ARENA_HEIGHT / 2f32; // Top of arena in Y coords
+const PADDING_ABOVE_SCORE_AS_SCREEN_PCT: f32 = 0.01;
+const FOUR_PLAYER_SCORE_FONT_SIZE_AS_SCREEN_PCT: f32 = 0.12;
+const FOUR_PLAYER_WIN_FONT_SIZE_AS_SCREEN_PCT: f32 = 0.08;
+const FOUR_PLAYER_NAME_FONT_SIZE_AS_SCREEN_PCT: f32 = 0.03;
+const PADDING_FROM_EDGE_AS_SCREEN_PCT: f32 = 0.05;
+const ELIMINATION_GOALS: u8 = 5;
+
+const NAME_TEXT_HEIGHT: f32 = NAME_FONT_SIZE_AS_SCREEN_PCT * ARENA_HEIGHT;
+const PADDING_ABOVE_SCORE: f32 = PADDING_ABOVE_SCORE_AS_SCREEN_PCT * ARENA_HEIGHT;
+const SCORE_TEXT_Y: f32 = (ARENA_HEIGHT / 2f32) - NAME_TEXT_HEIGHT - PADDING_ABOVE_SCORE; // Under name
 const SCORE_TEXT_HEIGHT: f32 = SCORE_FONT_SIZE_AS_SCREEN_PCT * ARENA_HEIGHT;
 const SCORE_BOTTOM: f32 = SCORE_TEXT_Y - SCORE_TEXT_HEIGHT;
 const PADDING_UNDER_SCORE: f32 = PADDING_UNDER_SCORE_AS_SCREEN_PCT * ARENA_HEIGHT;
@@ -33,6 +43,16 @@ const WIN_TEXT_Y: f32 = SCORE_BOTTOM - PADDING_UNDER_SCORE;
 const WIN_TEXT_HEIGHT: f32 = WIN_FONT_SIZE_AS_SCREEN_PCT * ARENA_HEIGHT;
 const RIGHT_SIDE_CENTER_X: f32 = ARENA_WIDTH / 4f32;
 const LEFT_SIDE_CENTER_X: f32 = -RIGHT_SIDE_CENTER_X;
+const FOUR_PLAYER_SCORE_HEIGHT: f32 = FOUR_PLAYER_SCORE_FONT_SIZE_AS_SCREEN_PCT * ARENA_HEIGHT;
+const FOUR_PLAYER_WIN_HEIGHT: f32 = FOUR_PLAYER_WIN_FONT_SIZE_AS_SCREEN_PCT * ARENA_HEIGHT;
+const FOUR_PLAYER_NAME_HEIGHT: f32 = FOUR_PLAYER_NAME_FONT_SIZE_AS_SCREEN_PCT * ARENA_HEIGHT;
+const PADDING_FROM_EDGE: f32 = PADDING_FROM_EDGE_AS_SCREEN_PCT * ARENA_HEIGHT;
+const WIN_TEXT_MAX_WIDTH: f32 = (ARENA_WIDTH / 2f32) - (2f32 * PADDING_FROM_EDGE); // Own half
+const FOUR_PLAYER_WIN_MAX_WIDTH: f32 = ARENA_WIDTH - (2f32 * PADDING_FROM_EDGE);
+const MATCH_TEXT_HEIGHT: f32 = NAME_TEXT_HEIGHT;
+const SET_SCORE_TEXT_Y: f32 = (ARENA_HEIGHT / 2f32) - PADDING_ABOVE_SCORE;
+const CLOCK_TEXT_Y: f32 = SET_SCORE_TEXT_Y - MATCH_TEXT_HEIGHT - PADDING_ABOVE_SCORE;
+const SUDDEN_DEATH_TEXT: &str = "Sudden Death";
 
 // -------------------------------------------------------------------------------------------------
 // Public API
@@ -43,6 +63,19 @@ const LEFT_SIDE_CENTER_X: f32 = -RIGHT_SIDE_CENTER_X;
 /// with other game logic to handle such things by sending or receiving
 /// the messages contained in this module.
 ///
+/// In two player mode, each player's score counts up, and each game is won according to the
+/// WinCondition from the gameplay Settings. A match is made up of one or more of these games,
+/// and is won by the first player to win the majority of them. The games won by each player
+/// are shown between the scores when there is more than one game, along with the time left in
+/// timed games. A timed game is won as soon as its time runs out with a player in the lead,
+/// even mid-rally, or otherwise goes to sudden death.
+///
+/// In four player mode, each player's score instead counts down the goals they may still
+/// concede before being eliminated, and the last player remaining wins the match.
+///
+/// Each score is labelled with the player's name from the PlayerNames resource, which is also
+/// used to announce the winner. The labels follow any change to the PlayerNames.
+///
 /// This plugin will only work properly if the app contains a single Window
 /// and a single Camera2d entity.
 ///
@@ -54,18 +87,50 @@ pub struct ScorePlugin;
 impl Plugin for ScorePlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(DynamicFontsizePlugin::default())
+            .init_resource::<GameMode>()
+            .init_resource::<Settings>()
+            .init_resource::<PlayerNames>()
             .insert_resource(Score::default())
+            .init_resource::<GamesWon>()
+            .init_resource::<GameClock>()
             .add_message::<PlayerScored>()
+            .add_message::<GoalConceded>()
+            .add_message::<PlayerEliminated>()
+            .add_message::<GameWon>()
             .add_message::<MaxScoreReached>()
+            .add_message::<GamePoint>()
+            .add_message::<StartNextGame>()
             .add_message::<ClearScores>()
+            .add_message::<StartBall>()
             .add_systems(Startup, setup.in_set(Systems::SetupAfterCamera))
             .add_systems(
                 Update,
                 (
+                    run_game_clock
+                        .in_set(Systems::StartBallRcvr)
+                        .in_set(Systems::GameWonSndr)
+                        .in_set(Systems::MaxScoreReachedSndr),
                     handle_player_score
+                        .after(run_game_clock)
                         .in_set(Systems::PlayerScoredRcvr)
+                        .in_set(Systems::GameWonSndr)
+                        .in_set(Systems::MaxScoreReachedSndr)
+                        .in_set(Systems::GamePointSndr),
+                    handle_goal_conceded
+                        .in_set(Systems::GoalConcededRcvr)
+                        .in_set(Systems::PlayerEliminatedSndr)
                         .in_set(Systems::MaxScoreReachedSndr),
+                    start_next_game.in_set(Systems::StartNextGameRcvr),
                     clear_scores.in_set(Systems::ClearScoresRcvr),
+                    update_player_names.run_if(resource_changed::<PlayerNames>),
+                    update_set_score_text
+                        .after(handle_player_score)
+                        .after(clear_scores)
+                        .run_if(resource_changed::<GamesWon>.or(resource_changed::<Settings>)),
+                    update_clock_text
+                        .after(handle_player_score)
+                        .after(start_next_game)
+                        .after(clear_scores),
                 ),
             );
     }
@@ -79,18 +144,58 @@ impl Plugin for ScorePlugin {
 pub struct PlayerScored(pub PlayerId);
 
 ///
-/// This message will be written by the score module when a player has reached
-/// the winning score and displayed the results. Other game logic should listen
-/// for this message and move to an end-of-game state as well.
+/// This message should be written by other code in four player mode, to notify the score
+/// module when a player has conceded a goal (including the conceding player's PlayerId).
+///
+#[derive(Message, Debug, PartialEq, Eq)]
+pub struct GoalConceded(pub PlayerId);
+
+///
+/// This message will be written by the score module in four player mode, when a player
+/// has conceded too many goals and is eliminated from the game. Other game logic should
+/// listen for this message and remove the player from play.
 ///
-#[derive(Message)]
-pub struct MaxScoreReached;
+#[derive(Message, Debug, PartialEq, Eq)]
+pub struct PlayerEliminated(pub PlayerId);
 
 ///
-/// This message should be triggered by other code to notify the score module when
-/// it should reset the scores to 0 and reflect this on-screen.
+/// This message will be written by the score module when a player has won a game, but not
+/// yet the match (including the winner's PlayerId). Other game logic should listen for this
+/// message, and write StartNextGame once it is time for the next game to begin. A timed game
+/// may be won while the ball is still in play, so the ball should be reset too.
 ///
-#[derive(Message)]
+#[derive(Message, Debug, PartialEq, Eq)]
+pub struct GameWon(pub PlayerId);
+
+///
+/// This message will be written by the score module when a player has won the match
+/// and displayed the results (including the winner's PlayerId). Other game logic should
+/// listen for this message and move to an end-of-match state as well, resetting the ball as
+/// for GameWon.
+///
+#[derive(Message, Debug, PartialEq, Eq)]
+pub struct MaxScoreReached(pub PlayerId);
+
+///
+/// This message will be written by the score module in two player and doubles matches, when
+/// a point leaves a player one point away from winning the game (including that player's
+/// PlayerId). In sudden death, both players are on game point.
+///
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GamePoint(pub PlayerId);
+
+///
+/// This message should be triggered by other code to notify the score module when the
+/// next game of a match begins. The scores are reset to 0, but the games won are kept.
+///
+#[derive(Message, Debug)]
+pub struct StartNextGame;
+
+///
+/// This message should be triggered by other code to notify the score module when a new
+/// match begins. The scores and games won are reset to 0, and this is reflected on-screen.
+///
+#[derive(Message, Debug)]
 pub struct ClearScores;
 
 ///
@@ -116,6 +221,33 @@ pub enum Systems {
     ///
     PlayerScoredRcvr,
 
+    ///
+    /// Systems handling GoalConceded messages occur in this set, so it should be ordered
+    /// after any systems that write this message type if it's necessary that the
+    /// score update should be reflected in the same frame. Must be in Update.
+    ///
+    GoalConcededRcvr,
+
+    ///
+    /// Systems sending PlayerEliminated messages occur in this set, so it should be ordered
+    /// before any systems that read this message type if its necessary that they
+    /// react to it in the same frame. Must be in Update.
+    ///
+    PlayerEliminatedSndr,
+
+    ///
+    /// Systems handling StartBall messages occur in this set, to run the clock of timed
+    /// games while the ball is in play. Must be in Update.
+    ///
+    StartBallRcvr,
+
+    ///
+    /// Systems sending GameWon messages occur in this set, so it should be ordered
+    /// before any systems that read this message type if its necessary that they
+    /// react to it in the same frame. Must be in Update.
+    ///
+    GameWonSndr,
+
     ///
     /// Systems sending MaxScoreReached messages occur in this set, so it should be ordered
     /// before any systems that read this message type if its necessary that they
@@ -123,22 +255,90 @@ pub enum Systems {
     ///
     MaxScoreReachedSndr,
 
+    ///
+    /// Systems sending GamePoint messages occur in this set, so it should be ordered
+    /// before any systems that read this message type if its necessary that they
+    /// react to it in the same frame. Must be in Update.
+    ///
+    GamePointSndr,
+
     ///
     /// Systems handling ClearScores messages occur in this set, so it should be ordered
     /// after any systems that write this message type if it's necessary that the
     /// score clearing should be reflected in the same frame. Must be in Update.
     ///
     ClearScoresRcvr,
+
+    ///
+    /// Systems handling StartNextGame messages occur in this set, so it should be ordered
+    /// after any systems that write this message type if it's necessary that the
+    /// score reset should be reflected in the same frame. Must be in Update.
+    ///
+    StartNextGameRcvr,
 }
 
 // -------------------------------------------------------------------------------------------------
 // Private Resources
 
-// Resource to track the current score of each player
-#[derive(Resource, Default, Debug, PartialEq, Eq)]
-struct Score {
-    p1: u8,
-    p2: u8,
+//
+// Resource to track the current score of each player, indexed by PlayerId. In four player
+// mode, this is the number of goals each player has conceded rather than scored.
+//
+#[derive(Resource, Debug, PartialEq, Eq)]
+struct Score(PerPlayer<u8>);
+
+impl Default for Score {
+    fn default() -> Self {
+        Score(PerPlayer::filled(&PlayerId::ALL, 0))
+    }
+}
+
+impl Index<PlayerId> for Score {
+    type Output = u8;
+
+    fn index(&self, player: PlayerId) -> &u8 {
+        &self.0[player]
+    }
+}
+
+impl IndexMut<PlayerId> for Score {
+    fn index_mut(&mut self, player: PlayerId) -> &mut u8 {
+        &mut self.0[player]
+    }
+}
+
+// Resource to track the number of games each player has won in the current match.
+#[derive(Resource, Debug, PartialEq, Eq)]
+struct GamesWon(PerPlayer<u8>);
+
+impl Default for GamesWon {
+    fn default() -> Self {
+        GamesWon(PerPlayer::filled(&PlayerId::ALL, 0))
+    }
+}
+
+//
+// Resource to track how long the ball has been in play during the current game, for timed
+// games. It runs from each StartBall message until the next point is scored.
+//
+#[derive(Resource, Debug)]
+struct GameClock(Stopwatch);
+
+impl Default for GameClock {
+    fn default() -> Self {
+        let mut stopwatch = Stopwatch::new();
+        stopwatch.pause();
+        GameClock(stopwatch)
+    }
+}
+
+impl GameClock {
+    // Whether the time limit of the game has run out, if it is a timed game.
+    fn time_up(&self, condition: WinCondition) -> bool {
+        condition
+            .time_limit()
+            .is_some_and(|limit| self.0.elapsed() >= limit)
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -148,97 +348,203 @@ struct Score {
 #[derive(Component)]
 struct ScoreText(PlayerId);
 
-// Component for the WinText Entity of each player ("Player X Won!")
+// Component for the WinText Entity of each player ("<Name> Wins!")
 #[derive(Component)]
 struct WinText(PlayerId);
 
+// Component for the NameText Entity of each player (their name, shown above their score)
+#[derive(Component)]
+struct NameText(PlayerId);
+
+// Component for the SetScoreText Entity (games won by each player, when there is more than one)
+#[derive(Component)]
+struct SetScoreText;
+
+// Component for the ClockText Entity (time left in a timed game)
+#[derive(Component)]
+struct ClockText;
+
 // -------------------------------------------------------------------------------------------------
 // Private Systems
 
 //
-// Startup system to spawn each of the 4 on-screen Entities managed by the score
-// module. Note that content and visibility of each may change, but they are
+// Startup system to spawn the on-screen Entities managed by the score module.
+// Note that content and visibility of each may change, but they are
 // all spawned during startup and exist throughout the duration of the game.
 //
-// The first 2 Entities are ScoreText - one on each side of the screen
-// for each player. They each start at "0" and will count up each time the
-// associated player scores. They will always be visible.
-//
-// The other 2 Entities are WinText - one on each side of the screen for each
-// player. Each has appropriate text to announce when that player wins. The
-// text of these will never change, but they both start hidden and will only
-// be made visible once the associated player has won the game.
-//
-fn setup(mut commands: Commands, camera_entity: Single<Entity, With<Camera2d>>) {
-    commands.spawn((
-        ScoreText(Player1),
-        DynamicFontSize {
-            height_in_world: SCORE_TEXT_HEIGHT,
-            render_camera: camera_entity.entity(),
-        },
-        Text2d::new("0"),
-        Anchor::TOP_CENTER,
-        Transform::from_translation(Vec3::new(
-            LEFT_SIDE_CENTER_X,
-            SCORE_TEXT_Y,
-            Z_BEHIND_GAMEPLAY,
-        )),
-    ));
-
-    commands.spawn((
-        ScoreText(Player2),
-        DynamicFontSize {
-            height_in_world: SCORE_TEXT_HEIGHT,
-            render_camera: camera_entity.entity(),
-        },
-        Text2d::new("0"),
-        Anchor::TOP_CENTER,
-        Transform::from_translation(Vec3::new(
-            RIGHT_SIDE_CENTER_X,
-            SCORE_TEXT_Y,
-            Z_BEHIND_GAMEPLAY,
-        )),
-    ));
-
-    commands.spawn((
-        WinText(Player1),
-        DynamicFontSize {
-            height_in_world: WIN_TEXT_HEIGHT,
-            render_camera: camera_entity.entity(),
-        },
-        Text2d::new(P1_WIN_TEXT),
-        Anchor::TOP_CENTER,
-        Transform::from_translation(Vec3::new(LEFT_SIDE_CENTER_X, WIN_TEXT_Y, Z_BEHIND_GAMEPLAY)),
-        Visibility::Hidden,
-    ));
-
-    commands.spawn((
-        WinText(Player2),
-        DynamicFontSize {
-            height_in_world: WIN_TEXT_HEIGHT,
-            render_camera: camera_entity.entity(),
-        },
-        Text2d::new(P2_WIN_TEXT),
-        Anchor::TOP_CENTER,
-        Transform::from_translation(Vec3::new(
-            RIGHT_SIDE_CENTER_X,
-            WIN_TEXT_Y,
-            Z_BEHIND_GAMEPLAY,
-        )),
-        Visibility::Hidden,
-    ));
+// Each player in the current GameMode gets a ScoreText, on their side of the
+// screen. It starts at the initial score and changes each time the associated
+// player scores (or concedes, in four player mode). It will always be visible.
+//
+// Each player also gets a NameText just above their ScoreText, and a WinText with
+// appropriate text to announce when that player wins. The text of these only changes
+// along with the player's name. WinTexts start hidden, and will only be made visible
+// once the associated player has won the match.
+//
+// Outside of four player mode, a SetScoreText and ClockText are spawned at the top center of
+// the arena, between the scores. These are left empty unless the match has more than one game,
+// or the games are timed, respectively.
+//
+fn setup(
+    mut commands: Commands,
+    camera_entity: Single<Entity, With<Camera2d>>,
+    mode: Res<GameMode>,
+    names: Res<PlayerNames>,
+    settings: Res<Settings>,
+) {
+    for &player in mode.players() {
+        let (name_translation, name_anchor, name_height) = name_text_layout(*mode, player);
+        commands.spawn((
+            NameText(player),
+            DynamicFontSize {
+                height_in_world: name_height,
+                render_camera: camera_entity.entity(),
+                max_width_in_world: None,
+            },
+            Text2d::new(names.name(player)),
+            name_anchor,
+            Transform::from_translation(name_translation),
+        ));
+
+        let (score_translation, score_anchor, score_height) = score_text_layout(*mode, player);
+        commands.spawn((
+            ScoreText(player),
+            DynamicFontSize {
+                height_in_world: score_height,
+                render_camera: camera_entity.entity(),
+                max_width_in_world: None,
+            },
+            Text2d::new(score_display(*mode, 0)),
+            score_anchor,
+            Transform::from_translation(score_translation),
+        ));
+
+        let (win_translation, win_anchor, win_height, win_max_width) =
+            win_text_layout(*mode, player);
+        commands.spawn((
+            WinText(player),
+            DynamicFontSize {
+                height_in_world: win_height,
+                render_camera: camera_entity.entity(),
+                max_width_in_world: Some(win_max_width),
+            },
+            Text2d::new(win_text(&names, player)),
+            win_anchor,
+            Transform::from_translation(win_translation),
+            Visibility::Hidden,
+        ));
+    }
+
+    if *mode != GameMode::FourPlayer {
+        commands.spawn((
+            SetScoreText,
+            DynamicFontSize {
+                height_in_world: MATCH_TEXT_HEIGHT,
+                render_camera: camera_entity.entity(),
+                max_width_in_world: None,
+            },
+            Text2d::new(set_score_display(*mode, &settings, &GamesWon::default())),
+            Anchor::TOP_CENTER,
+            Transform::from_xyz(0f32, SET_SCORE_TEXT_Y, Z_BEHIND_GAMEPLAY),
+        ));
+        commands.spawn((
+            ClockText,
+            DynamicFontSize {
+                height_in_world: MATCH_TEXT_HEIGHT,
+                render_camera: camera_entity.entity(),
+                max_width_in_world: None,
+            },
+            Text2d::new(clock_display(
+                settings.gameplay.win_condition,
+                &GameClock::default(),
+                true,
+            )),
+            Anchor::TOP_CENTER,
+            Transform::from_xyz(0f32, CLOCK_TEXT_Y, Z_BEHIND_GAMEPLAY),
+        ));
+    }
+}
+
+//
+// System to run the clock of the current game, from each StartBall message until a point is
+// scored. When the time of a timed game runs out with a player in the lead, the clock stops
+// and that player wins the game there and then, writing GameWon or MaxScoreReached as for a
+// winning point. With the scores level, the game goes on in sudden death instead.
+//
+#[allow(clippy::too_many_arguments)]
+fn run_game_clock(
+    mut start_msgs: MessageReader<StartBall>,
+    mut game_won_msgs: MessageWriter<GameWon>,
+    mut max_score_msgs: MessageWriter<MaxScoreReached>,
+    time: Res<Time>,
+    mode: Res<GameMode>,
+    settings: Res<Settings>,
+    scores: Res<Score>,
+    mut games: ResMut<GamesWon>,
+    mut clock: ResMut<GameClock>,
+    win_texts: Query<(&mut Visibility, &WinText)>,
+) {
+    if !start_msgs.is_empty() {
+        start_msgs.clear();
+        clock.0.unpause();
+    }
+
+    let gameplay = &settings.gameplay;
+    let was_time_up = clock.time_up(gameplay.win_condition);
+    clock.0.tick(time.delta());
+    if was_time_up || !clock.time_up(gameplay.win_condition) {
+        return;
+    }
+
+    let side_scores: Vec<(PlayerId, u8)> = mode
+        .players()
+        .iter()
+        .map(|&player| (player, scores[player]))
+        .collect();
+    let Some(winner) =
+        gameplay
+            .win_condition
+            .game_winner(gameplay.winning_score, &side_scores, true)
+    else {
+        return;
+    };
+
+    clock.0.pause();
+    let match_won = record_game_won(
+        winner,
+        gameplay,
+        &mut games,
+        &mut game_won_msgs,
+        &mut max_score_msgs,
+    );
+    if match_won {
+        for (mut vis, _) in win_texts.into_iter().filter(|(_, text)| text.0 == winner) {
+            *vis = Visibility::Visible;
+        }
+    }
 }
 
 //
 // System to handle messages generated when a player has scored. This system
-// will update the score as needed (both internally and adjust entities).
-// It will also check after each score received whether or not a player has
-// won. If so, it will generate the MaxScoreReached message as an output.
+// will update the score as needed (both internally and adjust entities), and stop
+// the game clock until the next round starts.
+//
+// It will also check after each score received whether or not a player has won the game,
+// according to the WinCondition. If so, it will generate the GameWon message as an output,
+// or the MaxScoreReached message instead if that was the last game the player needed.
+// Otherwise, a GamePoint message is generated for any player one point away from winning.
 //
+#[allow(clippy::too_many_arguments)]
 fn handle_player_score(
     mut score_msgs: MessageReader<PlayerScored>,
+    mut game_won_msgs: MessageWriter<GameWon>,
     mut max_score_msgs: MessageWriter<MaxScoreReached>,
+    mut game_point_msgs: MessageWriter<GamePoint>,
+    mode: Res<GameMode>,
+    settings: Res<Settings>,
     mut scores: ResMut<Score>,
+    mut games: ResMut<GamesWon>,
+    mut clock: ResMut<GameClock>,
     score_texts: Query<(&mut Text2d, &ScoreText)>,
     win_texts: Query<(&mut Visibility, &WinText)>,
 ) {
@@ -247,57 +553,147 @@ fn handle_player_score(
         return;
     }
 
-    let (p1_score_txt, p2_score_txt) = score_texts
-        .into_iter()
-        .map(|(text2d, score_text)| (score_text.0, text2d.into_inner()))
-        .as_per_player();
-
-    let (p1_win_txt, p2_win_txt) = win_texts
-        .into_iter()
-        .map(|(vis, win_text)| (win_text.0, vis.into_inner()))
-        .as_per_player();
+    let (mut score_texts, mut win_texts) = collect_texts(*mode, score_texts, win_texts);
 
     // Handle each score message (realistically only one will have happened)
-    for PlayerScored(scorer) in score_msgs.read() {
-        // Add to score for applicable player
-        match scorer {
-            Player1 => {
-                scores.p1 += 1;
-                p1_score_txt.0 = scores.p1.to_string();
+    let gameplay = &settings.gameplay;
+    for &PlayerScored(scorer) in score_msgs.read() {
+        // A point can't be scored once the clock has already ended the game
+        let time_up = clock.time_up(gameplay.win_condition);
+        if time_up && clock.0.is_paused() {
+            continue;
+        }
+
+        // Add to score for applicable player. Timed and win-by-two games have no score cap,
+        // so the score saturates rather than overflowing in a very long game.
+        scores[scorer] = scores[scorer].saturating_add(1);
+        if let Some(score_texts) = &mut score_texts {
+            score_texts[scorer].0 = score_display(*mode, scores[scorer]);
+        }
+        clock.0.pause();
+
+        // Detect if a player has won the game, and with it the match
+        let side_scores: Vec<(PlayerId, u8)> = mode
+            .players()
+            .iter()
+            .map(|&player| (player, scores[player]))
+            .collect();
+        let Some(winner) =
+            gameplay
+                .win_condition
+                .game_winner(gameplay.winning_score, &side_scores, time_up)
+        else {
+            for player in game_point_players(gameplay, &side_scores, time_up) {
+                game_point_msgs.write(GamePoint(player));
             }
-            Player2 => {
-                scores.p2 += 1;
-                p2_score_txt.0 = scores.p2.to_string();
+            continue;
+        };
+
+        let match_won = record_game_won(
+            winner,
+            gameplay,
+            &mut games,
+            &mut game_won_msgs,
+            &mut max_score_msgs,
+        );
+        if let (true, Some(win_texts)) = (match_won, &mut win_texts) {
+            *win_texts[winner] = Visibility::Visible;
+        }
+        break;
+    }
+}
+
+//
+// System to handle messages generated in four player mode when a player has conceded
+// a goal. This system will update the score as needed (both internally and adjust
+// entities). If the player has conceded too many goals, it will generate the
+// PlayerEliminated message. If only one player then remains, they have won, and
+// the MaxScoreReached message will be generated too.
+//
+fn handle_goal_conceded(
+    mut conceded_msgs: MessageReader<GoalConceded>,
+    mut eliminated_msgs: MessageWriter<PlayerEliminated>,
+    mut max_score_msgs: MessageWriter<MaxScoreReached>,
+    mode: Res<GameMode>,
+    mut scores: ResMut<Score>,
+    score_texts: Query<(&mut Text2d, &ScoreText)>,
+    win_texts: Query<(&mut Visibility, &WinText)>,
+) {
+    // Early return in case of no messages
+    if conceded_msgs.is_empty() {
+        return;
+    }
+
+    let (mut score_texts, mut win_texts) = collect_texts(*mode, score_texts, win_texts);
+
+    for &GoalConceded(player) in conceded_msgs.read() {
+        // Eliminated players can't concede any further
+        if scores[player] >= ELIMINATION_GOALS {
+            continue;
+        }
+
+        scores[player] = scores[player].saturating_add(1);
+        if let Some(score_texts) = &mut score_texts {
+            score_texts[player].0 = score_display(*mode, scores[player]);
+        }
+
+        if scores[player] >= ELIMINATION_GOALS {
+            eliminated_msgs.write(PlayerEliminated(player));
+
+            // Detect if only a single player is left standing
+            let mut remaining = scores.0.iter().filter(|&(player, &conceded)| {
+                mode.players().contains(&player) && (conceded < ELIMINATION_GOALS)
+            });
+            if let (Some((winner, _)), None) = (remaining.next(), remaining.next()) {
+                max_score_msgs.write(MaxScoreReached(winner));
+                if let Some(win_texts) = &mut win_texts {
+                    *win_texts[winner] = Visibility::Visible;
+                }
+                break;
             }
         }
+    }
+}
 
-        // Detect if either player has won
-        if scores.p1 >= WINNING_SCORE {
-            max_score_msgs.write(MaxScoreReached);
-            *p1_win_txt = Visibility::Visible;
-            break;
-        } else if scores.p2 >= WINNING_SCORE {
-            max_score_msgs.write(MaxScoreReached);
-            *p2_win_txt = Visibility::Visible;
-            break;
+// System to clear scores back to 0 for the next game of a match, keeping the games won.
+fn start_next_game(
+    mut messages: MessageReader<StartNextGame>,
+    mode: Res<GameMode>,
+    mut scores: ResMut<Score>,
+    mut clock: ResMut<GameClock>,
+    score_texts: Query<&mut Text2d, With<ScoreText>>,
+) {
+    if !messages.is_empty() {
+        messages.clear();
+
+        *scores = Score::default();
+        *clock = GameClock::default();
+
+        for mut score_text in score_texts.into_iter() {
+            score_text.0 = score_display(*mode, 0);
         }
     }
 }
 
-// System to clear scores back to 0 and return UI elements to original states
+// System to clear scores and games won back to 0 and return UI elements to original states
 fn clear_scores(
     mut messages: MessageReader<ClearScores>,
+    mode: Res<GameMode>,
     mut scores: ResMut<Score>,
+    mut games: ResMut<GamesWon>,
+    mut clock: ResMut<GameClock>,
     score_texts: Query<&mut Text2d, With<ScoreText>>,
     win_texts: Query<&mut Visibility, With<WinText>>,
 ) {
     if !messages.is_empty() {
         messages.clear();
 
-        *scores = Score { p1: 0, p2: 0 };
+        *scores = Score::default();
+        *games = GamesWon::default();
+        *clock = GameClock::default();
 
         for mut score_text in score_texts.into_iter() {
-            score_text.0 = String::from("0");
+            score_text.0 = score_display(*mode, 0);
         }
 
         for mut win_text in win_texts.into_iter() {
@@ -306,6 +702,272 @@ fn clear_scores(
     }
 }
 
+// System to relabel each player's NameText and WinText, whenever the PlayerNames change.
+fn update_player_names(
+    names: Res<PlayerNames>,
+    name_texts: Query<(&mut Text2d, &NameText), Without<WinText>>,
+    win_texts: Query<(&mut Text2d, &WinText), Without<NameText>>,
+) {
+    for (mut text, &NameText(player)) in name_texts {
+        text.0 = names.name(player);
+    }
+    for (mut text, &WinText(player)) in win_texts {
+        text.0 = win_text(&names, player);
+    }
+}
+
+// System to update the SetScoreText, whenever the games won or the number of games change.
+fn update_set_score_text(
+    mode: Res<GameMode>,
+    settings: Res<Settings>,
+    games: Res<GamesWon>,
+    set_score_texts: Query<&mut Text2d, With<SetScoreText>>,
+) {
+    for mut set_score_text in set_score_texts {
+        set_score_text.0 = set_score_display(*mode, &settings, &games);
+    }
+}
+
+// System to update the ClockText with the time left in the game, if it is a timed game.
+fn update_clock_text(
+    mode: Res<GameMode>,
+    settings: Res<Settings>,
+    scores: Res<Score>,
+    clock: Res<GameClock>,
+    clock_texts: Query<&mut Text2d, With<ClockText>>,
+) {
+    let level = mode
+        .players()
+        .iter()
+        .all(|&player| scores[player] == scores[Player1]);
+    let display = clock_display(settings.gameplay.win_condition, &clock, level);
+
+    // Only touch the text when it changes, to avoid needless text layout
+    for mut clock_text in clock_texts {
+        if clock_text.0 != display {
+            clock_text.0.clone_from(&display);
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Functions
+
+// Get the players who would win the game, with the given scores, by scoring the next point.
+fn game_point_players(
+    gameplay: &GameplaySettings,
+    scores: &[(PlayerId, u8)],
+    time_up: bool,
+) -> Vec<PlayerId> {
+    scores
+        .iter()
+        .map(|&(player, _)| player)
+        .filter(|&player| {
+            let next_scores: Vec<(PlayerId, u8)> = scores
+                .iter()
+                .map(|&(other, score)| (other, score.saturating_add(u8::from(other == player))))
+                .collect();
+            gameplay
+                .win_condition
+                .game_winner(gameplay.winning_score, &next_scores, time_up)
+                == Some(player)
+        })
+        .collect()
+}
+
+//
+// Get the text to display for a player's score. In four player mode, the score counts the
+// goals conceded, so the number of goals the player may still concede is shown instead.
+//
+fn score_display(mode: GameMode, score: u8) -> String {
+    match mode {
+        GameMode::TwoPlayer | GameMode::Doubles => score.to_string(),
+        GameMode::FourPlayer => ELIMINATION_GOALS.saturating_sub(score).to_string(),
+    }
+}
+
+//
+// Get the text to display for the games won by each player in the current match, e.g.
+// "Games 1 - 0". Empty if a match is just a single game.
+//
+fn set_score_display(mode: GameMode, settings: &Settings, games: &GamesWon) -> String {
+    if settings.gameplay.best_of <= 1 {
+        return String::new();
+    }
+
+    let games_won: Vec<String> = mode
+        .players()
+        .iter()
+        .map(|&player| games.0[player].to_string())
+        .collect();
+    format!("Games {}", games_won.join(" - "))
+}
+
+//
+// Get the text to display for the time left in the current game. Empty if the game isn't
+// timed. Once time runs out with the scores level, sudden death is announced instead.
+//
+fn clock_display(condition: WinCondition, clock: &GameClock, level: bool) -> String {
+    let Some(limit) = condition.time_limit() else {
+        return String::new();
+    };
+    if clock.time_up(condition) && level {
+        return SUDDEN_DEATH_TEXT.to_string();
+    }
+
+    let secs_left = limit.saturating_sub(clock.0.elapsed()).as_secs_f32().ceil() as u32;
+    format!("{}:{:02}", secs_left / 60, secs_left % 60)
+}
+
+// Get the text announcing the given player has won, by name.
+fn win_text(names: &PlayerNames, player: PlayerId) -> String {
+    format!("{} Wins!", names.name(player))
+}
+
+//
+// Get the (translation, anchor, height in world) for a player's ScoreText. In two player mode,
+// scores (or doubles team scores) sit at the top of each half of the arena, under the names of
+// the players. In four player mode, each score sits just inside the edge of the arena defended
+// by that player.
+//
+fn score_text_layout(mode: GameMode, player: PlayerId) -> (Vec3, Anchor, f32) {
+    match (mode, player.side()) {
+        (GameMode::TwoPlayer | GameMode::Doubles, ArenaSide::Left) => (
+            Vec3::new(LEFT_SIDE_CENTER_X, SCORE_TEXT_Y, Z_BEHIND_GAMEPLAY),
+            Anchor::TOP_CENTER,
+            SCORE_TEXT_HEIGHT,
+        ),
+        (GameMode::TwoPlayer | GameMode::Doubles, _) => (
+            Vec3::new(RIGHT_SIDE_CENTER_X, SCORE_TEXT_Y, Z_BEHIND_GAMEPLAY),
+            Anchor::TOP_CENTER,
+            SCORE_TEXT_HEIGHT,
+        ),
+        (GameMode::FourPlayer, side) => {
+            let edge = -side.inward_normal() * Vec2::new(ARENA_WIDTH / 2f32, ARENA_HEIGHT / 2f32);
+            let translation = edge + (side.inward_normal() * PADDING_FROM_EDGE);
+            let anchor = match side {
+                ArenaSide::Left => Anchor::CENTER_LEFT,
+                ArenaSide::Right => Anchor::CENTER_RIGHT,
+                ArenaSide::Top => Anchor::TOP_CENTER,
+                ArenaSide::Bottom => Anchor::BOTTOM_CENTER,
+            };
+            (
+                translation.extend(Z_BEHIND_GAMEPLAY),
+                anchor,
+                FOUR_PLAYER_SCORE_HEIGHT,
+            )
+        }
+    }
+}
+
+//
+// Get the (translation, anchor, height in world) for a player's NameText. It sits just above
+// the top of the player's ScoreText, aligned the same way horizontally.
+//
+fn name_text_layout(mode: GameMode, player: PlayerId) -> (Vec3, Anchor, f32) {
+    let (score_translation, score_anchor, score_height) = score_text_layout(mode, player);
+    let score_top = score_translation.y + (score_height * (0.5 - score_anchor.as_vec().y));
+    let height = match mode {
+        GameMode::TwoPlayer | GameMode::Doubles => NAME_TEXT_HEIGHT,
+        GameMode::FourPlayer => FOUR_PLAYER_NAME_HEIGHT,
+    };
+    (
+        score_translation.with_y(score_top + PADDING_ABOVE_SCORE),
+        Anchor(Vec2::new(score_anchor.as_vec().x, -0.5)),
+        height,
+    )
+}
+
+//
+// Get the (translation, anchor, height in world, max width in world) for a player's WinText.
+// In two player mode, these sit under each player's score, and are kept within that player's
+// half of the arena so long names don't overflow. In four player mode, only one can be shown
+// at a time, so they all sit in the center of the arena.
+//
+fn win_text_layout(mode: GameMode, player: PlayerId) -> (Vec3, Anchor, f32, f32) {
+    match (mode, player) {
+        (GameMode::TwoPlayer | GameMode::Doubles, Player1) => (
+            Vec3::new(LEFT_SIDE_CENTER_X, WIN_TEXT_Y, Z_BEHIND_GAMEPLAY),
+            Anchor::TOP_CENTER,
+            WIN_TEXT_HEIGHT,
+            WIN_TEXT_MAX_WIDTH,
+        ),
+        (GameMode::TwoPlayer | GameMode::Doubles, _) => (
+            Vec3::new(RIGHT_SIDE_CENTER_X, WIN_TEXT_Y, Z_BEHIND_GAMEPLAY),
+            Anchor::TOP_CENTER,
+            WIN_TEXT_HEIGHT,
+            WIN_TEXT_MAX_WIDTH,
+        ),
+        (GameMode::FourPlayer, _) => (
+            Vec3::new(0f32, 0f32, Z_BEHIND_GAMEPLAY),
+            Anchor::CENTER,
+            FOUR_PLAYER_WIN_HEIGHT,
+            FOUR_PLAYER_WIN_MAX_WIDTH,
+        ),
+    }
+}
+
+// The ScoreText and WinText of each player, mutably borrowed from their queries, if available.
+type PlayerTexts<'a> = (
+    Option<PerPlayer<Mut<'a, Text2d>>>,
+    Option<PerPlayer<Mut<'a, Visibility>>>,
+);
+
+//
+// Record a game won by the given player, writing MaxScoreReached if it was the last game they
+// needed to win the match, or GameWon otherwise. Returns whether the match was won.
+//
+fn record_game_won(
+    winner: PlayerId,
+    gameplay: &GameplaySettings,
+    games: &mut GamesWon,
+    game_won_msgs: &mut MessageWriter<GameWon>,
+    max_score_msgs: &mut MessageWriter<MaxScoreReached>,
+) -> bool {
+    games.0[winner] += 1;
+    let match_won = games.0[winner] >= gameplay.games_to_win();
+    if match_won {
+        max_score_msgs.write(MaxScoreReached(winner));
+    } else {
+        game_won_msgs.write(GameWon(winner));
+    }
+    match_won
+}
+
+//
+// Collect the ScoreText and WinText entities of each player in the game. If any player does
+// not have exactly one of either kind, a warning is logged and None is returned for that
+// kind, so that callers may skip updating that part of the scoreboard rather than crashing.
+// The scores themselves should still be kept by the callers.
+//
+fn collect_texts<'a>(
+    mode: GameMode,
+    score_texts: Query<'a, '_, (&mut Text2d, &ScoreText)>,
+    win_texts: Query<'a, '_, (&mut Visibility, &WinText)>,
+) -> PlayerTexts<'a> {
+    let score_texts = PerPlayer::try_from_iter(
+        mode.players(),
+        score_texts
+            .into_iter()
+            .map(|(text2d, score_text)| (score_text.0, text2d)),
+    );
+    let win_texts = PerPlayer::try_from_iter(
+        mode.players(),
+        win_texts
+            .into_iter()
+            .map(|(vis, win_text)| (win_text.0, vis)),
+    );
+
+    (
+        score_texts
+            .inspect_err(|err| warn!("Unable to update ScoreText entities: {err}"))
+            .ok(),
+        win_texts
+            .inspect_err(|err| warn!("Unable to update WinText entities: {err}"))
+            .ok(),
+    )
+}
+
 // -------------------------------------------------------------------------------------------------
 // Unit Tests
 
@@ -313,6 +975,7 @@ fn clear_scores(
 mod tests {
     use super::*;
     use bevy_test_helpers::prelude::*;
+    use std::time::Duration;
 
     #[test]
     fn test_plugin_build() {
@@ -331,14 +994,38 @@ mod tests {
             world.is_resource_added::<Score>(),
             "Expected Score resource to be added by ScorePlugin"
         );
+        assert!(
+            world.is_resource_added::<GamesWon>(),
+            "Expected GamesWon resource to be added by ScorePlugin"
+        );
+        assert!(
+            world.is_resource_added::<GameClock>(),
+            "Expected GameClock resource to be added by ScorePlugin"
+        );
         assert!(
             world.is_resource_added::<Messages<PlayerScored>>(),
             "Expected PlayerScored messages to be added by ScorePlugin"
         );
+        assert!(
+            world.is_resource_added::<Messages<GoalConceded>>(),
+            "Expected GoalConceded messages to be added by ScorePlugin"
+        );
+        assert!(
+            world.is_resource_added::<Messages<PlayerEliminated>>(),
+            "Expected PlayerEliminated messages to be added by ScorePlugin"
+        );
+        assert!(
+            world.is_resource_added::<Messages<GameWon>>(),
+            "Expected GameWon messages to be added by ScorePlugin"
+        );
         assert!(
             world.is_resource_added::<Messages<MaxScoreReached>>(),
             "Expected MaxScoreReached messages to be added by ScorePlugin"
         );
+        assert!(
+            world.is_resource_added::<Messages<StartNextGame>>(),
+            "Expected StartNextGame messages to be added by ScorePlugin"
+        );
         assert!(
             world.is_resource_added::<Messages<ClearScores>>(),
             "Expected ClearScores messages to be added by ScorePlugin"
@@ -346,33 +1033,113 @@ mod tests {
     }
 
     #[test]
-    fn test_plugin_sys_added_setup() {
-        validate_sys_in_plugin(ScorePlugin, Startup, setup, Some(Systems::SetupAfterCamera));
+    fn test_plugin_sys_added_setup() {
+        validate_sys_in_plugin(ScorePlugin, Startup, setup, Some(Systems::SetupAfterCamera));
+    }
+
+    #[test]
+    fn test_plugin_sys_added_handle_player_score() {
+        validate_sys_in_plugin(
+            ScorePlugin,
+            Update,
+            handle_player_score,
+            Some(Systems::PlayerScoredRcvr),
+        );
+        validate_sys_in_plugin(
+            ScorePlugin,
+            Update,
+            handle_player_score,
+            Some(Systems::GameWonSndr),
+        );
+        validate_sys_in_plugin(
+            ScorePlugin,
+            Update,
+            handle_player_score,
+            Some(Systems::MaxScoreReachedSndr),
+        );
+        validate_sys_in_plugin(
+            ScorePlugin,
+            Update,
+            handle_player_score,
+            Some(Systems::GamePointSndr),
+        );
+    }
+
+    #[test]
+    fn test_plugin_sys_added_run_game_clock() {
+        validate_sys_in_plugin(
+            ScorePlugin,
+            Update,
+            run_game_clock,
+            Some(Systems::StartBallRcvr),
+        );
+    }
+
+    #[test]
+    fn test_plugin_sys_added_handle_goal_conceded() {
+        validate_sys_in_plugin(
+            ScorePlugin,
+            Update,
+            handle_goal_conceded,
+            Some(Systems::GoalConcededRcvr),
+        );
+        validate_sys_in_plugin(
+            ScorePlugin,
+            Update,
+            handle_goal_conceded,
+            Some(Systems::PlayerEliminatedSndr),
+        );
+        validate_sys_in_plugin(
+            ScorePlugin,
+            Update,
+            handle_goal_conceded,
+            Some(Systems::MaxScoreReachedSndr),
+        );
+    }
+
+    #[test]
+    fn test_plugin_sys_added_clear_scores() {
+        validate_sys_in_plugin(
+            ScorePlugin,
+            Update,
+            clear_scores,
+            Some(Systems::ClearScoresRcvr),
+        );
+    }
+
+    #[test]
+    fn test_plugin_sys_added_start_next_game() {
+        validate_sys_in_plugin(
+            ScorePlugin,
+            Update,
+            start_next_game,
+            Some(Systems::StartNextGameRcvr),
+        );
     }
 
     #[test]
-    fn test_plugin_sys_added_handle_player_score() {
+    fn test_plugin_sys_added_match_texts() {
         validate_sys_in_plugin(
             ScorePlugin,
             Update,
-            handle_player_score,
-            Some(Systems::PlayerScoredRcvr),
+            update_set_score_text,
+            None::<bevy::ecs::schedule::AnonymousSet>,
         );
         validate_sys_in_plugin(
             ScorePlugin,
             Update,
-            handle_player_score,
-            Some(Systems::MaxScoreReachedSndr),
+            update_clock_text,
+            None::<bevy::ecs::schedule::AnonymousSet>,
         );
     }
 
     #[test]
-    fn test_plugin_sys_added_clear_scores() {
+    fn test_plugin_sys_added_update_player_names() {
         validate_sys_in_plugin(
             ScorePlugin,
             Update,
-            clear_scores,
-            Some(Systems::ClearScoresRcvr),
+            update_player_names,
+            None::<bevy::ecs::schedule::AnonymousSet>,
         );
     }
 
@@ -382,7 +1149,7 @@ mod tests {
         let world = app.add_plugins(ScorePlugin).world_mut();
 
         world.write_message(PlayerScored(Player1));
-        world.write_message(MaxScoreReached);
+        world.write_message(MaxScoreReached(Player1));
         world.write_message(ClearScores);
 
         // One game loop should not wipe out messages (due to double buffering)
@@ -437,6 +1204,9 @@ mod tests {
     #[test]
     fn test_setup_system() {
         let mut world = World::default();
+        world.init_resource::<GameMode>();
+        world.init_resource::<PlayerNames>();
+        world.init_resource::<Settings>();
 
         // Set up a system to create the Camera2d we'll need, plus the setup system itself
         let cam_create_sys =
@@ -503,6 +1273,12 @@ mod tests {
                 dyn_font.render_camera, cam_entity,
                 "Expected WinText to use Camera2d as render_camera entity"
             );
+            assert!(
+                dyn_font
+                    .max_width_in_world
+                    .is_some_and(|width| width <= ARENA_WIDTH / 2f32),
+                "Expected WinText to be kept within its player's half of the arena"
+            );
             assert_eq!(
                 vis,
                 Visibility::Hidden,
@@ -516,8 +1292,15 @@ mod tests {
         // Create world with necessary resources
         let mut world = World::default();
         world.init_resource::<Messages<PlayerScored>>();
+        world.init_resource::<Messages<GameWon>>();
         world.init_resource::<Messages<MaxScoreReached>>();
+        world.init_resource::<Messages<GamePoint>>();
         world.init_resource::<Score>();
+        world.init_resource::<GamesWon>();
+        world.init_resource::<GameClock>();
+        world.init_resource::<Settings>();
+        world.init_resource::<GameMode>();
+        world.init_resource::<PlayerNames>();
 
         // Systems we'll need for this test
         let cam_create_sys = world.register_system(
@@ -596,7 +1379,7 @@ mod tests {
         );
 
         // Prime ourselves for a victory on next score, then simulate p1 win
-        *world.get_resource_mut::<Score>().unwrap() = Score { p1: 9, p2: 9 };
+        *world.get_resource_mut::<Score>().unwrap() = Score(PerPlayer::from([9, 9, 0, 0]));
         world
             .query::<(&ScoreText, &mut Text2d)>()
             .iter_mut(&mut world)
@@ -619,8 +1402,9 @@ mod tests {
             world
                 .get_resource::<Messages<MaxScoreReached>>()
                 .unwrap()
-                .len(),
-            1,
+                .iter_current_update_messages()
+                .collect::<Vec<_>>(),
+            vec![&MaxScoreReached(Player1)],
             "Expected 1 MaxScoreReached message after run with p1 winning",
         );
         world
@@ -629,7 +1413,7 @@ mod tests {
             .clear(); // Clear for next test
 
         // Prime ourselves for a victory on next score, then simulate p2 win
-        *world.get_resource_mut::<Score>().unwrap() = Score { p1: 9, p2: 9 };
+        *world.get_resource_mut::<Score>().unwrap() = Score(PerPlayer::from([9, 9, 0, 0]));
         world
             .query_filtered::<&mut Text2d, With<ScoreText>>()
             .iter_mut(&mut world)
@@ -658,18 +1442,293 @@ mod tests {
             world
                 .get_resource::<Messages<MaxScoreReached>>()
                 .unwrap()
-                .len(),
-            1,
+                .iter_current_update_messages()
+                .collect::<Vec<_>>(),
+            vec![&MaxScoreReached(Player2)],
             "Expected 1 MaxScoreReached message after run with p2 winning",
         );
     }
 
+    #[test]
+    fn test_handle_player_score_missing_text() {
+        let mut world = World::default();
+        world.init_resource::<Messages<PlayerScored>>();
+        world.init_resource::<Messages<GameWon>>();
+        world.init_resource::<Messages<MaxScoreReached>>();
+        world.init_resource::<Messages<GamePoint>>();
+        world.init_resource::<Score>();
+        world.init_resource::<GamesWon>();
+        world.init_resource::<GameClock>();
+        world.init_resource::<Settings>();
+        world.init_resource::<GameMode>();
+
+        // Only spawn text entities for Player 1, so Player 2's are missing
+        world.spawn((Text2d::new("0"), ScoreText(Player1)));
+        world.spawn((Visibility::Hidden, WinText(Player1)));
+        world.write_message(PlayerScored(Player1));
+
+        // Expect the point to count, skipping only the text updates
+        let score_sys = world.register_system(handle_player_score);
+        world.run_system(score_sys).unwrap();
+        assert_eq!(
+            world.resource::<Score>()[Player1],
+            1,
+            "Expected the point to count when ScoreText entities are missing",
+        );
+        let mut score_texts = world.query::<(&Text2d, &ScoreText)>();
+        let (text, _) = score_texts.single(&world).unwrap();
+        assert_eq!(
+            text.0, "0",
+            "Expected the incomplete scoreboard to be left as is"
+        );
+    }
+
+    #[test]
+    fn test_player_name_texts() {
+        let mut world = World::default();
+        world.init_resource::<GameMode>();
+        world.init_resource::<Settings>();
+        let mut names = PlayerNames::default();
+        names.0.insert(Player1, "Ada".to_string());
+        world.insert_resource(names);
+        world.spawn(Camera2d);
+
+        let setup_sys = world.register_system(setup);
+        let update_sys = world.register_system(update_player_names);
+        world.run_system(setup_sys).unwrap();
+
+        // Names are shown above each score, and used to announce the winner
+        assert_eq!(
+            player_texts(&mut world, |name: &NameText| name.0),
+            vec![
+                (Player1, "Ada".to_string()),
+                (Player2, "Player 2".to_string())
+            ],
+        );
+        assert_eq!(
+            player_texts(&mut world, |win: &WinText| win.0),
+            vec![
+                (Player1, "Ada Wins!".to_string()),
+                (Player2, "Player 2 Wins!".to_string()),
+            ],
+        );
+
+        // Changing the names relabels the scoreboard
+        world
+            .resource_mut::<PlayerNames>()
+            .0
+            .insert(Player2, "Bob".to_string());
+        world.run_system(update_sys).unwrap();
+        assert_eq!(
+            player_texts(&mut world, |name: &NameText| name.0),
+            vec![(Player1, "Ada".to_string()), (Player2, "Bob".to_string())],
+        );
+        assert_eq!(
+            player_texts(&mut world, |win: &WinText| win.0),
+            vec![
+                (Player1, "Ada Wins!".to_string()),
+                (Player2, "Bob Wins!".to_string()),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_name_text_layout() {
+        for mode in [GameMode::TwoPlayer, GameMode::FourPlayer] {
+            for &player in mode.players() {
+                let (score_translation, _, _) = score_text_layout(mode, player);
+                let (name_translation, name_anchor, name_height) = name_text_layout(mode, player);
+                assert_eq!(
+                    name_translation.x, score_translation.x,
+                    "Expected {player:?} name to be aligned with their score in {mode:?} mode",
+                );
+                assert_eq!(
+                    name_anchor.as_vec().y,
+                    -0.5,
+                    "Expected {player:?} name to be anchored at its bottom in {mode:?} mode",
+                );
+                assert!(
+                    name_translation.y > score_translation.y,
+                    "Expected {player:?} name to sit above their score in {mode:?} mode",
+                );
+                assert!(
+                    name_translation.y + name_height <= ARENA_HEIGHT / 2f32,
+                    "Expected {player:?} name to fit in the arena in {mode:?} mode",
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_setup_system_four_player() {
+        let mut world = World::default();
+        world.insert_resource(GameMode::FourPlayer);
+        world.init_resource::<PlayerNames>();
+        world.init_resource::<Settings>();
+
+        let cam_create_sys = world.register_system(|mut commands: Commands| {
+            commands.spawn(Camera2d);
+        });
+        let setup_sys = world.register_system(setup);
+        world.run_system(cam_create_sys).unwrap();
+        world.run_system(setup_sys).unwrap();
+
+        // Every player should have one ScoreText, showing the goals they may concede
+        let mut query = world.query::<(&ScoreText, &Text2d)>();
+        assert_eq!(
+            query.iter(&world).len(),
+            4,
+            "Expected 4 ScoreTexts from setup in four player mode",
+        );
+        for player in PlayerId::ALL {
+            let texts: Vec<&Text2d> = query
+                .iter(&world)
+                .filter(|(score_text, _)| score_text.0 == player)
+                .map(|(_, text2d)| text2d)
+                .collect();
+            assert_eq!(texts.len(), 1, "Expected one ScoreText for {player:?}");
+            assert_eq!(
+                texts[0].0,
+                ELIMINATION_GOALS.to_string(),
+                "Expected {player:?} ScoreText to start at {ELIMINATION_GOALS}",
+            );
+        }
+
+        // Every player should have one hidden WinText
+        let mut query = world.query::<(&WinText, &Visibility)>();
+        assert_eq!(
+            query.iter(&world).len(),
+            4,
+            "Expected 4 WinTexts from setup in four player mode",
+        );
+        for (win_text, vis) in query.iter(&world) {
+            assert_eq!(
+                vis,
+                Visibility::Hidden,
+                "Expected {:?} WinText to start as hidden",
+                win_text.0,
+            );
+        }
+    }
+
+    #[test]
+    fn test_handle_goal_conceded_system() {
+        let mut world = World::default();
+        world.insert_resource(GameMode::FourPlayer);
+        world.init_resource::<PlayerNames>();
+        world.init_resource::<Messages<GoalConceded>>();
+        world.init_resource::<Messages<PlayerEliminated>>();
+        world.init_resource::<Messages<MaxScoreReached>>();
+        world.init_resource::<Messages<GamePoint>>();
+        world.init_resource::<Score>();
+        world.init_resource::<Settings>();
+
+        let cam_create_sys = world.register_system(|mut commands: Commands| {
+            commands.spawn(Camera2d);
+        });
+        let setup_sys = world.register_system(setup);
+        let conceded_sys = world.register_system(handle_goal_conceded);
+        world.run_system(cam_create_sys).unwrap();
+        world.run_system(setup_sys).unwrap();
+
+        // A single goal against player 3 should reduce their remaining goals
+        world.write_message(GoalConceded(Player3));
+        world.run_system(conceded_sys).unwrap();
+        assert_eq!(
+            *world.get_resource::<Score>().unwrap(),
+            Score(PerPlayer::from([0, 0, 1, 0])),
+            "Expected player 3 to have conceded 1 goal",
+        );
+        let mut query = world.query::<(&ScoreText, &Text2d)>();
+        for (score_text, text2d) in query.iter(&world) {
+            let exp_text = if score_text.0 == Player3 {
+                (ELIMINATION_GOALS - 1).to_string()
+            } else {
+                ELIMINATION_GOALS.to_string()
+            };
+            assert_eq!(
+                text2d.0, exp_text,
+                "Expected {:?} ScoreText of {exp_text}",
+                score_text.0,
+            );
+        }
+        assert!(
+            world
+                .get_resource::<Messages<PlayerEliminated>>()
+                .unwrap()
+                .is_empty(),
+            "Expected no PlayerEliminated message after a single goal",
+        );
+
+        // Eliminate players 1 and 2. Expect an elimination message but no winner yet.
+        *world.get_resource_mut::<Score>().unwrap() = Score(PerPlayer::from([4, 4, 1, 0]));
+        world.write_message(GoalConceded(Player1));
+        world.write_message(GoalConceded(Player2));
+        world.run_system(conceded_sys).unwrap();
+        let eliminated_msgs = world.get_resource::<Messages<PlayerEliminated>>().unwrap();
+        let eliminated: Vec<&PlayerEliminated> =
+            eliminated_msgs.iter_current_update_messages().collect();
+        assert_eq!(
+            eliminated,
+            vec![&PlayerEliminated(Player1), &PlayerEliminated(Player2)],
+            "Expected players 1 and 2 to be eliminated",
+        );
+        assert!(
+            world
+                .get_resource::<Messages<MaxScoreReached>>()
+                .unwrap()
+                .is_empty(),
+            "Expected no MaxScoreReached message with two players remaining",
+        );
+
+        // Further goals against eliminated players are ignored
+        world.write_message(GoalConceded(Player1));
+        world.run_system(conceded_sys).unwrap();
+        assert_eq!(
+            world.get_resource::<Score>().unwrap()[Player1],
+            ELIMINATION_GOALS,
+            "Expected eliminated player's score to be unchanged",
+        );
+
+        // Eliminate player 3. Player 4 is the last one standing, and wins.
+        *world.get_resource_mut::<Score>().unwrap() = Score(PerPlayer::from([5, 5, 4, 0]));
+        world.write_message(GoalConceded(Player3));
+        world.run_system(conceded_sys).unwrap();
+        assert_eq!(
+            world
+                .get_resource::<Messages<MaxScoreReached>>()
+                .unwrap()
+                .iter_current_update_messages()
+                .collect::<Vec<_>>(),
+            vec![&MaxScoreReached(Player4)],
+            "Expected 1 MaxScoreReached message once a single player remains",
+        );
+        let mut query = world.query::<(&WinText, &Visibility)>();
+        for (win_text, vis) in query.iter(&world) {
+            let exp_vis = if win_text.0 == Player4 {
+                Visibility::Visible
+            } else {
+                Visibility::Hidden
+            };
+            assert_eq!(
+                vis, exp_vis,
+                "Expected {:?} WinText visibility {exp_vis:?}",
+                win_text.0,
+            );
+        }
+    }
+
     #[test]
     fn test_clear_scores_system() {
         // Create world with necessary resources
         let mut world = World::default();
         world.init_resource::<Messages<ClearScores>>();
         world.init_resource::<Score>();
+        world.init_resource::<GamesWon>();
+        world.init_resource::<GameClock>();
+        world.init_resource::<Settings>();
+        world.init_resource::<GameMode>();
+        world.init_resource::<PlayerNames>();
 
         // Systems we'll need for this test
         let cam_create_sys = world.register_system(
@@ -686,7 +1745,7 @@ mod tests {
         world.run_system(setup_sys).unwrap();
 
         // Start by setting everything to a "non-cleared" state
-        *world.get_resource_mut::<Score>().unwrap() = Score { p1: 10, p2: 10 };
+        *world.get_resource_mut::<Score>().unwrap() = Score(PerPlayer::from([10, 10, 0, 0]));
         world
             .query_filtered::<&mut Text2d, With<ScoreText>>()
             .iter_mut(&mut world)
@@ -714,6 +1773,7 @@ mod tests {
         );
 
         // And now send the message and confirm everything is wiped out
+        world.resource_mut::<GamesWon>().0[Player2] = 2;
         world.write_message(ClearScores);
         world.run_system(clear_sys).unwrap();
         validate_scores(
@@ -726,10 +1786,387 @@ mod tests {
             false,
             "after sending clear message",
         );
+        assert_eq!(
+            *world.resource::<GamesWon>(),
+            GamesWon::default(),
+            "Expected games won to be cleared for a new match",
+        );
+    }
+
+    #[test]
+    fn test_best_of_match() {
+        let mut world = setup_match_world(GameplaySettings {
+            winning_score: 2,
+            best_of: 3,
+            ..default()
+        });
+        assert_eq!(set_score_text(&mut world), "Games 0 - 0");
+
+        // Winning the first game doesn't win the match
+        for _ in 0..2 {
+            world.write_message(PlayerScored(Player1));
+            run_match_systems(&mut world);
+        }
+        assert_eq!(
+            world
+                .resource::<Messages<GameWon>>()
+                .iter_current_update_messages()
+                .collect::<Vec<_>>(),
+            vec![&GameWon(Player1)],
+            "Expected player 1 to win the first game",
+        );
+        assert!(world.resource::<Messages<MaxScoreReached>>().is_empty());
+        assert_eq!(set_score_text(&mut world), "Games 1 - 0");
+
+        // The next game starts from 0-0, keeping the games won
+        world.write_message(StartNextGame);
+        run_match_systems(&mut world);
+        validate_scores(
+            &mut world,
+            0,
+            0,
+            "0",
+            "0",
+            false,
+            false,
+            "after starting the next game",
+        );
+        assert_eq!(set_score_text(&mut world), "Games 1 - 0");
+
+        // Winning the second game wins the match
+        for _ in 0..2 {
+            world.write_message(PlayerScored(Player1));
+            run_match_systems(&mut world);
+        }
+        assert_eq!(
+            world
+                .resource::<Messages<MaxScoreReached>>()
+                .iter_current_update_messages()
+                .collect::<Vec<_>>(),
+            vec![&MaxScoreReached(Player1)],
+            "Expected player 1 to win the match after 2 of 3 games",
+        );
+        validate_scores(
+            &mut world,
+            2,
+            0,
+            "2",
+            "0",
+            true,
+            false,
+            "after winning the match",
+        );
+        assert_eq!(set_score_text(&mut world), "Games 2 - 0");
+    }
+
+    #[test]
+    fn test_game_point() {
+        let mut world = setup_match_world(GameplaySettings {
+            winning_score: 3,
+            win_condition: WinCondition::WinByTwo,
+            ..default()
+        });
+        for (scorer, exp_game_points) in [
+            (Player1, vec![]),
+            (Player1, vec![GamePoint(Player1)]),
+            (Player2, vec![GamePoint(Player1)]),
+            (Player2, vec![]),
+            (Player2, vec![GamePoint(Player2)]),
+        ] {
+            world.resource_mut::<Messages<GamePoint>>().update();
+            world.write_message(PlayerScored(scorer));
+            run_match_systems(&mut world);
+            assert_eq!(
+                world
+                    .resource::<Messages<GamePoint>>()
+                    .iter_current_update_messages()
+                    .copied()
+                    .collect::<Vec<_>>(),
+                exp_game_points,
+                "Expected game points {exp_game_points:?} after {scorer:?} scored",
+            );
+        }
+
+        // No game point once the game is won
+        world.resource_mut::<Messages<GamePoint>>().update();
+        world.write_message(PlayerScored(Player2));
+        run_match_systems(&mut world);
+        assert_eq!(
+            world
+                .resource::<Messages<GamePoint>>()
+                .iter_current_update_messages()
+                .count(),
+            0,
+            "Expected no game point once the game is won",
+        );
+    }
+
+    #[test]
+    fn test_game_point_players() {
+        let first_to = GameplaySettings {
+            winning_score: 5,
+            ..default()
+        };
+        assert_eq!(
+            game_point_players(&first_to, &[(Player1, 4), (Player2, 4)], false),
+            vec![Player1, Player2],
+        );
+        assert_eq!(
+            game_point_players(&first_to, &[(Player1, 3), (Player2, 4)], false),
+            vec![Player2],
+        );
+        assert!(game_point_players(&first_to, &[(Player1, 3), (Player2, 0)], false).is_empty());
+
+        // Timed games only have game points in sudden death
+        let timed = GameplaySettings {
+            win_condition: WinCondition::Timed { secs: 60 },
+            ..default()
+        };
+        let scores = [(Player1, 2), (Player2, 2)];
+        assert!(game_point_players(&timed, &scores, false).is_empty());
+        assert_eq!(
+            game_point_players(&timed, &scores, true),
+            vec![Player1, Player2],
+        );
+    }
+
+    #[test]
+    fn test_timed_game_sudden_death() {
+        let mut world = setup_match_world(GameplaySettings {
+            winning_score: 1,
+            win_condition: WinCondition::Timed { secs: 60 },
+            ..default()
+        });
+        assert_eq!(set_score_text(&mut world), "", "Expected no set score");
+        assert_eq!(clock_text(&mut world), "1:00");
+
+        // The winning score is ignored while there is time left
+        world.write_message(StartBall);
+        advance_time(&mut world, 30.5);
+        world.write_message(PlayerScored(Player2));
+        run_match_systems(&mut world);
+        assert!(world.resource::<Messages<MaxScoreReached>>().is_empty());
+        assert_eq!(clock_text(&mut world), "0:30");
+
+        // The clock is stopped between rounds
+        advance_time(&mut world, 10.0);
+        run_match_systems(&mut world);
+        assert_eq!(clock_text(&mut world), "0:30");
+
+        // Time running out with the scores level goes to sudden death
+        world.write_message(StartBall);
+        advance_time(&mut world, 25.0);
+        world.write_message(PlayerScored(Player1));
+        run_match_systems(&mut world);
+        world.write_message(StartBall);
+        advance_time(&mut world, 10.0);
+        run_match_systems(&mut world);
+        assert_eq!(clock_text(&mut world), SUDDEN_DEATH_TEXT);
+        assert!(world.resource::<Messages<MaxScoreReached>>().is_empty());
+
+        // The next point wins
+        world.write_message(PlayerScored(Player2));
+        run_match_systems(&mut world);
+        assert_eq!(
+            world
+                .resource::<Messages<MaxScoreReached>>()
+                .iter_current_update_messages()
+                .collect::<Vec<_>>(),
+            vec![&MaxScoreReached(Player2)],
+            "Expected the sudden death point to win the game",
+        );
+        assert_eq!(clock_text(&mut world), "0:00");
+    }
+
+    #[test]
+    fn test_timed_game_won_when_time_runs_out() {
+        let mut world = setup_match_world(GameplaySettings {
+            win_condition: WinCondition::Timed { secs: 60 },
+            ..default()
+        });
+        for (secs, scorer) in [(10.0, Player1), (10.0, Player2), (10.0, Player1)] {
+            world.write_message(StartBall);
+            advance_time(&mut world, secs);
+            world.write_message(PlayerScored(scorer));
+            run_match_systems(&mut world);
+        }
+        assert!(world.resource::<Messages<MaxScoreReached>>().is_empty());
+
+        // Time running out mid-rally with a 2-1 lead wins the game, without waiting for a point
+        world.write_message(StartBall);
+        advance_time(&mut world, 29.5);
+        run_match_systems(&mut world);
+        assert!(world.resource::<Messages<MaxScoreReached>>().is_empty());
+        advance_time(&mut world, 0.5);
+        run_match_systems(&mut world);
+        assert_eq!(
+            world
+                .resource::<Messages<MaxScoreReached>>()
+                .iter_current_update_messages()
+                .collect::<Vec<_>>(),
+            vec![&MaxScoreReached(Player1)],
+            "Expected the leading player to win as soon as time ran out",
+        );
+        assert_eq!(clock_text(&mut world), "0:00");
+        let mut query = world.query::<(&WinText, &Visibility)>();
+        for (&WinText(player), &vis) in query.iter(&world) {
+            let exp_vis = match player {
+                Player1 => Visibility::Visible,
+                _ => Visibility::Hidden,
+            };
+            assert_eq!(vis, exp_vis, "Expected {player:?} WinText {exp_vis:?}");
+        }
+
+        // A point from the rally cut short by the clock doesn't count
+        advance_time(&mut world, 1.0);
+        world.write_message(PlayerScored(Player2));
+        run_match_systems(&mut world);
+        assert_eq!(world.resource::<Score>()[Player2], 1);
+        assert_eq!(
+            world
+                .resource::<Messages<MaxScoreReached>>()
+                .iter_current_update_messages()
+                .count(),
+            0,
+            "Expected no further win from the late point",
+        );
+    }
+
+    #[test]
+    fn test_score_saturates() {
+        let mut world = setup_match_world(GameplaySettings {
+            win_condition: WinCondition::Timed { secs: 60 },
+            ..default()
+        });
+        world.resource_mut::<Score>()[Player1] = u8::MAX;
+        world.resource_mut::<Score>()[Player2] = u8::MAX - 1;
+
+        // Scoring at the maximum score keeps the score, rather than overflowing
+        world.write_message(StartBall);
+        advance_time(&mut world, 10.0);
+        world.write_message(PlayerScored(Player1));
+        run_match_systems(&mut world);
+        assert_eq!(world.resource::<Score>()[Player1], u8::MAX);
+        assert!(world.resource::<Messages<MaxScoreReached>>().is_empty());
+
+        // So the leading player still wins once time is up
+        world.write_message(StartBall);
+        advance_time(&mut world, 60.0);
+        world.write_message(PlayerScored(Player1));
+        run_match_systems(&mut world);
+        assert_eq!(world.resource::<Score>()[Player1], u8::MAX);
+        assert_eq!(
+            world
+                .resource::<Messages<MaxScoreReached>>()
+                .iter_current_update_messages()
+                .collect::<Vec<_>>(),
+            vec![&MaxScoreReached(Player1)],
+            "Expected the player leading at the maximum score to win",
+        );
+    }
+
+    #[test]
+    fn test_match_texts_four_player() {
+        let mut world = World::default();
+        world.insert_resource(GameMode::FourPlayer);
+        world.init_resource::<PlayerNames>();
+        world.init_resource::<Settings>();
+        world.spawn(Camera2d);
+        world.run_system_cached(setup).unwrap();
+
+        // Four player mode is always a single, untimed game
+        assert_eq!(
+            world
+                .query_filtered::<(), Or<(With<SetScoreText>, With<ClockText>)>>()
+                .iter(&world)
+                .count(),
+            0,
+            "Expected no set score or clock in four player mode",
+        );
+    }
+
+    #[test]
+    fn test_clock_display() {
+        let timed = WinCondition::Timed { secs: 125 };
+        let mut clock = GameClock::default();
+        assert_eq!(clock_display(WinCondition::FirstTo, &clock, true), "");
+        assert_eq!(clock_display(timed, &clock, true), "2:05");
+
+        // Partial seconds left are rounded up, so the clock only reads 0:00 when time is up
+        clock.0.unpause();
+        clock.0.tick(Duration::from_secs_f32(124.5));
+        assert_eq!(clock_display(timed, &clock, true), "0:01");
+        clock.0.tick(Duration::from_secs(1));
+        assert_eq!(clock_display(timed, &clock, false), "0:00");
+        assert_eq!(clock_display(timed, &clock, true), SUDDEN_DEATH_TEXT);
     }
 
     // --- Helper Functions ---
 
+    // Create a two player world with the scoreboard set up, using the given gameplay settings.
+    fn setup_match_world(gameplay: GameplaySettings) -> World {
+        let mut world = World::default();
+        world.init_resource::<GameMode>();
+        world.init_resource::<PlayerNames>();
+        world.insert_resource(Settings {
+            gameplay,
+            ..default()
+        });
+        world.init_resource::<Time>();
+        world.init_resource::<Score>();
+        world.init_resource::<GamesWon>();
+        world.init_resource::<GameClock>();
+        world.init_resource::<Messages<StartBall>>();
+        world.init_resource::<Messages<PlayerScored>>();
+        world.init_resource::<Messages<GameWon>>();
+        world.init_resource::<Messages<MaxScoreReached>>();
+        world.init_resource::<Messages<GamePoint>>();
+        world.init_resource::<Messages<StartNextGame>>();
+        world.spawn(Camera2d);
+        world.run_system_cached(setup).unwrap();
+        world
+    }
+
+    // Advance the time of the world by the given number of seconds, for the next update.
+    fn advance_time(world: &mut World, secs: f32) {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(secs));
+    }
+
+    // Run the match related score systems once in order, clearing out old messages first.
+    fn run_match_systems(world: &mut World) {
+        world.resource_mut::<Messages<GameWon>>().update();
+        world.resource_mut::<Messages<MaxScoreReached>>().update();
+        world.run_system_cached(run_game_clock).unwrap();
+        world.run_system_cached(handle_player_score).unwrap();
+        world.run_system_cached(start_next_game).unwrap();
+        world.run_system_cached(update_set_score_text).unwrap();
+        world.run_system_cached(update_clock_text).unwrap();
+        world.resource_mut::<Time>().advance_by(Duration::ZERO);
+    }
+
+    // Get the text of the SetScoreText entity.
+    fn set_score_text(world: &mut World) -> String {
+        world
+            .query_filtered::<&Text2d, With<SetScoreText>>()
+            .single(world)
+            .unwrap()
+            .0
+            .clone()
+    }
+
+    // Get the text of the ClockText entity.
+    fn clock_text(world: &mut World) -> String {
+        world
+            .query_filtered::<&Text2d, With<ClockText>>()
+            .single(world)
+            .unwrap()
+            .0
+            .clone()
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn validate_scores(
         world: &mut World,
         p1: u8,
@@ -742,7 +2179,7 @@ mod tests {
     ) {
         assert_eq!(
             *world.get_resource::<Score>().unwrap(),
-            Score { p1, p2 },
+            Score(PerPlayer::from([p1, p2, 0, 0])),
             "Expected score to be {}-{} {}",
             p1,
             p2,
@@ -771,4 +2208,18 @@ mod tests {
             );
         }
     }
+
+    // Get the text of each player's entity with the given text component, in player order.
+    fn player_texts<T: Component>(
+        world: &mut World,
+        player: fn(&T) -> PlayerId,
+    ) -> Vec<(PlayerId, String)> {
+        let mut texts: Vec<(PlayerId, String)> = world
+            .query::<(&T, &Text2d)>()
+            .iter(world)
+            .map(|(component, text)| (player(component), text.0.clone()))
+            .collect();
+        texts.sort_by_key(|(player, _)| player.index());
+        texts
+    }
 }
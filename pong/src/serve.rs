@@ -0,0 +1,747 @@
+//!
+//! The serve module contains the ServePlugin, which decides who each serve goes to according
+//! to the serve rule of the gameplay Settings, and shows the direction of the serve on screen
+//! before the ball is launched. With manual serves, the serving player aims the serve and
+//! launches the ball themselves.
+//!
+
+// -------------------------------------------------------------------------------------------------
+// Included Symbols
+
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use rand::Rng;
+use rand::seq::IndexedRandom;
+
+use bevy_dyn_fontsize::{DynamicFontSize, DynamicFontsizePlugin};
+
+use crate::ai::AiPlayers;
+use crate::ball::{self, BALL_SERVE_MAX_ANGLE, Ball, BallOffScreen, NextServe, StartBall};
+use crate::common::*;
+use crate::paddle::{self, AllPaddleHitboxes, PaddlePosition, RestorePaddles};
+use crate::settings::{PaddleKeyOverrides, ServeRule, Settings};
+
+// -------------------------------------------------------------------------------------------------
+// Constants
+
+// How quickly a serving player can sweep their aim, in radians per second.
+const AIM_SPEED: f32 = BALL_SERVE_MAX_ANGLE;
+
+const INDICATOR_LENGTH: f32 = 0.08 * ARENA_WIDTH;
+const INDICATOR_THICKNESS: f32 = 0.008 * ARENA_HEIGHT;
+const INDICATOR_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.5);
+
+const HINT_FONT_SIZE_AS_SCREEN_PCT: f32 = 0.035;
+const HINT_HEIGHT: f32 = HINT_FONT_SIZE_AS_SCREEN_PCT * ARENA_HEIGHT;
+const HINT_Y: f32 = -0.1 * ARENA_HEIGHT;
+
+// -------------------------------------------------------------------------------------------------
+// Public API
+
+///
+/// The ServePlugin plans each serve while the ball is waiting in the middle of the arena, and
+/// shows an indicator pointing the way the ball will go. The receiver is chosen by the serve
+/// rule of the gameplay Settings. Once a ServeBall message arrives the serve is launched with
+/// a StartBall message, straight away for automatic serves, or when the server presses their
+/// serve key for manual serves. Until then, a manual server aims with their paddle keys.
+///
+/// Computer controlled players always serve automatically.
+///
+/// This plugin will only work properly if the app contains a single Camera2d entity.
+///
+pub struct ServePlugin;
+
+impl Plugin for ServePlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<DynamicFontsizePlugin>() {
+            app.add_plugins(DynamicFontsizePlugin::default());
+        }
+
+        app.init_resource::<Settings>()
+            .init_resource::<PaddleKeyOverrides>()
+            .init_resource::<AiPlayers>()
+            .init_resource::<PlayerNames>()
+            .init_resource::<GameRng>()
+            .init_resource::<NextServe>()
+            .init_resource::<ServeState>()
+            .add_message::<ServeBall>()
+            .add_message::<StartBall>()
+            .add_message::<BallOffScreen>()
+            .add_message::<RestorePaddles>()
+            .add_systems(
+                Startup,
+                setup_serve_indicator.in_set(Systems::SetupAfterCamera),
+            )
+            .add_systems(
+                Update,
+                (
+                    plan_serve
+                        .after(ball::Systems::BallOffScreenSndr)
+                        .after(ball::Systems::ResetBallRcvr)
+                        .after(paddle::Systems::WallOffPaddleRcvr)
+                        .after(paddle::Systems::RestorePaddlesRcvr),
+                    launch_serve
+                        .after(plan_serve)
+                        .in_set(Systems::ServeBallRcvr)
+                        .in_set(Systems::StartBallSndr),
+                    update_serve_indicator.after(launch_serve),
+                ),
+            )
+            .configure_sets(
+                Update,
+                Systems::StartBallSndr.before(ball::Systems::StartBallRcvr),
+            );
+    }
+}
+
+///
+/// System sets to allow modules consuming this plugin to create ordering constraints
+/// based on functionality exposed in the API of the Plugin.
+///
+#[derive(SystemSet, Debug, Clone, Hash, PartialEq, Eq)]
+pub enum Systems {
+    ///
+    /// Startup systems which spawn the serve indicator. These require the camera to exist,
+    /// so the camera setup should be ordered before this set.
+    ///
+    SetupAfterCamera,
+
+    ///
+    /// Update systems which receive ServeBall messages. To serve in the same frame as the
+    /// message is sent, the sender should be ordered before this system set.
+    ///
+    ServeBallRcvr,
+
+    /// Update systems which send StartBall messages, ordered before the ball receives them.
+    StartBallSndr,
+}
+
+///
+/// This message should be sent by another module when it is time to serve the ball. An
+/// automatic serve is launched straight away, while a manual serve is launched once the
+/// serving player presses their serve key.
+///
+#[derive(Message, Debug)]
+pub struct ServeBall;
+
+// -------------------------------------------------------------------------------------------------
+// Private Types
+
+// The serve to make once the ball is next served.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PlannedServe {
+    // The edge of the arena the ball will be served towards.
+    receiver: ArenaSide,
+
+    // The player who launches the serve, or None when it's served automatically.
+    server: Option<PlayerId>,
+
+    // Angle of the serve away from straight at the receiver's edge, in radians.
+    angle: f32,
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Resources
+
+// Progress of the upcoming serve, and what is remembered of previous points to plan it.
+#[derive(Resource, Debug, Default)]
+struct ServeState {
+    // The upcoming serve, planned while the ball waits to be served.
+    planned: Option<PlannedServe>,
+
+    // True once a ServeBall message has arrived for the planned serve.
+    ready: bool,
+
+    // The edge of the arena the previous serve went towards.
+    last_receiver: Option<ArenaSide>,
+
+    // The edge of the arena the ball last left through, conceding a point.
+    last_conceded: Option<ArenaSide>,
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Components
+
+// Component for the bar pointing in the direction of the planned serve.
+#[derive(Component)]
+struct ServeIndicator;
+
+// Component for the text telling a manual server how to launch the serve.
+#[derive(Component)]
+struct ServeHint;
+
+// -------------------------------------------------------------------------------------------------
+// Private Systems
+
+//
+// Spawns the serve indicator in the middle of the arena, and the serve hint text below it.
+// Both are hidden until there is a serve to show.
+//
+fn setup_serve_indicator(mut commands: Commands, camera_entity: Single<Entity, With<Camera2d>>) {
+    commands.spawn((
+        ServeIndicator,
+        Sprite {
+            color: INDICATOR_COLOR,
+            custom_size: Some(Vec2::new(INDICATOR_LENGTH, INDICATOR_THICKNESS)),
+            ..default()
+        },
+        Anchor::CENTER_LEFT,
+        Transform::from_xyz(0f32, 0f32, Z_BEHIND_GAMEPLAY),
+        Visibility::Hidden,
+    ));
+
+    commands.spawn((
+        ServeHint,
+        DynamicFontSize {
+            height_in_world: HINT_HEIGHT,
+            render_camera: camera_entity.entity(),
+            max_width_in_world: None,
+        },
+        Text2d::default(),
+        Transform::from_xyz(0f32, HINT_Y, Z_FOREGROUND),
+    ));
+}
+
+//
+// Plans the next serve once the ball has been reset to the middle of the arena. A plan is
+// dropped and made again if the edge of its receiver or server is walled off, the paddles are
+// restored, or the settings change. The side which conceded each point is remembered for the
+// toward loser serve rule.
+//
+#[allow(clippy::too_many_arguments)]
+fn plan_serve(
+    mut off_screen_msgs: MessageReader<BallOffScreen>,
+    mut restore_msgs: MessageReader<RestorePaddles>,
+    mut state: ResMut<ServeState>,
+    ball: Single<&Ball>,
+    paddles: Query<AllPaddleHitboxes>,
+    settings: Res<Settings>,
+    ai_players: Res<AiPlayers>,
+    mut rng: ResMut<GameRng>,
+) {
+    if let Some(msg) = off_screen_msgs.read().last() {
+        state.last_conceded = Some(msg.side());
+    }
+
+    let mut active_sides: Vec<ArenaSide> = Vec::new();
+    for hitbox in &paddles {
+        if !hitbox.is_walled() && !active_sides.contains(&hitbox.side()) {
+            active_sides.push(hitbox.side());
+        }
+    }
+
+    let restored = !restore_msgs.is_empty();
+    restore_msgs.clear();
+    let plan_invalid = state.planned.is_some_and(|planned| {
+        !active_sides.contains(&planned.receiver)
+            || planned
+                .server
+                .is_some_and(|server| !active_sides.contains(&server.side()))
+    });
+    if restored || plan_invalid || settings.is_changed() {
+        state.planned = None;
+    }
+
+    if state.planned.is_some() || ball.movement_dir().is_some() {
+        return;
+    }
+
+    let Some(receiver) = choose_receiver(
+        settings.gameplay.serve_rule,
+        &state,
+        &active_sides,
+        &mut rng,
+    ) else {
+        return;
+    };
+
+    // The serve comes from the player opposite the receiver, if they're able to make it
+    let server_side = receiver.opposite();
+    let server = server_side.player();
+    let manual = settings.gameplay.manual_serve
+        && active_sides.contains(&server_side)
+        && !ai_players.0.contains(server);
+    let angle = if manual {
+        0f32
+    } else {
+        rng.rng()
+            .random_range(-BALL_SERVE_MAX_ANGLE..BALL_SERVE_MAX_ANGLE)
+    };
+
+    state.planned = Some(PlannedServe {
+        receiver,
+        server: manual.then_some(server),
+        angle,
+    });
+}
+
+//
+// Launches the planned serve once a ServeBall message has arrived. An automatic serve is
+// launched straight away, whereas the server of a manual serve aims with their paddle keys
+// until they press their serve key.
+//
+#[allow(clippy::too_many_arguments)]
+fn launch_serve(
+    mut serve_msgs: MessageReader<ServeBall>,
+    mut start_ball_msgs: MessageWriter<StartBall>,
+    mut state: ResMut<ServeState>,
+    mut next_serve: ResMut<NextServe>,
+    keys: Res<ButtonInput<KeyCode>>,
+    settings: Res<Settings>,
+    key_overrides: Res<PaddleKeyOverrides>,
+    time: Res<Time>,
+) {
+    if !serve_msgs.is_empty() {
+        serve_msgs.clear();
+        state.ready = true;
+    }
+
+    let Some(mut planned) = state.planned else {
+        return;
+    };
+
+    let launch = match planned.server {
+        Some(server) => {
+            let paddle_keys =
+                key_overrides.paddle(&settings.key_bindings, server, PaddlePosition::Back);
+            let aim = match (
+                keys.pressed(paddle_keys.positive),
+                keys.pressed(paddle_keys.negative),
+            ) {
+                (true, false) => 1f32,
+                (false, true) => -1f32,
+                _ => 0f32,
+            };
+            planned.angle = (planned.angle
+                + aim * aim_sign(planned.receiver) * AIM_SPEED * time.delta_secs())
+            .clamp(-BALL_SERVE_MAX_ANGLE, BALL_SERVE_MAX_ANGLE);
+            keys.just_pressed(settings.key_bindings.serve(server))
+        }
+        None => true,
+    };
+
+    if state.ready && launch {
+        *next_serve = NextServe {
+            receiver: Some(planned.receiver),
+            angle: Some(planned.angle),
+        };
+        start_ball_msgs.write(StartBall);
+        state.last_receiver = Some(planned.receiver);
+        state.planned = None;
+        state.ready = false;
+    } else if state.planned != Some(planned) {
+        state.planned = Some(planned);
+    }
+}
+
+//
+// Points the serve indicator in the direction of the planned serve, and tells a manual server
+// which key launches it. Both are hidden while there is no serve planned.
+//
+fn update_serve_indicator(
+    state: Res<ServeState>,
+    settings: Res<Settings>,
+    names: Res<PlayerNames>,
+    mut indicators: Query<(&mut Transform, &mut Visibility), With<ServeIndicator>>,
+    mut hints: Query<&mut Text2d, With<ServeHint>>,
+) {
+    for (mut indicator_tf, mut visibility) in &mut indicators {
+        match state.planned {
+            Some(planned) => {
+                indicator_tf.rotation = Quat::from_rotation_z(serve_dir(planned).to_angle());
+                visibility.set_if_neq(Visibility::Inherited);
+            }
+            None => {
+                visibility.set_if_neq(Visibility::Hidden);
+            }
+        }
+    }
+
+    let hint = match state.planned.and_then(|planned| planned.server) {
+        Some(server) if state.ready => format!(
+            "{}: press {:?} to serve",
+            names.name(server),
+            settings.key_bindings.serve(server)
+        ),
+        Some(server) => format!("{} to serve", names.name(server)),
+        None => String::new(),
+    };
+    for mut text in &mut hints {
+        if text.0 != hint {
+            text.0.clone_from(&hint);
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Functions
+
+//
+// Chooses the edge of the arena the next serve goes towards. Only edges in active_sides are
+// chosen, falling back to a random one where the serve rule has no preference.
+//
+fn choose_receiver(
+    rule: ServeRule,
+    state: &ServeState,
+    active_sides: &[ArenaSide],
+    rng: &mut GameRng,
+) -> Option<ArenaSide> {
+    let preferred = match rule {
+        ServeRule::Random => None,
+        ServeRule::Alternate => state.last_receiver.and_then(|last| {
+            // The next active side after the last receiver, in the order of ArenaSide::ALL
+            let start = ArenaSide::ALL.iter().position(|&side| side == last)?;
+            (1..=ArenaSide::ALL.len())
+                .map(|offset| ArenaSide::ALL[(start + offset) % ArenaSide::ALL.len()])
+                .find(|side| active_sides.contains(side))
+        }),
+        ServeRule::TowardLoser => state
+            .last_conceded
+            .filter(|side| active_sides.contains(side)),
+    };
+    preferred.or_else(|| active_sides.choose(rng.rng()).copied())
+}
+
+// The direction the ball will be served in for the given planned serve.
+fn serve_dir(planned: PlannedServe) -> Vec2 {
+    Rot2::radians(planned.angle) * -planned.receiver.inward_normal()
+}
+
+//
+// Gets the sign to apply to the aim input of the player serving towards receiver, such that
+// their positive paddle key aims the serve towards positive y (left and right edges) or
+// positive x (top and bottom edges), matching the way it moves their paddle.
+//
+fn aim_sign(receiver: ArenaSide) -> f32 {
+    let positive_axis = if receiver.is_vertical() {
+        Vec2::Y
+    } else {
+        Vec2::X
+    };
+    (-receiver.inward_normal()).perp().dot(positive_axis)
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::AiDifficulty;
+    use crate::ball::tests::spawn_test_ball;
+    use crate::paddle::tests::{spawn_test_paddle, wall_off_test_paddles};
+    use bevy::ecs::schedule::AnonymousSet;
+    use bevy_test_helpers::prelude::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_plugin_sys_added_setup() {
+        validate_sys_in_plugin(
+            ServePlugin,
+            Startup,
+            setup_serve_indicator,
+            Some(Systems::SetupAfterCamera),
+        );
+    }
+
+    #[test]
+    fn test_plugin_sys_added_update() {
+        validate_sys_in_plugin(ServePlugin, Update, plan_serve, None::<AnonymousSet>);
+        validate_sys_in_plugin(
+            ServePlugin,
+            Update,
+            launch_serve,
+            Some(Systems::ServeBallRcvr),
+        );
+        validate_sys_in_plugin(
+            ServePlugin,
+            Update,
+            launch_serve,
+            Some(Systems::StartBallSndr),
+        );
+        validate_sys_in_plugin(
+            ServePlugin,
+            Update,
+            update_serve_indicator,
+            None::<AnonymousSet>,
+        );
+    }
+
+    #[test]
+    fn test_choose_receiver() {
+        let mut rng = GameRng::seeded(7);
+        let all_sides = [ArenaSide::Left, ArenaSide::Right];
+
+        // Alternate picks the next active side after the last receiver, wrapping around
+        let state = ServeState {
+            last_receiver: Some(ArenaSide::Left),
+            ..default()
+        };
+        let receiver = choose_receiver(ServeRule::Alternate, &state, &all_sides, &mut rng);
+        assert_eq!(receiver, Some(ArenaSide::Right));
+        let state = ServeState {
+            last_receiver: Some(ArenaSide::Right),
+            ..default()
+        };
+        let receiver = choose_receiver(ServeRule::Alternate, &state, &all_sides, &mut rng);
+        assert_eq!(receiver, Some(ArenaSide::Left));
+
+        // Walled off sides are skipped in four player games
+        let four_sides = [ArenaSide::Left, ArenaSide::Right, ArenaSide::Bottom];
+        let state = ServeState {
+            last_receiver: Some(ArenaSide::Right),
+            ..default()
+        };
+        let receiver = choose_receiver(ServeRule::Alternate, &state, &four_sides, &mut rng);
+        assert_eq!(receiver, Some(ArenaSide::Bottom));
+
+        // Toward loser serves to the side which conceded, unless it's walled off
+        let state = ServeState {
+            last_receiver: Some(ArenaSide::Left),
+            last_conceded: Some(ArenaSide::Left),
+            ..default()
+        };
+        let receiver = choose_receiver(ServeRule::TowardLoser, &state, &all_sides, &mut rng);
+        assert_eq!(receiver, Some(ArenaSide::Left));
+        let state = ServeState {
+            last_conceded: Some(ArenaSide::Top),
+            ..default()
+        };
+        let receiver = choose_receiver(ServeRule::TowardLoser, &state, &four_sides, &mut rng);
+        assert!(
+            receiver.is_some_and(|side| four_sides.contains(&side)),
+            "Expected a random active side when the loser is walled off, got {receiver:?}"
+        );
+
+        // Nothing to serve towards without active paddles
+        let receiver = choose_receiver(ServeRule::Random, &default(), &[], &mut rng);
+        assert_eq!(receiver, None);
+    }
+
+    #[test]
+    fn test_plan_serve_sys() {
+        let mut world = setup_serve_world(ServeRule::TowardLoser, true);
+        world.write_message(BallOffScreen::Right);
+        world.run_system_cached(plan_serve).unwrap();
+
+        // Serve goes to the loser, aimed straight by the human serving it
+        let planned = world.resource::<ServeState>().planned;
+        assert_eq!(
+            planned,
+            Some(PlannedServe {
+                receiver: ArenaSide::Right,
+                server: Some(Player1),
+                angle: 0f32,
+            })
+        );
+
+        // Plan is replaced once the server is walled off, leaving only automatic serves
+        wall_off_test_paddles(&mut world, &[Player1]);
+        world.run_system_cached(plan_serve).unwrap();
+        let planned = world.resource::<ServeState>().planned.unwrap();
+        assert_eq!(planned.receiver, ArenaSide::Right);
+        assert_eq!(planned.server, None);
+        assert!(planned.angle.abs() <= BALL_SERVE_MAX_ANGLE);
+    }
+
+    #[test]
+    fn test_plan_serve_ai_server() {
+        let mut world = setup_serve_world(ServeRule::TowardLoser, true);
+        world.insert_resource(AiPlayers(PerPlayer::filled(
+            &[Player2],
+            AiDifficulty::Medium,
+        )));
+        world.write_message(BallOffScreen::Left);
+        world.run_system_cached(plan_serve).unwrap();
+
+        let planned = world.resource::<ServeState>().planned.unwrap();
+        assert_eq!(planned.receiver, ArenaSide::Left);
+        assert_eq!(
+            planned.server, None,
+            "Expected AI player to serve automatically"
+        );
+    }
+
+    #[test]
+    fn test_launch_serve_automatic() {
+        let mut world = setup_serve_world(ServeRule::Alternate, false);
+        world.run_system_cached(plan_serve).unwrap();
+        world.run_system_cached(launch_serve).unwrap();
+        assert_eq!(
+            start_ball_count(&mut world),
+            0,
+            "Expected no serve before ServeBall"
+        );
+
+        let planned = world.resource::<ServeState>().planned.unwrap();
+        world.write_message(ServeBall);
+        world.run_system_cached(launch_serve).unwrap();
+        assert_eq!(start_ball_count(&mut world), 1);
+        assert_eq!(
+            *world.resource::<NextServe>(),
+            NextServe {
+                receiver: Some(planned.receiver),
+                angle: Some(planned.angle),
+            }
+        );
+
+        // The next serve alternates to the other side
+        let state = world.resource::<ServeState>();
+        assert_eq!(state.planned, None);
+        assert_eq!(state.last_receiver, Some(planned.receiver));
+        world.run_system_cached(plan_serve).unwrap();
+        let next = world.resource::<ServeState>().planned.unwrap();
+        assert_eq!(next.receiver, planned.receiver.opposite());
+    }
+
+    #[test]
+    fn test_launch_serve_manual() {
+        let mut world = setup_serve_world(ServeRule::TowardLoser, true);
+        world.write_message(BallOffScreen::Left);
+        world.run_system_cached(plan_serve).unwrap();
+        world.write_message(ServeBall);
+
+        // Player 2 serves towards the left, so their up key aims the serve upwards
+        let keys = world
+            .resource::<Settings>()
+            .key_bindings
+            .paddle(Player2, PaddlePosition::Back);
+        press_key(&mut world, keys.positive);
+        advance_time(&mut world, 0.5);
+        world.run_system_cached(launch_serve).unwrap();
+        assert_eq!(
+            start_ball_count(&mut world),
+            0,
+            "Expected serve to wait for key"
+        );
+        let planned = world.resource::<ServeState>().planned.unwrap();
+        assert!(serve_dir(planned).y > 0f32, "Expected serve aimed upwards");
+
+        // Aim is held within the serve cone
+        advance_time(&mut world, 5.0);
+        world.run_system_cached(launch_serve).unwrap();
+        let planned = world.resource::<ServeState>().planned.unwrap();
+        assert_eq!(planned.angle.abs(), BALL_SERVE_MAX_ANGLE);
+
+        let serve_key = world.resource::<Settings>().key_bindings.serve(Player2);
+        press_key(&mut world, serve_key);
+        world.run_system_cached(launch_serve).unwrap();
+        assert_eq!(start_ball_count(&mut world), 1);
+        let next_serve = world.resource::<NextServe>();
+        assert_eq!(next_serve.receiver, Some(ArenaSide::Left));
+        assert_eq!(next_serve.angle, Some(planned.angle));
+    }
+
+    #[test]
+    fn test_launch_serve_key_before_ready() {
+        let mut world = setup_serve_world(ServeRule::TowardLoser, true);
+        world.write_message(BallOffScreen::Left);
+        world.run_system_cached(plan_serve).unwrap();
+
+        let serve_key = world.resource::<Settings>().key_bindings.serve(Player2);
+        press_key(&mut world, serve_key);
+        world.run_system_cached(launch_serve).unwrap();
+        assert_eq!(
+            start_ball_count(&mut world),
+            0,
+            "Expected no serve before ServeBall"
+        );
+    }
+
+    #[test]
+    fn test_aim_sign() {
+        for (receiver, axis) in [
+            (ArenaSide::Left, Vec2::Y),
+            (ArenaSide::Right, Vec2::Y),
+            (ArenaSide::Top, Vec2::X),
+            (ArenaSide::Bottom, Vec2::X),
+        ] {
+            let aimed = serve_dir(PlannedServe {
+                receiver,
+                server: None,
+                angle: aim_sign(receiver) * 0.1,
+            });
+            assert!(
+                aimed.dot(axis) > 0f32,
+                "Expected positive aim towards {axis} when serving to {receiver:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_update_serve_indicator_sys() {
+        let mut world = setup_serve_world(ServeRule::TowardLoser, true);
+        world.spawn(Camera2d);
+        world.run_system_cached(setup_serve_indicator).unwrap();
+        world.run_system_cached(update_serve_indicator).unwrap();
+        assert_eq!(indicator_visibility(&mut world), Visibility::Hidden);
+        assert_eq!(hint_text(&mut world), "");
+
+        world.write_message(BallOffScreen::Right);
+        world.run_system_cached(plan_serve).unwrap();
+        world.run_system_cached(update_serve_indicator).unwrap();
+        assert_eq!(indicator_visibility(&mut world), Visibility::Inherited);
+        assert_eq!(hint_text(&mut world), "Player 1 to serve");
+
+        world.write_message(ServeBall);
+        world.run_system_cached(launch_serve).unwrap();
+        world.run_system_cached(update_serve_indicator).unwrap();
+        assert_eq!(hint_text(&mut world), "Player 1: press KeyD to serve");
+    }
+
+    // --- Helper Functions ---
+
+    // Creates a two player world with a paused ball and the given serve settings.
+    fn setup_serve_world(serve_rule: ServeRule, manual_serve: bool) -> World {
+        let mut world = World::new();
+        let mut settings = Settings::default();
+        settings.gameplay.serve_rule = serve_rule;
+        settings.gameplay.manual_serve = manual_serve;
+        world.insert_resource(settings);
+        world.init_resource::<PaddleKeyOverrides>();
+        world.insert_resource(GameRng::seeded(1));
+        world.init_resource::<AiPlayers>();
+        world.init_resource::<PlayerNames>();
+        world.init_resource::<NextServe>();
+        world.init_resource::<ServeState>();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<Time>();
+        world.init_resource::<Messages<ServeBall>>();
+        world.init_resource::<Messages<StartBall>>();
+        world.init_resource::<Messages<BallOffScreen>>();
+        world.init_resource::<Messages<RestorePaddles>>();
+        spawn_test_ball(&mut world, Vec2::ZERO, None);
+        spawn_test_paddle(&mut world, 10f32, -10f32, Player1);
+        spawn_test_paddle(&mut world, 10f32, -10f32, Player2);
+        world
+    }
+
+    fn press_key(world: &mut World, key: KeyCode) {
+        let mut keys = world.resource_mut::<ButtonInput<KeyCode>>();
+        keys.clear();
+        keys.press(key);
+    }
+
+    fn advance_time(world: &mut World, secs: f32) {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(secs));
+    }
+
+    fn start_ball_count(world: &mut World) -> usize {
+        let mut messages = world.resource_mut::<Messages<StartBall>>();
+        let count = messages.len();
+        messages.clear();
+        count
+    }
+
+    fn indicator_visibility(world: &mut World) -> Visibility {
+        let mut query = world.query_filtered::<&Visibility, With<ServeIndicator>>();
+        *query.single(world).unwrap()
+    }
+
+    fn hint_text(world: &mut World) -> String {
+        let mut query = world.query_filtered::<&Text2d, With<ServeHint>>();
+        query.single(world).unwrap().0.clone()
+    }
+}
@@ -0,0 +1,1149 @@
+//!
+//! The settings module contains the user-configurable settings of the game, and persists
+//! them to a file in the platform's config directory so they survive between runs.
+//!
+
+// -------------------------------------------------------------------------------------------------
+// Included Symbols
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use bevy::audio::{GlobalVolume, Volume};
+use bevy::prelude::*;
+use bevy::reflect::Enum;
+use bevy::window::{PresentMode, WindowMode};
+use serde::{Deserialize, Serialize};
+
+use crate::common::*;
+use crate::paddle::PaddlePosition;
+
+// -------------------------------------------------------------------------------------------------
+// Constants
+
+const SETTINGS_FILE_NAME: &str = "settings.ron";
+
+const DEFAULT_WINDOW_WIDTH: u32 = 1600;
+const DEFAULT_WINDOW_HEIGHT: u32 = 900;
+const DEFAULT_VOLUME: f32 = 1.0;
+const DEFAULT_WINNING_SCORE: u8 = 10;
+const DEFAULT_BEST_OF: u8 = 1;
+const DEFAULT_IMPACT_SPARKS: u32 = 12;
+const DEFAULT_GOAL_SPARKS: u32 = 60;
+const DEFAULT_SPARK_LIFETIME_SECS: f32 = 0.5;
+const DEFAULT_MAX_PARTICLES: u32 = 400;
+
+// -------------------------------------------------------------------------------------------------
+// Public API
+
+///
+/// The SettingsPlugin loads the game Settings from a file when the plugin is built, and
+/// inserts them as a resource. Since this happens while building, any plugins added after
+/// this one (e.g. the window plugin) can read the Settings during their own build.
+///
+/// Any time the Settings resource is changed, it is written back to the same file. If no
+/// file path is given, settings are not loaded or persisted and the defaults are used.
+///
+/// Any overrides given are applied on top of the loaded settings, for this run only. They are
+/// never written to the file: when the Settings are changed in game, only those changes are
+/// saved, and every setting still overridden keeps the value loaded from the file. Paddle key
+/// overrides are kept out of the Settings entirely, in the PaddleKeyOverrides resource.
+///
+pub struct SettingsPlugin {
+    /// Location of the settings file. Defaults to a file in the platform's config directory.
+    pub path: Option<PathBuf>,
+    /// Settings to use in place of those loaded from the file, e.g. from the command line.
+    pub overrides: SettingsOverrides,
+}
+
+impl Default for SettingsPlugin {
+    fn default() -> Self {
+        SettingsPlugin {
+            path: default_settings_path(),
+            overrides: SettingsOverrides::default(),
+        }
+    }
+}
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        let saved = match &self.path {
+            Some(path) => load_settings(path).unwrap_or_else(|err| {
+                warn!(
+                    "Using default settings, failed to load {}: {err}",
+                    path.display()
+                );
+                Settings::default()
+            }),
+            None => Settings::default(),
+        };
+        let mut settings = saved.clone();
+        self.overrides.apply(&mut settings);
+
+        app.insert_resource(settings)
+            .insert_resource(PaddleKeyOverrides(self.overrides.paddle_keys.clone()))
+            .insert_resource(SettingsFile {
+                path: self.path.clone(),
+                saved,
+                overrides: self.overrides.clone(),
+            })
+            .add_systems(
+                Update,
+                (
+                    save_settings.run_if(not(resource_added::<Settings>)),
+                    apply_volume,
+                )
+                    .run_if(resource_changed::<Settings>)
+                    .in_set(Systems::SettingsChangedRcvr),
+            );
+    }
+}
+
+/// These SystemSets are used to control any system ordering dependencies on this plugin
+#[derive(SystemSet, Debug, Clone, Hash, PartialEq, Eq)]
+pub enum Systems {
+    ///
+    /// Update systems which persist and apply changes to the Settings resource. To react
+    /// to changes in the same frame, systems modifying Settings should be ordered before this.
+    ///
+    SettingsChangedRcvr,
+}
+
+///
+/// Optional replacements for individual Settings, which take priority over the settings file
+/// for a single run of the game. Any field left as None keeps its loaded value, and only the
+/// paddles listed in paddle_keys are rebound (see PaddleKeyOverrides).
+///
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SettingsOverrides {
+    /// Replacement for the display mode
+    pub display_mode: Option<DisplayMode>,
+    /// Replacement for the window width, in logical pixels
+    pub window_width: Option<u32>,
+    /// Replacement for the window height, in logical pixels
+    pub window_height: Option<u32>,
+    /// Replacement for the score a player needs to win
+    pub winning_score: Option<u8>,
+    /// Replacement for the way each game is won
+    pub win_condition: Option<WinCondition>,
+    /// Replacement for the number of games in a match
+    pub best_of: Option<u8>,
+    /// Replacement for the way the player receiving each serve is chosen
+    pub serve_rule: Option<ServeRule>,
+    /// Replacement for whether players launch their own serves
+    pub manual_serve: Option<bool>,
+    /// Replacement keys for individual paddles, e.g. from a player's profile
+    pub paddle_keys: Vec<(PlayerId, PaddlePosition, PaddleKeys)>,
+}
+
+impl SettingsOverrides {
+    /// Replace each of the given settings which has an override, except for the paddle keys.
+    pub fn apply(&self, settings: &mut Settings) {
+        if let Some(display_mode) = self.display_mode {
+            settings.display_mode = display_mode;
+        }
+        if let Some(window_width) = self.window_width {
+            settings.window_width = window_width;
+        }
+        if let Some(window_height) = self.window_height {
+            settings.window_height = window_height;
+        }
+        if let Some(winning_score) = self.winning_score {
+            settings.gameplay.winning_score = winning_score;
+        }
+        if let Some(win_condition) = self.win_condition {
+            settings.gameplay.win_condition = win_condition;
+        }
+        if let Some(best_of) = self.best_of {
+            settings.gameplay.best_of = best_of;
+        }
+        if let Some(serve_rule) = self.serve_rule {
+            settings.gameplay.serve_rule = serve_rule;
+        }
+        if let Some(manual_serve) = self.manual_serve {
+            settings.gameplay.manual_serve = manual_serve;
+        }
+    }
+
+    ///
+    /// Undo each override which is still in effect, putting back its value from the saved
+    /// settings. A setting which has been changed since it was overridden is kept as it is.
+    ///
+    pub fn unapply(&self, settings: &mut Settings, saved: &Settings) {
+        if self.display_mode == Some(settings.display_mode) {
+            settings.display_mode = saved.display_mode;
+        }
+        if self.window_width == Some(settings.window_width) {
+            settings.window_width = saved.window_width;
+        }
+        if self.window_height == Some(settings.window_height) {
+            settings.window_height = saved.window_height;
+        }
+        let (gameplay, saved_gameplay) = (&mut settings.gameplay, &saved.gameplay);
+        if self.winning_score == Some(gameplay.winning_score) {
+            gameplay.winning_score = saved_gameplay.winning_score;
+        }
+        if self.win_condition == Some(gameplay.win_condition) {
+            gameplay.win_condition = saved_gameplay.win_condition;
+        }
+        if self.best_of == Some(gameplay.best_of) {
+            gameplay.best_of = saved_gameplay.best_of;
+        }
+        if self.serve_rule == Some(gameplay.serve_rule) {
+            gameplay.serve_rule = saved_gameplay.serve_rule;
+        }
+        if self.manual_serve == Some(gameplay.manual_serve) {
+            gameplay.manual_serve = saved_gameplay.manual_serve;
+        }
+    }
+}
+
+///
+/// Resource holding every user-configurable setting of the game. Any field missing from the
+/// settings file takes its default value, so older files keep loading as settings are added.
+///
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    /// Whether the game is played in a window or fullscreen
+    pub display_mode: DisplayMode,
+    /// Whether presentation waits for vertical sync
+    pub vsync: bool,
+    /// Width of the game window in logical pixels, when windowed
+    pub window_width: u32,
+    /// Height of the game window in logical pixels, when windowed
+    pub window_height: u32,
+    /// Global audio volume, in the range 0.0 to 1.0
+    pub volume: f32,
+    /// Whether a tick sounds for each number of the countdown before a round starts
+    pub countdown_ticks: bool,
+    /// Whether a fading trail is drawn behind the ball
+    pub ball_trail: bool,
+    /// Sparks shown when the ball hits something or a goal is scored
+    pub particles: ParticleSettings,
+    /// Whether the camera shakes and zooms on big moments, which some players may find unpleasant
+    pub camera_effects: bool,
+    /// Keys used to move each paddle
+    pub key_bindings: KeyBindings,
+    /// Options affecting the rules of the game
+    pub gameplay: GameplaySettings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            display_mode: DisplayMode::Windowed,
+            vsync: true,
+            window_width: DEFAULT_WINDOW_WIDTH,
+            window_height: DEFAULT_WINDOW_HEIGHT,
+            volume: DEFAULT_VOLUME,
+            countdown_ticks: true,
+            ball_trail: true,
+            particles: ParticleSettings::default(),
+            camera_effects: true,
+            key_bindings: KeyBindings::default(),
+            gameplay: GameplaySettings::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Get the window PresentMode to use for the configured vsync setting.
+    pub fn present_mode(&self) -> PresentMode {
+        if self.vsync {
+            PresentMode::AutoVsync
+        } else {
+            PresentMode::Immediate
+        }
+    }
+
+    /// Get the WindowMode to use for the configured display mode.
+    pub fn window_mode(&self) -> WindowMode {
+        match self.display_mode {
+            DisplayMode::Windowed => WindowMode::Windowed,
+            DisplayMode::Fullscreen => WindowMode::BorderlessFullscreen(MonitorSelection::Primary),
+        }
+    }
+}
+
+/// Represents the ways the game window may be displayed
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayMode {
+    /// Game is displayed in a resizable window
+    Windowed,
+    /// Game covers the entire primary monitor
+    Fullscreen,
+}
+
+///
+/// Settings which affect the rules of the game. The win condition and number of games only
+/// apply to two player and doubles matches, as four player mode is always a single game which
+/// lasts until one player remains.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct GameplaySettings {
+    /// Score a player must reach to win a two player or doubles game
+    pub winning_score: u8,
+    /// How each game of a two player or doubles match is won
+    pub win_condition: WinCondition,
+    /// Number of games in a match, of which a player must win the majority to win the match
+    pub best_of: u8,
+    /// How the player receiving each serve is chosen
+    pub serve_rule: ServeRule,
+    ///
+    /// Whether the serving player aims and launches each serve themselves with their serve
+    /// key, rather than it being served in a random direction automatically. Serves by
+    /// computer players are always automatic.
+    ///
+    pub manual_serve: bool,
+}
+
+impl Default for GameplaySettings {
+    fn default() -> Self {
+        GameplaySettings {
+            winning_score: DEFAULT_WINNING_SCORE,
+            win_condition: WinCondition::default(),
+            best_of: DEFAULT_BEST_OF,
+            serve_rule: ServeRule::default(),
+            manual_serve: false,
+        }
+    }
+}
+
+impl GameplaySettings {
+    /// Get the number of games a player must win to win the match.
+    pub fn games_to_win(&self) -> u8 {
+        (self.best_of.max(1) / 2) + 1
+    }
+}
+
+///
+/// Settings for the sparks thrown off by ball impacts and goals. The game caps the counts and
+/// lifetime at its own limits, however large the values given here.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct ParticleSettings {
+    /// Whether any sparks are shown
+    pub enabled: bool,
+    /// Number of sparks thrown off each time the ball bounces off a wall or paddle
+    pub impact_sparks: u32,
+    /// Number of sparks in the burst when a goal is scored
+    pub goal_sparks: u32,
+    /// How long each spark lasts, in seconds
+    pub lifetime_secs: f32,
+    /// Most sparks shown at once. No new sparks are added while this many are shown.
+    pub max_particles: u32,
+}
+
+impl Default for ParticleSettings {
+    fn default() -> Self {
+        ParticleSettings {
+            enabled: true,
+            impact_sparks: DEFAULT_IMPACT_SPARKS,
+            goal_sparks: DEFAULT_GOAL_SPARKS,
+            lifetime_secs: DEFAULT_SPARK_LIFETIME_SECS,
+            max_particles: DEFAULT_MAX_PARTICLES,
+        }
+    }
+}
+
+///
+/// The ways a single game may be won. Whichever condition is used, a game can only be won
+/// once a point has been scored, so the rally in play when time runs out is always finished.
+///
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum WinCondition {
+    /// The first player to reach the winning score wins
+    #[default]
+    FirstTo,
+    /// The first player to reach the winning score wins, but only once they lead by two points
+    WinByTwo,
+    ///
+    /// The player leading when the time limit runs out wins, regardless of the winning score.
+    /// If the scores are level, the game goes to sudden death, and the next point wins it.
+    ///
+    Timed {
+        /// Length of the game, counting only the time the ball is in play
+        secs: u32,
+    },
+}
+
+impl WinCondition {
+    /// Get the length of each game, if games are timed.
+    pub fn time_limit(self) -> Option<Duration> {
+        match self {
+            WinCondition::Timed { secs } => Some(Duration::from_secs(u64::from(secs))),
+            WinCondition::FirstTo | WinCondition::WinByTwo => None,
+        }
+    }
+
+    ///
+    /// Get the winner of a game with the given score for each side, if it has been won yet.
+    /// The time_up flag should be set once the time limit of a timed game has run out.
+    ///
+    pub fn game_winner(
+        self,
+        winning_score: u8,
+        scores: &[(PlayerId, u8)],
+        time_up: bool,
+    ) -> Option<PlayerId> {
+        let &(leader, leading_score) = scores.iter().max_by_key(|&&(_, score)| score)?;
+        let next_best = scores
+            .iter()
+            .filter(|&&(player, _)| player != leader)
+            .map(|&(_, score)| score)
+            .max()
+            .unwrap_or(0);
+        let lead = leading_score.saturating_sub(next_best);
+
+        let won = match self {
+            WinCondition::FirstTo => leading_score >= winning_score,
+            WinCondition::WinByTwo => (leading_score >= winning_score) && (lead >= 2),
+            WinCondition::Timed { .. } => time_up && (lead >= 1),
+        };
+        won.then_some(leader)
+    }
+}
+
+impl fmt::Display for WinCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WinCondition::FirstTo => write!(f, "First to Score"),
+            WinCondition::WinByTwo => write!(f, "Win by Two"),
+            WinCondition::Timed { secs } => write!(f, "Timed ({}:{:02})", secs / 60, secs % 60),
+        }
+    }
+}
+
+///
+/// The ways the player receiving each serve may be chosen. The ball is always served from the
+/// center of the arena, by the player across from the receiver.
+///
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ServeRule {
+    /// Each serve goes towards a random player
+    #[default]
+    Random,
+    /// Serves go towards each player in turn
+    Alternate,
+    /// Each serve goes towards the player who conceded the previous point
+    TowardLoser,
+}
+
+impl fmt::Display for ServeRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServeRule::Random => write!(f, "Random"),
+            ServeRule::Alternate => write!(f, "Alternate"),
+            ServeRule::TowardLoser => write!(f, "Toward Loser"),
+        }
+    }
+}
+
+///
+/// The keys which move a single paddle. For paddles on the left and right edges, positive
+/// is up. For paddles on the top and bottom edges, positive is right.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaddleKeys {
+    /// Key moving the paddle in the positive direction
+    #[serde(with = "key_code_serde")]
+    pub positive: KeyCode,
+    /// Key moving the paddle in the negative direction
+    #[serde(with = "key_code_serde")]
+    pub negative: KeyCode,
+}
+
+impl PaddleKeys {
+    const fn new(positive: KeyCode, negative: KeyCode) -> Self {
+        PaddleKeys { positive, negative }
+    }
+}
+
+///
+/// Parses the keys of a paddle from the names of the positive and negative keys, separated
+/// by a comma (e.g. "KeyW,KeyS"). Key names are those of the KeyCode variants.
+///
+impl FromStr for PaddleKeys {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (positive, negative) = s.split_once(',').ok_or_else(|| {
+            format!("expected POSITIVE,NEGATIVE keys, e.g. KeyW,KeyS, but got '{s}'")
+        })?;
+        let parse_key = |name: &str| {
+            key_code_serde::from_name(name.trim()).ok_or_else(|| {
+                format!(
+                    "unknown key '{}', expected a name like KeyW or ArrowUp",
+                    name.trim()
+                )
+            })
+        };
+        Ok(PaddleKeys::new(parse_key(positive)?, parse_key(negative)?))
+    }
+}
+
+impl fmt::Display for PaddleKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{},{}",
+            self.positive.variant_name(),
+            self.negative.variant_name()
+        )
+    }
+}
+
+///
+/// The keys used to move each paddle in the game. Forward paddles only exist in doubles
+/// mode, and the top and bottom paddles only exist in four player mode.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub player1: PaddleKeys,
+    pub player1_forward: PaddleKeys,
+    pub player2: PaddleKeys,
+    pub player2_forward: PaddleKeys,
+    pub player3: PaddleKeys,
+    pub player4: PaddleKeys,
+    #[serde(with = "key_code_serde")]
+    pub player1_serve: KeyCode,
+    #[serde(with = "key_code_serde")]
+    pub player2_serve: KeyCode,
+    #[serde(with = "key_code_serde")]
+    pub player3_serve: KeyCode,
+    #[serde(with = "key_code_serde")]
+    pub player4_serve: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings {
+            player1: PaddleKeys::new(KeyCode::KeyW, KeyCode::KeyS),
+            player1_forward: PaddleKeys::new(KeyCode::KeyT, KeyCode::KeyG),
+            player2: PaddleKeys::new(KeyCode::ArrowUp, KeyCode::ArrowDown),
+            player2_forward: PaddleKeys::new(KeyCode::KeyI, KeyCode::KeyK),
+            player3: PaddleKeys::new(KeyCode::KeyL, KeyCode::KeyJ),
+            player4: PaddleKeys::new(KeyCode::Numpad6, KeyCode::Numpad4),
+            player1_serve: KeyCode::KeyD,
+            player2_serve: KeyCode::ArrowLeft,
+            player3_serve: KeyCode::KeyK,
+            player4_serve: KeyCode::Numpad5,
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Every (player, position) pair which has its own key bindings, in display order
+    pub const PADDLES: [(PlayerId, PaddlePosition); 6] = [
+        (Player1, PaddlePosition::Back),
+        (Player1, PaddlePosition::Forward),
+        (Player2, PaddlePosition::Back),
+        (Player2, PaddlePosition::Forward),
+        (Player3, PaddlePosition::Back),
+        (Player4, PaddlePosition::Back),
+    ];
+
+    ///
+    /// Get the keys for the given player's paddle at the given position. The top and bottom
+    /// edges never have forward paddles, so their back paddle keys are returned instead.
+    ///
+    pub fn paddle(&self, player: PlayerId, position: PaddlePosition) -> PaddleKeys {
+        match (player, position) {
+            (Player1, PaddlePosition::Back) => self.player1,
+            (Player1, PaddlePosition::Forward) => self.player1_forward,
+            (Player2, PaddlePosition::Back) => self.player2,
+            (Player2, PaddlePosition::Forward) => self.player2_forward,
+            (Player3, _) => self.player3,
+            (Player4, _) => self.player4,
+        }
+    }
+
+    /// Get the key the given player presses to launch their serve, when serving manually.
+    pub fn serve(&self, player: PlayerId) -> KeyCode {
+        match player {
+            Player1 => self.player1_serve,
+            Player2 => self.player2_serve,
+            Player3 => self.player3_serve,
+            Player4 => self.player4_serve,
+        }
+    }
+
+    /// Mutable version of serve, for rebinding the serve key of a player.
+    pub fn serve_mut(&mut self, player: PlayerId) -> &mut KeyCode {
+        match player {
+            Player1 => &mut self.player1_serve,
+            Player2 => &mut self.player2_serve,
+            Player3 => &mut self.player3_serve,
+            Player4 => &mut self.player4_serve,
+        }
+    }
+
+    /// Returns true if the given key moves any paddle or launches any serve, false otherwise.
+    pub fn is_bound(&self, key: KeyCode) -> bool {
+        let paddle_bound = Self::PADDLES.iter().any(|&(player, position)| {
+            let keys = self.paddle(player, position);
+            (keys.positive == key) || (keys.negative == key)
+        });
+        paddle_bound
+            || PlayerId::ALL
+                .iter()
+                .any(|&player| self.serve(player) == key)
+    }
+
+    /// Mutable version of paddle, for rebinding the keys of a paddle.
+    pub fn paddle_mut(&mut self, player: PlayerId, position: PaddlePosition) -> &mut PaddleKeys {
+        match (player, position) {
+            (Player1, PaddlePosition::Back) => &mut self.player1,
+            (Player1, PaddlePosition::Forward) => &mut self.player1_forward,
+            (Player2, PaddlePosition::Back) => &mut self.player2,
+            (Player2, PaddlePosition::Forward) => &mut self.player2_forward,
+            (Player3, _) => &mut self.player3,
+            (Player4, _) => &mut self.player4,
+        }
+    }
+}
+
+///
+/// Resource holding the keys which replace the bound keys of individual paddles for this run
+/// only, e.g. from a player's profile. These take priority over the key bindings in the
+/// Settings, but are never written to the settings file.
+///
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct PaddleKeyOverrides(pub Vec<(PlayerId, PaddlePosition, PaddleKeys)>);
+
+impl PaddleKeyOverrides {
+    /// Get the keys for the given paddle, from its override if it has one, else the bindings.
+    pub fn paddle(
+        &self,
+        bindings: &KeyBindings,
+        player: PlayerId,
+        position: PaddlePosition,
+    ) -> PaddleKeys {
+        self.0
+            .iter()
+            .rev()
+            .find(|&&(over_player, over_position, _)| {
+                (over_player, over_position) == (player, position)
+            })
+            .map_or_else(|| bindings.paddle(player, position), |&(_, _, keys)| keys)
+    }
+
+    /// Rebind each overridden paddle in the given key bindings to its override.
+    pub fn apply(&self, bindings: &mut KeyBindings) {
+        for &(player, position, keys) in &self.0 {
+            *bindings.paddle_mut(player, position) = keys;
+        }
+    }
+}
+
+///
+/// Get the default location of the settings file, in the platform's config directory.
+/// Returns None if the platform has no config directory.
+///
+pub fn default_settings_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_DIR_NAME).join(SETTINGS_FILE_NAME))
+}
+
+///
+/// Load the settings from the file at the given path. If there is no file there yet,
+/// the default settings are returned.
+///
+pub fn load_settings(path: &Path) -> Result<Settings, RonFileError> {
+    load_ron_file(path).map(Option::unwrap_or_default)
+}
+
+/// Save the settings to the file at the given path, creating its directory if needed.
+pub fn save_settings_to(path: &Path, settings: &Settings) -> Result<(), RonFileError> {
+    save_ron_file(path, settings)
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Resources
+
+// Location the Settings are persisted to, if any, along with the settings last saved there and
+// the overrides which are kept out of the file.
+#[derive(Resource)]
+struct SettingsFile {
+    path: Option<PathBuf>,
+    saved: Settings,
+    overrides: SettingsOverrides,
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Systems
+
+// Writes the Settings to the settings file whenever they change, leaving out any overrides.
+fn save_settings(settings: Res<Settings>, mut file: ResMut<SettingsFile>) {
+    let file = &mut *file;
+    let Some(path) = &file.path else {
+        return;
+    };
+
+    let mut to_save = settings.clone();
+    file.overrides.unapply(&mut to_save, &file.saved);
+    match save_settings_to(path, &to_save) {
+        Ok(()) => file.saved = to_save,
+        Err(err) => warn!("Failed to save settings to {}: {err}", path.display()),
+    }
+}
+
+// Applies the configured volume to all game audio, whenever the Settings change.
+fn apply_volume(settings: Res<Settings>, global_volume: Option<ResMut<GlobalVolume>>) {
+    if let Some(mut global_volume) = global_volume {
+        global_volume.volume = Volume::Linear(settings.volume);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Functions
+
+//
+// Serde helpers to store a KeyCode as the name of its variant (e.g. "KeyW"), using its
+// reflection data. This keeps the settings file readable and editable by hand.
+//
+pub(crate) mod key_code_serde {
+    use bevy::input::keyboard::KeyCode;
+    use bevy::reflect::{DynamicEnum, DynamicVariant, Enum, FromReflect, Typed, VariantInfo};
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(key: &KeyCode, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(key.variant_name())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<KeyCode, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        from_name(&name).ok_or_else(|| D::Error::custom(format!("unknown key code {name}")))
+    }
+
+    // Get the KeyCode with the given variant name, if there is one.
+    pub fn from_name(name: &str) -> Option<KeyCode> {
+        // Only unit variants can be named on their own, e.g. not Unidentified(NativeKeyCode)
+        let is_unit_variant = KeyCode::type_info()
+            .as_enum()
+            .is_ok_and(|info| matches!(info.variant(name), Some(VariantInfo::Unit(_))));
+        if !is_unit_variant {
+            return None;
+        }
+
+        KeyCode::from_reflect(&DynamicEnum::new(name, DynamicVariant::Unit))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_test_helpers::prelude::*;
+    use std::fs;
+
+    #[test]
+    fn test_plugin_build_without_path() {
+        let mut app = App::new();
+        app.add_plugins(SettingsPlugin {
+            path: None,
+            ..default()
+        });
+
+        assert_eq!(
+            *app.world().resource::<Settings>(),
+            Settings::default(),
+            "Expected default settings when no settings file is used",
+        );
+    }
+
+    #[test]
+    fn test_plugin_sys_added_save_settings() {
+        validate_sys_in_plugin(
+            SettingsPlugin {
+                path: None,
+                ..default()
+            },
+            Update,
+            save_settings,
+            Some(Systems::SettingsChangedRcvr),
+        );
+    }
+
+    #[test]
+    fn test_plugin_sys_added_apply_volume() {
+        validate_sys_in_plugin(
+            SettingsPlugin {
+                path: None,
+                ..default()
+            },
+            Update,
+            apply_volume,
+            Some(Systems::SettingsChangedRcvr),
+        );
+    }
+
+    #[test]
+    fn test_plugin_build_with_overrides() {
+        let path = test_settings_path("overrides");
+        let saved = Settings {
+            volume: 0.5,
+            ..default()
+        };
+        save_settings_to(&path, &saved).unwrap();
+
+        let mut app = App::new();
+        app.add_plugins(SettingsPlugin {
+            path: Some(path.clone()),
+            overrides: SettingsOverrides {
+                window_width: Some(800),
+                winning_score: Some(3),
+                best_of: Some(5),
+                serve_rule: Some(ServeRule::TowardLoser),
+                ..default()
+            },
+        });
+
+        // Overrides replace loaded settings, but shouldn't be saved just by starting up
+        let mut exp_settings = saved.clone();
+        exp_settings.window_width = 800;
+        exp_settings.gameplay.winning_score = 3;
+        exp_settings.gameplay.best_of = 5;
+        exp_settings.gameplay.serve_rule = ServeRule::TowardLoser;
+        app.update();
+        assert_eq!(
+            *app.world().resource::<Settings>(),
+            exp_settings,
+            "Expected overrides to be applied on top of the loaded settings",
+        );
+        assert_eq!(
+            load_settings(&path).unwrap(),
+            saved,
+            "Expected settings file to be untouched at startup",
+        );
+
+        // Later changes are saved, but the overrides are not
+        app.world_mut().resource_mut::<Settings>().volume = 0.2;
+        let mut exp_saved = saved.clone();
+        exp_saved.volume = 0.2;
+        app.update();
+        assert_eq!(
+            load_settings(&path).unwrap(),
+            exp_saved,
+            "Expected changed settings to be saved without the overrides",
+        );
+
+        // Unless an overridden setting is itself changed in game
+        app.world_mut().resource_mut::<Settings>().window_width = 1024;
+        exp_saved.window_width = 1024;
+        app.update();
+        assert_eq!(
+            load_settings(&path).unwrap(),
+            exp_saved,
+            "Expected an overridden setting changed in game to be saved",
+        );
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_paddle_key_overrides_not_saved() {
+        let path = test_settings_path("paddle_key_overrides");
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        // e.g. the keys of player 2's profile
+        let profile_keys = PaddleKeys::new(KeyCode::KeyO, KeyCode::KeyL);
+        let mut app = App::new();
+        app.add_plugins(SettingsPlugin {
+            path: Some(path.clone()),
+            overrides: SettingsOverrides {
+                paddle_keys: vec![(Player2, PaddlePosition::Back, profile_keys)],
+                ..default()
+            },
+        });
+        app.update();
+
+        let key_overrides = app.world().resource::<PaddleKeyOverrides>();
+        let bindings = &app.world().resource::<Settings>().key_bindings;
+        assert_eq!(
+            key_overrides.paddle(bindings, Player2, PaddlePosition::Back),
+            profile_keys,
+            "Expected the overridden paddle to be moved with the profile's keys",
+        );
+        assert_eq!(
+            key_overrides.paddle(bindings, Player1, PaddlePosition::Back),
+            KeyBindings::default().player1,
+            "Expected other paddles to keep their bound keys",
+        );
+        assert_eq!(
+            *bindings,
+            KeyBindings::default(),
+            "Expected the profile's keys to be kept out of the Settings",
+        );
+
+        // Changing any setting in game saves the file, still without the profile's keys
+        app.world_mut().resource_mut::<Settings>().vsync = false;
+        app.update();
+        assert_eq!(
+            load_settings(&path).unwrap().key_bindings,
+            KeyBindings::default(),
+            "Expected the profile's keys never to reach the settings file",
+        );
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_save_and_load_settings() {
+        let path = test_settings_path("save_and_load");
+        let mut settings = Settings {
+            display_mode: DisplayMode::Fullscreen,
+            vsync: false,
+            volume: 0.3,
+            countdown_ticks: false,
+            ball_trail: false,
+            ..default()
+        };
+        settings.key_bindings.player2.positive = KeyCode::KeyP;
+        settings.gameplay.winning_score = 3;
+        settings.gameplay.win_condition = WinCondition::Timed { secs: 90 };
+        settings.gameplay.manual_serve = true;
+        settings.key_bindings.player3_serve = KeyCode::Space;
+
+        save_settings_to(&path, &settings).unwrap();
+        let loaded = load_settings(&path).unwrap();
+        assert_eq!(loaded, settings, "Expected loaded settings to match saved");
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_load_settings_missing_file() {
+        let path = test_settings_path("missing_file");
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        let loaded = load_settings(&path).unwrap();
+        assert_eq!(
+            loaded,
+            Settings::default(),
+            "Expected default settings when there is no settings file",
+        );
+    }
+
+    #[test]
+    fn test_load_settings_partial_and_invalid() {
+        let path = test_settings_path("partial_and_invalid");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        // Missing fields take their default values
+        fs::write(
+            &path,
+            "(vsync: false, key_bindings: (player1: (positive: \"KeyQ\", negative: \"KeyA\")))",
+        )
+        .unwrap();
+        let loaded = load_settings(&path).unwrap();
+        assert!(!loaded.vsync, "Expected vsync to be loaded from file");
+        assert_eq!(
+            loaded.key_bindings.player1,
+            PaddleKeys::new(KeyCode::KeyQ, KeyCode::KeyA),
+            "Expected player 1 bindings to be loaded from file",
+        );
+        assert_eq!(
+            loaded.key_bindings.player2,
+            KeyBindings::default().player2,
+            "Expected player 2 bindings to be defaulted",
+        );
+        assert_eq!(loaded.window_width, DEFAULT_WINDOW_WIDTH);
+
+        // Nested settings are defaulted field by field too
+        fs::write(&path, "(particles: (goal_sparks: 5))").unwrap();
+        let loaded = load_settings(&path).unwrap();
+        assert_eq!(
+            loaded.particles,
+            ParticleSettings {
+                goal_sparks: 5,
+                ..default()
+            },
+            "Expected only the goal sparks to be loaded from file",
+        );
+
+        // Unknown key names are an error
+        fs::write(
+            &path,
+            "(key_bindings: (player1: (positive: \"NotAKey\", negative: \"KeyA\")))",
+        )
+        .unwrap();
+        assert!(
+            matches!(load_settings(&path), Err(RonFileError::Parse(_))),
+            "Expected parse error for unknown key name",
+        );
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_save_settings_system() {
+        let path = test_settings_path("save_system");
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        let mut world = World::default();
+        let settings = Settings {
+            volume: 0.5,
+            ..default()
+        };
+        world.insert_resource(settings.clone());
+        world.insert_resource(SettingsFile {
+            path: Some(path.clone()),
+            saved: Settings::default(),
+            overrides: SettingsOverrides::default(),
+        });
+
+        let save_sys = world.register_system(save_settings);
+        world.run_system(save_sys).unwrap();
+        assert_eq!(
+            load_settings(&path).unwrap(),
+            settings,
+            "Expected settings to be written to the settings file",
+        );
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_apply_volume_system() {
+        let mut world = World::default();
+        let settings = Settings {
+            volume: 0.25,
+            ..default()
+        };
+        world.insert_resource(settings);
+        world.insert_resource(GlobalVolume::default());
+
+        let volume_sys = world.register_system(apply_volume);
+        world.run_system(volume_sys).unwrap();
+        assert_eq!(
+            world.resource::<GlobalVolume>().volume,
+            Volume::Linear(0.25),
+            "Expected global volume to match settings",
+        );
+    }
+
+    #[test]
+    fn test_key_bindings_paddle() {
+        let mut bindings = KeyBindings::default();
+        bindings
+            .paddle_mut(Player1, PaddlePosition::Forward)
+            .negative = KeyCode::KeyB;
+        assert_eq!(
+            bindings.paddle(Player1, PaddlePosition::Forward),
+            PaddleKeys::new(KeyCode::KeyT, KeyCode::KeyB),
+        );
+        assert_eq!(
+            bindings.paddle(Player3, PaddlePosition::Forward),
+            bindings.player3,
+            "Expected top paddle to use back paddle keys for any position",
+        );
+
+        *bindings.serve_mut(Player4) = KeyCode::Enter;
+        assert_eq!(bindings.serve(Player4), KeyCode::Enter);
+        assert_eq!(bindings.serve(Player1), KeyCode::KeyD);
+    }
+
+    #[test]
+    fn test_key_bindings_is_bound() {
+        let mut bindings = KeyBindings::default();
+        assert!(
+            bindings.is_bound(KeyCode::KeyL),
+            "Expected player 3 paddle key"
+        );
+        assert!(
+            bindings.is_bound(KeyCode::Numpad5),
+            "Expected player 4 serve key"
+        );
+        assert!(!bindings.is_bound(KeyCode::Tab));
+
+        bindings
+            .paddle_mut(Player2, PaddlePosition::Forward)
+            .positive = KeyCode::Tab;
+        assert!(bindings.is_bound(KeyCode::Tab));
+        assert!(
+            !bindings.is_bound(KeyCode::KeyI),
+            "Expected rebound key to be free"
+        );
+    }
+
+    #[test]
+    fn test_paddle_keys_from_str() {
+        let keys = PaddleKeys::new(KeyCode::ArrowUp, KeyCode::Numpad2);
+        assert_eq!("ArrowUp, Numpad2".parse(), Ok(keys));
+        assert_eq!(
+            keys.to_string().parse(),
+            Ok(keys),
+            "Expected displayed keys to parse back to the same keys",
+        );
+        for invalid in ["KeyW", "KeyW,Nope", "Unidentified,KeyS"] {
+            assert!(
+                invalid.parse::<PaddleKeys>().is_err(),
+                "Expected '{invalid}' to fail to parse",
+            );
+        }
+    }
+
+    #[test]
+    fn test_win_condition_game_winner() {
+        let scores = |p1, p2| [(Player1, p1), (Player2, p2)];
+        let first_to = WinCondition::FirstTo;
+        assert_eq!(first_to.game_winner(5, &scores(4, 2), false), None);
+        assert_eq!(first_to.game_winner(5, &scores(4, 5), false), Some(Player2));
+
+        // Winning by two goes to deuce when the winning score is reached with a lead of one
+        let win_by_two = WinCondition::WinByTwo;
+        assert_eq!(win_by_two.game_winner(5, &scores(5, 4), false), None);
+        assert_eq!(
+            win_by_two.game_winner(5, &scores(7, 5), false),
+            Some(Player1)
+        );
+        assert_eq!(
+            win_by_two.game_winner(5, &scores(5, 3), false),
+            Some(Player1)
+        );
+
+        // Timed games ignore the winning score, and go to sudden death when level
+        let timed = WinCondition::Timed { secs: 60 };
+        assert_eq!(timed.game_winner(5, &scores(9, 2), false), None);
+        assert_eq!(timed.game_winner(5, &scores(3, 3), true), None);
+        assert_eq!(timed.game_winner(5, &scores(3, 4), true), Some(Player2));
+        assert_eq!(timed.time_limit(), Some(Duration::from_secs(60)));
+        assert_eq!(first_to.time_limit(), None);
+    }
+
+    #[test]
+    fn test_games_to_win() {
+        for (best_of, exp_games) in [(0, 1), (1, 1), (3, 2), (5, 3), (4, 3)] {
+            let gameplay = GameplaySettings {
+                best_of,
+                ..default()
+            };
+            assert_eq!(
+                gameplay.games_to_win(),
+                exp_games,
+                "Expected {exp_games} games to win a best of {best_of} match",
+            );
+        }
+    }
+
+    // --- Helper Functions ---
+
+    // Get a settings file path unique to the given test, in the temp directory.
+    fn test_settings_path(test_name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!(
+                "rust_pong_settings_{}_{test_name}",
+                std::process::id()
+            ))
+            .join(SETTINGS_FILE_NAME)
+    }
+}
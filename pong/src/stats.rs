@@ -0,0 +1,743 @@
+//!
+//! The stats module keeps statistics about each match as it is played, such as the length of
+//! each rally and how often each player hit the ball. Once a match ends, the statistics are
+//! summarized on screen, and may be exported to a JSON file.
+//!
+
+// -------------------------------------------------------------------------------------------------
+// Included Symbols
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use serde::{Deserialize, Serialize};
+
+use bevy_dyn_fontsize::DynamicFontSize;
+
+use crate::ball::{self, BallHitPaddle, StartBall};
+use crate::common::*;
+use crate::score::{self, ClearScores, GoalConceded, MaxScoreReached, PlayerScored};
+
+// -------------------------------------------------------------------------------------------------
+// Constants
+
+const SUMMARY_FONT_SIZE_AS_SCREEN_PCT: f32 = 0.03;
+const SUMMARY_TOP_AS_SCREEN_PCT: f32 = -0.08;
+
+const SUMMARY_TEXT_HEIGHT: f32 = SUMMARY_FONT_SIZE_AS_SCREEN_PCT * ARENA_HEIGHT;
+const SUMMARY_TEXT_Y: f32 = SUMMARY_TOP_AS_SCREEN_PCT * ARENA_HEIGHT;
+
+// -------------------------------------------------------------------------------------------------
+// Public API
+
+///
+/// This plugin keeps the MatchStats resource up to date while a match is played, based on the
+/// messages of the ball and score modules. A new match begins with the first StartBall
+/// message after the previous match ended, and ends with the MaxScoreReached message. The
+/// statistics of the finished match remain available until the next match begins.
+///
+/// Once a match ends, a summary of its statistics is shown below the center of the arena, until
+/// the scores are cleared. If an export directory is given, the statistics are also saved
+/// there as a JSON file named after the time the match ended.
+///
+/// This plugin will only work properly if the app contains a single Window and a single
+/// Camera2d entity, which MUST be created before the SetupAfterCamera SystemSet runs.
+///
+pub struct StatsPlugin {
+    /// Directory to save the statistics of each finished match to, if any.
+    pub export_dir: Option<PathBuf>,
+}
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameMode>()
+            .init_resource::<MatchStats>()
+            .init_resource::<MatchTracker>()
+            .add_message::<StartBall>()
+            .add_message::<BallHitPaddle>()
+            .add_message::<PlayerScored>()
+            .add_message::<GoalConceded>()
+            .add_message::<MaxScoreReached>()
+            .add_message::<ClearScores>()
+            .add_systems(Startup, setup.in_set(Systems::SetupAfterCamera))
+            .add_systems(
+                Update,
+                (
+                    track_match_stats.in_set(Systems::TrackStats),
+                    update_match_summary.after(Systems::TrackStats),
+                ),
+            )
+            .configure_sets(
+                Update,
+                Systems::TrackStats
+                    .after(ball::Systems::BallHitPaddleSndr)
+                    .after(score::Systems::MaxScoreReachedSndr),
+            );
+
+        if let Some(export_dir) = &self.export_dir {
+            app.insert_resource(StatsExport(export_dir.clone()))
+                .add_systems(Update, export_match_stats.after(Systems::TrackStats));
+        }
+    }
+}
+
+/// These SystemSets are used to control any system ordering dependencies on this plugin
+#[derive(SystemSet, Debug, Clone, Hash, PartialEq, Eq)]
+pub enum Systems {
+    ///
+    /// The required single Camera2d Entity MUST be created
+    /// BEFORE this SystemSet is run. Must be in Startup.
+    ///
+    SetupAfterCamera,
+
+    ///
+    /// Update systems which keep the MatchStats resource up to date. This set is already
+    /// ordered after the systems writing BallHitPaddle and MaxScoreReached messages, so a
+    /// system reading the MatchStats of a finished match in the same frame as the
+    /// MaxScoreReached message should be ordered after this set.
+    ///
+    TrackStats,
+}
+
+///
+/// Resource holding the statistics of the match currently being played, or of the last match
+/// played if it has finished. Per player statistics hold an entry for each player of the
+/// GameMode (each team, in doubles mode).
+///
+#[derive(Resource, Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchStats {
+    /// The variant of pong being played
+    pub mode: GameMode,
+    /// The player who won the match, or None while the match is still being played
+    pub winner: Option<PlayerId>,
+    /// Time the match has been played for, in seconds
+    pub duration_secs: f32,
+    /// Every point finished so far, in the order they were played
+    pub points: Vec<PointStats>,
+    /// The most times the ball was hit by a paddle during a single point
+    pub longest_rally: u32,
+    /// Number of times each player hit the ball with a paddle
+    pub paddle_hits: PerPlayer<u32>,
+    /// The strongest curve (from 0 to 3) each player put on the ball
+    pub max_curve_level: PerPlayer<u8>,
+    /// Number of points each player won with a curving ball
+    pub curve_points: PerPlayer<u32>,
+}
+
+impl MatchStats {
+    /// Create the statistics for a new match of the given mode, before any point is played.
+    pub fn new(mode: GameMode) -> Self {
+        Self {
+            mode,
+            paddle_hits: PerPlayer::filled(mode.players(), 0),
+            max_curve_level: PerPlayer::filled(mode.players(), 0),
+            curve_points: PerPlayer::filled(mode.players(), 0),
+            ..default()
+        }
+    }
+
+    /// Get the average number of paddle hits per point, or 0 if no point has finished.
+    pub fn average_rally(&self) -> f32 {
+        let total: u32 = self.points.iter().map(|point| point.rally_length).sum();
+        total as f32 / self.points.len().max(1) as f32
+    }
+
+    /// Get the average time taken to play a point, in seconds, or 0 if no point has finished.
+    pub fn average_point_secs(&self) -> f32 {
+        let total: f32 = self.points.iter().map(|point| point.duration_secs).sum();
+        total / self.points.len().max(1) as f32
+    }
+}
+
+/// Statistics about a single point of a match.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PointStats {
+    ///
+    /// The player who won the point. In four player mode, this is the player who last hit the
+    /// ball, which is None if nobody hit it or the conceding player hit it last.
+    ///
+    pub scorer: Option<PlayerId>,
+    /// The player who conceded the point, in four player mode
+    pub conceded_by: Option<PlayerId>,
+    /// Number of times the ball was hit by a paddle during the point
+    pub rally_length: u32,
+    /// Time taken to play the point, in seconds
+    pub duration_secs: f32,
+    /// Whether the point was won by a curving ball, hit by the scorer
+    pub curve_shot: bool,
+}
+
+/// Errors which can occur while exporting match statistics.
+#[derive(Debug)]
+pub enum StatsError {
+    /// The statistics file could not be written
+    Io(io::Error),
+    /// The statistics could not be converted to JSON
+    Serialize(serde_json::Error),
+}
+
+impl fmt::Display for StatsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatsError::Io(err) => write!(f, "file error: {err}"),
+            StatsError::Serialize(err) => write!(f, "failed to serialize match stats: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for StatsError {}
+
+/// Save match statistics to the given file as JSON, creating its parent directory if needed.
+pub fn save_match_stats_to(path: &Path, stats: &MatchStats) -> Result<(), StatsError> {
+    let contents = serde_json::to_string_pretty(stats).map_err(StatsError::Serialize)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(StatsError::Io)?;
+    }
+    fs::write(path, contents).map_err(StatsError::Io)
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Resources
+
+// Progress through the current match and point, used to work out their statistics.
+#[derive(Resource, Default)]
+struct MatchTracker {
+    // Elapsed game time when the current match started, or None between matches.
+    match_start: Option<f32>,
+
+    // Elapsed game time when the current point started, or None between points.
+    point_start: Option<f32>,
+
+    // Number of paddle hits so far in the current point.
+    rally_length: u32,
+
+    // The most recent paddle hit in the current point, if any.
+    last_hit: Option<BallHitPaddle>,
+}
+
+// The directory to export the statistics of each finished match to.
+#[derive(Resource)]
+struct StatsExport(PathBuf);
+
+// -------------------------------------------------------------------------------------------------
+// Private Components
+
+// Component for the text Entity summarizing the statistics of a finished match.
+#[derive(Component)]
+struct MatchSummary;
+
+// -------------------------------------------------------------------------------------------------
+// Private Systems
+
+// Startup system to spawn the match summary text, which is hidden until a match ends.
+fn setup(mut commands: Commands, camera_entity: Single<Entity, With<Camera2d>>) {
+    commands.spawn((
+        MatchSummary,
+        DynamicFontSize {
+            height_in_world: SUMMARY_TEXT_HEIGHT,
+            render_camera: camera_entity.entity(),
+            max_width_in_world: None,
+        },
+        Text2d::default(),
+        TextLayout::new_with_justify(Justify::Center),
+        Anchor::TOP_CENTER,
+        Transform::from_xyz(0f32, SUMMARY_TEXT_Y, Z_BEHIND_GAMEPLAY),
+        Visibility::Hidden,
+    ));
+}
+
+//
+// System to update the MatchStats from the messages of the ball and score modules. Within a
+// frame, messages are handled in the order they occur during a point: the start of the
+// point, paddle hits, the point being scored, and finally the end of the match.
+//
+#[allow(clippy::too_many_arguments)]
+fn track_match_stats(
+    time: Res<Time>,
+    mode: Res<GameMode>,
+    mut start_msgs: MessageReader<StartBall>,
+    mut hit_msgs: MessageReader<BallHitPaddle>,
+    mut score_msgs: MessageReader<PlayerScored>,
+    mut conceded_msgs: MessageReader<GoalConceded>,
+    mut max_score_msgs: MessageReader<MaxScoreReached>,
+    mut stats: ResMut<MatchStats>,
+    mut tracker: ResMut<MatchTracker>,
+) {
+    let now = time.elapsed_secs();
+
+    if !start_msgs.is_empty() {
+        start_msgs.clear();
+        if tracker.match_start.is_none() {
+            *stats = MatchStats::new(*mode);
+            tracker.match_start = Some(now);
+        }
+        tracker.point_start = Some(now);
+        tracker.rally_length = 0;
+        tracker.last_hit = None;
+    }
+
+    for &hit in hit_msgs.read() {
+        if tracker.point_start.is_none() {
+            continue;
+        }
+        tracker.rally_length += 1;
+        tracker.last_hit = Some(hit);
+        if let Some(hits) = stats.paddle_hits.get_mut(hit.player) {
+            *hits += 1;
+        }
+        if let Some(max_level) = stats.max_curve_level.get_mut(hit.player) {
+            *max_level = (*max_level).max(hit.curve_level);
+        }
+    }
+
+    let scored = score_msgs
+        .read()
+        .map(|&PlayerScored(scorer)| (Some(scorer), None));
+    let conceded = conceded_msgs
+        .read()
+        .map(|&GoalConceded(player)| (None, Some(player)));
+    for (scorer, conceded_by) in scored.chain(conceded) {
+        let Some(point_start) = tracker.point_start.take() else {
+            continue;
+        };
+
+        // A conceded goal is won by whoever last hit the ball, unless they conceded it
+        let last_hit = tracker.last_hit.take();
+        let scorer = scorer.or(last_hit
+            .map(|hit| hit.player)
+            .filter(|&player| Some(player) != conceded_by));
+        let curve_shot =
+            last_hit.is_some_and(|hit| (Some(hit.player) == scorer) && (hit.curve_level > 0));
+        if let Some(scorer) = scorer.filter(|_| curve_shot)
+            && let Some(curve_points) = stats.curve_points.get_mut(scorer)
+        {
+            *curve_points += 1;
+        }
+
+        stats.longest_rally = stats.longest_rally.max(tracker.rally_length);
+        stats.points.push(PointStats {
+            scorer,
+            conceded_by,
+            rally_length: tracker.rally_length,
+            duration_secs: now - point_start,
+            curve_shot,
+        });
+    }
+
+    if let Some(match_start) = tracker.match_start {
+        stats.duration_secs = now - match_start;
+    }
+    if let Some(&MaxScoreReached(winner)) = max_score_msgs.read().last() {
+        stats.winner = Some(winner);
+        tracker.match_start = None;
+    }
+}
+
+//
+// System to show the summary of the match statistics once a match ends, and hide it again
+// when the scores are cleared for the next match.
+//
+fn update_match_summary(
+    mut max_score_msgs: MessageReader<MaxScoreReached>,
+    mut clear_msgs: MessageReader<ClearScores>,
+    stats: Res<MatchStats>,
+    summary: Single<(&mut Text2d, &mut Visibility), With<MatchSummary>>,
+) {
+    let (mut text, mut visibility) = summary.into_inner();
+
+    if !clear_msgs.is_empty() {
+        clear_msgs.clear();
+        *visibility = Visibility::Hidden;
+    }
+
+    if !max_score_msgs.is_empty() {
+        max_score_msgs.clear();
+        text.0 = summary_text(&stats);
+        *visibility = Visibility::Visible;
+    }
+}
+
+// System to export the statistics of a match to the export directory once it ends.
+fn export_match_stats(
+    mut max_score_msgs: MessageReader<MaxScoreReached>,
+    export: Res<StatsExport>,
+    stats: Res<MatchStats>,
+) {
+    if max_score_msgs.is_empty() {
+        return;
+    }
+    max_score_msgs.clear();
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let path = export.0.join(format!("match-{timestamp}.json"));
+    match save_match_stats_to(&path, &stats) {
+        Ok(()) => info!("Saved match stats to {}", path.display()),
+        Err(err) => warn!("Failed to save match stats to {}: {err}", path.display()),
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Functions
+
+// Get the text summarizing the statistics of a finished match.
+fn summary_text(stats: &MatchStats) -> String {
+    let duration_secs = stats.duration_secs.max(0f32).round() as u32;
+    [
+        format!("Longest rally: {} hits", stats.longest_rally),
+        format!("Average rally: {:.1} hits", stats.average_rally()),
+        per_player_line("Paddle hits", &stats.paddle_hits),
+        per_player_line("Best curve", &stats.max_curve_level),
+        per_player_line("Curve points", &stats.curve_points),
+        format!(
+            "Match time: {}:{:02} ({:.1}s per point)",
+            duration_secs / 60,
+            duration_secs % 60,
+            stats.average_point_secs(),
+        ),
+    ]
+    .join("\n")
+}
+
+// Get a line of the summary text listing a statistic for each player, e.g. "Hits: P1 3  P2 4".
+fn per_player_line<T: fmt::Display>(label: &str, values: &PerPlayer<T>) -> String {
+    let values: Vec<String> = values
+        .iter()
+        .map(|(player, value)| format!("P{} {value}", player.index() + 1))
+        .collect();
+    format!("{label}: {}", values.join("  "))
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paddle::PaddlePosition;
+    use bevy_test_helpers::prelude::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_plugin_build() {
+        let mut app = App::new();
+        app.add_plugins(StatsPlugin { export_dir: None });
+
+        let world = app.world();
+        assert_eq!(*world.resource::<MatchStats>(), MatchStats::default());
+        assert!(
+            !world.contains_resource::<StatsExport>(),
+            "Expected no StatsExport resource without an export directory",
+        );
+    }
+
+    #[test]
+    fn test_plugin_sys_added_setup() {
+        validate_sys_in_plugin(
+            StatsPlugin { export_dir: None },
+            Startup,
+            setup,
+            Some(Systems::SetupAfterCamera),
+        );
+    }
+
+    #[test]
+    fn test_plugin_sys_added_track_match_stats() {
+        validate_sys_in_plugin(
+            StatsPlugin { export_dir: None },
+            Update,
+            track_match_stats,
+            Some(Systems::TrackStats),
+        );
+    }
+
+    #[test]
+    fn test_plugin_sys_added_update_match_summary() {
+        validate_sys_in_plugin(
+            StatsPlugin { export_dir: None },
+            Update,
+            update_match_summary,
+            None::<bevy::ecs::schedule::AnonymousSet>,
+        );
+    }
+
+    #[test]
+    fn test_plugin_sys_added_export_match_stats() {
+        validate_sys_in_plugin(
+            StatsPlugin {
+                export_dir: Some(test_export_dir("sys_added")),
+            },
+            Update,
+            export_match_stats,
+            None::<bevy::ecs::schedule::AnonymousSet>,
+        );
+    }
+
+    #[test]
+    fn test_track_match_stats_two_player() {
+        let mut world = stats_test_world(GameMode::TwoPlayer);
+        let track_sys = world.register_system(track_match_stats);
+
+        // First point: P1 and P2 rally, and P2 wins it with a curve shot
+        world.write_message(StartBall);
+        world.run_system(track_sys).unwrap();
+        advance_time(&mut world, 2000);
+        write_hit(&mut world, Player1, 1);
+        write_hit(&mut world, Player2, 2);
+        world.run_system(track_sys).unwrap();
+        advance_time(&mut world, 1000);
+        write_hit(&mut world, Player1, 0);
+        write_hit(&mut world, Player2, 1);
+        world.write_message(PlayerScored(Player2));
+        world.run_system(track_sys).unwrap();
+
+        // Hits between points (e.g. against a paused ball) are ignored
+        write_hit(&mut world, Player1, 3);
+        world.run_system(track_sys).unwrap();
+
+        // Second point: P1 wins after a single hit of their own, and the match ends
+        advance_time(&mut world, 1000);
+        world.write_message(StartBall);
+        world.run_system(track_sys).unwrap();
+        advance_time(&mut world, 1000);
+        write_hit(&mut world, Player1, 0);
+        world.write_message(PlayerScored(Player1));
+        world.write_message(MaxScoreReached(Player1));
+        world.run_system(track_sys).unwrap();
+
+        let stats = world.resource::<MatchStats>();
+        assert_eq!(
+            stats.points,
+            vec![
+                PointStats {
+                    scorer: Some(Player2),
+                    conceded_by: None,
+                    rally_length: 4,
+                    duration_secs: 3.0,
+                    curve_shot: true,
+                },
+                PointStats {
+                    scorer: Some(Player1),
+                    conceded_by: None,
+                    rally_length: 1,
+                    duration_secs: 1.0,
+                    curve_shot: false,
+                },
+            ],
+        );
+        assert_eq!(stats.winner, Some(Player1));
+        assert_eq!(stats.duration_secs, 5.0);
+        assert_eq!(stats.longest_rally, 4);
+        assert_eq!(stats.paddle_hits, two_players(3, 2));
+        assert_eq!(stats.max_curve_level, two_players(1, 2));
+        assert_eq!(stats.curve_points, two_players(0, 1));
+
+        // The finished match stays in place until the next one starts
+        advance_time(&mut world, 3000);
+        world.run_system(track_sys).unwrap();
+        assert_eq!(world.resource::<MatchStats>().duration_secs, 5.0);
+
+        world.write_message(StartBall);
+        world.run_system(track_sys).unwrap();
+        assert_eq!(
+            *world.resource::<MatchStats>(),
+            MatchStats::new(GameMode::TwoPlayer),
+            "Expected stats to be reset when the next match starts",
+        );
+    }
+
+    #[test]
+    fn test_track_match_stats_four_player() {
+        let mut world = stats_test_world(GameMode::FourPlayer);
+        let track_sys = world.register_system(track_match_stats);
+
+        // P3 hits the ball past P1 with a curve, so P3 wins the point
+        world.write_message(StartBall);
+        world.run_system(track_sys).unwrap();
+        write_hit(&mut world, Player3, 2);
+        world.write_message(GoalConceded(Player1));
+        world.run_system(track_sys).unwrap();
+
+        // P2 hits the ball into their own goal, so nobody wins the point
+        world.write_message(StartBall);
+        world.run_system(track_sys).unwrap();
+        write_hit(&mut world, Player2, 1);
+        world.write_message(GoalConceded(Player2));
+        world.run_system(track_sys).unwrap();
+
+        let stats = world.resource::<MatchStats>();
+        let scorers: Vec<_> = stats
+            .points
+            .iter()
+            .map(|point| (point.scorer, point.conceded_by, point.curve_shot))
+            .collect();
+        assert_eq!(
+            scorers,
+            vec![
+                (Some(Player3), Some(Player1), true),
+                (None, Some(Player2), false),
+            ],
+        );
+        assert_eq!(stats.curve_points, PerPlayer::from([0, 0, 1, 0]));
+        assert_eq!(stats.winner, None, "Expected the match to still be in play");
+    }
+
+    #[test]
+    fn test_update_match_summary() {
+        let mut world = World::default();
+        world.init_resource::<Messages<MaxScoreReached>>();
+        world.init_resource::<Messages<ClearScores>>();
+        world.insert_resource(MatchStats {
+            longest_rally: 7,
+            ..MatchStats::new(GameMode::TwoPlayer)
+        });
+        let summary = world
+            .spawn((MatchSummary, Text2d::default(), Visibility::Hidden))
+            .id();
+        let summary_sys = world.register_system(update_match_summary);
+
+        world.write_message(MaxScoreReached(Player2));
+        world.run_system(summary_sys).unwrap();
+        let (text, visibility) = world
+            .query::<(&Text2d, &Visibility)>()
+            .get(&world, summary)
+            .unwrap();
+        assert_eq!(*visibility, Visibility::Visible);
+        assert!(
+            text.0.starts_with("Longest rally: 7 hits\n"),
+            "Expected summary of the match stats, got {}",
+            text.0,
+        );
+
+        world.write_message(ClearScores);
+        world.run_system(summary_sys).unwrap();
+        assert_eq!(
+            world.get::<Visibility>(summary),
+            Some(&Visibility::Hidden),
+            "Expected summary to be hidden when scores are cleared",
+        );
+    }
+
+    #[test]
+    fn test_summary_text() {
+        let mut stats = MatchStats::new(GameMode::TwoPlayer);
+        stats.duration_secs = 125.4;
+        stats.longest_rally = 6;
+        stats.paddle_hits = two_players(5, 4);
+        stats.max_curve_level[Player1] = 3;
+        stats.curve_points[Player2] = 1;
+        stats.points = vec![
+            PointStats {
+                rally_length: 6,
+                duration_secs: 10.0,
+                ..default()
+            },
+            PointStats {
+                rally_length: 3,
+                duration_secs: 5.0,
+                ..default()
+            },
+        ];
+
+        assert_eq!(
+            summary_text(&stats),
+            "Longest rally: 6 hits\n\
+             Average rally: 4.5 hits\n\
+             Paddle hits: P1 5  P2 4\n\
+             Best curve: P1 3  P2 0\n\
+             Curve points: P1 0  P2 1\n\
+             Match time: 2:05 (7.5s per point)",
+        );
+    }
+
+    #[test]
+    fn test_export_match_stats() {
+        let export_dir = test_export_dir("export");
+        let mut world = World::default();
+        world.init_resource::<Messages<MaxScoreReached>>();
+        world.insert_resource(StatsExport(export_dir.clone()));
+        let mut stats = MatchStats::new(GameMode::Doubles);
+        stats.winner = Some(Player2);
+        stats.points.push(PointStats {
+            scorer: Some(Player2),
+            rally_length: 2,
+            ..default()
+        });
+        world.insert_resource(stats.clone());
+        let export_sys = world.register_system(export_match_stats);
+
+        // Nothing is exported until the match ends
+        world.run_system(export_sys).unwrap();
+        assert!(!export_dir.exists(), "Expected no export before match end");
+
+        world.write_message(MaxScoreReached(Player2));
+        world.run_system(export_sys).unwrap();
+        let files: Vec<PathBuf> = fs::read_dir(&export_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(files.len(), 1, "Expected a single exported file");
+        let exported: MatchStats =
+            serde_json::from_str(&fs::read_to_string(&files[0]).unwrap()).unwrap();
+        assert_eq!(exported, stats, "Expected exported stats to match");
+
+        let _ = fs::remove_dir_all(&export_dir);
+    }
+
+    // --- Helper Functions ---
+
+    // Create a world with the resources needed to track the stats of a match of the given mode.
+    fn stats_test_world(mode: GameMode) -> World {
+        let mut world = World::default();
+        world.insert_resource(mode);
+        world.init_resource::<Time>();
+        world.init_resource::<MatchStats>();
+        world.init_resource::<MatchTracker>();
+        world.init_resource::<Messages<StartBall>>();
+        world.init_resource::<Messages<BallHitPaddle>>();
+        world.init_resource::<Messages<PlayerScored>>();
+        world.init_resource::<Messages<GoalConceded>>();
+        world.init_resource::<Messages<MaxScoreReached>>();
+        world
+    }
+
+    // Create a collection holding the given values for players 1 and 2 only.
+    fn two_players<T>(p1: T, p2: T) -> PerPlayer<T> {
+        PerPlayer::try_from_iter(
+            GameMode::TwoPlayer.players(),
+            [(Player1, p1), (Player2, p2)],
+        )
+        .unwrap()
+    }
+
+    // Advance the game time by the given milliseconds.
+    fn advance_time(world: &mut World, millis: u64) {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(millis));
+    }
+
+    // Write a message for the back paddle of a player hitting the ball.
+    fn write_hit(world: &mut World, player: PlayerId, curve_level: u8) {
+        world.write_message(BallHitPaddle {
+            player,
+            position: PaddlePosition::Back,
+            curve_level,
+        });
+    }
+
+    // Get an export directory unique to the given test, in the temp directory.
+    fn test_export_dir(test_name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "rust_pong_stats_{}_{test_name}",
+            std::process::id()
+        ))
+    }
+}
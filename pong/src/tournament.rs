@@ -0,0 +1,1119 @@
+//!
+//! The tournament module runs a tournament between player profiles, as a series of two player
+//! matches. Entrants are drawn into a single-elimination bracket or a round-robin, and the
+//! bracket is shown between matches until a champion is crowned. The tournament is persisted
+//! to a file after every match, so it can be resumed after the game is restarted.
+//!
+
+// -------------------------------------------------------------------------------------------------
+// Included Symbols
+
+use std::cmp::Reverse;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use serde::{Deserialize, Serialize};
+
+use bevy_dyn_fontsize::{DynamicFontSize, DynamicFontsizePlugin};
+
+use crate::ball::StartBall;
+use crate::common::*;
+use crate::paddle::PaddleColors;
+use crate::profiles::PlayerProfile;
+use crate::score::{ClearScores, MaxScoreReached};
+use crate::stats::{self, MatchStats};
+
+// -------------------------------------------------------------------------------------------------
+// Constants
+
+const TOURNAMENT_FILE_NAME: &str = "tournament.ron";
+
+const BACKGROUND_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.9);
+const LINE_FONT_SIZE_AS_SCREEN_PCT: f32 = 0.035;
+const LINE_HEIGHT: f32 = LINE_FONT_SIZE_AS_SCREEN_PCT * ARENA_HEIGHT;
+
+// The seat each side of a tournament match is played from.
+const SEATS: [PlayerId; 2] = [Player1, Player2];
+
+// -------------------------------------------------------------------------------------------------
+// Public API
+
+///
+/// The TournamentPlugin plays the remaining matches of the tournament saved in the given file.
+/// Before each match, the two entrants are seated as players 1 and 2, under the names and
+/// paddle colors of their profiles. Entrants move their paddles with the keys of their seat,
+/// as their profile keys would rebind the other seat's keys for the rest of the tournament.
+///
+/// When a match ends, its result is recorded and saved, and the bracket is shown until the
+/// next match starts. Once a champion is crowned the bracket stays on screen, and the
+/// tournament_finished run condition can be used to stop further games from starting.
+///
+/// If the file holds no tournament, or can't be loaded, a warning is logged and no tournament
+/// is played. This plugin will only work properly if the app contains a single Camera2d entity.
+///
+pub struct TournamentPlugin {
+    /// The file the tournament is loaded from, and saved to after every match.
+    pub path: PathBuf,
+}
+
+impl Plugin for TournamentPlugin {
+    fn build(&self, app: &mut App) {
+        let tournament = match load_tournament(&self.path) {
+            Ok(Some(tournament)) => tournament,
+            Ok(None) => {
+                warn!("No tournament to play in {}", self.path.display());
+                return;
+            }
+            Err(err) => {
+                warn!(
+                    "Failed to load tournament from {}: {err}",
+                    self.path.display()
+                );
+                return;
+            }
+        };
+
+        if !app.is_plugin_added::<DynamicFontsizePlugin>() {
+            app.add_plugins(DynamicFontsizePlugin::default());
+        }
+
+        let (names, colors) = seating(&tournament);
+        app.insert_resource(names)
+            .insert_resource(colors)
+            .insert_resource(tournament)
+            .insert_resource(TournamentPath(self.path.clone()))
+            .init_resource::<MatchStats>()
+            .add_message::<StartBall>()
+            .add_message::<MaxScoreReached>()
+            .add_message::<ClearScores>()
+            .add_systems(
+                Startup,
+                setup_bracket_screen.in_set(Systems::SetupAfterCamera),
+            )
+            .add_systems(
+                Update,
+                (
+                    record_tournament_result
+                        .in_set(Systems::RecordResult)
+                        .after(stats::Systems::TrackStats),
+                    prepare_next_match.in_set(Systems::PrepareMatch),
+                    hide_bracket_screen,
+                    update_bracket_text
+                        .after(Systems::RecordResult)
+                        .run_if(resource_changed::<Tournament>),
+                ),
+            );
+    }
+}
+
+/// These SystemSets are used to control any system ordering dependencies on this plugin
+#[derive(SystemSet, Debug, Clone, Hash, PartialEq, Eq)]
+pub enum Systems {
+    ///
+    /// Startup systems which spawn the bracket screen entities. These require the camera
+    /// to exist, so the camera setup should be ordered before this set.
+    ///
+    SetupAfterCamera,
+
+    ///
+    /// Update systems which record the result of a match once MaxScoreReached is written,
+    /// and show the bracket. Should be ordered after the MaxScoreReached sender.
+    ///
+    RecordResult,
+
+    ///
+    /// Update systems which seat the entrants of the next match when a ClearScores message
+    /// starts a new game. Should be ordered after the ClearScores sender, and before paddles
+    /// are restored so that they take on the new entrants' colors.
+    ///
+    PrepareMatch,
+}
+
+///
+/// Resource holding a tournament between player profiles. The matches of each round are
+/// decided in order, and the entrants of each match are seated as players 1 and 2.
+///
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Tournament {
+    /// How entrants are drawn into matches
+    pub format: TournamentFormat,
+    /// The profile of each entrant, in seeding order
+    pub entrants: Vec<PlayerProfile>,
+    /// The matches of each round, in the order they are played
+    pub rounds: Vec<Vec<TournamentMatch>>,
+}
+
+/// The ways a tournament's entrants can be drawn into matches.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TournamentFormat {
+    ///
+    /// Entrants play in a bracket, where the loser of each match is knocked out. Top seeds
+    /// are given a bye through the first round when the entrants don't fill the bracket.
+    ///
+    SingleElimination,
+    /// Every entrant plays every other entrant once, and the most wins takes the title.
+    RoundRobin,
+}
+
+///
+/// A single match of a tournament. A player is None until the match feeding into their
+/// place is decided, or for good if the other player was given a bye.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TournamentMatch {
+    /// Name of the entrant seated as player 1 and player 2, in that order
+    pub players: [Option<String>; 2],
+    /// The result of the match, once it has been decided
+    pub result: Option<MatchResult>,
+}
+
+impl TournamentMatch {
+    /// Get the name of the entrant who won the match, once it has been decided.
+    pub fn winner(&self) -> Option<&str> {
+        let result = self.result?;
+        self.players[result.winner].as_deref()
+    }
+
+    /// Whether the match was decided by giving its only entrant a bye.
+    pub fn is_bye(&self) -> bool {
+        self.result.is_some() && self.players.iter().any(Option::is_none)
+    }
+}
+
+/// The result of a decided tournament match.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchResult {
+    /// Index of the winner in the match's players (0 or 1)
+    pub winner: usize,
+    /// Final score of each player, in the same order as the match's players
+    pub scores: [u8; 2],
+}
+
+/// An entrant's record across the decided matches of a tournament.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    /// The name of the entrant
+    pub name: String,
+    /// Number of matches won, not counting byes
+    pub wins: u32,
+    /// Number of matches lost
+    pub losses: u32,
+    /// Points scored minus points conceded
+    pub point_difference: i32,
+}
+
+impl Tournament {
+    ///
+    /// Draw the given entrants into a new tournament of the given format, in seeding order.
+    /// There must be at least two entrants, and each must have a different name.
+    ///
+    pub fn new(
+        format: TournamentFormat,
+        entrants: Vec<PlayerProfile>,
+    ) -> Result<Self, TournamentError> {
+        validate_entrants(&entrants)?;
+
+        let names: Vec<String> = entrants.iter().map(|e| e.name.clone()).collect();
+        let mut tournament = Tournament {
+            format,
+            entrants,
+            rounds: match format {
+                TournamentFormat::SingleElimination => elimination_rounds(&names),
+                TournamentFormat::RoundRobin => round_robin_rounds(&names),
+            },
+        };
+
+        // Byes are decided from the start, so their entrants go straight through
+        let byes: Vec<usize> = (0..tournament.rounds[0].len())
+            .filter(|&index| tournament.rounds[0][index].is_bye())
+            .collect();
+        for index in byes {
+            tournament.advance_winner(0, index);
+        }
+        Ok(tournament)
+    }
+
+    ///
+    /// Check that the tournament is one which could have been drawn and played, e.g. after
+    /// loading it from a file. Every match must be between entrants with a valid winner, and
+    /// each single-elimination round must have half the matches of the round before it.
+    ///
+    pub fn validate(&self) -> Result<(), TournamentError> {
+        validate_entrants(&self.entrants)?;
+        if self.rounds.is_empty() {
+            return Err(TournamentError::InvalidBracket("it has no rounds"));
+        }
+        for tournament_match in self.rounds.iter().flatten() {
+            let mut players = tournament_match.players.iter().flatten();
+            if players.any(|name| self.entrant(name).is_none()) {
+                return Err(TournamentError::InvalidBracket(
+                    "a match is played by someone who wasn't entered",
+                ));
+            }
+            if tournament_match
+                .result
+                .is_some_and(|result| result.winner > 1)
+            {
+                return Err(TournamentError::InvalidBracket(
+                    "a match was won by a third player",
+                ));
+            }
+        }
+        if self.format == TournamentFormat::SingleElimination {
+            let halved = self.rounds.windows(2).all(|r| r[0].len() == 2 * r[1].len());
+            if !halved || self.rounds[self.rounds.len() - 1].len() != 1 {
+                return Err(TournamentError::InvalidBracket(
+                    "its rounds don't narrow down to a final",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the profile of the entrant with the given name, if there is one.
+    pub fn entrant(&self, name: &str) -> Option<&PlayerProfile> {
+        self.entrants.iter().find(|entrant| entrant.name == name)
+    }
+
+    /// Get the next match to be played, or None once the tournament is over.
+    pub fn next_match(&self) -> Option<&TournamentMatch> {
+        let (round, index) = self.next_match_index()?;
+        Some(&self.rounds[round][index])
+    }
+
+    ///
+    /// Record the result of the next match to be played, sending its winner through to their
+    /// next match in a single-elimination tournament. Returns false if no match is left.
+    ///
+    pub fn record_result(&mut self, result: MatchResult) -> bool {
+        let Some((round, index)) = self.next_match_index() else {
+            return false;
+        };
+
+        self.rounds[round][index].result = Some(result);
+        self.advance_winner(round, index);
+        true
+    }
+
+    ///
+    /// Get the name of the champion, once the tournament is over. In a round-robin, this is
+    /// the entrant at the top of the standings.
+    ///
+    pub fn champion(&self) -> Option<&str> {
+        match self.format {
+            TournamentFormat::SingleElimination => self.rounds.last()?.first()?.winner(),
+            TournamentFormat::RoundRobin => {
+                if self.next_match().is_some() {
+                    return None;
+                }
+                let champion = self.standings().into_iter().next()?;
+                self.entrant(&champion.name).map(|e| e.name.as_str())
+            }
+        }
+    }
+
+    ///
+    /// Get the record of every entrant, best first. Entrants are ranked by wins, then by
+    /// point difference, and finally by seeding.
+    ///
+    pub fn standings(&self) -> Vec<Standing> {
+        let mut standings: Vec<Standing> = self
+            .entrants
+            .iter()
+            .map(|entrant| Standing {
+                name: entrant.name.clone(),
+                wins: 0,
+                losses: 0,
+                point_difference: 0,
+            })
+            .collect();
+
+        for tournament_match in self.rounds.iter().flatten() {
+            let Some(result) = tournament_match.result else {
+                continue;
+            };
+            for (side, player) in tournament_match.players.iter().enumerate() {
+                let Some(standing) = player
+                    .as_ref()
+                    .and_then(|name| standings.iter_mut().find(|s| s.name == *name))
+                else {
+                    continue;
+                };
+                if tournament_match.is_bye() {
+                    continue;
+                }
+                if side == result.winner {
+                    standing.wins += 1;
+                } else {
+                    standing.losses += 1;
+                }
+                standing.point_difference +=
+                    result.scores[side] as i32 - result.scores[1 - side] as i32;
+            }
+        }
+
+        // A stable sort keeps entrants with equal records in seeding order
+        standings.sort_by_key(|s| Reverse((s.wins, s.point_difference)));
+        standings
+    }
+
+    ///
+    /// Get the lines describing the bracket. For single-elimination, every match of every
+    /// round is listed. For a round-robin, the standings are listed along with the matches
+    /// of the current round, since the full schedule grows quickly with the entrants.
+    ///
+    pub fn bracket_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        match self.format {
+            TournamentFormat::SingleElimination => {
+                for (round, matches) in self.rounds.iter().enumerate() {
+                    lines.push(elimination_round_name(round, self.rounds.len()));
+                    lines.extend(matches.iter().map(match_line));
+                }
+            }
+            TournamentFormat::RoundRobin => {
+                lines.push("Standings".to_string());
+                for (rank, standing) in self.standings().iter().enumerate() {
+                    lines.push(format!(
+                        "{}. {}  {}-{}  {:+}",
+                        rank + 1,
+                        standing.name,
+                        standing.wins,
+                        standing.losses,
+                        standing.point_difference,
+                    ));
+                }
+                let round = self
+                    .next_match_index()
+                    .map_or(self.rounds.len() - 1, |(round, _)| round);
+                lines.push(format!("Round {} of {}", round + 1, self.rounds.len()));
+                lines.extend(self.rounds[round].iter().map(match_line));
+            }
+        }
+        lines
+    }
+
+    /// Get a line announcing the next match, or the champion once the tournament is over.
+    pub fn status_line(&self) -> String {
+        match (self.champion(), self.next_match()) {
+            (Some(champion), _) => format!("{champion} is the champion!"),
+            (None, Some(next)) => format!("Next match: {}", players_text(next)),
+            (None, None) => String::new(),
+        }
+    }
+
+    // Get the round and index of the next match to be played, if any.
+    fn next_match_index(&self) -> Option<(usize, usize)> {
+        self.rounds.iter().enumerate().find_map(|(round, matches)| {
+            matches
+                .iter()
+                .position(|m| m.result.is_none() && m.players.iter().all(Option::is_some))
+                .map(|index| (round, index))
+        })
+    }
+
+    //
+    // Send the winner of a decided single-elimination match through to their place in the
+    // next round. Each pair of matches feeds both places of a match in the next round.
+    //
+    fn advance_winner(&mut self, round: usize, index: usize) {
+        if self.format != TournamentFormat::SingleElimination || round + 1 >= self.rounds.len() {
+            return;
+        }
+        let winner = self.rounds[round][index].winner().map(str::to_string);
+        self.rounds[round + 1][index / 2].players[index % 2] = winner;
+    }
+}
+
+/// Errors which may occur when creating a tournament
+#[derive(Debug)]
+pub enum TournamentError {
+    /// A tournament needs at least two entrants
+    TooFewEntrants,
+    /// More than one entrant has the given name
+    DuplicateEntrant(String),
+    /// The matches of the tournament can't be played, for the given reason
+    InvalidBracket(&'static str),
+}
+
+impl fmt::Display for TournamentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TournamentError::TooFewEntrants => write!(f, "a tournament needs at least 2 entrants"),
+            TournamentError::DuplicateEntrant(name) => {
+                write!(f, "'{name}' was entered more than once")
+            }
+            TournamentError::InvalidBracket(reason) => {
+                write!(f, "the tournament bracket is invalid, as {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TournamentError {}
+
+///
+/// Run condition which is true once the tournament being played has crowned its champion.
+/// It is always false when no tournament is being played.
+///
+pub fn tournament_finished(tournament: Option<Res<Tournament>>) -> bool {
+    tournament.is_some_and(|tournament| tournament.champion().is_some())
+}
+
+///
+/// Get the default location of the tournament file, in the platform's data directory.
+/// Returns None if the platform has no data directory.
+///
+pub fn default_tournament_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(APP_DIR_NAME).join(TOURNAMENT_FILE_NAME))
+}
+
+///
+/// Load the tournament from the file at the given path. If there is no file there yet,
+/// there is no tournament and None is returned. A tournament which fails validation is
+/// returned as an invalid file.
+///
+pub fn load_tournament(path: &Path) -> Result<Option<Tournament>, RonFileError> {
+    let tournament: Option<Tournament> = load_ron_file(path)?;
+    if let Some(tournament) = &tournament {
+        tournament
+            .validate()
+            .map_err(|err| RonFileError::Invalid(Box::new(err)))?;
+    }
+    Ok(tournament)
+}
+
+/// Save the tournament to the file at the given path, creating its directory if needed.
+pub fn save_tournament_to(path: &Path, tournament: &Tournament) -> Result<(), RonFileError> {
+    save_ron_file(path, tournament)
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Resources
+
+// Location the Tournament is saved to after every match.
+#[derive(Resource)]
+struct TournamentPath(PathBuf);
+
+// -------------------------------------------------------------------------------------------------
+// Private Components
+
+// Component for the root entity of the bracket screen, which is its background.
+#[derive(Component)]
+struct BracketRoot;
+
+// Component for the text entity listing the bracket.
+#[derive(Component)]
+struct BracketText;
+
+// -------------------------------------------------------------------------------------------------
+// Private Systems
+
+//
+// Startup system to spawn the bracket screen, a background covering the arena with the
+// bracket text as a child. It starts visible, to show the first match until it starts.
+//
+fn setup_bracket_screen(
+    mut commands: Commands,
+    camera_entity: Single<Entity, With<Camera2d>>,
+    tournament: Res<Tournament>,
+) {
+    let camera_entity = camera_entity.entity();
+    commands
+        .spawn((
+            BracketRoot,
+            Sprite {
+                color: BACKGROUND_COLOR,
+                custom_size: Some(Vec2::new(ARENA_WIDTH, ARENA_HEIGHT)),
+                ..default()
+            },
+            Transform::from_xyz(0f32, 0f32, Z_OVERLAY),
+            Visibility::Visible,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                BracketText,
+                DynamicFontSize {
+                    height_in_world: LINE_HEIGHT,
+                    render_camera: camera_entity,
+                    max_width_in_world: None,
+                },
+                Text2d::new(bracket_text(&tournament)),
+                TextLayout::new_with_justify(Justify::Center),
+                Anchor::CENTER,
+                Transform::from_xyz(0f32, 0f32, 1f32),
+            ));
+        });
+}
+
+//
+// System to record the result of each finished match in the tournament, using the score
+// from the MatchStats, and save it. The bracket is then shown until the next match starts.
+//
+fn record_tournament_result(
+    mut max_score_msgs: MessageReader<MaxScoreReached>,
+    stats: Res<MatchStats>,
+    path: Res<TournamentPath>,
+    mut tournament: ResMut<Tournament>,
+    mut root_vis: Query<&mut Visibility, With<BracketRoot>>,
+) {
+    let Some(&MaxScoreReached(winner)) = max_score_msgs.read().last() else {
+        return;
+    };
+    let Some(winner) = SEATS.iter().position(|&seat| seat == winner) else {
+        warn!("Ignoring tournament match won by {winner:?}, who has no seat");
+        return;
+    };
+
+    let scores = SEATS.map(|seat| {
+        let points = stats.points.iter().filter(|p| p.scorer == Some(seat));
+        points.count().min(u8::MAX as usize) as u8
+    });
+    if !tournament.record_result(MatchResult { winner, scores }) {
+        return;
+    }
+    if let Err(err) = save_tournament_to(&path.0, &tournament) {
+        warn!("Failed to save tournament to {}: {err}", path.0.display());
+    }
+
+    for mut vis in root_vis.iter_mut() {
+        *vis = Visibility::Visible;
+    }
+}
+
+// System to seat the entrants of the next match, whenever a new game starts.
+fn prepare_next_match(
+    mut clear_msgs: MessageReader<ClearScores>,
+    tournament: Res<Tournament>,
+    mut names: ResMut<PlayerNames>,
+    mut colors: ResMut<PaddleColors>,
+) {
+    if clear_msgs.is_empty() {
+        return;
+    }
+    clear_msgs.clear();
+
+    (*names, *colors) = seating(&tournament);
+}
+
+// System to hide the bracket screen once the ball starts moving.
+fn hide_bracket_screen(
+    mut start_msgs: MessageReader<StartBall>,
+    mut root_vis: Query<&mut Visibility, With<BracketRoot>>,
+) {
+    if start_msgs.is_empty() {
+        return;
+    }
+    start_msgs.clear();
+
+    for mut vis in root_vis.iter_mut() {
+        *vis = Visibility::Hidden;
+    }
+}
+
+// System to update the bracket text whenever the tournament changes.
+fn update_bracket_text(tournament: Res<Tournament>, texts: Query<&mut Text2d, With<BracketText>>) {
+    for mut text in texts {
+        text.0 = bracket_text(&tournament);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Private Functions
+
+//
+// Get the names and paddle colors of the players seated for the next match. Once the
+// tournament is over, nobody is seated and the default names and colors are used.
+//
+fn seating(tournament: &Tournament) -> (PlayerNames, PaddleColors) {
+    let mut names = PlayerNames::default();
+    let mut colors = PaddleColors::default();
+    if let Some(next) = tournament.next_match() {
+        for (&seat, name) in SEATS.iter().zip(&next.players) {
+            let Some(entrant) = name.as_deref().and_then(|name| tournament.entrant(name)) else {
+                continue;
+            };
+            names.0.insert(seat, entrant.name.clone());
+            if let Some(color) = entrant.color() {
+                colors.0.insert(seat, color);
+            }
+        }
+    }
+    (names, colors)
+}
+
+// Check there are at least two entrants, and that each has a different name.
+fn validate_entrants(entrants: &[PlayerProfile]) -> Result<(), TournamentError> {
+    if entrants.len() < 2 {
+        return Err(TournamentError::TooFewEntrants);
+    }
+    for (i, entrant) in entrants.iter().enumerate() {
+        if entrants[..i].iter().any(|e| e.name == entrant.name) {
+            return Err(TournamentError::DuplicateEntrant(entrant.name.clone()));
+        }
+    }
+    Ok(())
+}
+
+// Get the full text of the bracket screen: a title, the bracket, and the tournament status.
+fn bracket_text(tournament: &Tournament) -> String {
+    let title = match tournament.format {
+        TournamentFormat::SingleElimination => "Single-Elimination Tournament",
+        TournamentFormat::RoundRobin => "Round-Robin Tournament",
+    };
+    let mut lines = vec![title.to_string(), String::new()];
+    lines.extend(tournament.bracket_lines());
+    lines.push(String::new());
+    lines.push(tournament.status_line());
+    lines.join("\n")
+}
+
+//
+// Get the matches of each round of a single-elimination bracket for the given entrants.
+// The bracket is padded out to a power of two with byes, and seeded so that the top seeds
+// can only meet in the later rounds. The byes go to the top seeds, in the first round.
+//
+fn elimination_rounds(names: &[String]) -> Vec<Vec<TournamentMatch>> {
+    let size = names.len().next_power_of_two();
+
+    // Seed order of each place in the first round, e.g. [0, 3, 1, 2] for four entrants
+    let mut seeds = vec![0usize];
+    while seeds.len() < size {
+        let len = seeds.len() * 2;
+        seeds = seeds
+            .iter()
+            .flat_map(|&seed| [seed, len - 1 - seed])
+            .collect();
+    }
+
+    let mut rounds = vec![
+        seeds
+            .chunks(2)
+            .map(|pair| {
+                let players = [names.get(pair[0]).cloned(), names.get(pair[1]).cloned()];
+                let result = players[1].is_none().then_some(MatchResult {
+                    winner: 0,
+                    scores: [0, 0],
+                });
+                TournamentMatch { players, result }
+            })
+            .collect::<Vec<_>>(),
+    ];
+    while rounds[rounds.len() - 1].len() > 1 {
+        let count = rounds[rounds.len() - 1].len() / 2;
+        rounds.push(vec![
+            TournamentMatch {
+                players: [None, None],
+                result: None,
+            };
+            count
+        ]);
+    }
+    rounds
+}
+
+//
+// Get the matches of each round of a round-robin between the given entrants, using the
+// circle method: one entrant stays in place while the rest rotate around them each round.
+// With an odd number of entrants, whoever would play the empty place sits that round out.
+//
+fn round_robin_rounds(names: &[String]) -> Vec<Vec<TournamentMatch>> {
+    let mut places: Vec<Option<&String>> = names.iter().map(Some).collect();
+    if places.len() % 2 == 1 {
+        places.push(None);
+    }
+
+    let count = places.len();
+    let mut rounds = Vec::new();
+    for _ in 1..count {
+        let round = (0..count / 2)
+            .filter_map(|i| match (places[i], places[count - 1 - i]) {
+                (Some(a), Some(b)) => Some(TournamentMatch {
+                    players: [Some(a.clone()), Some(b.clone())],
+                    result: None,
+                }),
+                _ => None,
+            })
+            .collect();
+        rounds.push(round);
+        places[1..].rotate_right(1);
+    }
+    rounds
+}
+
+// Get the name of a round of a single-elimination bracket, counting back from the final.
+fn elimination_round_name(round: usize, round_count: usize) -> String {
+    match round_count - round {
+        1 => "Final".to_string(),
+        2 => "Semifinals".to_string(),
+        3 => "Quarterfinals".to_string(),
+        _ => format!("Round {}", round + 1),
+    }
+}
+
+// Get the line describing a match, including its score once it has been decided.
+fn match_line(tournament_match: &TournamentMatch) -> String {
+    let [first, second] = &tournament_match.players;
+    match (tournament_match.result, first, second) {
+        (Some(_), Some(name), None) | (Some(_), None, Some(name)) => format!("{name} (bye)"),
+        (Some(result), Some(first), Some(second)) => format!(
+            "{first} {} - {} {second}",
+            result.scores[0], result.scores[1]
+        ),
+        _ => players_text(tournament_match),
+    }
+}
+
+// Get the names of the players of a match, with places yet to be decided shown as "?".
+fn players_text(tournament_match: &TournamentMatch) -> String {
+    let [first, second] = tournament_match
+        .players
+        .each_ref()
+        .map(|name| name.as_deref().unwrap_or("?"));
+    format!("{first} vs {second}")
+}
+
+// -------------------------------------------------------------------------------------------------
+// Unit Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::PointStats;
+    use bevy_test_helpers::prelude::*;
+    use std::fs;
+
+    #[test]
+    fn test_plugin_sys_added_setup() {
+        let path = test_tournament_path("plugin_setup");
+        save_tournament_to(
+            &path,
+            &test_tournament(TournamentFormat::SingleElimination, 2),
+        )
+        .unwrap();
+        validate_sys_in_plugin(
+            TournamentPlugin { path: path.clone() },
+            Startup,
+            setup_bracket_screen,
+            Some(Systems::SetupAfterCamera),
+        );
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_plugin_sys_added_update() {
+        let path = test_tournament_path("plugin_update");
+        save_tournament_to(
+            &path,
+            &test_tournament(TournamentFormat::SingleElimination, 2),
+        )
+        .unwrap();
+        let plugin = || TournamentPlugin { path: path.clone() };
+        validate_sys_in_plugin(
+            plugin(),
+            Update,
+            record_tournament_result,
+            Some(Systems::RecordResult),
+        );
+        validate_sys_in_plugin(
+            plugin(),
+            Update,
+            prepare_next_match,
+            Some(Systems::PrepareMatch),
+        );
+        validate_sys_in_plugin(
+            plugin(),
+            Update,
+            hide_bracket_screen,
+            None::<bevy::ecs::schedule::AnonymousSet>,
+        );
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_plugin_build_without_tournament() {
+        let mut app = App::new();
+        app.add_plugins(TournamentPlugin {
+            path: test_tournament_path("missing"),
+        });
+        assert!(
+            !app.world().contains_resource::<Tournament>(),
+            "Expected no tournament when the file doesn't exist",
+        );
+    }
+
+    #[test]
+    fn test_new_tournament_invalid() {
+        assert!(matches!(
+            Tournament::new(
+                TournamentFormat::RoundRobin,
+                vec![PlayerProfile::new("Ada")]
+            ),
+            Err(TournamentError::TooFewEntrants)
+        ));
+        assert!(matches!(
+            Tournament::new(
+                TournamentFormat::SingleElimination,
+                vec![PlayerProfile::new("Ada"), PlayerProfile::new("Ada")],
+            ),
+            Err(TournamentError::DuplicateEntrant(name)) if name == "Ada"
+        ));
+    }
+
+    #[test]
+    fn test_single_elimination() {
+        let mut tournament = test_tournament(TournamentFormat::SingleElimination, 5);
+
+        // Five entrants fill a bracket of eight, so the top three seeds get byes
+        assert_eq!(tournament.rounds.len(), 3);
+        assert_eq!(
+            tournament.bracket_lines(),
+            vec![
+                "Quarterfinals",
+                "P1 (bye)",
+                "P4 vs P5",
+                "P2 (bye)",
+                "P3 (bye)",
+                "Semifinals",
+                "P1 vs ?",
+                "P2 vs P3",
+                "Final",
+                "? vs ?",
+            ],
+        );
+
+        // The winner of each match goes through, until the final crowns a champion
+        for exp_next in [Some("P1 vs P5"), Some("P2 vs P3"), Some("P5 vs P3"), None] {
+            assert_eq!(tournament.champion(), None);
+            assert!(tournament.record_result(MatchResult {
+                winner: 1,
+                scores: [2, 5],
+            }));
+            assert_eq!(
+                tournament.next_match().map(players_text).as_deref(),
+                exp_next
+            );
+        }
+        assert_eq!(tournament.champion(), Some("P3"));
+        assert_eq!(tournament.status_line(), "P3 is the champion!");
+        assert!(!tournament.record_result(MatchResult {
+            winner: 0,
+            scores: [5, 0]
+        }));
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let mut tournament = test_tournament(TournamentFormat::RoundRobin, 3);
+
+        // Each entrant sits out one round, and plays every other entrant once
+        assert_eq!(tournament.rounds.len(), 3);
+        let mut pairings: Vec<String> = tournament
+            .rounds
+            .iter()
+            .flatten()
+            .map(players_text)
+            .collect();
+        pairings.sort();
+        assert_eq!(pairings, vec!["P1 vs P2", "P1 vs P3", "P2 vs P3"]);
+
+        // P3 wins every match, so takes the title ahead of P1 on point difference
+        while let Some(next) = tournament.next_match() {
+            assert_eq!(tournament.champion(), None);
+            let p3_index = next.players.iter().position(|p| p.as_deref() == Some("P3"));
+            let (winner, scores) = match p3_index {
+                Some(0) => (0, [5, 0]),
+                Some(_) => (1, [0, 5]),
+                None => (0, [5, 4]),
+            };
+            tournament.record_result(MatchResult { winner, scores });
+        }
+        assert_eq!(tournament.champion(), Some("P3"));
+        let standings = tournament.standings();
+        assert_eq!(
+            standings
+                .iter()
+                .map(|s| (s.name.as_str(), s.wins, s.losses, s.point_difference))
+                .collect::<Vec<_>>(),
+            vec![("P3", 2, 0, 10), ("P1", 1, 1, -4), ("P2", 0, 2, -6)],
+        );
+    }
+
+    #[test]
+    fn test_save_and_load_tournament() {
+        let path = test_tournament_path("save_load");
+        assert!(matches!(load_tournament(&path), Ok(None)));
+
+        let mut tournament = test_tournament(TournamentFormat::SingleElimination, 4);
+        tournament.record_result(MatchResult {
+            winner: 1,
+            scores: [3, 5],
+        });
+        save_tournament_to(&path, &tournament).unwrap();
+        assert_eq!(load_tournament(&path).unwrap(), Some(tournament));
+
+        fs::write(&path, "not a tournament").unwrap();
+        assert!(matches!(
+            load_tournament(&path),
+            Err(RonFileError::Parse(_))
+        ));
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_load_invalid_tournament() {
+        let path = test_tournament_path("invalid");
+        let valid = test_tournament(TournamentFormat::SingleElimination, 4);
+
+        // e.g. a hand-edited file, where advancing a winner would index past the next round
+        let mut missing_round_match = valid.clone();
+        missing_round_match.rounds[1].clear();
+        let mut missing_final = valid.clone();
+        missing_final.rounds.pop();
+        let mut no_rounds = test_tournament(TournamentFormat::RoundRobin, 3);
+        no_rounds.rounds.clear();
+        let mut unknown_player = valid.clone();
+        unknown_player.rounds[0][0].players[0] = Some("Nobody".to_string());
+        let mut third_winner = valid.clone();
+        third_winner.rounds[0][0].result = Some(MatchResult {
+            winner: 2,
+            scores: [5, 0],
+        });
+        let mut one_entrant = valid.clone();
+        one_entrant.entrants.truncate(1);
+
+        for tournament in [
+            missing_round_match,
+            missing_final,
+            no_rounds,
+            unknown_player,
+            third_winner,
+            one_entrant,
+        ] {
+            assert!(tournament.validate().is_err());
+            save_tournament_to(&path, &tournament).unwrap();
+            assert!(
+                matches!(load_tournament(&path), Err(RonFileError::Invalid(_))),
+                "Expected {tournament:?} to fail to load",
+            );
+        }
+        assert!(matches!(valid.validate(), Ok(())));
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_tournament_systems() {
+        let path = test_tournament_path("systems");
+        let mut tournament = test_tournament(TournamentFormat::SingleElimination, 2);
+        tournament.entrants[1].paddle_color = Some([255, 0, 0]);
+
+        let mut world = World::default();
+        world.spawn(Camera2d);
+        let (names, colors) = seating(&tournament);
+        world.insert_resource(names);
+        world.insert_resource(colors);
+        world.insert_resource(tournament);
+        world.insert_resource(TournamentPath(path.clone()));
+        world.init_resource::<MatchStats>();
+        world.init_resource::<Messages<StartBall>>();
+        world.init_resource::<Messages<MaxScoreReached>>();
+        world.init_resource::<Messages<ClearScores>>();
+
+        // Entrants are seated under their profile names and colors
+        assert_eq!(world.resource::<PlayerNames>().name(Player2), "P2");
+        assert_eq!(
+            world.resource::<PaddleColors>().color(Player2),
+            Color::srgb_u8(255, 0, 0)
+        );
+
+        let setup_sys = world.register_system(setup_bracket_screen);
+        let record_sys = world.register_system(record_tournament_result);
+        let prepare_sys = world.register_system(prepare_next_match);
+        let hide_sys = world.register_system(hide_bracket_screen);
+        let text_sys = world.register_system(update_bracket_text);
+        world.run_system(setup_sys).unwrap();
+        let mut root_vis = world.query_filtered::<&Visibility, With<BracketRoot>>();
+        assert_eq!(root_vis.single(&world).unwrap(), Visibility::Visible);
+
+        // The bracket is hidden once play starts
+        world.write_message(StartBall);
+        world.run_system(hide_sys).unwrap();
+        assert_eq!(root_vis.single(&world).unwrap(), Visibility::Hidden);
+
+        // When the match ends, the result is recorded from the stats and saved
+        let mut stats = MatchStats::new(GameMode::TwoPlayer);
+        for scorer in [Player2, Player1, Player2] {
+            stats.points.push(PointStats {
+                scorer: Some(scorer),
+                ..default()
+            });
+        }
+        world.insert_resource(stats);
+        world.write_message(MaxScoreReached(Player2));
+        world.run_system(record_sys).unwrap();
+        world.run_system(text_sys).unwrap();
+        assert_eq!(root_vis.single(&world).unwrap(), Visibility::Visible);
+        let tournament = world.resource::<Tournament>().clone();
+        assert_eq!(tournament.champion(), Some("P2"));
+        assert_eq!(tournament.rounds[0][0].result.unwrap().scores, [1, 2]);
+        assert_eq!(load_tournament(&path).unwrap(), Some(tournament));
+        let mut text = world.query_filtered::<&Text2d, With<BracketText>>();
+        assert!(
+            text.single(&world)
+                .unwrap()
+                .0
+                .ends_with("P2 is the champion!"),
+            "Expected the bracket to crown the champion",
+        );
+
+        // Once the tournament is over, nobody is seated for another game
+        world.write_message(ClearScores);
+        world.run_system(prepare_sys).unwrap();
+        assert_eq!(world.resource::<PlayerNames>().name(Player2), "Player 2");
+        assert_eq!(*world.resource::<PaddleColors>(), PaddleColors::default());
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_tournament_finished() {
+        let mut world = World::default();
+        let finished_sys = world.register_system(tournament_finished);
+        assert!(!world.run_system(finished_sys).unwrap());
+
+        let mut tournament = test_tournament(TournamentFormat::RoundRobin, 2);
+        world.insert_resource(tournament.clone());
+        assert!(!world.run_system(finished_sys).unwrap());
+
+        tournament.record_result(MatchResult {
+            winner: 0,
+            scores: [5, 1],
+        });
+        world.insert_resource(tournament);
+        assert!(world.run_system(finished_sys).unwrap());
+    }
+
+    // --- Helper Functions ---
+
+    // Create a tournament between the given number of entrants, named P1, P2, etc.
+    fn test_tournament(format: TournamentFormat, entrants: usize) -> Tournament {
+        let entrants = (1..=entrants)
+            .map(|i| PlayerProfile::new(format!("P{i}")))
+            .collect();
+        Tournament::new(format, entrants).unwrap()
+    }
+
+    // Get a tournament file path unique to the given test, in the temp directory.
+    fn test_tournament_path(test_name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!(
+                "rust_pong_tournament_{}_{test_name}",
+                std::process::id()
+            ))
+            .join(TOURNAMENT_FILE_NAME)
+    }
+}
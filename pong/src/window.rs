@@ -6,21 +6,23 @@
 // -------------------------------------------------------------------------------------------------
 // Included Symbols
 
+use std::time::Duration;
+
+use bevy::app::ScheduleRunnerPlugin;
 use bevy::prelude::*;
 use bevy::render::RenderPlugin;
 use bevy::render::settings::Backends;
 use bevy::render::settings::RenderCreation;
 use bevy::render::settings::WgpuSettings;
-use bevy::window::PresentMode;
-use bevy::window::WindowMode;
-use bevy::window::WindowResolution;
+use bevy::window::{ExitCondition, WindowResolution};
+use bevy::winit::WinitPlugin;
+
+use crate::settings::{DisplayMode, Settings};
 
 // -------------------------------------------------------------------------------------------------
 // Constants
 
 const PONG_WINDOW_TITLE: &str = "Rust Pong";
-const INITIAL_WINDOW_WIDTH: u32 = 1600;
-const INITIAL_WINDOW_HEIGHT: u32 = 900;
 const WINDOW_SIZE_CONSTRAINTS: WindowResizeConstraints = WindowResizeConstraints {
     min_width: 160.0,
     min_height: 90.0,
@@ -30,6 +32,7 @@ const WINDOW_SIZE_CONSTRAINTS: WindowResizeConstraints = WindowResizeConstraints
 const EXIT_WINDOW_KEY: KeyCode = KeyCode::Escape;
 const TOGGLE_VSYNC_KEY: KeyCode = KeyCode::KeyV;
 const TOGGLE_FULLSCREEN_KEY: KeyCode = KeyCode::KeyF;
+const HEADLESS_FRAME_TIME: Duration = Duration::from_nanos(1_000_000_000 / 60);
 
 // -------------------------------------------------------------------------------------------------
 // Public API
@@ -37,37 +40,104 @@ const TOGGLE_FULLSCREEN_KEY: KeyCode = KeyCode::KeyF;
 ///
 /// The PongWindowPlugin is the main type required to be added to the game to implement
 /// the window for pong. The plugin will create a new window on the screen configured
-/// with default settings. It will also handle keypress events to change window settings
-/// or exit the window.
+/// with the game Settings, so the SettingsPlugin should be added before this one (or
+/// defaults are used). It will also handle keypress events to change window settings
+/// or exit the window, and apply any other changes to the window Settings.
+///
+/// In headless mode, no window is created and nothing is rendered. The game instead runs
+/// in a loop at a fixed rate of 60 updates per second, until something exits the app.
 ///
-pub struct PongWindowPlugin;
+#[derive(Default)]
+pub struct PongWindowPlugin {
+    /// The graphics API used for rendering. Ignored in headless mode.
+    pub backend: RenderBackend,
+    /// Whether to run the game without a window or renderer.
+    pub headless: bool,
+}
 
 impl Plugin for PongWindowPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(
-            DefaultPlugins
-                .set(WindowPlugin {
-                    primary_window: Some(Window {
-                        title: PONG_WINDOW_TITLE.to_string(),
-                        resize_constraints: WINDOW_SIZE_CONSTRAINTS,
-                        present_mode: PresentMode::AutoVsync,
-                        resolution: WindowResolution::new(
-                            INITIAL_WINDOW_WIDTH,
-                            INITIAL_WINDOW_HEIGHT,
-                        ),
-                        ..default()
-                    }),
-                    ..default()
-                })
-                .set(RenderPlugin {
-                    render_creation: RenderCreation::Automatic(WgpuSettings {
-                        backends: Some(Backends::DX12),
+        let settings = app.world_mut().get_resource_or_init::<Settings>().clone();
+
+        let backends = if self.headless {
+            None
+        } else {
+            self.backend.backends()
+        };
+        let default_plugins = DefaultPlugins.set(RenderPlugin {
+            render_creation: RenderCreation::Automatic(WgpuSettings {
+                backends,
+                ..default()
+            }),
+            ..default()
+        });
+
+        if self.headless {
+            app.add_plugins(
+                default_plugins
+                    .set(WindowPlugin {
+                        primary_window: None,
+                        exit_condition: ExitCondition::DontExit,
+                        close_when_requested: false,
                         ..default()
-                    }),
+                    })
+                    .disable::<WinitPlugin>()
+                    .add(ScheduleRunnerPlugin::run_loop(HEADLESS_FRAME_TIME)),
+            );
+        } else {
+            app.add_plugins(default_plugins.set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: PONG_WINDOW_TITLE.to_string(),
+                    resize_constraints: WINDOW_SIZE_CONSTRAINTS,
+                    present_mode: settings.present_mode(),
+                    mode: settings.window_mode(),
+                    resolution: WindowResolution::new(
+                        settings.window_width,
+                        settings.window_height,
+                    ),
                     ..default()
                 }),
-        )
-        .add_systems(Update, (handle_exit_pressed, update_window_settings));
+                ..default()
+            }));
+        }
+
+        app.add_systems(
+            Update,
+            (
+                handle_exit_pressed,
+                update_window_settings,
+                apply_window_settings.run_if(resource_changed::<Settings>),
+            ),
+        );
+    }
+}
+
+/// The graphics APIs which the game can use for rendering.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RenderBackend {
+    /// Let the renderer choose, preferring the primary API of the platform.
+    Auto,
+    /// Vulkan, available on Windows, Linux and Android.
+    Vulkan,
+    /// DirectX 12, available on Windows only.
+    #[default]
+    Dx12,
+    /// Metal, available on macOS and iOS only.
+    Metal,
+    /// OpenGL (or OpenGL ES), for older hardware.
+    Gl,
+}
+
+impl RenderBackend {
+    // Get the wgpu backends to enable for this choice of API.
+    fn backends(self) -> Option<Backends> {
+        match self {
+            RenderBackend::Auto => WgpuSettings::default().backends,
+            RenderBackend::Vulkan => Some(Backends::VULKAN),
+            RenderBackend::Dx12 => Some(Backends::DX12),
+            RenderBackend::Metal => Some(Backends::METAL),
+            RenderBackend::Gl => Some(Backends::GL),
+        }
     }
 }
 
@@ -83,20 +153,37 @@ fn handle_exit_pressed(keys: Res<ButtonInput<KeyCode>>, mut exit_msgs: MessageWr
 
 //
 // Detects when the vsync or fullscreen toggle keys are pressed, and toggles the
-// corresponding setting on the game window.
+// corresponding setting. The change is then applied to the window by apply_window_settings.
 //
-fn update_window_settings(keys: Res<ButtonInput<KeyCode>>, mut window: Single<&mut Window>) {
+fn update_window_settings(keys: Res<ButtonInput<KeyCode>>, mut settings: ResMut<Settings>) {
     if keys.just_pressed(TOGGLE_VSYNC_KEY) {
-        window.present_mode = match window.present_mode {
-            PresentMode::AutoVsync => PresentMode::Immediate,
-            _ => PresentMode::AutoVsync,
-        };
+        settings.vsync = !settings.vsync;
     }
 
     if keys.just_pressed(TOGGLE_FULLSCREEN_KEY) {
-        window.mode = match window.mode {
-            WindowMode::Windowed => WindowMode::BorderlessFullscreen(MonitorSelection::Primary),
-            _ => WindowMode::Windowed,
+        settings.display_mode = match settings.display_mode {
+            DisplayMode::Windowed => DisplayMode::Fullscreen,
+            DisplayMode::Fullscreen => DisplayMode::Windowed,
         };
     }
 }
+
+//
+// Applies the window-related Settings to the game window whenever the Settings change. The
+// window is only resized when the configured size itself changes, so that any manual
+// resizing of the window isn't undone by unrelated settings changes.
+//
+fn apply_window_settings(
+    settings: Res<Settings>,
+    mut window: Single<&mut Window>,
+    mut applied_size: Local<Option<(u32, u32)>>,
+) {
+    window.present_mode = settings.present_mode();
+    window.mode = settings.window_mode();
+
+    let size = (settings.window_width, settings.window_height);
+    if applied_size.is_some_and(|applied_size| applied_size != size) {
+        window.resolution.set(size.0 as f32, size.1 as f32);
+    }
+    *applied_size = Some(size);
+}